@@ -71,6 +71,13 @@
         weather-rs = craneLib.buildPackage (commonArgs // {
           inherit cargoArtifacts;
         });
+
+        # Arguments for building the services crate and its web example for wasm32
+        wasmArgs = commonArgs // {
+          pname = "weather-api-services-wasm";
+          cargoExtraArgs = "-p weather-api-services --features web --target wasm32-unknown-unknown";
+          doCheck = false;
+        };
       in
       {
         checks = {
@@ -88,6 +95,12 @@
             cargoClippyExtraArgs = "--all-targets -- --deny warnings";
           });
 
+          # Build the services crate with the web example for wasm32
+          weather-api-services-wasm = craneLib.cargoBuild (wasmArgs // {
+            cargoArtifacts = craneLib.buildDepsOnly wasmArgs;
+            cargoExtraArgs = "${wasmArgs.cargoExtraArgs} --example web";
+          });
+
           weather-rs-doc = craneLib.cargoDoc (commonArgs // {
             inherit cargoArtifacts;
           });
@@ -0,0 +1,30 @@
+//! Fetches the current weather in a browser or an edge runtime and logs it to the JavaScript console.
+//!
+//! Build it for the web with:
+//!
+//! ```bash
+//! cargo build -p weather-api-services --example web --features web --target wasm32-unknown-unknown
+//! ```
+use wasm_bindgen::prelude::*;
+use weather_api_services::{weatherapi_service::WeatherApiService, WeatherApi};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(message: &str);
+}
+
+fn main() {
+    wasm_bindgen_futures::spawn_local(async {
+        let weather_api = WeatherApiService::builder()
+            .client(reqwest::Client::new())
+            .api_key("<your api key>")
+            .build()
+            .expect("url and api key must not be empty");
+
+        match weather_api.get_weather_data("London", &None).await {
+            Ok(weather_data) => log(&format!("{weather_data:?}")),
+            Err(err) => log(&format!("Failed to fetch weather data: {err}")),
+        }
+    });
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use tokio::runtime::{Builder, Runtime};
+
+use super::{models::WeatherData, WeatherApi};
+
+/// Blocking (synchronous) client that wraps any `WeatherApi` service for usage in non-async programs.
+///
+/// The client owns a single-threaded runtime that drives the asynchronous service.
+/// It must not be used from within an asynchronous context, because blocking the runtime thread panics.
+#[derive(Debug)]
+pub struct WeatherClientBlocking<T: WeatherApi> {
+    weather_api: T,
+    runtime: Runtime,
+}
+
+/// `WeatherClientBlocking` constructors and methods
+impl<T: WeatherApi> WeatherClientBlocking<T> {
+    /// Creates a new instance of `WeatherClientBlocking`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_api` - The weather API service used to retrieve weather data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the initialized `WeatherClientBlocking` or an error if the runtime can't be created.
+    pub fn new(weather_api: T) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+
+        Ok(WeatherClientBlocking {
+            weather_api,
+            runtime,
+        })
+    }
+
+    /// Retrieves weather data for a specific address and date (if provided), blocking the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retrieved weather data or an error if the request fails.
+    pub fn get_weather_data(&self, address: &str, date: &Option<String>) -> Result<WeatherData> {
+        self.runtime
+            .block_on(self.weather_api.get_weather_data(address, date))
+    }
+
+    /// Consumes the client and returns the wrapped weather API service.
+    ///
+    /// # Returns
+    ///
+    /// The wrapped weather API service.
+    pub fn into_inner(self) -> T {
+        self.weather_api
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantities::HectoPascal;
+    use crate::weatherapi_service::WeatherApiService;
+    use reqwest::Client;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    fn test_get_weather_data() {
+        let address = "SomeCity";
+        let api_key = "123";
+        let mock_response = json!({
+            "current": {
+                "temp_c": 12.5,
+                "condition": {"text": "Sunny"},
+                "wind_kph": 36.0,
+                "pressure_mb": 1010.0,
+                "humidity": 40,
+                "vis_km": 10.0
+            }
+        });
+
+        let mut mock_server = mockito::Server::new();
+        let mock_endpoint = mock_server
+            .mock("GET", "/current.json")
+            .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
+            .match_query(mockito::Matcher::UrlEncoded("key".into(), api_key.into()))
+            .with_status(200)
+            .with_body(mock_response.to_string())
+            .create();
+
+        let api = WeatherApiService::builder()
+            .client(Client::new())
+            .url(mock_server.url())
+            .api_key(api_key)
+            .build()
+            .unwrap();
+        let client = WeatherClientBlocking::new(api).unwrap();
+
+        let result = client.get_weather_data(address, &None).unwrap();
+
+        mock_endpoint.assert();
+        assert_eq!(result.temp, 12.5);
+        assert_eq!(result.humidity, 40);
+        assert_eq!(result.pressure, HectoPascal(1010));
+        assert_eq!(result.wind_speed, 10.0);
+        assert_eq!(result.visibility, 10000);
+        assert_eq!(result.description, "Sunny");
+    }
+}
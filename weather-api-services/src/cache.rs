@@ -0,0 +1,398 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{models::*, WeatherApi};
+
+/// The default maximum number of results kept by `CachedWeatherApi`.
+pub const DEFAULT_CAPACITY: usize = 128;
+
+/// The default time a result is reused for by `CachedWeatherApi`.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// The key of a cached result: the name of the method, the address and the other argument (e.g. the date).
+type CacheKey = (&'static str, String, String);
+
+/// Represents a cached result of a `WeatherApi` method.
+struct CacheEntry {
+    /// The time the result was stored.
+    stored_at: Instant,
+    /// The tick of the last use of the result, so the least recently used result can be evicted.
+    last_used: u64,
+    /// The result (e.g. `WeatherData` or `Vec<ForecastDay>`).
+    value: Box<dyn Any + Send + Sync>,
+}
+
+/// Represents the cached results ordered by their last use.
+#[derive(Default)]
+struct CacheEntries {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// The tick incremented on every use of a result.
+    clock: u64,
+}
+
+/// Weather API service that wraps any `WeatherApi` service with an in-process LRU cache of its results.
+///
+/// Successful results are reused for the same method and arguments (e.g. the address and the date) until they're
+/// older than the time to live; errors aren't cached. Once the cache is full, the least recently used result is
+/// evicted. Unlike the `http::Cache` middleware, which keeps raw responses of a single request pipeline, it keeps
+/// normalized results, so long-running programs (e.g. daemons) avoid duplicate calls within a short window
+/// regardless of the provider.
+pub struct CachedWeatherApi<T: WeatherApi> {
+    weather_api: T,
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<CacheEntries>,
+}
+
+/// `CachedWeatherApi` constructors and methods
+impl<T: WeatherApi> CachedWeatherApi<T> {
+    /// Creates a new instance of `CachedWeatherApi` with `DEFAULT_CAPACITY` and `DEFAULT_TTL`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_api` - The weather API service whose results are cached.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `CachedWeatherApi`.
+    pub fn new(weather_api: T) -> Self {
+        CachedWeatherApi {
+            weather_api,
+            capacity: DEFAULT_CAPACITY,
+            ttl: DEFAULT_TTL,
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// Sets the maximum number of cached results; `0` disables the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of cached results.
+    ///
+    /// # Returns
+    ///
+    /// The `CachedWeatherApi` with the capacity set.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the time a result is reused for.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - The time to live of cached results.
+    ///
+    /// # Returns
+    ///
+    /// The `CachedWeatherApi` with the time to live set.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns the number of cached results, including the expired ones that haven't been evicted yet.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Checks whether there are no cached results.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached results, e.g. to force fresh data.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Consumes the cache and returns the wrapped weather API service.
+    ///
+    /// # Returns
+    ///
+    /// The wrapped weather API service.
+    pub fn into_inner(self) -> T {
+        self.weather_api
+    }
+
+    /// Locks the cached results; a poisoned lock is recovered, because the results stay consistent.
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheEntries> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the cached result of a call or makes the call and caches its successful result.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the call.
+    /// * `call` - The call of the wrapped service.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the cached or fetched result or the error of the call.
+    async fn cached<V>(&self, key: CacheKey, call: impl Future<Output = Result<V>>) -> Result<V>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        if let Some(value) = self.get(&key) {
+            return Ok(value);
+        }
+
+        let value = call.await?;
+        self.insert(key, value.clone());
+
+        Ok(value)
+    }
+
+    /// Gets a cached result that isn't older than the time to live; an expired result is evicted.
+    fn get<V: Clone + 'static>(&self, key: &CacheKey) -> Option<V> {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+
+        match entries.entries.get_mut(key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => {
+                entry.last_used = clock;
+                entry.value.downcast_ref::<V>().cloned()
+            }
+            Some(_) => {
+                entries.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches a result, evicting the least recently used result if the cache is full.
+    fn insert<V: Send + Sync + 'static>(&self, key: CacheKey, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.lock();
+        if !entries.entries.contains_key(&key) && entries.entries.len() >= self.capacity {
+            let least_recently_used = entries
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                entries.entries.remove(&least_recently_used);
+            }
+        }
+
+        entries.clock += 1;
+        let last_used = entries.clock;
+        entries.entries.insert(
+            key,
+            CacheEntry {
+                stored_at: Instant::now(),
+                last_used,
+                value: Box::new(value),
+            },
+        );
+    }
+}
+
+/// Creates the key of a call.
+fn key(method: &'static str, address: &str, argument: impl ToString) -> CacheKey {
+    (method, address.to_owned(), argument.to_string())
+}
+
+#[async_trait]
+impl<T: WeatherApi + Send + Sync> WeatherApi for CachedWeatherApi<T> {
+    async fn get_weather_data(&self, address: &str, date: &Option<String>) -> Result<WeatherData> {
+        self.cached(
+            key("weather", address, date.clone().unwrap_or_default()),
+            self.weather_api.get_weather_data(address, date),
+        )
+        .await
+    }
+
+    async fn get_raw_weather_data(
+        &self,
+        address: &str,
+        date: &Option<String>,
+    ) -> Result<serde_json::Value> {
+        self.cached(
+            key("raw_weather", address, date.clone().unwrap_or_default()),
+            self.weather_api.get_raw_weather_data(address, date),
+        )
+        .await
+    }
+
+    fn supports_postal_codes(&self) -> bool {
+        self.weather_api.supports_postal_codes()
+    }
+
+    async fn get_forecast(&self, address: &str, days: u8) -> Result<Vec<ForecastDay>> {
+        self.cached(
+            key("forecast", address, days),
+            self.weather_api.get_forecast(address, days),
+        )
+        .await
+    }
+
+    async fn get_marine_data(&self, address: &str, days: u8) -> Result<MarineData> {
+        self.cached(
+            key("marine", address, days),
+            self.weather_api.get_marine_data(address, days),
+        )
+        .await
+    }
+
+    async fn get_snow_report(&self, address: &str, days: u8) -> Result<SnowReport> {
+        self.cached(
+            key("snow", address, days),
+            self.weather_api.get_snow_report(address, days),
+        )
+        .await
+    }
+
+    async fn get_stations(&self, address: &str) -> Result<Vec<Station>> {
+        self.cached(
+            key("stations", address, ""),
+            self.weather_api.get_stations(address),
+        )
+        .await
+    }
+
+    async fn get_nowcast(&self, address: &str) -> Result<Vec<MinutePrecipitation>> {
+        self.cached(
+            key("nowcast", address, ""),
+            self.weather_api.get_nowcast(address),
+        )
+        .await
+    }
+
+    async fn get_alerts(&self, address: &str) -> Result<Vec<WeatherAlert>> {
+        self.cached(
+            key("alerts", address, ""),
+            self.weather_api.get_alerts(address),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantities::Celsius;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Weather API service that counts its calls; the address `Nowhere` fails.
+    #[derive(Default)]
+    struct CountingApi {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl WeatherApi for CountingApi {
+        async fn get_weather_data(
+            &self,
+            _address: &str,
+            _date: &Option<String>,
+        ) -> Result<WeatherData> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
+            anyhow::bail!("not used")
+        }
+
+        async fn get_stations(&self, address: &str) -> Result<Vec<Station>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if address == "Nowhere" {
+                anyhow::bail!("Unknown address");
+            }
+
+            Ok(Vec::new())
+        }
+
+        async fn get_forecast(&self, _address: &str, days: u8) -> Result<Vec<ForecastDay>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
+            Ok((0..days)
+                .map(|day| ForecastDay {
+                    date: format!("2024-07-0{}", day + 1),
+                    min_temp: Celsius(10.0),
+                    max_temp: Celsius(20.0),
+                    description: "Sunny".to_owned(),
+                    chance_of_rain: 0,
+                    chance_of_snow: 0,
+                    precipitation: 0.0,
+                    hours: Vec::new(),
+                })
+                .collect())
+        }
+    }
+
+    fn calls(api: CachedWeatherApi<CountingApi>) -> usize {
+        api.into_inner().calls.load(Ordering::SeqCst)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_cached_results() {
+        let api = CachedWeatherApi::new(CountingApi::default());
+
+        assert_eq!(api.get_forecast("London", 3).await.unwrap().len(), 3);
+        assert_eq!(api.get_forecast("London", 3).await.unwrap().len(), 3);
+        assert_eq!(api.get_forecast("London", 1).await.unwrap().len(), 1);
+        api.get_stations("London").await.unwrap();
+        api.get_stations("London").await.unwrap();
+
+        assert_eq!(api.len(), 3);
+        assert_eq!(calls(api), 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let api = CachedWeatherApi::new(CountingApi::default());
+
+        assert!(api.get_stations("Nowhere").await.is_err());
+        assert!(api.get_stations("Nowhere").await.is_err());
+
+        assert!(api.is_empty());
+        assert_eq!(calls(api), 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_expired_results() {
+        let api = CachedWeatherApi::new(CountingApi::default()).ttl(Duration::ZERO);
+
+        api.get_stations("London").await.unwrap();
+        api.get_stations("London").await.unwrap();
+
+        assert_eq!(calls(api), 2);
+    }
+
+    #[rstest]
+    #[case(0, 5, 0)]
+    #[case(2, 4, 2)]
+    #[case(3, 3, 3)]
+    #[tokio::test]
+    async fn test_least_recently_used_eviction(
+        #[case] capacity: usize,
+        #[case] expected_calls: usize,
+        #[case] expected_len: usize,
+    ) {
+        let api = CachedWeatherApi::new(CountingApi::default()).capacity(capacity);
+
+        api.get_stations("London").await.unwrap();
+        api.get_stations("Paris").await.unwrap();
+        api.get_stations("London").await.unwrap();
+        api.get_stations("Kyiv").await.unwrap();
+        api.get_stations("Paris").await.unwrap();
+
+        assert_eq!(api.len(), expected_len);
+        assert_eq!(calls(api), expected_calls);
+    }
+}
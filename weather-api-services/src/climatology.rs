@@ -0,0 +1,481 @@
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use reqwest::{Client, RequestBuilder};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+use super::{
+    http::Pipeline,
+    models::openmeteo_model::{
+        ArchiveDaily, OpenMeteoArchiveData, OpenMeteoErrorData, OpenMeteoGeocodingData,
+    },
+    quantities::Celsius,
+    WeatherApiError,
+};
+
+/// The URL of the Open-Meteo geocoding API.
+pub const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+
+/// The URL of the Open-Meteo historical weather archive API.
+pub const ARCHIVE_URL: &str = "https://archive-api.open-meteo.com/v1/archive";
+
+/// The number of past years the climate normal is averaged over.
+pub const NORMAL_YEARS: i32 = 10;
+
+/// Represents a geographic location resolved from an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Location {
+    pub name: String,
+    /// The first-level administrative division (e.g. a state or region).
+    #[serde(default)]
+    pub admin1: Option<String>,
+    pub country: Option<String>,
+    /// The ISO 3166-1 alpha-2 code of the country.
+    #[serde(default)]
+    pub country_code: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// The elevation in meters; `None` if it's unknown.
+    #[serde(default)]
+    pub elevation: Option<f32>,
+}
+
+/// `Location` methods
+impl Location {
+    /// Returns the location as a `latitude,longitude` query that providers accept as an address.
+    pub fn query(&self) -> String {
+        format!("{:.4},{:.4}", self.latitude, self.longitude)
+    }
+}
+
+impl fmt::Display for Location {
+    /// Formats the location with its region and country (e.g. `Springfield, Illinois, United States`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for part in [&self.admin1, &self.country].into_iter().flatten() {
+            if *part != self.name {
+                write!(f, ", {part}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents the climate normal (the average over past years) of a calendar day.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClimateNormal {
+    /// The calendar day in the `MM-DD` format.
+    pub month_day: String,
+    /// The average of the daily mean temperatures of the calendar day.
+    pub mean_temp: Celsius,
+    /// The first year of the averaged period.
+    pub first_year: i32,
+    /// The last year of the averaged period.
+    pub last_year: i32,
+    /// The number of years with an observation of the calendar day.
+    pub years: usize,
+}
+
+/// `ClimateNormal` methods
+impl ClimateNormal {
+    /// Computes the anomaly of a temperature, i.e. its difference from the normal.
+    ///
+    /// # Arguments
+    ///
+    /// * `temp` - The observed temperature.
+    ///
+    /// # Returns
+    ///
+    /// The anomaly in degrees Celsius; positive if the temperature is above normal.
+    pub fn anomaly(&self, temp: Celsius) -> f32 {
+        temp.value() - self.mean_temp.value()
+    }
+}
+
+/// Client of the Open-Meteo geocoding and historical weather archive APIs that computes climate normals.
+///
+/// The Open-Meteo APIs are free for non-commercial usage and don't require an API key.
+#[derive(Debug)]
+pub struct ClimateArchive {
+    geocoding_url: String,
+    archive_url: String,
+    client: Client,
+    pipeline: Pipeline,
+}
+
+/// `ClimateArchive` constructors and methods
+impl ClimateArchive {
+    /// Creates a new instance of `ClimateArchive` using the public Open-Meteo APIs.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The HTTP client (reqwest) to use for making requests.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `ClimateArchive`.
+    pub fn new(client: Client) -> Self {
+        ClimateArchive {
+            geocoding_url: GEOCODING_URL.to_owned(),
+            archive_url: ARCHIVE_URL.to_owned(),
+            client,
+            pipeline: Pipeline::default(),
+        }
+    }
+
+    /// Sets the URLs of the geocoding and archive APIs (e.g. for self-hosted Open-Meteo instances).
+    ///
+    /// # Arguments
+    ///
+    /// * `geocoding_url` - The URL of the geocoding search endpoint.
+    /// * `archive_url` - The URL of the archive endpoint.
+    ///
+    /// # Returns
+    ///
+    /// The `ClimateArchive` with the URLs set.
+    pub fn with_urls(mut self, geocoding_url: String, archive_url: String) -> Self {
+        self.geocoding_url = geocoding_url;
+        self.archive_url = archive_url;
+        self
+    }
+
+    /// Asynchronously resolves an address to a geographic location.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address (e.g. a city name).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the best matching `Location` or an error if the location is not found.
+    pub async fn locate(&self, address: &str) -> Result<Location> {
+        self.search(address, None, 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| WeatherApiError::LocationNotFound(address.yellow().to_string()).into())
+    }
+
+    /// Asynchronously searches the locations matching an address (e.g. every "Springfield"), best matches first.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address (e.g. a city name).
+    /// * `country_code` - An optional ISO 3166-1 alpha-2 code of the country the locations are limited to.
+    /// * `count` - The maximum number of locations.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the matching locations (empty if nothing is found) or an error if the request fails.
+    pub async fn search(
+        &self,
+        address: &str,
+        country_code: Option<&str>,
+        count: u8,
+    ) -> Result<Vec<Location>> {
+        let mut params = vec![
+            ("name", address.to_owned()),
+            ("count", count.to_string()),
+            ("format", "json".to_owned()),
+        ];
+        if let Some(country_code) = country_code {
+            params.push(("countryCode", country_code.to_uppercase()));
+        }
+
+        let request = self.client.get(&self.geocoding_url).query(&params);
+        let geocoding_data: OpenMeteoGeocodingData = self.request_json(request).await?;
+
+        Ok(geocoding_data
+            .results
+            .into_iter()
+            .map(|result| Location {
+                name: result.name,
+                admin1: result.admin1,
+                country: result.country,
+                country_code: result.country_code,
+                latitude: result.latitude,
+                longitude: result.longitude,
+                elevation: result.elevation,
+            })
+            .collect())
+    }
+
+    /// Asynchronously computes the climate normal of a calendar day at a location.
+    ///
+    /// The daily mean temperatures of the `NORMAL_YEARS` years before `year` are fetched in a single
+    /// archive request and averaged locally.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - The location of the normal.
+    /// * `month_day` - The calendar day in the `MM-DD` format.
+    /// * `year` - The current year; the normal is averaged over the preceding years.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ClimateNormal` or an error if the request fails or there are no observations.
+    pub async fn get_normal(
+        &self,
+        location: &Location,
+        month_day: &str,
+        year: i32,
+    ) -> Result<ClimateNormal> {
+        let first_year = year - NORMAL_YEARS;
+        let last_year = year - 1;
+
+        let request = self.client.get(&self.archive_url).query(&[
+            ("latitude", location.latitude.to_string()),
+            ("longitude", location.longitude.to_string()),
+            ("start_date", format!("{first_year}-01-01")),
+            ("end_date", format!("{last_year}-12-31")),
+            ("daily", "temperature_2m_mean".to_owned()),
+            ("timezone", "auto".to_owned()),
+        ]);
+        let archive_data: OpenMeteoArchiveData = self.request_json(request).await?;
+        let (mean_temp, years) =
+            aggregate_normal(&archive_data.daily, month_day).ok_or_else(|| {
+                WeatherApiError::DateOutOfRange(
+                    format!("no observations of {month_day}")
+                        .yellow()
+                        .to_string(),
+                )
+            })?;
+
+        Ok(ClimateNormal {
+            month_day: month_day.to_owned(),
+            mean_temp: Celsius(mean_temp),
+            first_year,
+            last_year,
+            years,
+        })
+    }
+
+    /// Sends a request to an Open-Meteo API through the request pipeline and parses the JSON response.
+    async fn request_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        self.pipeline
+            .request_json(
+                request,
+                "Open-Meteo API",
+                |_, error_data: OpenMeteoErrorData| {
+                    WeatherApiError::Server(error_data.reason.yellow().to_string())
+                },
+            )
+            .await
+    }
+}
+
+/// Averages the daily mean temperatures of a calendar day over all years of the archive data.
+///
+/// # Arguments
+///
+/// * `daily` - The daily archive data.
+/// * `month_day` - The calendar day in the `MM-DD` format.
+///
+/// # Returns
+///
+/// The average temperature and the number of averaged years or `None` if there are no observations of the day.
+pub fn aggregate_normal(daily: &ArchiveDaily, month_day: &str) -> Option<(f32, usize)> {
+    let temps: Vec<f32> = daily
+        .time
+        .iter()
+        .zip(&daily.temperature_2m_mean)
+        .filter(|(date, _)| date.get(5..) == Some(month_day))
+        .filter_map(|(_, temp)| *temp)
+        .collect();
+
+    if temps.is_empty() {
+        return None;
+    }
+
+    Some((temps.iter().sum::<f32>() / temps.len() as f32, temps.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn archive_daily() -> ArchiveDaily {
+        ArchiveDaily {
+            time: vec![
+                "2021-07-01".to_owned(),
+                "2021-07-02".to_owned(),
+                "2022-07-01".to_owned(),
+                "2023-07-01".to_owned(),
+            ],
+            temperature_2m_mean: vec![Some(18.0), Some(30.0), Some(21.0), None],
+        }
+    }
+
+    #[rstest]
+    #[case("07-01", Some((19.5, 2)))]
+    #[case("07-02", Some((30.0, 1)))]
+    #[case("12-31", None)]
+    fn test_aggregate_normal(#[case] month_day: &str, #[case] expected: Option<(f32, usize)>) {
+        assert_eq!(aggregate_normal(&archive_daily(), month_day), expected);
+    }
+
+    #[rstest]
+    #[case(16.0, 4.0)]
+    #[case(9.5, -2.5)]
+    fn test_anomaly(#[case] temp: f32, #[case] expected: f32) {
+        let normal = ClimateNormal {
+            month_day: "07-01".to_owned(),
+            mean_temp: Celsius(12.0),
+            first_year: 2014,
+            last_year: 2023,
+            years: 10,
+        };
+
+        let anomaly = normal.anomaly(Celsius(temp));
+
+        assert!(approx_eq!(f32, anomaly, expected, epsilon = 0.01));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_locate_and_get_normal() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let geocoding_endpoint = mock_server
+            .mock("GET", "/search")
+            .match_query(mockito::Matcher::UrlEncoded("name".into(), "Kyiv".into()))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "results": [{"name": "Kyiv", "latitude": 50.45, "longitude": 30.52, "country": "Ukraine"}]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let archive_endpoint = mock_server
+            .mock("GET", "/archive")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("start_date".into(), "2014-01-01".into()),
+                mockito::Matcher::UrlEncoded("end_date".into(), "2023-12-31".into()),
+            ]))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "daily": {
+                        "time": ["2022-07-01", "2022-07-02", "2023-07-01"],
+                        "temperature_2m_mean": [20.0, 25.0, 22.0]
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let archive = ClimateArchive::new(Client::new()).with_urls(
+            format!("{}/search", mock_server.url()),
+            format!("{}/archive", mock_server.url()),
+        );
+        let location = archive.locate("Kyiv").await.unwrap();
+        let normal = archive.get_normal(&location, "07-01", 2024).await.unwrap();
+
+        geocoding_endpoint.assert_async().await;
+        archive_endpoint.assert_async().await;
+        assert_eq!(location.country.as_deref(), Some("Ukraine"));
+        assert_eq!(normal.years, 2);
+        assert_eq!((normal.first_year, normal.last_year), (2014, 2023));
+        assert!(approx_eq!(
+            f32,
+            normal.mean_temp.value(),
+            21.0,
+            epsilon = 0.01
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_locate_not_found() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let geocoding_endpoint = mock_server
+            .mock("GET", "/search")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(json!({"generationtime_ms": 0.5}).to_string())
+            .create_async()
+            .await;
+
+        let archive = ClimateArchive::new(Client::new()).with_urls(
+            format!("{}/search", mock_server.url()),
+            format!("{}/archive", mock_server.url()),
+        );
+        let result: WeatherApiError = archive
+            .locate("Nowhere")
+            .await
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+
+        geocoding_endpoint.assert_async().await;
+        assert!(matches!(result, WeatherApiError::LocationNotFound(_)));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_search() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let geocoding_endpoint = mock_server
+            .mock("GET", "/search")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("name".into(), "Springfield".into()),
+                mockito::Matcher::UrlEncoded("count".into(), "10".into()),
+                mockito::Matcher::UrlEncoded("countryCode".into(), "US".into()),
+            ]))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "results": [
+                        {"name": "Springfield", "latitude": 39.80172, "longitude": -89.64371, "admin1": "Illinois", "country": "United States", "country_code": "US"},
+                        {"name": "Springfield", "latitude": 37.21533, "longitude": -93.29824, "admin1": "Missouri", "country": "United States", "country_code": "US"}
+                    ]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let archive = ClimateArchive::new(Client::new()).with_urls(
+            format!("{}/search", mock_server.url()),
+            format!("{}/archive", mock_server.url()),
+        );
+        let result = archive.search("Springfield", Some("us"), 10).await.unwrap();
+
+        geocoding_endpoint.assert_async().await;
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[1].to_string(),
+            "Springfield, Missouri, United States"
+        );
+        assert_eq!(result[1].query(), "37.2153,-93.2982");
+    }
+
+    #[rstest]
+    #[case("Kyiv", Some("Kyiv"), Some("Ukraine"), "Kyiv, Ukraine")]
+    #[case("Monaco", None, Some("Monaco"), "Monaco")]
+    #[case("Nowhere", None, None, "Nowhere")]
+    fn test_location_display(
+        #[case] name: &str,
+        #[case] admin1: Option<&str>,
+        #[case] country: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let location = Location {
+            name: name.to_owned(),
+            admin1: admin1.map(str::to_owned),
+            country: country.map(str::to_owned),
+            country_code: None,
+            latitude: 0.0,
+            longitude: 0.0,
+            elevation: None,
+        };
+
+        assert_eq!(location.to_string(), expected);
+    }
+}
@@ -0,0 +1,273 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, NaiveTime, TimeZone, Utc, Weekday};
+use owo_colors::OwoColorize;
+
+use super::DateTimeError;
+
+/// The number of minutes around now in which the weather of a date is the current weather.
+pub const CURRENT_WINDOW_MINUTES: i64 = 30;
+
+/// Represents the endpoint of a provider that has the weather of a date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateRoute {
+    /// The history of the past weather.
+    History,
+    /// The current weather.
+    Current,
+    /// The forecast of the future weather.
+    Forecast,
+}
+
+/// Routes the weather of a date to the endpoint that has it: dates within `CURRENT_WINDOW_MINUTES` of now are
+/// answered with the current weather, earlier dates from the history and later dates from the forecast.
+///
+/// # Arguments
+///
+/// * `datetime` - The date and time of the requested weather.
+/// * `now` - The current time.
+///
+/// # Returns
+///
+/// The `DateRoute` of the date.
+pub fn route(datetime: DateTime<Utc>, now: DateTime<Utc>) -> DateRoute {
+    let window = Duration::minutes(CURRENT_WINDOW_MINUTES);
+
+    if datetime < now - window {
+        DateRoute::History
+    } else if datetime > now + window {
+        DateRoute::Forecast
+    } else {
+        DateRoute::Current
+    }
+}
+
+/// Parses a date and time like `parse_datetime`, in the time zone of a location, so a date like `tomorrow 18:00`
+/// is the evening at the location rather than at the user.
+///
+/// # Arguments
+///
+/// * `input` - The date and time to parse.
+/// * `utc_offset` - The offset from UTC of the location; the local time zone is used if `None`.
+///
+/// # Returns
+///
+/// A `Result` containing the date and time in UTC or a `DateTimeError` if the input isn't recognized.
+pub fn parse_datetime_in(
+    input: &str,
+    utc_offset: Option<FixedOffset>,
+) -> Result<DateTime<Utc>, DateTimeError> {
+    match utc_offset {
+        Some(utc_offset) => parse_datetime_at(input, Utc::now().with_timezone(&utc_offset)),
+        None => parse_datetime(input),
+    }
+}
+
+/// Parses a date and time given in a recognized format (see `dateparser`) or in natural language, relative to the current local time.
+///
+/// The natural-language expressions are:
+///
+/// * `now`
+/// * `today`, `tomorrow` or `yesterday` with an optional time (e.g. `tomorrow 18:00` or `tomorrow 6pm`)
+/// * `in <N> <UNIT>` and `<N> <UNIT> ago` with minutes, hours, days or weeks (e.g. `in 3 hours` or `2 days ago`)
+/// * `next <WEEKDAY>` and `last <WEEKDAY>` with an optional time (e.g. `next monday 09:30`)
+///
+/// Expressions without a time keep the current time of day.
+///
+/// # Arguments
+///
+/// * `input` - The date and time to parse.
+///
+/// # Returns
+///
+/// A `Result` containing the date and time in UTC or a `DateTimeError` if the input isn't recognized.
+pub fn parse_datetime(input: &str) -> Result<DateTime<Utc>, DateTimeError> {
+    parse_datetime_at(input, Local::now())
+}
+
+/// Parses a date and time like `parse_datetime`, relative to the given current time.
+///
+/// # Arguments
+///
+/// * `input` - The date and time to parse.
+/// * `now` - The current time; its time zone is used for expressions and formats without a time zone.
+///
+/// # Returns
+///
+/// A `Result` containing the date and time in UTC or a `DateTimeError` if the input isn't recognized.
+pub fn parse_datetime_at<Tz: TimeZone>(
+    input: &str,
+    now: DateTime<Tz>,
+) -> Result<DateTime<Utc>, DateTimeError> {
+    let text = input.trim().to_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    parse_relative(&words, &now)
+        .or_else(|| dateparser::parse_with_timezone(input.trim(), &now.timezone()).ok())
+        .ok_or_else(|| DateTimeError::Parse(input.yellow().to_string()))
+}
+
+/// Parses a natural-language expression split into lowercase words.
+fn parse_relative<Tz: TimeZone>(words: &[&str], now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let datetime = match words {
+        ["now"] => now.clone(),
+        ["in", amount, unit] => now
+            .clone()
+            .checked_add_signed(parse_duration(amount, unit)?)?,
+        [amount, unit, "ago"] => now
+            .clone()
+            .checked_sub_signed(parse_duration(amount, unit)?)?,
+        [day, time @ ..] if day_offset(day).is_some() => at_time(now, day_offset(day)?, time)?,
+        ["next", weekday, time @ ..] => {
+            let days_ahead = weekday_number(weekday)? + 7 - weekday_number_of(now.weekday());
+            at_time(now, (days_ahead - 1) % 7 + 1, time)?
+        }
+        ["last", weekday, time @ ..] => {
+            let days_behind = weekday_number_of(now.weekday()) + 7 - weekday_number(weekday)?;
+            at_time(now, -((days_behind - 1) % 7 + 1), time)?
+        }
+        _ => return None,
+    };
+
+    Some(datetime.with_timezone(&Utc))
+}
+
+/// Returns the date and time that is a number of days away from now at the given time (or the current time of day).
+fn at_time<Tz: TimeZone>(now: &DateTime<Tz>, days: i64, time: &[&str]) -> Option<DateTime<Tz>> {
+    let date = now.clone() + Duration::days(days);
+    let time = match time {
+        [] => return Some(date),
+        [time] => parse_time(time)?,
+        [time, meridiem @ ("am" | "pm")] => parse_time(&format!("{time}{meridiem}"))?,
+        _ => return None,
+    };
+
+    now.timezone()
+        .from_local_datetime(&date.date_naive().and_time(time))
+        .earliest()
+}
+
+/// Returns the number of days from today of a day keyword (e.g. `1` for `tomorrow`).
+fn day_offset(day: &str) -> Option<i64> {
+    match day {
+        "today" => Some(0),
+        "tomorrow" => Some(1),
+        "yesterday" => Some(-1),
+        _ => None,
+    }
+}
+
+/// Parses an amount and a time unit (e.g. `3` and `hours`) to a duration; `None` if it's out of range.
+fn parse_duration(amount: &str, unit: &str) -> Option<Duration> {
+    let amount: i64 = match amount {
+        "a" | "an" => 1,
+        _ => amount.parse().ok()?,
+    };
+
+    match unit.trim_end_matches('s') {
+        "min" | "minute" => Duration::try_minutes(amount),
+        "h" | "hr" | "hour" => Duration::try_hours(amount),
+        "d" | "day" => Duration::try_days(amount),
+        "w" | "week" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Parses a time of day in the 24-hour (`18:00`) or 12-hour (`6pm`, `6:30pm`) format.
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    let (clock, offset) = match time.strip_suffix("am").or(time.strip_suffix("pm")) {
+        Some(clock) => (clock, if time.ends_with("pm") { 12 } else { 0 }),
+        None => (time, 0),
+    };
+    let (hour, minute) = clock.split_once(':').unwrap_or((clock, "0"));
+    let (hour, minute): (u32, u32) = (hour.parse().ok()?, minute.parse().ok()?);
+    let is_12_hour = time != clock;
+
+    if is_12_hour && !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = if is_12_hour { hour % 12 + offset } else { hour };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Returns the number of a weekday name or its abbreviation, starting from `0` for Monday.
+fn weekday_number(weekday: &str) -> Option<i64> {
+    weekday.parse::<Weekday>().ok().map(weekday_number_of)
+}
+
+/// Returns the number of a weekday, starting from `0` for Monday.
+fn weekday_number_of(weekday: Weekday) -> i64 {
+    i64::from(weekday.num_days_from_monday())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// Returns Wednesday, 2024-07-10 12:30 at UTC+2.
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2024-07-10T12:30:00+02:00").unwrap()
+    }
+
+    #[rstest]
+    #[case("now", "2024-07-10T12:30:00+02:00")]
+    #[case("tomorrow 18:00", "2024-07-11T18:00:00+02:00")]
+    #[case("Tomorrow 6pm", "2024-07-11T18:00:00+02:00")]
+    #[case("tomorrow 6:15 am", "2024-07-11T06:15:00+02:00")]
+    #[case("today 12am", "2024-07-10T00:00:00+02:00")]
+    #[case("yesterday", "2024-07-09T12:30:00+02:00")]
+    #[case("in 3 hours", "2024-07-10T15:30:00+02:00")]
+    #[case("in an hour", "2024-07-10T13:30:00+02:00")]
+    #[case("in 45 min", "2024-07-10T13:15:00+02:00")]
+    #[case("2 days ago", "2024-07-08T12:30:00+02:00")]
+    #[case("next monday", "2024-07-15T12:30:00+02:00")]
+    #[case("next wednesday 09:30", "2024-07-17T09:30:00+02:00")]
+    #[case("last fri", "2024-07-05T12:30:00+02:00")]
+    #[case("last wednesday", "2024-07-03T12:30:00+02:00")]
+    fn test_parse_datetime_relative(#[case] input: &str, #[case] expected: &str) {
+        let expected = DateTime::parse_from_rfc3339(expected).unwrap();
+
+        assert_eq!(parse_datetime_at(input, now()).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("2024-07-01")]
+    #[case("2024-07-01 10:00")]
+    fn test_parse_datetime_absolute(#[case] input: &str) {
+        assert!(parse_datetime_at(input, now()).is_ok());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("tomorrow 25:00")]
+    #[case("tomorrow 13pm")]
+    #[case("in 3 fortnights")]
+    #[case("in 9223372036854775807 weeks")]
+    #[case("next someday")]
+    #[case("random string")]
+    fn test_parse_datetime_error(#[case] input: &str) {
+        let result = parse_datetime_at(input, now());
+
+        assert!(matches!(result, Err(DateTimeError::Parse(_))));
+    }
+
+    #[rstest]
+    #[case("2024-07-10T08:00:00Z", DateRoute::History)]
+    #[case("2024-07-10T10:05:00Z", DateRoute::Current)]
+    #[case("2024-07-10T10:45:00Z", DateRoute::Current)]
+    #[case("2024-07-11T16:00:00Z", DateRoute::Forecast)]
+    fn test_route(#[case] datetime: &str, #[case] expected: DateRoute) {
+        let datetime = DateTime::parse_from_rfc3339(datetime).unwrap().to_utc();
+
+        assert_eq!(route(datetime, now().to_utc()), expected);
+    }
+
+    #[rstest]
+    fn test_parse_datetime_in() {
+        let tokyo = FixedOffset::east_opt(9 * 3600);
+
+        let result = parse_datetime_in("2024-07-11 18:00", tokyo).unwrap();
+
+        assert_eq!(result.to_rfc3339(), "2024-07-11T09:00:00+00:00");
+    }
+}
@@ -0,0 +1,257 @@
+use serde::Serialize;
+use std::fmt;
+
+use super::quantities::{Celsius, HectoPascal, MetersPerSecond};
+
+/// Computes the "feels like" (apparent) temperature from the temperature, relative humidity and wind speed.
+///
+/// The wind chill (Environment Canada formula) is used for cold and windy weather (temperature at or below 10 °C
+/// and wind speed above 4.8 km/h), the heat index (NWS Rothfusz regression) is used for hot weather
+/// (temperature at or above 27 °C and relative humidity at or above 40 %), and the temperature itself otherwise.
+///
+/// # Arguments
+///
+/// * `temp` - The temperature.
+/// * `humidity` - The relative humidity in percent.
+/// * `wind_speed` - The wind speed.
+///
+/// # Returns
+///
+/// The "feels like" temperature.
+pub fn feels_like(temp: Celsius, humidity: u8, wind_speed: MetersPerSecond) -> Celsius {
+    let wind_speed_km_per_hour = wind_speed.kph();
+
+    if temp.value() <= 10.0 && wind_speed_km_per_hour > 4.8 {
+        Celsius(wind_chill(temp.value(), wind_speed_km_per_hour))
+    } else if temp.value() >= 27.0 && humidity >= 40 {
+        Celsius(heat_index(temp.value(), humidity))
+    } else {
+        temp
+    }
+}
+
+/// Checks whether it's day (between sunrise and sunset) at the given time.
+///
+/// # Arguments
+///
+/// * `time` - The time as a unix timestamp.
+/// * `sunrise` - The sunrise time as a unix timestamp.
+/// * `sunset` - The sunset time as a unix timestamp.
+///
+/// # Returns
+///
+/// `true` if the time is after the sunrise and before the sunset, `false` otherwise.
+pub fn is_day(time: i64, sunrise: i64, sunset: i64) -> bool {
+    (sunrise..sunset).contains(&time)
+}
+
+/// Computes the dew point from the temperature and relative humidity (Magnus formula).
+///
+/// # Arguments
+///
+/// * `temp` - The temperature.
+/// * `humidity` - The relative humidity in percent.
+///
+/// # Returns
+///
+/// The dew point.
+pub fn dew_point(temp: Celsius, humidity: u8) -> Celsius {
+    const A: f32 = 17.62;
+    const B: f32 = 243.12;
+
+    // the logarithm of zero humidity is undefined, so the humidity is at least 1 %
+    let humidity = humidity.clamp(1, 100) as f32;
+    let temp = temp.value();
+    let gamma = (humidity / 100.0).ln() + A * temp / (B + temp);
+
+    Celsius(B * gamma / (A - gamma))
+}
+
+/// Computes the pressure at an elevation (station pressure) from the sea-level pressure (barometric formula
+/// with the standard temperature lapse rate of 6.5 °C per kilometer).
+///
+/// # Arguments
+///
+/// * `sea_level_pressure` - The sea-level pressure.
+/// * `elevation` - The elevation in meters.
+/// * `temp` - The temperature at the elevation.
+///
+/// # Returns
+///
+/// The pressure at the elevation.
+pub fn station_pressure(
+    sea_level_pressure: HectoPascal,
+    elevation: f32,
+    temp: Celsius,
+) -> HectoPascal {
+    const LAPSE_RATE: f32 = 0.0065;
+    const EXPONENT: f32 = 5.257;
+
+    let lapse = LAPSE_RATE * elevation;
+
+    HectoPascal::rounded(
+        f32::from(sea_level_pressure) * (1.0 - lapse / (temp.kelvin() + lapse)).powf(EXPONENT),
+    )
+}
+
+/// Represents how comfortable the air humidity feels, classified by the dew point.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HumidityComfort {
+    /// The dew point is below 10 °C.
+    Dry,
+    /// The dew point is from 10 °C to 16 °C.
+    Comfortable,
+    /// The dew point is from 16 °C to 21 °C.
+    Humid,
+    /// The dew point is 21 °C or higher.
+    Oppressive,
+}
+
+/// `HumidityComfort` constructors
+impl HumidityComfort {
+    /// Classifies the humidity comfort by the dew point.
+    ///
+    /// # Arguments
+    ///
+    /// * `dew_point` - The dew point.
+    ///
+    /// # Returns
+    ///
+    /// The `HumidityComfort` class of the dew point.
+    pub fn from_dew_point(dew_point: Celsius) -> Self {
+        let dew_point = dew_point.value();
+
+        if dew_point < 10.0 {
+            HumidityComfort::Dry
+        } else if dew_point < 16.0 {
+            HumidityComfort::Comfortable
+        } else if dew_point < 21.0 {
+            HumidityComfort::Humid
+        } else {
+            HumidityComfort::Oppressive
+        }
+    }
+}
+
+impl fmt::Display for HumidityComfort {
+    /// Formats the humidity comfort class as a human-readable name (e.g. `Comfortable`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            HumidityComfort::Dry => "Dry",
+            HumidityComfort::Comfortable => "Comfortable",
+            HumidityComfort::Humid => "Humid",
+            HumidityComfort::Oppressive => "Oppressive",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Computes the wind chill in degrees Celsius from the temperature (°C) and the wind speed (km/h).
+fn wind_chill(temp: f32, wind_speed_km_per_hour: f32) -> f32 {
+    let wind_factor = wind_speed_km_per_hour.powf(0.16);
+
+    13.12 + 0.6215 * temp - 11.37 * wind_factor + 0.3965 * temp * wind_factor
+}
+
+/// Computes the heat index in degrees Celsius from the temperature (°C) and the relative humidity (%).
+fn heat_index(temp: f32, humidity: u8) -> f32 {
+    let t = temp * 9.0 / 5.0 + 32.0;
+    let rh = humidity as f32;
+
+    let heat_index = -42.379 + 2.049_015_3 * t + 10.143_332 * rh
+        - 0.224_755_4 * t * rh
+        - 0.006_837_83 * t * t
+        - 0.054_817_17 * rh * rh
+        + 0.001_228_74 * t * t * rh
+        + 0.000_852_82 * t * rh * rh
+        - 0.000_001_99 * t * t * rh * rh;
+
+    (heat_index - 32.0) * 5.0 / 9.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(20.0, 50, 3.0, 20.0)]
+    #[case(-10.0, 80, 0.5, -10.0)]
+    #[case(-10.0, 80, 5.0, -17.4)]
+    #[case(5.0, 60, 10.0, -0.4)]
+    #[case(32.0, 70, 2.0, 40.5)]
+    #[case(30.0, 30, 2.0, 30.0)]
+    fn test_feels_like(
+        #[case] temp: f32,
+        #[case] humidity: u8,
+        #[case] wind_speed: f32,
+        #[case] expected: f32,
+    ) {
+        let result = feels_like(Celsius(temp), humidity, MetersPerSecond(wind_speed)).value();
+
+        assert!(
+            approx_eq!(f32, result, expected, epsilon = 0.1),
+            "{result} != {expected}"
+        );
+    }
+
+    #[rstest]
+    #[case(1_700_000_000, 1_699_990_000, 1_700_020_000, true)]
+    #[case(1_699_980_000, 1_699_990_000, 1_700_020_000, false)]
+    #[case(1_700_020_000, 1_699_990_000, 1_700_020_000, false)]
+    fn test_is_day(
+        #[case] time: i64,
+        #[case] sunrise: i64,
+        #[case] sunset: i64,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_day(time, sunrise, sunset), expected);
+    }
+
+    #[rstest]
+    #[case(1013, 0.0, 15.0, 1013)]
+    #[case(1013, 1000.0, 15.0, 901)]
+    #[case(1020, 500.0, 5.0, 960)]
+    fn test_station_pressure(
+        #[case] sea_level_pressure: u16,
+        #[case] elevation: f32,
+        #[case] temp: f32,
+        #[case] expected: u16,
+    ) {
+        let result = station_pressure(HectoPascal(sea_level_pressure), elevation, Celsius(temp));
+
+        assert_eq!(result, HectoPascal(expected));
+    }
+
+    #[rstest]
+    #[case(20.0, 50, 9.3)]
+    #[case(30.0, 80, 26.2)]
+    #[case(-5.0, 90, -6.4)]
+    #[case(10.0, 100, 10.0)]
+    fn test_dew_point(#[case] temp: f32, #[case] humidity: u8, #[case] expected: f32) {
+        let result = dew_point(Celsius(temp), humidity).value();
+
+        assert!(
+            approx_eq!(f32, result, expected, epsilon = 0.1),
+            "{result} != {expected}"
+        );
+    }
+
+    #[rstest]
+    #[case(-3.0, HumidityComfort::Dry)]
+    #[case(12.0, HumidityComfort::Comfortable)]
+    #[case(18.5, HumidityComfort::Humid)]
+    #[case(24.0, HumidityComfort::Oppressive)]
+    fn test_humidity_comfort_from_dew_point(
+        #[case] dew_point: f32,
+        #[case] expected: HumidityComfort,
+    ) {
+        assert_eq!(
+            HumidityComfort::from_dew_point(Celsius(dew_point)),
+            expected
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::{header::HeaderMap, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+use super::http::{HttpResponse, Middleware, Next};
+
+/// Query parameters that carry credentials; they are removed from recorded URLs and fixture names and redacted
+/// in request plans.
+pub const SECRET_PARAMS: [&str; 6] = [
+    "key",
+    "appid",
+    "api_key",
+    "apikey",
+    "client_secret",
+    "token",
+];
+
+/// Represents errors related to recorded provider responses (fixtures).
+#[derive(Error, Debug)]
+pub enum FixtureError {
+    /// An error indicating that there is no recorded response for a request in the replay mode.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the path to the expected fixture file.
+    #[error("No recorded response '{0}'; record it first with '--record <DIR>'")]
+    Missing(String),
+
+    /// An error indicating a failure to read or write a fixture file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the fixture file.
+    #[error("Failed to access the recorded response '{1}'")]
+    Io(std::io::Error, String),
+
+    /// An error indicating that a fixture file isn't a valid recorded response.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `serde_json::Error` indicating the specific JSON error.
+    /// * `1` - A string representing the path to the fixture file.
+    #[error("Invalid recorded response '{1}'")]
+    Invalid(serde_json::Error, String),
+
+    /// An error indicating that a request wasn't sent because of the dry run mode (not a failure).
+    #[error("The request was not sent (dry run)")]
+    DryRun,
+}
+
+pub use crate::secret::REDACTED;
+
+/// Represents a raw provider response saved to disk.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Fixture {
+    /// The request URL without credentials.
+    pub url: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// The raw body of the response.
+    pub body: String,
+}
+
+/// Middleware that saves every provider response to a directory (e.g. to attach it to a bug report).
+#[derive(Debug)]
+pub struct Record {
+    dir: PathBuf,
+}
+
+/// `Record` constructors
+impl Record {
+    /// Creates a new instance of the `Record` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory the responses are saved to; it's created if it doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `Record` middleware.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Record { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl Middleware for Record {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let url = request_url(&request);
+        let response = next.run(request).await?;
+
+        if let Some(url) = url {
+            let path = self.dir.join(fixture_name(&url));
+            let fixture = Fixture {
+                url,
+                status: response.status.as_u16(),
+                body: response.body.clone(),
+            };
+            let content = serde_json::to_string_pretty(&fixture)
+                .map_err(|err| FixtureError::Invalid(err, path.display().to_string()))?;
+
+            fs::create_dir_all(&self.dir)
+                .and_then(|_| fs::write(&path, content))
+                .map_err(|err| FixtureError::Io(err, path.display().to_string()))?;
+        }
+
+        Ok(response)
+    }
+}
+
+/// Middleware that answers requests with responses saved by the `Record` middleware instead of sending them.
+#[derive(Debug)]
+pub struct Replay {
+    dir: PathBuf,
+}
+
+/// `Replay` constructors
+impl Replay {
+    /// Creates a new instance of the `Replay` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory with the recorded responses.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `Replay` middleware.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Replay { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl Middleware for Replay {
+    async fn handle(&self, request: RequestBuilder, _next: Next<'_>) -> Result<HttpResponse> {
+        let url = request_url(&request).unwrap_or_default();
+        let fixture = read_fixture(&self.dir.join(fixture_name(&url)))?;
+
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(fixture.status).unwrap_or(StatusCode::OK),
+            headers: HeaderMap::new(),
+            body: fixture.body,
+            cache_hit: false,
+        })
+    }
+}
+
+/// Middleware that reports the plan of every request (the provider, method, URL and query parameters with
+/// credentials redacted) to a log function instead of sending it.
+///
+/// The request fails with `FixtureError::DryRun`, so requests that depend on its response are never planned.
+#[derive(Clone)]
+pub struct DryRun {
+    log: Arc<dyn Fn(&str) + Send + Sync>,
+}
+
+/// `DryRun` constructors
+impl DryRun {
+    /// Creates a new instance of the `DryRun` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `log` - The function called with the plan of a request, a line per item.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `DryRun` middleware.
+    pub fn new(log: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        DryRun { log: Arc::new(log) }
+    }
+}
+
+impl fmt::Debug for DryRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DryRun").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Middleware for DryRun {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        if let Some(plan) = request_plan(&request, next.provider_name()) {
+            (self.log)(&plan);
+        }
+
+        Err(FixtureError::DryRun.into())
+    }
+}
+
+/// Describes the plan of a request, e.g.
+///
+/// ```text
+/// Provider: Weather API
+/// Request:  GET http://api.weatherapi.com/v1/current.json
+/// Query:    key=<redacted>
+///           q=London
+/// ```
+///
+/// # Arguments
+///
+/// * `request` - The request to describe.
+/// * `provider_name` - A string representing the name of the service provider.
+///
+/// # Returns
+///
+/// The plan of the request with the credentials query parameters (`SECRET_PARAMS`) redacted or `None` if the request
+/// can't be built.
+pub fn request_plan(request: &RequestBuilder, provider_name: &str) -> Option<String> {
+    let request = request.try_clone()?.build().ok()?;
+    let mut url = request.url().clone();
+    let query: Vec<String> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            if SECRET_PARAMS.contains(&name.as_ref()) {
+                format!("{name}={REDACTED}")
+            } else {
+                format!("{name}={value}")
+            }
+        })
+        .collect();
+    url.set_query(None);
+
+    let mut plan = format!(
+        "Provider: {provider_name}\nRequest:  {} {url}",
+        request.method()
+    );
+    for (index, param) in query.iter().enumerate() {
+        let label = if index == 0 { "Query:" } else { "" };
+        plan.push_str(&format!("\n{label:<10}{param}"));
+    }
+
+    Some(plan)
+}
+
+/// Reads a recorded response.
+///
+/// # Arguments
+///
+/// * `path` - The path to the fixture file.
+///
+/// # Returns
+///
+/// A `Result` containing the `Fixture` or a `FixtureError` if it's missing or invalid.
+pub fn read_fixture(path: &Path) -> Result<Fixture, FixtureError> {
+    let content = fs::read_to_string(path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => FixtureError::Missing(path.display().to_string()),
+        _ => FixtureError::Io(err, path.display().to_string()),
+    })?;
+
+    serde_json::from_str(&content)
+        .map_err(|err| FixtureError::Invalid(err, path.display().to_string()))
+}
+
+/// Returns the URL of a request without the credentials query parameters (`SECRET_PARAMS`).
+fn request_url(request: &RequestBuilder) -> Option<String> {
+    let mut url = request.try_clone()?.build().ok()?.url().clone();
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !SECRET_PARAMS.contains(&name.as_ref()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+
+    if query.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    Some(url.to_string())
+}
+
+/// Creates the file name of the fixture of a request URL.
+///
+/// The name consists of the readable path of the URL and a stable (FNV-1a) hash of the whole URL,
+/// e.g. `v1-current.json-3f2a9c0d1b7e4a65.json`.
+///
+/// # Arguments
+///
+/// * `url` - The request URL without credentials.
+///
+/// # Returns
+///
+/// The file name of the fixture.
+pub fn fixture_name(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .splitn(4, '/')
+        .nth(3)
+        .unwrap_or_default();
+    let readable: String = path
+        .chars()
+        .map(|char| {
+            if char.is_ascii_alphanumeric() || char == '.' {
+                char
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    format!("{}-{hash:016x}.json", readable.trim_matches('-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::Pipeline;
+    use reqwest::Client;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "https://api.weatherapi.com/v1/current.json?q=London",
+        "v1-current.json-"
+    )]
+    #[case("http://127.0.0.1:1234/", "-")]
+    fn test_fixture_name(#[case] url: &str, #[case] expected_prefix: &str) {
+        let name = fixture_name(url);
+
+        assert!(name.starts_with(expected_prefix), "{name}");
+        assert!(name.ends_with(".json"));
+        assert_eq!(name, fixture_name(url));
+        assert_ne!(name, fixture_name(&format!("{url}&days=2")));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = std::env::temp_dir().join("weather-api-services-test-fixtures");
+        let _ = fs::remove_dir_all(&dir);
+        let mut mock_server = mockito::Server::new_async().await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/current.json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"value": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let request = |client: &Client| {
+            client
+                .get(format!("{}/current.json", mock_server.url()))
+                .query(&[("q", "London"), ("key", "secret")])
+        };
+
+        let recorded = Pipeline::default()
+            .with(Record::new(&dir))
+            .send(request(&Client::new()), "Test API")
+            .await
+            .unwrap();
+        let replayed = Pipeline::default()
+            .with(Replay::new(&dir))
+            .send(request(&Client::new()), "Test API")
+            .await
+            .unwrap();
+
+        mock_endpoint.assert_async().await;
+        assert_eq!(replayed.status, recorded.status);
+        assert_eq!(replayed.body, r#"{"value": 1}"#);
+        let content = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect::<String>();
+        assert!(content.contains("q=London"));
+        assert!(!content.contains("secret"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_replay_missing() {
+        let dir = std::env::temp_dir().join("weather-api-services-test-fixtures-missing");
+
+        let result: FixtureError = Pipeline::default()
+            .with(Replay::new(dir))
+            .send(
+                Client::new().get("http://127.0.0.1:9/current.json"),
+                "Test API",
+            )
+            .await
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+
+        assert!(matches!(result, FixtureError::Missing(_)));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_dry_run() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/current.json")
+            .match_query(mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+        let plans = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let logged = plans.clone();
+
+        let result: FixtureError = Pipeline::default()
+            .with(DryRun::new(move |plan| {
+                logged.lock().unwrap().push(plan.to_owned())
+            }))
+            .send(
+                Client::new()
+                    .get(format!("{}/current.json", mock_server.url()))
+                    .query(&[("key", "secret"), ("q", "New York")]),
+                "Test API",
+            )
+            .await
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+
+        mock_endpoint.assert_async().await;
+        assert!(matches!(result, FixtureError::DryRun));
+        assert_eq!(
+            plans.lock().unwrap().as_slice(),
+            [format!(
+                "Provider: Test API\nRequest:  GET {}/current.json\nQuery:    key=<redacted>\n          q=New York",
+                mock_server.url()
+            )]
+        );
+    }
+}
@@ -0,0 +1,563 @@
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use reqwest::{Client, StatusCode};
+use std::fmt;
+
+use super::{
+    climatology::Location,
+    http::Pipeline,
+    models::what3words_model::{What3WordsData, What3WordsErrorData},
+    secret::Secret,
+    WeatherApiError,
+};
+
+/// The URL of the what3words API converting addresses to coordinates.
+pub const WHAT3WORDS_URL: &str = "https://api.what3words.com/v3/convert-to-coordinates";
+
+/// The digits of plus codes; a digit's value is its index.
+const CODE_ALPHABET: &str = "23456789CFGHJMPQRVWX";
+
+/// The position of the separator (`+`) in full plus codes.
+const SEPARATOR_POSITION: usize = 8;
+
+/// The number of digits encoded as latitude and longitude pairs; the following digits refine a grid.
+const PAIR_CODE_LENGTH: usize = 10;
+
+/// The maximum number of digits of a plus code.
+const MAX_CODE_LENGTH: usize = 15;
+
+/// The rows and columns of the grid refining a cell by each digit after the pairs.
+const GRID_ROWS: f64 = 5.0;
+const GRID_COLUMNS: f64 = 4.0;
+
+/// Represents a plus code (Open Location Code), e.g. the full `8FVC9G8F+6X` or the short `9G8F+6X`,
+/// which is relative to a nearby locality.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlusCode {
+    /// The values of the digits without the separator and padding.
+    digits: Vec<usize>,
+    /// The position of the separator; lower than `SEPARATOR_POSITION` for short codes.
+    separator: usize,
+}
+
+/// `PlusCode` constructors and methods
+impl PlusCode {
+    /// Parses a plus code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The plus code, case-insensitive (e.g. `8fvc9g8f+6x` or `7FG49Q00+`).
+    ///
+    /// # Returns
+    ///
+    /// The `PlusCode` or `None` if the string isn't a valid plus code.
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim().to_uppercase();
+        let (before, after) = code.split_once('+')?;
+        let separator = before.len();
+        if after.contains('+')
+            || separator > SEPARATOR_POSITION
+            || separator % 2 == 1
+            || after.len() == 1
+        {
+            return None;
+        }
+
+        let unpadded = before.trim_end_matches('0');
+        if unpadded.len() < separator
+            && (separator != SEPARATOR_POSITION
+                || unpadded.is_empty()
+                || unpadded.len() % 2 == 1
+                || !after.is_empty())
+        {
+            return None;
+        }
+
+        let digits = unpadded
+            .chars()
+            .chain(after.chars())
+            .map(|char| CODE_ALPHABET.find(char))
+            .collect::<Option<Vec<usize>>>()?;
+        if digits.len() > MAX_CODE_LENGTH {
+            return None;
+        }
+
+        let plus_code = PlusCode { digits, separator };
+        // the first latitude digit can't exceed 180 degrees and the first longitude digit 360 degrees
+        if plus_code.is_full() && (plus_code.digits[0] >= 9 || plus_code.digits[1] >= 18) {
+            return None;
+        }
+
+        Some(plus_code)
+    }
+
+    /// Checks whether the code is a full code (not relative to a locality).
+    pub fn is_full(&self) -> bool {
+        self.separator == SEPARATOR_POSITION
+    }
+
+    /// Decodes a full code to the center of its area.
+    ///
+    /// # Returns
+    ///
+    /// The latitude and longitude of the center or `None` if the code is short.
+    pub fn decode(&self) -> Option<(f64, f64)> {
+        self.is_full().then(|| decode_digits(&self.digits))
+    }
+
+    /// Recovers the location of a short code from a reference location nearby (e.g. the center of the locality
+    /// given with the code); the nearest area matching the code is taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `latitude` - The latitude of the reference location.
+    /// * `longitude` - The longitude of the reference location.
+    ///
+    /// # Returns
+    ///
+    /// The latitude and longitude of the center of the area (the decoded location for full codes).
+    pub fn recover(&self, latitude: f64, longitude: f64) -> (f64, f64) {
+        if self.is_full() {
+            return decode_digits(&self.digits);
+        }
+
+        let padding_length = SEPARATOR_POSITION - self.separator;
+        let resolution = 20_f64.powi(2 - (padding_length / 2) as i32);
+        let half = resolution / 2.0;
+        let mut digits = encode_pairs(latitude, longitude, padding_length);
+        digits.extend(&self.digits);
+        let (mut lat, mut lng) = decode_digits(&digits);
+
+        if latitude + half < lat && lat - resolution >= -90.0 {
+            lat -= resolution;
+        } else if latitude - half > lat && lat + resolution <= 90.0 {
+            lat += resolution;
+        }
+        if longitude + half < lng {
+            lng -= resolution;
+        } else if longitude - half > lng {
+            lng += resolution;
+        }
+
+        (lat, normalize_longitude(lng))
+    }
+}
+
+/// Splits an address into a plus code and the locality following it (e.g. `9G8F+6X Zurich`).
+///
+/// # Arguments
+///
+/// * `address` - The address given by the user.
+///
+/// # Returns
+///
+/// The `PlusCode` and the locality (empty if there is none) or `None` if the address doesn't start with a plus code.
+pub fn split_plus_code(address: &str) -> Option<(PlusCode, &str)> {
+    let address = address.trim();
+    let (code, locality) = address
+        .split_once(char::is_whitespace)
+        .unwrap_or((address, ""));
+    let code = PlusCode::parse(code.trim_end_matches(','))?;
+
+    Some((code, locality.trim_start_matches([',', ' '])))
+}
+
+/// Recognizes a what3words address, i.e. three words separated by dots with an optional `///` prefix
+/// (e.g. `///filled.count.soap`).
+///
+/// Without the prefix every word must be at least two letters long, so abbreviations (e.g. `Washington.D.C`)
+/// aren't taken for what3words addresses.
+///
+/// # Arguments
+///
+/// * `address` - The address given by the user.
+///
+/// # Returns
+///
+/// The three words in lowercase (e.g. `filled.count.soap`) or `None` if the address isn't a what3words address.
+pub fn what3words_address(address: &str) -> Option<String> {
+    let address = address.trim();
+    let (words, prefixed) = match address.strip_prefix("///") {
+        Some(words) => (words, true),
+        None => (address, false),
+    };
+    let min_length = if prefixed { 1 } else { 2 };
+    let parts: Vec<&str> = words.split('.').collect();
+
+    (parts.len() == 3
+        && parts.iter().all(|part| {
+            part.chars().count() >= min_length && part.chars().all(char::is_alphabetic)
+        }))
+    .then(|| words.to_lowercase())
+}
+
+/// The prefix marking postal code queries passed to providers (e.g. `zip:10115,DE`).
+pub const POSTAL_CODE_PREFIX: &str = "zip:";
+
+/// The maximum length of a postal code (the longest codes in use have 10 characters, e.g. `10000-0000`).
+const MAX_POSTAL_CODE_LENGTH: usize = 10;
+
+/// Represents a postal code with an optional country, e.g. `10115,DE` or `SW1A 1AA,GB`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostalCode {
+    /// The postal code in uppercase (e.g. `10115` or `SW1A 1AA`).
+    pub code: String,
+    /// The ISO 3166-1 alpha-2 code of the country in uppercase (e.g. `DE`); `None` if it isn't given.
+    pub country: Option<String>,
+}
+
+/// `PostalCode` constructors and methods
+impl PostalCode {
+    /// Parses a postal code in the form `<CODE>[,<COUNTRY>]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The postal code with an optional country code, case-insensitive (e.g. `10115,de`).
+    ///
+    /// # Returns
+    ///
+    /// The `PostalCode` or `None` if the string isn't a valid postal code.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (code, country) = match text.split_once(',') {
+            Some((code, country)) => (code.trim(), Some(country.trim())),
+            None => (text.trim(), None),
+        };
+        let is_code = !code.is_empty()
+            && code.len() <= MAX_POSTAL_CODE_LENGTH
+            && code.chars().any(|char| char.is_ascii_digit())
+            && code
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == ' ' || char == '-');
+        let is_country = country.is_none_or(|country| {
+            country.len() == 2 && country.chars().all(|char| char.is_ascii_alphabetic())
+        });
+
+        (is_code && is_country).then(|| PostalCode {
+            code: code.to_uppercase(),
+            country: country.map(str::to_uppercase),
+        })
+    }
+
+    /// Parses a postal code query passed to a provider (e.g. `zip:10115,DE`).
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query passed to the provider.
+    ///
+    /// # Returns
+    ///
+    /// The `PostalCode` or `None` if the query isn't a postal code query.
+    pub fn from_query(query: &str) -> Option<Self> {
+        PostalCode::parse(query.strip_prefix(POSTAL_CODE_PREFIX)?)
+    }
+
+    /// Returns the query passed to providers supporting postal codes natively (e.g. `zip:10115,DE`).
+    pub fn to_query(&self) -> String {
+        format!("{POSTAL_CODE_PREFIX}{self}")
+    }
+}
+
+impl fmt::Display for PostalCode {
+    /// Formats the postal code as `<CODE>[,<COUNTRY>]` (e.g. `10115,DE`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.country {
+            Some(country) => write!(f, "{},{country}", self.code),
+            None => write!(f, "{}", self.code),
+        }
+    }
+}
+
+/// Client of the what3words API that converts what3words addresses to coordinates (needs an API key).
+#[derive(Debug)]
+pub struct What3Words {
+    url: String,
+    api_key: Secret,
+    client: Client,
+    pipeline: Pipeline,
+}
+
+/// `What3Words` constructors and methods
+impl What3Words {
+    /// Creates a new instance of `What3Words` using the public what3words API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The HTTP client (reqwest) to use for making requests.
+    /// * `api_key` - The what3words API key.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `What3Words`.
+    pub fn new(client: Client, api_key: impl Into<Secret>) -> Self {
+        What3Words {
+            url: WHAT3WORDS_URL.to_owned(),
+            api_key: api_key.into(),
+            client,
+            pipeline: Pipeline::default(),
+        }
+    }
+
+    /// Sets the URL of the convert-to-coordinates endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the endpoint.
+    ///
+    /// # Returns
+    ///
+    /// The `What3Words` with the URL set.
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = url;
+        self
+    }
+
+    /// Asynchronously converts a what3words address to the location of its square.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The three words of the address (e.g. `filled.count.soap`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Location` named after the nearest place or an error if the words are unknown
+    /// or the request fails.
+    pub async fn locate(&self, words: &str) -> Result<Location> {
+        let request = self
+            .client
+            .get(&self.url)
+            .query(&[("words", words), ("key", self.api_key.expose())]);
+        let data: What3WordsData = self
+            .pipeline
+            .request_json(request, "what3words API", to_api_error)
+            .await?;
+
+        Ok(Location {
+            name: data.nearest_place.unwrap_or(data.words),
+            admin1: None,
+            country: None,
+            country_code: data.country,
+            latitude: data.coordinates.lat,
+            longitude: data.coordinates.lng,
+            elevation: None,
+        })
+    }
+}
+
+/// Decodes the digits of a full plus code to the center of its area.
+fn decode_digits(digits: &[usize]) -> (f64, f64) {
+    let (mut lat, mut lng) = (-90.0, -180.0);
+    let (mut lat_resolution, mut lng_resolution) = (400.0, 400.0);
+
+    for pair in digits[..digits.len().min(PAIR_CODE_LENGTH)].chunks(2) {
+        lat_resolution /= 20.0;
+        lng_resolution /= 20.0;
+        lat += pair[0] as f64 * lat_resolution;
+        lng += pair.get(1).copied().unwrap_or_default() as f64 * lng_resolution;
+    }
+    for &digit in digits.iter().skip(PAIR_CODE_LENGTH) {
+        lat_resolution /= GRID_ROWS;
+        lng_resolution /= GRID_COLUMNS;
+        lat += (digit / GRID_COLUMNS as usize) as f64 * lat_resolution;
+        lng += (digit % GRID_COLUMNS as usize) as f64 * lng_resolution;
+    }
+
+    (
+        (lat + lat_resolution / 2.0).min(90.0),
+        lng + lng_resolution / 2.0,
+    )
+}
+
+/// Encodes a location to the given number of leading digits (latitude and longitude pairs) of its plus code.
+fn encode_pairs(latitude: f64, longitude: f64, length: usize) -> Vec<usize> {
+    let mut lat = latitude.clamp(-90.0, 90.0) + 90.0;
+    let mut lng = normalize_longitude(longitude) + 180.0;
+    let mut resolution = 20.0;
+    let mut digits = Vec::with_capacity(length);
+
+    for _ in 0..length / 2 {
+        let lat_digit = ((lat / resolution).floor() as usize).min(CODE_ALPHABET.len() - 1);
+        let lng_digit = ((lng / resolution).floor() as usize).min(CODE_ALPHABET.len() - 1);
+        lat -= lat_digit as f64 * resolution;
+        lng -= lng_digit as f64 * resolution;
+        digits.extend([lat_digit, lng_digit]);
+        resolution /= 20.0;
+    }
+
+    digits
+}
+
+/// Normalizes a longitude to the range `-180..180`.
+fn normalize_longitude(longitude: f64) -> f64 {
+    (longitude + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Converts an error response of the what3words API to a `WeatherApiError`.
+fn to_api_error(status_code: StatusCode, error_data: What3WordsErrorData) -> WeatherApiError {
+    let message = error_data.error.message.yellow().to_string();
+
+    match (status_code, error_data.error.code.as_str()) {
+        (_, "InvalidKey") | (StatusCode::UNAUTHORIZED, _) => WeatherApiError::Unauthorized(message),
+        (StatusCode::TOO_MANY_REQUESTS, _) => WeatherApiError::QuotaExceeded(message),
+        (_, "BadWords") => WeatherApiError::LocationNotFound(message),
+        _ => WeatherApiError::Server(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case("8FVC9G8F+6X", Some((47.365_562, 8.524_937)))]
+    #[case("7fg49q00+", Some((20.375, 2.775)))]
+    #[case("8FVC9G8F+6XQ", Some((47.365_587_5, 8.524_984_4)))]
+    #[case("CFX30000+", Some((89.5, 1.5)))]
+    #[case("9G8F+6X", None)]
+    fn test_plus_code_decode(#[case] code: &str, #[case] expected: Option<(f64, f64)>) {
+        let result = PlusCode::parse(code).unwrap().decode();
+
+        match (result, expected) {
+            (Some((lat, lng)), Some((expected_lat, expected_lng))) => {
+                assert!(
+                    approx_eq!(f64, lat, expected_lat, epsilon = 0.000_01),
+                    "{lat}"
+                );
+                assert!(
+                    approx_eq!(f64, lng, expected_lng, epsilon = 0.000_01),
+                    "{lng}"
+                );
+            }
+            (result, expected) => assert_eq!(result, expected),
+        }
+    }
+
+    #[rstest]
+    #[case("8FVC9G8F")]
+    #[case("8FVC9G8+6X")]
+    #[case("8FVC9G8F+6")]
+    #[case("8FVC0000+6X")]
+    #[case("8F0C0000+")]
+    #[case("8FVC9G80+")]
+    #[case("WFVC9G8F+6X")]
+    #[case("8FVC9G8F+6X+")]
+    #[case("London")]
+    fn test_plus_code_parse_invalid(#[case] code: &str) {
+        assert_eq!(PlusCode::parse(code), None);
+    }
+
+    #[rstest]
+    #[case("9G8F+6X", 47.4, 8.6, (47.365_562, 8.524_937))]
+    #[case("9QCJ+2VX", 51.370_867_5, -1.217_765_625, (51.370_112_5, -1.217_765_625))]
+    #[case("8FVC9G8F+6X", 0.0, 0.0, (47.365_562, 8.524_937))]
+    fn test_plus_code_recover(
+        #[case] code: &str,
+        #[case] latitude: f64,
+        #[case] longitude: f64,
+        #[case] expected: (f64, f64),
+    ) {
+        let (lat, lng) = PlusCode::parse(code).unwrap().recover(latitude, longitude);
+
+        assert!(approx_eq!(f64, lat, expected.0, epsilon = 0.000_2), "{lat}");
+        assert!(approx_eq!(f64, lng, expected.1, epsilon = 0.000_2), "{lng}");
+    }
+
+    #[rstest]
+    #[case("9G8F+6X Zurich", Some("Zurich"))]
+    #[case("9G8F+6X, Zurich, Switzerland", Some("Zurich, Switzerland"))]
+    #[case("8FVC9G8F+6X", Some(""))]
+    #[case("Zurich 9G8F+6X", None)]
+    fn test_split_plus_code(#[case] address: &str, #[case] expected: Option<&str>) {
+        let result = split_plus_code(address).map(|(_, locality)| locality);
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("///filled.count.soap", Some("filled.count.soap"))]
+    #[case("Filled.Count.Soap", Some("filled.count.soap"))]
+    #[case("///a.b.c", Some("a.b.c"))]
+    #[case("Washington.D.C", None)]
+    #[case("filled.count", None)]
+    #[case("filled.count.soap 2", None)]
+    fn test_what3words_address(#[case] address: &str, #[case] expected: Option<&str>) {
+        assert_eq!(what3words_address(address).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("10115,DE", Some(("10115", Some("DE"))))]
+    #[case(" sw1a 1aa , gb ", Some(("SW1A 1AA", Some("GB"))))]
+    #[case("90210", Some(("90210", None)))]
+    #[case("10115,Germany", None)]
+    #[case("Berlin,DE", None)]
+    #[case("10115;DE", None)]
+    #[case("", None)]
+    fn test_postal_code_parse(#[case] text: &str, #[case] expected: Option<(&str, Option<&str>)>) {
+        let result = PostalCode::parse(text);
+
+        assert_eq!(
+            result
+                .as_ref()
+                .map(|postal_code| (postal_code.code.as_str(), postal_code.country.as_deref())),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("zip:10115,DE", Some("10115,DE"))]
+    #[case("zip:90210", Some("90210"))]
+    #[case("10115,DE", None)]
+    fn test_postal_code_from_query(#[case] query: &str, #[case] expected: Option<&str>) {
+        let result = PostalCode::from_query(query);
+
+        assert_eq!(
+            result.as_ref().map(ToString::to_string).as_deref(),
+            expected
+        );
+        if let Some(postal_code) = result {
+            assert_eq!(postal_code.to_query(), query);
+        }
+    }
+
+    #[rstest]
+    #[case(200, json!({"country": "GB", "nearestPlace": "Bayswater, London", "coordinates": {"lat": 51.520847, "lng": -0.195521}, "words": "filled.count.soap"}), None)]
+    #[case(400, json!({"error": {"code": "BadWords", "message": "Invalid or non-existent 3 word address"}}), Some("LocationNotFound"))]
+    #[case(401, json!({"error": {"code": "InvalidKey", "message": "Authentication failed; invalid API key"}}), Some("Unauthorized"))]
+    #[tokio::test]
+    async fn test_what3words_locate(
+        #[case] status: usize,
+        #[case] body: serde_json::Value,
+        #[case] expected_error: Option<&str>,
+    ) {
+        let mut mock_server = mockito::Server::new_async().await;
+        let endpoint = mock_server
+            .mock("GET", "/convert-to-coordinates")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("words".into(), "filled.count.soap".into()),
+                mockito::Matcher::UrlEncoded("key".into(), "secret".into()),
+            ]))
+            .with_status(status)
+            .with_body(body.to_string())
+            .create_async()
+            .await;
+
+        let what3words = What3Words::new(Client::new(), "secret".to_owned())
+            .with_url(format!("{}/convert-to-coordinates", mock_server.url()));
+        let result = what3words.locate("filled.count.soap").await;
+
+        endpoint.assert_async().await;
+        match (result, expected_error) {
+            (Ok(location), None) => {
+                assert_eq!(location.name, "Bayswater, London");
+                assert_eq!(location.query(), "51.5208,-0.1955");
+            }
+            (Err(err), Some(expected)) => {
+                let err: WeatherApiError = err.downcast().unwrap();
+                assert!(format!("{err:?}").starts_with(expected), "{err:?}");
+            }
+            (result, _) => panic!("unexpected result {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,1427 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use owo_colors::OwoColorize;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Client, RequestBuilder, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{collections::HashMap, fs, path::PathBuf, sync::Mutex, time::Instant};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::sync::watch;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::fixtures::fixture_name;
+use super::models::lenient::{self, ParsingMode};
+#[cfg(not(target_arch = "wasm32"))]
+use super::secret::Secret;
+use super::{models::WeatherDataError, WeatherApiError};
+
+/// The maximum `Retry-After` delay that is waited before retrying a rate limited request.
+///
+/// Longer delays are not waited; the `QuotaExceeded` error with the reset time is returned instead.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// The default `User-Agent` header of requests sent by the weather API services.
+pub const DEFAULT_USER_AGENT: &str = concat!("weather-api-services/", env!("CARGO_PKG_VERSION"));
+
+/// Creates an HTTP client for a weather API service.
+///
+/// The client negotiates gzip and brotli compression of responses and decodes them transparently, which shrinks
+/// large forecast and history payloads (on `wasm32` targets the browser negotiates compression itself).
+///
+/// # Arguments
+///
+/// * `timeout` - An optional timeout of a whole request (ignored on `wasm32` targets, where `fetch` has no timeout).
+/// * `user_agent` - An optional `User-Agent` header; `DEFAULT_USER_AGENT` if not set.
+///
+/// # Returns
+///
+/// A `Result` containing the HTTP client or an error if the user agent is invalid or the client can't be created.
+pub fn build_client(timeout: Option<Duration>, user_agent: Option<&str>) -> Result<Client> {
+    let builder = Client::builder().user_agent(user_agent.unwrap_or(DEFAULT_USER_AGENT));
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    }
+    .gzip(true)
+    .brotli(true);
+    #[cfg(target_arch = "wasm32")]
+    let _ = timeout;
+
+    Ok(builder.build().map_err(|_| WeatherApiError::Creation)?)
+}
+
+/// Represents a response of a weather API provider with the body read as text.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+    /// Whether the response was served by the `Cache` middleware instead of the provider.
+    pub cache_hit: bool,
+}
+
+/// Represents the metadata of a request sent through the pipeline (the provenance of the data).
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RequestMetadata {
+    /// The name of the service provider the request was sent to.
+    pub provider: String,
+    /// The time the response was received as a Unix timestamp.
+    pub fetched_at: i64,
+    /// Whether the response was served by the `Cache` middleware.
+    pub cache_hit: bool,
+    /// The time the request took through the pipeline in milliseconds.
+    pub latency_ms: u64,
+}
+
+/// Trait for middleware of the request pipeline (e.g. logging, retrying, rate limiting or caching).
+///
+/// A middleware gets a request and the rest of the pipeline; it may change the request, call the rest
+/// of the pipeline any number of times (or not at all) and change the response.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Handles a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to send.
+    /// * `next` - The rest of the pipeline, which sends the request to the provider at the end.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response or an error if the request fails.
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse>;
+}
+
+/// Represents the rest of the request pipeline after a middleware.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    provider_name: &'a str,
+}
+
+/// `Next` methods
+impl<'a> Next<'a> {
+    /// Gets the name of the service provider the request is sent to.
+    pub fn provider_name(&self) -> &'a str {
+        self.provider_name
+    }
+
+    /// Passes a request to the next middleware or sends it to the provider if there are no middlewares left.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to send.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response or an error if the request fails.
+    pub async fn run(self, request: RequestBuilder) -> Result<HttpResponse> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                let next = Next {
+                    middlewares,
+                    provider_name: self.provider_name,
+                };
+                middleware.handle(request, next).await
+            }
+            None => execute(request, self.provider_name).await,
+        }
+    }
+}
+
+/// A function reporting the warnings of the lenient parsing mode.
+type WarningHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A function reporting the metadata of requests.
+type RequestHandler = Arc<dyn Fn(&RequestMetadata) + Send + Sync>;
+
+/// The request pipeline shared by the weather API services.
+///
+/// Requests pass the middlewares in the order they were added, after the middlewares of the default pipeline
+/// (`SingleFlight`, except on `wasm32` targets, and then `Retry`).
+/// JSON responses are parsed in the parsing mode of the pipeline (see `ParsingMode`).
+#[derive(Clone)]
+pub struct Pipeline {
+    middlewares: Vec<Arc<dyn Middleware>>,
+    parsing_mode: ParsingMode,
+    on_warning: Option<WarningHandler>,
+    on_request: Option<RequestHandler>,
+}
+
+/// Creates the pipeline with the `SingleFlight` (except on `wasm32` targets) and `Retry` middleware and the lenient
+/// parsing mode.
+impl Default for Pipeline {
+    fn default() -> Self {
+        Pipeline {
+            middlewares: vec![
+                #[cfg(not(target_arch = "wasm32"))]
+                Arc::new(SingleFlight::default()),
+                Arc::new(Retry::default()),
+            ],
+            parsing_mode: ParsingMode::default(),
+            on_warning: None,
+            on_request: None,
+        }
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("middlewares", &self.middlewares)
+            .field("parsing_mode", &self.parsing_mode)
+            .finish_non_exhaustive()
+    }
+}
+
+/// `Pipeline` constructors and methods
+impl Pipeline {
+    /// Adds a middleware to the end of the pipeline (the closest one to the provider).
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - The middleware to add.
+    ///
+    /// # Returns
+    ///
+    /// The `Pipeline` with the middleware added.
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Sets the parsing mode of JSON responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `parsing_mode` - The parsing mode of values of unexpected types in responses.
+    ///
+    /// # Returns
+    ///
+    /// The `Pipeline` with the parsing mode set.
+    pub fn parsing_mode(mut self, parsing_mode: ParsingMode) -> Self {
+        self.parsing_mode = parsing_mode;
+        self
+    }
+
+    /// Sets the function reporting the warnings of the lenient parsing mode; warnings are dropped if it isn't set.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_warning` - The function called with a line per warning (e.g. `Weather API: 'N/A' isn't a number`).
+    ///
+    /// # Returns
+    ///
+    /// The `Pipeline` with the function set.
+    pub fn on_warning(mut self, on_warning: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.on_warning = Some(Arc::new(on_warning));
+        self
+    }
+
+    /// Sets the function reporting the metadata of every successful request (e.g. to show the provenance of
+    /// the data); the metadata isn't collected on `wasm32` targets, which have no monotonic clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_request` - The function called with the metadata of a request after its response is received.
+    ///
+    /// # Returns
+    ///
+    /// The `Pipeline` with the function set.
+    pub fn on_request(
+        mut self,
+        on_request: impl Fn(&RequestMetadata) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_request = Some(Arc::new(on_request));
+        self
+    }
+
+    /// Sends a request through the pipeline and reads the response body.
+    ///
+    /// If the request is still rate limited (HTTP 429 with the `Retry-After` header) after the pipeline,
+    /// a `QuotaExceeded` error with the reset time is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to send.
+    /// * `provider_name` - A string representing the name of the service provider (used in errors).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response or an error if the request fails.
+    pub async fn send(&self, request: RequestBuilder, provider_name: &str) -> Result<HttpResponse> {
+        let next = Next {
+            middlewares: &self.middlewares,
+            provider_name,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        let started_at = Instant::now();
+        let response = next.run(request).await?;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(on_request) = &self.on_request {
+            let fetched_at = now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs() as i64);
+            on_request(&RequestMetadata {
+                provider: provider_name.to_owned(),
+                fetched_at,
+                cache_hit: response.cache_hit,
+                latency_ms: started_at.elapsed().as_millis() as u64,
+            });
+        }
+
+        if let Some(delay) = retry_after(&response) {
+            let reset_time = format!("retry after {} seconds", delay.as_secs());
+            return Err(WeatherApiError::QuotaExceeded(reset_time.yellow().to_string()).into());
+        }
+
+        Ok(response)
+    }
+
+    /// Sends a request through the pipeline and parses the JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to send.
+    /// * `provider_name` - A string representing the name of the service provider (used in errors and warnings).
+    /// * `to_error` - A function that converts the status code and the parsed error response of the provider
+    ///   to a `WeatherApiError`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed data of a successful (HTTP 200) response or an error if the request fails,
+    /// the provider responds with an error or the response can't be parsed.
+    pub async fn request_json<T, E>(
+        &self,
+        request: RequestBuilder,
+        provider_name: &str,
+        to_error: impl FnOnce(StatusCode, E) -> WeatherApiError,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        let response = self.send(request, provider_name).await?;
+
+        if response.status == StatusCode::OK {
+            let (data, warnings) = lenient::parse(&response.body, self.parsing_mode)
+                .map_err(WeatherDataError::JsonParse)?;
+            if let Some(on_warning) = &self.on_warning {
+                for warning in warnings {
+                    on_warning(&format!("{provider_name}: {warning}"));
+                }
+            }
+
+            Ok(data)
+        } else {
+            let error_data: E =
+                serde_json::from_str(&response.body).map_err(WeatherDataError::JsonParse)?;
+
+            Err(to_error(response.status, error_data).into())
+        }
+    }
+}
+
+/// Sends a request to the provider and reads the response body; the end of every pipeline.
+async fn execute(request: RequestBuilder, provider_name: &str) -> Result<HttpResponse> {
+    let response = request.send().await.map_err(|err| {
+        WeatherApiError::Request(err.without_url(), provider_name.yellow().to_string())
+    })?;
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| WeatherApiError::BodyText(err.without_url()))?;
+
+    Ok(HttpResponse {
+        status,
+        headers,
+        body,
+        cache_hit: false,
+    })
+}
+
+/// Middleware that retries a rate limited request once.
+///
+/// When the provider responds with HTTP 429 (Too Many Requests) and the `Retry-After` delay
+/// is not longer than `max_delay`, the request is sent again after the delay.
+/// Retrying is disabled on `wasm32` targets, where there is no timer outside of the browser event loop.
+#[derive(Debug)]
+pub struct Retry {
+    pub max_delay: Duration,
+}
+
+/// Creates the `Retry` middleware waiting at most `MAX_RETRY_AFTER`.
+impl Default for Retry {
+    fn default() -> Self {
+        Retry {
+            max_delay: MAX_RETRY_AFTER,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Middleware for Retry {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let retry_request = request.try_clone();
+        let response = next.run(request).await?;
+
+        match (retry_request, retry_after(&response)) {
+            (Some(retry_request), Some(delay)) if delay <= self.max_delay => {
+                Ok(retry(retry_request, delay, next).await?.unwrap_or(response))
+            }
+            _ => Ok(response),
+        }
+    }
+}
+
+/// Middleware that reports every request and its status code to a log function.
+///
+/// Only the URL without the query is reported, so API keys passed as query parameters are not leaked.
+#[derive(Clone)]
+pub struct Logging {
+    log: Arc<dyn Fn(&str) + Send + Sync>,
+}
+
+/// `Logging` constructors
+impl Logging {
+    /// Creates a new instance of the `Logging` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `log` - The function called with a line per request (e.g. `Weather API: GET https://... -> 200 OK`).
+    ///
+    /// # Returns
+    ///
+    /// The initialized `Logging` middleware.
+    pub fn new(log: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Logging { log: Arc::new(log) }
+    }
+}
+
+impl fmt::Debug for Logging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Logging").finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Middleware for Logging {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let target = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .map(|request| {
+                let mut url = request.url().clone();
+                url.set_query(None);
+                format!("{} {url}", request.method())
+            })
+            .unwrap_or_default();
+
+        let result = next.run(request).await;
+
+        let outcome = match &result {
+            Ok(response) => response.status.to_string(),
+            Err(err) => format!("error: {err}"),
+        };
+        (self.log)(&format!("{}: {target} -> {outcome}", next.provider_name()));
+
+        result
+    }
+}
+
+/// Middleware that keeps a minimal interval between requests sent through it (e.g. for free API plans).
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct RateLimit {
+    interval: Duration,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// `RateLimit` constructors
+impl RateLimit {
+    /// Creates a new instance of the `RateLimit` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The minimal interval between two requests.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `RateLimit` middleware.
+    pub fn new(interval: Duration) -> Self {
+        RateLimit {
+            interval,
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// Reserves the next free slot for a request and returns the delay until it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut next_slot = self.next_slot.lock().unwrap_or_else(|err| err.into_inner());
+        let slot = next_slot.filter(|slot| *slot > now).unwrap_or(now);
+        *next_slot = Some(slot + self.interval);
+
+        slot - now
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Middleware for RateLimit {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let delay = self.reserve(Instant::now());
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        next.run(request).await
+    }
+}
+
+/// Middleware that keeps successful (HTTP 200) responses in memory and reuses them for the same URL.
+///
+/// Once a response is older than the time to live, it's revalidated with a conditional request if the provider
+/// sent an `ETag` or `Last-Modified` header; an HTTP 304 (Not Modified) response is treated as a cache hit, which
+/// saves the quota of providers that don't count unchanged responses. With a directory, the responses and their
+/// validators are also kept on disk, so they're reused across processes (e.g. one-shot CLI invocations).
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct Cache {
+    ttl: Duration,
+    dir: Option<PathBuf>,
+    entries: Mutex<HashMap<String, (SystemTime, HttpResponse)>>,
+}
+
+/// Represents a response of the `Cache` middleware saved to disk.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Serialize, Deserialize, Debug)]
+struct CachedResponse {
+    /// The time the response was stored or last revalidated as a Unix timestamp.
+    stored_at: u64,
+    /// The value of the `ETag` header.
+    etag: Option<String>,
+    /// The value of the `Last-Modified` header.
+    last_modified: Option<String>,
+    /// The raw body of the response.
+    body: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// `Cache` constructors and methods
+impl Cache {
+    /// Creates a new instance of the `Cache` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - The time a response is reused for.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `Cache` middleware.
+    pub fn new(ttl: Duration) -> Self {
+        Cache {
+            ttl,
+            dir: None,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the directory the responses are also kept in; it's created if it doesn't exist.
+    ///
+    /// The files are named after a hash of the whole URL, so the credentials in the query aren't readable.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The cache directory (e.g. the XDG cache directory of an application).
+    ///
+    /// # Returns
+    ///
+    /// The `Cache` middleware with the directory set.
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Gets the cached response of a URL from memory or, if it isn't there, from the cache directory.
+    fn get(&self, key: &str) -> Option<(SystemTime, HttpResponse)> {
+        let cached = {
+            let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+            entries.get(key).cloned()
+        };
+
+        cached.or_else(|| {
+            let path = self.dir.as_ref()?.join(fixture_name(key));
+            let cached: CachedResponse =
+                serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+            let mut headers = HeaderMap::new();
+            for (name, value) in [(ETAG, cached.etag), (LAST_MODIFIED, cached.last_modified)] {
+                if let Some(value) = value.and_then(|value| value.parse().ok()) {
+                    headers.insert(name, value);
+                }
+            }
+            let response = HttpResponse {
+                status: StatusCode::OK,
+                headers,
+                body: cached.body,
+                cache_hit: false,
+            };
+
+            Some((
+                SystemTime::UNIX_EPOCH + Duration::from_secs(cached.stored_at),
+                response,
+            ))
+        })
+    }
+
+    /// Caches a response in memory and in the cache directory; failing to write the file only loses the entry
+    /// for later processes.
+    fn insert(&self, key: String, response: &HttpResponse) {
+        let stored_at = now();
+        if let Some(dir) = &self.dir {
+            let header = |name| {
+                let value = response.headers.get(name)?.to_str().ok()?;
+                Some(value.to_string())
+            };
+            let cached = CachedResponse {
+                stored_at: stored_at
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map_or(0, |time| time.as_secs()),
+                etag: header(ETAG),
+                last_modified: header(LAST_MODIFIED),
+                body: response.body.clone(),
+            };
+            if let Ok(content) = serde_json::to_string(&cached) {
+                let _ = fs::create_dir_all(dir)
+                    .and_then(|_| fs::write(dir.join(fixture_name(&key)), content));
+            }
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.insert(key, (stored_at, response.clone()));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Middleware for Cache {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let Some(key) = get_url(&request) else {
+            return next.run(request).await;
+        };
+
+        let cached = self.get(&key);
+        let request = match &cached {
+            Some((stored_at, response))
+                if now()
+                    .duration_since(*stored_at)
+                    .is_ok_and(|age| age < self.ttl) =>
+            {
+                return Ok(HttpResponse {
+                    cache_hit: true,
+                    ..response.clone()
+                });
+            }
+            Some((_, response)) => conditional_request(request, response),
+            None => request,
+        };
+
+        let response = next.run(request).await?;
+        if let (StatusCode::NOT_MODIFIED, Some((_, cached))) = (response.status, cached) {
+            self.insert(key, &cached);
+
+            return Ok(HttpResponse {
+                cache_hit: true,
+                ..cached
+            });
+        }
+        if response.status == StatusCode::OK {
+            self.insert(key, &response);
+        }
+
+        Ok(response)
+    }
+}
+
+/// Middleware that coalesces identical requests in flight (GET requests of the same URL, e.g. the weather of the same
+/// address and date from the same provider) into a single request whose response is shared.
+///
+/// The first request is sent and the others wait for its response; if it fails (or is cancelled), the waiting
+/// requests are sent on their own, so errors aren't shared. It's part of the default pipeline, so concurrent
+/// batch operations (e.g. a dashboard of locations with the same address) send a single request.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+pub struct SingleFlight {
+    in_flight: Arc<Mutex<HashMap<String, watch::Receiver<Option<HttpResponse>>>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Middleware for SingleFlight {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let Some(key) = get_url(&request) else {
+            return next.run(request).await;
+        };
+
+        let leader = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+            match in_flight.get(&key) {
+                Some(receiver) => Err(receiver.clone()),
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+                    in_flight.insert(key.clone(), receiver);
+                    Ok(InFlight {
+                        key,
+                        in_flight: self.in_flight.clone(),
+                        sender,
+                    })
+                }
+            }
+        };
+
+        match leader {
+            Ok(flight) => {
+                let result = next.run(request).await;
+                if let Ok(response) = &result {
+                    flight.sender.send_replace(Some(response.clone()));
+                }
+
+                result
+            }
+            Err(mut receiver) => {
+                let response = receiver
+                    .wait_for(Option::is_some)
+                    .await
+                    .ok()
+                    .and_then(|response| response.clone());
+                match response {
+                    Some(response) => Ok(response),
+                    // the first request failed or was cancelled
+                    None => next.run(request).await,
+                }
+            }
+        }
+    }
+}
+
+/// Represents a request in flight sent by the `SingleFlight` middleware; it's removed from the requests in flight
+/// when it's dropped (also if it's cancelled), which ends the waiting of identical requests without a response.
+#[cfg(not(target_arch = "wasm32"))]
+struct InFlight {
+    key: String,
+    in_flight: Arc<Mutex<HashMap<String, watch::Receiver<Option<HttpResponse>>>>>,
+    sender: watch::Sender<Option<HttpResponse>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+        in_flight.remove(&self.key);
+    }
+}
+
+/// Gets the URL of a GET request, e.g. to identify identical requests.
+///
+/// # Arguments
+///
+/// * `request` - The request.
+///
+/// # Returns
+///
+/// The URL or `None` if the request isn't a GET request or can't be built.
+#[cfg(not(target_arch = "wasm32"))]
+fn get_url(request: &RequestBuilder) -> Option<String> {
+    request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .filter(|request| request.method() == reqwest::Method::GET)
+        .map(|request| request.url().to_string())
+}
+
+/// Adds the validators of a cached response (`If-None-Match` and `If-Modified-Since` headers) to a request.
+///
+/// # Arguments
+///
+/// * `request` - The request to send.
+/// * `cached` - The cached response of the same URL.
+///
+/// # Returns
+///
+/// The conditional request; unchanged if the cached response has neither an `ETag` nor a `Last-Modified` header.
+#[cfg(not(target_arch = "wasm32"))]
+fn conditional_request(mut request: RequestBuilder, cached: &HttpResponse) -> RequestBuilder {
+    if let Some(etag) = cached.headers.get(ETAG) {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = cached.headers.get(LAST_MODIFIED) {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    request
+}
+
+/// Represents the ways the `KeyRotation` middleware picks an API key for a request.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Rotation {
+    /// The same key is used until the provider rejects it because of its quota; the next key is used from then on.
+    #[default]
+    OnQuota,
+    /// Every request uses the next key in turn, spreading the requests over the quotas of all keys.
+    RoundRobin,
+}
+
+/// Middleware that rotates several API keys of a provider.
+///
+/// Requests are built by the services with the first key; the middleware replaces the value of the query parameter
+/// holding it with the selected key (see `Rotation`). When the provider rejects a key because of its quota or
+/// an access restriction (HTTP 429 or 403), the request is sent again with the next key until every key was tried.
+///
+/// It isn't available on `wasm32` targets, where requests can't be rebuilt with another key.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct KeyRotation {
+    keys: Vec<Secret>,
+    rotation: Rotation,
+    current: AtomicUsize,
+}
+
+/// `KeyRotation` constructors
+#[cfg(not(target_arch = "wasm32"))]
+impl KeyRotation {
+    /// Creates a new instance of the `KeyRotation` middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The API keys of the provider; the first one is the key the services build requests with.
+    /// * `rotation` - The way a key is picked for a request.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `KeyRotation` middleware.
+    pub fn new(keys: Vec<Secret>, rotation: Rotation) -> Self {
+        KeyRotation {
+            keys,
+            rotation,
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    /// Replaces the value of the query parameter holding one of the keys with a key.
+    ///
+    /// # Returns
+    ///
+    /// The request with the key or `None` if the request has no parameter with a key or can't be built.
+    fn with_key(&self, request: &RequestBuilder, key: &str) -> Option<RequestBuilder> {
+        let (client, request) = request.try_clone()?.build_split();
+        let mut request = request.ok()?;
+
+        let pairs: Vec<(String, String)> = request.url().query_pairs().into_owned().collect();
+        if !pairs.iter().any(|(_, value)| self.is_key(value)) {
+            return None;
+        }
+        request
+            .url_mut()
+            .query_pairs_mut()
+            .clear()
+            .extend_pairs(pairs.iter().map(|(name, value)| {
+                (
+                    name,
+                    if self.is_key(value) {
+                        key
+                    } else {
+                        value.as_str()
+                    },
+                )
+            }));
+
+        Some(RequestBuilder::from_parts(client, request))
+    }
+
+    /// Checks whether a value is one of the keys.
+    fn is_key(&self, value: &str) -> bool {
+        self.keys.iter().any(|key| key.expose() == value)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Middleware for KeyRotation {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        if self.keys.len() < 2 {
+            return next.run(request).await;
+        }
+
+        let first = match self.rotation {
+            Rotation::OnQuota => self.current.load(Ordering::Relaxed),
+            Rotation::RoundRobin => self.current.fetch_add(1, Ordering::Relaxed),
+        };
+        for attempt in 0..self.keys.len() {
+            let index = (first + attempt) % self.keys.len();
+            let Some(keyed_request) = self.with_key(&request, self.keys[index].expose()) else {
+                return next.run(request).await;
+            };
+
+            let response = next.run(keyed_request).await?;
+            let is_rejected = matches!(
+                response.status,
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::FORBIDDEN
+            );
+            if !is_rejected || attempt + 1 == self.keys.len() {
+                return Ok(response);
+            }
+            if self.rotation == Rotation::OnQuota {
+                self.current
+                    .store((index + 1) % self.keys.len(), Ordering::Relaxed);
+            }
+        }
+
+        next.run(request).await
+    }
+}
+
+/// Returns the `Retry-After` delay of a rate limited (HTTP 429) response.
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    if response.status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    let value = response.headers.get(RETRY_AFTER)?.to_str().ok()?;
+
+    parse_retry_after(value, now())
+}
+
+/// Returns the current time.
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// Returns the current time from the JavaScript clock, because `SystemTime::now` panics on `wasm32` targets.
+#[cfg(target_arch = "wasm32")]
+fn now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(js_sys::Date::now() as u64)
+}
+
+/// Parses the value of the `Retry-After` header.
+///
+/// # Arguments
+///
+/// * `value` - The header value, either a number of seconds or an HTTP date.
+/// * `now` - The current time used to compute the delay until an HTTP date.
+///
+/// # Returns
+///
+/// The delay before the request can be retried or `None` if the value is invalid.
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+/// Sends a rate limited request again through the rest of the pipeline after waiting for the `Retry-After` delay.
+#[cfg(not(target_arch = "wasm32"))]
+async fn retry(
+    request: RequestBuilder,
+    delay: Duration,
+    next: Next<'_>,
+) -> Result<Option<HttpResponse>> {
+    tokio::time::sleep(delay).await;
+
+    next.run(request).await.map(Some)
+}
+
+/// Retrying is disabled on `wasm32` targets, where there is no timer outside of the browser event loop.
+#[cfg(target_arch = "wasm32")]
+async fn retry(
+    _request: RequestBuilder,
+    _delay: Duration,
+    _next: Next<'_>,
+) -> Result<Option<HttpResponse>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("120", Some(Duration::from_secs(120)))]
+    #[case(" 0 ", Some(Duration::ZERO))]
+    #[case("Thu, 01 Jan 1970 00:01:00 GMT", Some(Duration::from_secs(30)))]
+    #[case("Thu, 01 Jan 1970 00:00:00 GMT", Some(Duration::ZERO))]
+    #[case("soon", None)]
+    fn test_parse_retry_after(#[case] value: &str, #[case] expected: Option<Duration>) {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(30);
+
+        assert_eq!(parse_retry_after(value, now), expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_send_retries_once() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let rate_limited_endpoint = mock_server
+            .mock("GET", "/")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create_async()
+            .await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body("ok")
+            .create_async()
+            .await;
+
+        let request = Client::new().get(mock_server.url());
+        let response = Pipeline::default().send(request, "Test API").await.unwrap();
+
+        rate_limited_endpoint.assert_async().await;
+        mock_endpoint.assert_async().await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.body, "ok");
+    }
+
+    #[rstest]
+    #[case("0", 2)]
+    #[case("3600", 1)]
+    #[tokio::test]
+    async fn test_send_quota_exceeded(#[case] retry_after: &str, #[case] hits: usize) {
+        let mut mock_server = mockito::Server::new_async().await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/")
+            .with_status(429)
+            .with_header("retry-after", retry_after)
+            .expect(hits)
+            .create_async()
+            .await;
+
+        let request = Client::new().get(mock_server.url());
+        let result: WeatherApiError = Pipeline::default()
+            .send(request, "Test API")
+            .await
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+
+        mock_endpoint.assert_async().await;
+        assert!(matches!(result, WeatherApiError::QuotaExceeded(_)));
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct TestData {
+        value: u8,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct TestErrorData {
+        message: String,
+    }
+
+    fn to_test_error(_: StatusCode, error_data: TestErrorData) -> WeatherApiError {
+        WeatherApiError::Server(error_data.message)
+    }
+
+    #[rstest]
+    #[case(200, r#"{"value": 7}"#, Some(7))]
+    #[case(500, r#"{"message": "failure"}"#, None)]
+    #[tokio::test]
+    async fn test_request_json(
+        #[case] status: usize,
+        #[case] body: &str,
+        #[case] expected: Option<u8>,
+    ) {
+        let mut mock_server = mockito::Server::new_async().await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/")
+            .with_status(status)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let request = Client::new().get(mock_server.url());
+        let result: Result<TestData> = Pipeline::default()
+            .request_json(request, "Test API", to_test_error)
+            .await;
+
+        mock_endpoint.assert_async().await;
+        match expected {
+            Some(value) => assert_eq!(result.unwrap(), TestData { value }),
+            None => assert!(matches!(
+                result.unwrap_err().downcast().unwrap(),
+                WeatherApiError::Server(message) if message == "failure"
+            )),
+        }
+    }
+
+    #[rstest]
+    #[case(ParsingMode::Lenient, Some(7), 1)]
+    #[case(ParsingMode::Strict, None, 0)]
+    #[tokio::test]
+    async fn test_request_json_parsing_mode(
+        #[case] parsing_mode: ParsingMode,
+        #[case] expected: Option<u8>,
+        #[case] expected_warnings: usize,
+    ) {
+        #[derive(serde::Deserialize)]
+        struct LenientTestData {
+            #[serde(deserialize_with = "lenient::number")]
+            value: u8,
+        }
+
+        let mut mock_server = mockito::Server::new_async().await;
+        mock_server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(r#"{"value": "7"}"#)
+            .create_async()
+            .await;
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let reported_warnings = warnings.clone();
+
+        let pipeline = Pipeline::default()
+            .parsing_mode(parsing_mode)
+            .on_warning(move |warning| reported_warnings.lock().unwrap().push(warning.to_owned()));
+        let request = Client::new().get(mock_server.url());
+        let result: Result<LenientTestData> = pipeline
+            .request_json(request, "Test API", to_test_error)
+            .await;
+
+        assert_eq!(result.ok().map(|data| data.value), expected);
+        let warnings = warnings.lock().unwrap();
+        assert_eq!(warnings.len(), expected_warnings);
+        assert!(warnings
+            .iter()
+            .all(|warning| warning == "Test API: the number '7' is sent as a string"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_logging_and_cache() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/current.json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"value": 1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let log_lines = lines.clone();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let reported_requests = requests.clone();
+
+        let pipeline = Pipeline::default()
+            .with(Logging::new(move |line| {
+                log_lines.lock().unwrap().push(line.to_owned())
+            }))
+            .with(Cache::new(Duration::from_secs(60)))
+            .on_request(move |metadata| reported_requests.lock().unwrap().push(metadata.clone()));
+        for _ in 0..2 {
+            let request = Client::new()
+                .get(format!("{}/current.json", mock_server.url()))
+                .query(&[("key", "secret")]);
+            let result: TestData = pipeline
+                .request_json(request, "Test API", to_test_error)
+                .await
+                .unwrap();
+            assert_eq!(result, TestData { value: 1 });
+        }
+
+        mock_endpoint.assert_async().await;
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Test API: GET http://"));
+        assert!(lines[0].ends_with("/current.json -> 200 OK"));
+        assert!(!lines[0].contains("secret"));
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|metadata| metadata.provider == "Test API"));
+        assert!(!requests[0].cache_hit);
+        assert!(requests[1].cache_hit);
+        assert!(requests[1].fetched_at > 0);
+    }
+
+    #[rstest]
+    #[case(Rotation::OnQuota, &["k1", "k2", "k2", "k2"])]
+    #[case(Rotation::RoundRobin, &["k1", "k2", "k2", "k1", "k2"])]
+    #[tokio::test]
+    async fn test_key_rotation(#[case] rotation: Rotation, #[case] expected_keys: &[&str]) {
+        let mut mock_server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for (key, status) in [("k1", 429), ("k2", 200)] {
+            let mock = mock_server
+                .mock("GET", "/current.json")
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::UrlEncoded("key".into(), key.into()),
+                    mockito::Matcher::UrlEncoded("q".into(), "London".into()),
+                ]))
+                .with_status(status)
+                .with_body(r#"{"value": 1}"#)
+                .expect_at_least(1)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let log_keys = keys.clone();
+
+        let pipeline = Pipeline::default()
+            .with(KeyRotation::new(
+                vec![Secret::from("k1"), Secret::from("k2")],
+                rotation,
+            ))
+            .with(KeyLog(log_keys));
+        for _ in 0..3 {
+            let request = Client::new()
+                .get(format!("{}/current.json", mock_server.url()))
+                .query(&[("key", "k1"), ("q", "London")]);
+            let result: TestData = pipeline
+                .request_json(request, "Test API", to_test_error)
+                .await
+                .unwrap();
+            assert_eq!(result, TestData { value: 1 });
+        }
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(*keys.lock().unwrap(), expected_keys);
+    }
+
+    /// Middleware recording the `key` query parameter of requests.
+    #[derive(Debug)]
+    struct KeyLog(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl Middleware for KeyLog {
+        async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+            let key = request
+                .try_clone()
+                .and_then(|request| request.build().ok())
+                .and_then(|request| {
+                    request
+                        .url()
+                        .query_pairs()
+                        .find(|(name, _)| name == "key")
+                        .map(|(_, key)| key.into_owned())
+                });
+            self.0.lock().unwrap().extend(key);
+
+            next.run(request).await
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_build_client_decodes_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let hours: Vec<String> = (0..2000)
+            .map(|hour| {
+                format!(r#"{{"time_epoch": {hour}, "temp_c": 12.5, "condition": "Cloudy"}}"#)
+            })
+            .collect();
+        let body = format!(r#"{{"hour": [{}]}}"#, hours.join(","));
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert!(compressed.len() < body.len() / 10);
+
+        let mut mock_server = mockito::Server::new_async().await;
+        let mock_endpoint = mock_server
+            .mock("GET", "/forecast.json")
+            .match_header(
+                "accept-encoding",
+                mockito::Matcher::Regex("gzip".to_owned()),
+            )
+            .with_status(200)
+            .with_header("content-encoding", "gzip")
+            .with_body(compressed)
+            .create_async()
+            .await;
+
+        let client = build_client(None, None).unwrap();
+        let request = client.get(format!("{}/forecast.json", mock_server.url()));
+        let response = Pipeline::default().send(request, "Test API").await.unwrap();
+
+        mock_endpoint.assert_async().await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.body, body);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_cache_revalidation() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let full_endpoint = mock_server
+            .mock("GET", "/forecast.json")
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("etag", r#""v1""#)
+            .with_body(r#"{"value": 2}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let not_modified_endpoint = mock_server
+            .mock("GET", "/forecast.json")
+            .match_header("if-none-match", r#""v1""#)
+            .with_status(304)
+            .expect(2)
+            .create_async()
+            .await;
+
+        // a zero time to live revalidates every request after the first one
+        let pipeline = Pipeline::default().with(Cache::new(Duration::ZERO));
+        for _ in 0..3 {
+            let request = Client::new().get(format!("{}/forecast.json", mock_server.url()));
+            let result: TestData = pipeline
+                .request_json(request, "Test API", to_test_error)
+                .await
+                .unwrap();
+            assert_eq!(result, TestData { value: 2 });
+        }
+
+        full_endpoint.assert_async().await;
+        not_modified_endpoint.assert_async().await;
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_cache_dir() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let full_endpoint = mock_server
+            .mock("GET", "/forecast.json")
+            .match_query(mockito::Matcher::Any)
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("etag", r#""v1""#)
+            .with_body(r#"{"value": 2}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let not_modified_endpoint = mock_server
+            .mock("GET", "/forecast.json")
+            .match_query(mockito::Matcher::Any)
+            .match_header("if-none-match", r#""v1""#)
+            .with_status(304)
+            .expect(1)
+            .create_async()
+            .await;
+        let dir = std::env::temp_dir().join("weather-api-services-test-cache");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // every pipeline stands for a separate process, which only shares the cache directory
+        for (ttl, expected_cache_hit) in [
+            (Duration::ZERO, false),
+            (Duration::ZERO, true),
+            (Duration::from_secs(60), true),
+        ] {
+            let pipeline = Pipeline::default().with(Cache::new(ttl).dir(&dir));
+            let request =
+                Client::new().get(format!("{}/forecast.json?key=secret", mock_server.url()));
+            let response = pipeline.send(request, "Test API").await.unwrap();
+            assert_eq!(response.body, r#"{"value": 2}"#);
+            assert_eq!(response.cache_hit, expected_cache_hit);
+        }
+
+        full_endpoint.assert_async().await;
+        not_modified_endpoint.assert_async().await;
+        let files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert!(!files[0].contains("secret"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_single_flight() {
+        let mut mock_server = mockito::Server::new_async().await;
+        let endpoint = mock_server
+            .mock("GET", "/current.json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"value": 2}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let pipeline = Pipeline::default();
+        let request = |address: &str| {
+            Client::new().get(format!("{}/current.json?q={address}", mock_server.url()))
+        };
+        let (london, same_london, paris) = tokio::join!(
+            pipeline.request_json::<TestData, TestErrorData>(
+                request("London"),
+                "Test API",
+                to_test_error
+            ),
+            pipeline.request_json::<TestData, TestErrorData>(
+                request("London"),
+                "Test API",
+                to_test_error
+            ),
+            pipeline.request_json::<TestData, TestErrorData>(
+                request("Paris"),
+                "Test API",
+                to_test_error
+            ),
+        );
+
+        endpoint.assert_async().await;
+        assert_eq!(london.unwrap(), TestData { value: 2 });
+        assert_eq!(same_london.unwrap(), TestData { value: 2 });
+        assert_eq!(paris.unwrap(), TestData { value: 2 });
+    }
+
+    #[rstest]
+    fn test_rate_limit_reserve() {
+        let rate_limit = RateLimit::new(Duration::from_secs(1));
+        let now = Instant::now();
+
+        assert_eq!(rate_limit.reserve(now), Duration::ZERO);
+        assert_eq!(rate_limit.reserve(now), Duration::from_secs(1));
+        assert_eq!(rate_limit.reserve(now), Duration::from_secs(2));
+        assert_eq!(
+            rate_limit.reserve(now + Duration::from_secs(5)),
+            Duration::ZERO
+        );
+    }
+}
@@ -1,15 +1,53 @@
+/// Module that contains a blocking (synchronous) client for non-async programs (`blocking` feature)
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+/// Module that contains the in-process LRU cache of the results of any weather API service
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
+/// Module that contains climate normals computed from the Open-Meteo historical weather archive
+pub mod climatology;
+/// Module that contains the parser of dates and times given in recognized formats or in natural language
+pub mod dates;
+/// Module that contains weather values derived from other values (e.g. "feels like" temperature)
+pub mod derived;
+/// Module that contains middleware recording provider responses to disk, replaying them and planning requests without sending them
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fixtures;
+/// Module that contains the decoding of plus codes and what3words addresses to coordinates
+pub mod geocoding;
+/// Module that contains the shared HTTP request pipeline (with logging, retry, rate limit and cache middleware) of weather API services
+pub mod http;
 /// Module that contains structs that represent data from different providers
 pub mod models;
 /// Module that contains structs and methods for working with the OpenWeather API
 pub mod openweather_service;
+/// Module that contains extra provider-specific options of weather API services
+pub mod options;
+/// Module that contains the output representations of weather data with temperatures and speeds in a unit system
+pub mod output;
+/// Module that contains typed quantities (temperature, speed and pressure) carrying their unit conversions
+pub mod quantities;
+/// Module that contains the wrapper of credentials redacted when they're printed
+pub mod secret;
+/// Module that contains unit systems and conversions of weather data
+pub mod units;
+/// Module that contains the validation of weather data against physically plausible ranges
+pub mod validation;
 /// Module that contains structs and methods for working with the Weather API
 pub mod weatherapi_service;
+/// Module that contains JavaScript bindings for browser and edge runtimes (`web` feature)
+#[cfg(feature = "web")]
+pub mod web;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use owo_colors::OwoColorize;
 use thiserror::Error;
 
 use models::*;
+use options::ServiceOptions;
+use secret::Secret;
+use units::{ConvertUnits, Units};
 
 /// Represents an error that occurs when there is an issue with parsing date and time data.
 #[derive(Error, Debug)]
@@ -22,7 +60,7 @@ pub enum DateTimeError {
     /// # Parameters
     ///
     /// * `0` - A string representing the invalid datetime string that caused the error.
-    #[error("Invalid datetime - {0}. Please use a recognized datetime format (e.g., 'MM/DD/YYYY' or 'YYYY-MM-DD hh:mm' or 'YYYY-MM-DD')")]
+    #[error("Invalid datetime - {0}. Please use a recognized datetime format (e.g., 'MM/DD/YYYY', 'YYYY-MM-DD hh:mm', 'tomorrow 18:00' or 'in 3 hours')")]
     Parse(String),
 }
 
@@ -58,6 +96,38 @@ pub enum WeatherApiError {
     #[error("Provider server response error '{0}'")]
     Server(String),
 
+    /// Represents an error when the provider rejects the request because of a missing, invalid or disabled API key.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the error message from the provider server.
+    #[error("Provider rejected the API key '{0}'")]
+    Unauthorized(String),
+
+    /// Represents an error when the request quota (rate limit or monthly calls) of the API key is exceeded.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the error message from the provider server.
+    #[error("Provider request quota is exceeded '{0}'")]
+    QuotaExceeded(String),
+
+    /// Represents an error when the provider can't find a location matching the requested address.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the error message from the provider server.
+    #[error("Provider can't find the location '{0}'")]
+    LocationNotFound(String),
+
+    /// Represents an error when the requested date is outside of the range supported by the provider.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the error message from the provider server.
+    #[error("Requested date is out of the range supported by the provider '{0}'")]
+    DateOutOfRange(String),
+
     /// Represents an error when processing the body text from the response.
     ///
     /// # Parameters
@@ -76,7 +146,10 @@ pub enum WeatherApiError {
 }
 
 /// The `WeatherApi` trait defines the contract for retrieving weather data for a given address and optional date.
-#[async_trait]
+///
+/// On `wasm32` targets the returned futures are not `Send`, because the browser `fetch` API is single-threaded.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait WeatherApi {
     /// Asynchronously retrieves weather data for a specific address and date (if provided).
     ///
@@ -89,4 +162,125 @@ pub trait WeatherApi {
     ///
     /// A `Result` containing the retrieved weather data or an error if the request fails.
     async fn get_weather_data(&self, address: &str, date: &Option<String>) -> Result<WeatherData>;
+
+    /// Asynchronously retrieves the original response of the provider to the weather data request, without
+    /// normalizing it to `WeatherData` (e.g. for fields `WeatherData` doesn't carry).
+    ///
+    /// Providers without access to their original responses return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the JSON response of the provider or an error if the request fails.
+    async fn get_raw_weather_data(
+        &self,
+        _address: &str,
+        _date: &Option<String>,
+    ) -> Result<serde_json::Value> {
+        Err(WeatherApiError::Feature("raw provider responses".yellow().to_string()).into())
+    }
+
+    /// Checks whether the provider resolves postal code queries (e.g. `zip:10115,DE`, see
+    /// `geocoding::PostalCode`) natively; other providers get the coordinates of postal codes instead.
+    fn supports_postal_codes(&self) -> bool {
+        false
+    }
+
+    /// Asynchronously retrieves the daily and hourly weather forecast for a specific address.
+    ///
+    /// Providers without forecast support return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which the forecast is requested.
+    /// * `days` - The number of forecasted days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the forecasted days or an error if the request fails.
+    async fn get_forecast(&self, _address: &str, _days: u8) -> Result<Vec<ForecastDay>> {
+        Err(WeatherApiError::Feature("forecast".yellow().to_string()).into())
+    }
+
+    /// Asynchronously retrieves marine weather data (tides, swell and water temperature) for a specific address.
+    ///
+    /// Providers without marine data support return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address (a coastal location) for which marine data is requested.
+    /// * `days` - The number of days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the marine data or an error if the request fails.
+    async fn get_marine_data(&self, _address: &str, _days: u8) -> Result<MarineData> {
+        Err(WeatherApiError::Feature("marine data (tides and swell)".yellow().to_string()).into())
+    }
+
+    /// Asynchronously retrieves the snow report (daily snowfall and temperatures) for a specific address.
+    ///
+    /// Providers without snow data support return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address (e.g. a ski resort) for which the snow report is requested.
+    /// * `days` - The number of days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the snow report or an error if the request fails.
+    async fn get_snow_report(&self, _address: &str, _days: u8) -> Result<SnowReport> {
+        Err(WeatherApiError::Feature("snow report".yellow().to_string()).into())
+    }
+
+    /// Asynchronously retrieves the observation stations near a specific address, nearest first.
+    ///
+    /// Providers without station metadata return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address near which stations are requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the stations or an error if the request fails.
+    async fn get_stations(&self, _address: &str) -> Result<Vec<Station>> {
+        Err(WeatherApiError::Feature("station metadata".yellow().to_string()).into())
+    }
+
+    /// Asynchronously retrieves the minutely precipitation forecast for the next hour (a nowcast) for a specific
+    /// address.
+    ///
+    /// Providers without minutely forecasts return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which the nowcast is requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the forecasted minutes or an error if the request fails.
+    async fn get_nowcast(&self, _address: &str) -> Result<Vec<MinutePrecipitation>> {
+        Err(WeatherApiError::Feature("minutely precipitation forecast".yellow().to_string()).into())
+    }
+
+    /// Asynchronously retrieves the active weather alerts (e.g. storm warnings) issued for a specific address.
+    ///
+    /// Providers without weather alerts return the `WeatherApiError::Feature` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which alerts are requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the alerts (empty if there are none) or an error if the request fails.
+    async fn get_alerts(&self, _address: &str) -> Result<Vec<WeatherAlert>> {
+        Err(WeatherApiError::Feature("weather alerts".yellow().to_string()).into())
+    }
 }
@@ -1,13 +1,27 @@
+/// Module that contains lenient deserializers tolerating provider payload drift (numeric strings, nulls, missing blocks)
+pub mod lenient;
+/// Module that contains structs that represent data from Open-Meteo APIs
+pub mod openmeteo_model;
 /// Module that contains structs that represent data from OpenWeather API provider
 pub mod openweather_model;
 /// Module that contains structs that represent data from Weather API provider
 pub mod weatherapi_model;
+/// Module that contains structs that represent data from the what3words API
+pub mod what3words_model;
 
-use serde::Serialize;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use openweather_model::OpenWeatherData;
-use weatherapi_model::{WeatherApiData, WeatherApiHistoryData};
+use super::derived;
+use super::quantities::{Celsius, HectoPascal, MetersPerSecond};
+use openweather_model::{
+    ForecastItem, OpenWeatherData, OpenWeatherForecastData, OpenWeatherOneCallData,
+};
+use weatherapi_model::{
+    WeatherApiAlertsData, WeatherApiData, WeatherApiForecastData, WeatherApiHistoryData,
+    WeatherApiMarineData,
+};
 
 /// Represents an error that occurs when there is an issue with parsing JSON response data.
 #[derive(Error, Debug)]
@@ -22,17 +36,285 @@ pub enum WeatherDataError {
     /// * `0` - The `serde_json::Error` indicating the specific JSON parsing error.
     #[error("Failed to parse JSON response")]
     JsonParse(#[from] serde_json::Error),
+
+    /// An error indicating that a valid JSON response doesn't contain the requested data.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string describing the missing data.
+    #[error("The response doesn't contain {0}")]
+    Missing(&'static str),
+
+    /// An error indicating that the response contains physically implausible values (see `validation::validate`).
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string listing the implausible values.
+    #[error("The response contains implausible values - {0}")]
+    Implausible(String),
+}
+
+/// The visibility reported by OpenWeather when it's missing in the response (the maximum it reports).
+const OPENWEATHER_MAX_VISIBILITY: u32 = 10_000;
+
+/// The attribution of OpenWeather data required by its terms of use.
+pub const OPENWEATHER_ATTRIBUTION: &str =
+    "Weather data provided by OpenWeather (https://openweathermap.org)";
+
+/// The attribution of Weather API data required by its terms of use.
+pub const WEATHERAPI_ATTRIBUTION: &str = "Powered by WeatherAPI.com (https://www.weatherapi.com)";
+
+/// Represents the source of weather data and the attribution required by its provider.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DataSource {
+    /// The name of the provider (e.g. `OpenWeather`).
+    pub provider: String,
+    /// The identifier of the weather station; `None` if the provider doesn't report it.
+    pub station: Option<String>,
+    /// The attribution text required by the provider; `None` if it isn't required.
+    pub attribution: Option<String>,
 }
 
 /// Represents weather data with temperature, humidity, pressure, wind speed, visibility, and description.
-#[derive(Serialize, Debug)]
+///
+/// The struct is non-exhaustive, so fields can be added without breaking library users; use `WeatherData::builder`
+/// to create it outside of the library. It round-trips through serde, so normalized data can be cached and replayed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub struct WeatherData {
-    pub temp: f32,
+    pub temp: Celsius,
+    /// The "feels like" temperature; computed from the temperature, humidity and wind speed
+    /// if the provider doesn't supply it.
+    pub feels_like: Celsius,
     pub humidity: u8,
-    pub pressure: u16,
-    pub wind_speed: f32,
-    pub visibility: u16,
+    /// The dew point; computed from the temperature and humidity if the provider doesn't supply it.
+    pub dew_point: Celsius,
+    /// The sea-level pressure in hectopascals.
+    pub pressure: HectoPascal,
+    /// The pressure at the elevation of the station (surface pressure) in hectopascals; `None` if the provider
+    /// doesn't report it.
+    pub station_pressure: Option<HectoPascal>,
+    pub wind_speed: MetersPerSecond,
+    pub visibility: u32,
     pub description: String,
+    /// The UV index; `None` if the provider doesn't support it.
+    pub uv_index: Option<f32>,
+    /// `true` if it's day (between sunrise and sunset) at the location, `false` at night.
+    pub is_day: bool,
+    /// The time of the observation (or of the forecasted hour) reported by the provider as a Unix timestamp;
+    /// `None` if the provider doesn't report it.
+    pub observed_at: Option<i64>,
+    /// The source (provider and station) of the data.
+    pub source: DataSource,
+}
+
+/// `WeatherData` constructors and accessors
+impl WeatherData {
+    /// Creates a builder of `WeatherData`.
+    ///
+    /// # Returns
+    ///
+    /// A `WeatherDataBuilder` with zero values, an empty description, day time and the default source.
+    pub fn builder() -> WeatherDataBuilder {
+        WeatherDataBuilder {
+            is_day: true,
+            ..WeatherDataBuilder::default()
+        }
+    }
+
+    /// Returns the temperature.
+    pub fn temp(&self) -> Celsius {
+        self.temp
+    }
+
+    /// Returns the "feels like" temperature.
+    pub fn feels_like(&self) -> Celsius {
+        self.feels_like
+    }
+
+    /// Returns the relative humidity in percent.
+    pub fn humidity(&self) -> u8 {
+        self.humidity
+    }
+
+    /// Returns the dew point.
+    pub fn dew_point(&self) -> Celsius {
+        self.dew_point
+    }
+
+    /// Returns the sea-level pressure in hectopascals.
+    pub fn pressure(&self) -> HectoPascal {
+        self.pressure
+    }
+
+    /// Returns the station (surface) pressure in hectopascals; `None` if the provider doesn't report it.
+    pub fn station_pressure(&self) -> Option<HectoPascal> {
+        self.station_pressure
+    }
+
+    /// Returns the wind speed.
+    pub fn wind_speed(&self) -> MetersPerSecond {
+        self.wind_speed
+    }
+
+    /// Returns the visibility in meters.
+    pub fn visibility(&self) -> u32 {
+        self.visibility
+    }
+
+    /// Returns the description of the weather condition.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the UV index; `None` if the provider doesn't support it.
+    pub fn uv_index(&self) -> Option<f32> {
+        self.uv_index
+    }
+
+    /// Returns `true` if it's day at the location, `false` at night.
+    pub fn is_day(&self) -> bool {
+        self.is_day
+    }
+
+    /// Returns the time of the observation as a Unix timestamp; `None` if the provider doesn't report it.
+    pub fn observed_at(&self) -> Option<i64> {
+        self.observed_at
+    }
+
+    /// Returns the source (provider and station) of the data.
+    pub fn source(&self) -> &DataSource {
+        &self.source
+    }
+}
+
+/// Builder of `WeatherData`, so fields can be added to it without breaking library users (e.g. tests and custom
+/// providers constructing it).
+#[derive(Debug, Default)]
+pub struct WeatherDataBuilder {
+    temp: Celsius,
+    feels_like: Option<Celsius>,
+    humidity: u8,
+    dew_point: Option<Celsius>,
+    pressure: HectoPascal,
+    station_pressure: Option<HectoPascal>,
+    wind_speed: MetersPerSecond,
+    visibility: u32,
+    description: String,
+    uv_index: Option<f32>,
+    is_day: bool,
+    observed_at: Option<i64>,
+    source: DataSource,
+}
+
+/// `WeatherDataBuilder` methods
+impl WeatherDataBuilder {
+    /// Sets the temperature.
+    pub fn temp(mut self, temp: Celsius) -> Self {
+        self.temp = temp;
+        self
+    }
+
+    /// Sets the "feels like" temperature; computed from the temperature, humidity and wind speed if not set.
+    pub fn feels_like(mut self, feels_like: Celsius) -> Self {
+        self.feels_like = Some(feels_like);
+        self
+    }
+
+    /// Sets the relative humidity in percent.
+    pub fn humidity(mut self, humidity: u8) -> Self {
+        self.humidity = humidity;
+        self
+    }
+
+    /// Sets the dew point; computed from the temperature and humidity if not set.
+    pub fn dew_point(mut self, dew_point: Celsius) -> Self {
+        self.dew_point = Some(dew_point);
+        self
+    }
+
+    /// Sets the sea-level pressure in hectopascals.
+    pub fn pressure(mut self, pressure: HectoPascal) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Sets the station (surface) pressure in hectopascals; `None` if the provider doesn't report it.
+    pub fn station_pressure(mut self, station_pressure: Option<HectoPascal>) -> Self {
+        self.station_pressure = station_pressure;
+        self
+    }
+
+    /// Sets the wind speed.
+    pub fn wind_speed(mut self, wind_speed: MetersPerSecond) -> Self {
+        self.wind_speed = wind_speed;
+        self
+    }
+
+    /// Sets the visibility in meters.
+    pub fn visibility(mut self, visibility: u32) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Sets the description of the weather condition.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the UV index; `None` if the provider doesn't support it.
+    pub fn uv_index(mut self, uv_index: Option<f32>) -> Self {
+        self.uv_index = uv_index;
+        self
+    }
+
+    /// Sets whether it's day at the location; day if not set.
+    pub fn is_day(mut self, is_day: bool) -> Self {
+        self.is_day = is_day;
+        self
+    }
+
+    /// Sets the time of the observation as a Unix timestamp; `None` if the provider doesn't report it.
+    pub fn observed_at(mut self, observed_at: Option<i64>) -> Self {
+        self.observed_at = observed_at;
+        self
+    }
+
+    /// Sets the source (provider and station) of the data.
+    pub fn source(mut self, source: DataSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Creates the `WeatherData`.
+    ///
+    /// The "feels like" temperature and the dew point are computed if they aren't set.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `WeatherData`.
+    pub fn build(self) -> WeatherData {
+        WeatherData {
+            temp: self.temp,
+            feels_like: self
+                .feels_like
+                .unwrap_or_else(|| derived::feels_like(self.temp, self.humidity, self.wind_speed)),
+            humidity: self.humidity,
+            dew_point: self
+                .dew_point
+                .unwrap_or_else(|| derived::dew_point(self.temp, self.humidity)),
+            pressure: self.pressure,
+            station_pressure: self.station_pressure,
+            wind_speed: self.wind_speed,
+            visibility: self.visibility,
+            description: self.description,
+            uv_index: self.uv_index,
+            is_day: self.is_day,
+            observed_at: self.observed_at,
+            source: self.source,
+        }
+    }
 }
 
 /// Converts data from OpenWeather API to `WeatherData`
@@ -41,14 +323,37 @@ impl From<OpenWeatherData> for WeatherData {
         let main = openweather_data.main;
         let mut weather = openweather_data.weather;
         let wind = openweather_data.wind;
+        let is_day = match (openweather_data.dt, openweather_data.sys) {
+            (Some(time), Some(sys)) => derived::is_day(time, sys.sunrise, sys.sunset),
+            _ => true,
+        };
+
+        let temp = Celsius(main.temp);
+        let wind_speed = MetersPerSecond(wind.speed);
 
         WeatherData {
-            temp: main.temp,
+            temp,
+            feels_like: main.feels_like.map_or_else(
+                || derived::feels_like(temp, main.humidity, wind_speed),
+                Celsius,
+            ),
             humidity: main.humidity,
-            pressure: main.pressure,
-            wind_speed: wind.speed,
-            visibility: openweather_data.visibility,
+            dew_point: derived::dew_point(temp, main.humidity),
+            pressure: HectoPascal(main.pressure),
+            station_pressure: main.grnd_level.map(HectoPascal),
+            wind_speed,
+            visibility: openweather_data
+                .visibility
+                .unwrap_or(OPENWEATHER_MAX_VISIBILITY),
             description: weather.pop().map_or_else(String::new, |w| w.description),
+            uv_index: None,
+            is_day,
+            observed_at: openweather_data.dt,
+            source: DataSource {
+                provider: "OpenWeather".to_owned(),
+                station: None,
+                attribution: Some(OPENWEATHER_ATTRIBUTION.to_owned()),
+            },
         }
     }
 }
@@ -58,47 +363,485 @@ impl From<WeatherApiData> for WeatherData {
     fn from(weatherapi_data: WeatherApiData) -> Self {
         let current = weatherapi_data.current;
 
+        let temp = Celsius(current.temp_c);
+        let wind_speed = MetersPerSecond::from_kph(current.wind_kph);
+
         WeatherData {
-            temp: current.temp_c,
+            temp,
+            feels_like: current.feelslike_c.map_or_else(
+                || derived::feels_like(temp, current.humidity, wind_speed),
+                Celsius,
+            ),
             humidity: current.humidity,
-            pressure: current.pressure_mb as u16,
-            wind_speed: km_per_hour_to_m_per_sec(current.wind_kph),
+            dew_point: current
+                .dewpoint_c
+                .map_or_else(|| derived::dew_point(temp, current.humidity), Celsius),
+            pressure: HectoPascal::rounded(current.pressure_mb),
+            station_pressure: None,
+            wind_speed,
             visibility: km_to_m(current.vis_km),
             description: current.condition.text,
+            uv_index: current.uv,
+            is_day: current.is_day != 0,
+            observed_at: current.last_updated_epoch,
+            source: DataSource {
+                provider: "Weather API".to_owned(),
+                station: None,
+                attribution: Some(WEATHERAPI_ATTRIBUTION.to_owned()),
+            },
         }
     }
 }
 
 /// Converts data for specific date in history from Weather API to `WeatherData`.
-impl From<WeatherApiHistoryData> for WeatherData {
-    fn from(mut weatherapi_history_data: WeatherApiHistoryData) -> Self {
+///
+/// Fails with `WeatherDataError::Missing` if the response has no hours for the date.
+impl TryFrom<WeatherApiHistoryData> for WeatherData {
+    type Error = WeatherDataError;
+
+    fn try_from(mut weatherapi_history_data: WeatherApiHistoryData) -> Result<Self, Self::Error> {
         let currents = weatherapi_history_data
             .forecast
             .forecastday
             .pop()
-            .unwrap()
-            .hour;
-        let current = currents.get(0).unwrap();
+            .map(|forecast_day| forecast_day.hour)
+            .unwrap_or_default();
+        let current = currents
+            .first()
+            .ok_or(WeatherDataError::Missing("the weather of the date"))?;
 
-        WeatherData {
-            temp: current.temp_c,
+        let temp = Celsius(current.temp_c);
+        let wind_speed = MetersPerSecond::from_kph(current.wind_kph);
+
+        Ok(WeatherData {
+            temp,
+            feels_like: current.feelslike_c.map_or_else(
+                || derived::feels_like(temp, current.humidity, wind_speed),
+                Celsius,
+            ),
             humidity: current.humidity,
-            pressure: current.pressure_mb as u16,
-            wind_speed: km_per_hour_to_m_per_sec(current.wind_kph),
+            dew_point: current
+                .dewpoint_c
+                .map_or_else(|| derived::dew_point(temp, current.humidity), Celsius),
+            pressure: HectoPascal::rounded(current.pressure_mb),
+            station_pressure: None,
+            wind_speed,
             visibility: km_to_m(current.vis_km),
             description: current.condition.text.clone(),
+            uv_index: current.uv,
+            is_day: current.is_day != 0,
+            observed_at: current.time_epoch,
+            source: DataSource {
+                provider: "Weather API".to_owned(),
+                station: None,
+                attribution: Some(WEATHERAPI_ATTRIBUTION.to_owned()),
+            },
+        })
+    }
+}
+
+/// Represents the forecast for a day with temperature range, precipitation and hourly forecasts.
+#[derive(Serialize, Clone, Debug)]
+pub struct ForecastDay {
+    /// The date of the forecast in the `YYYY-MM-DD` format.
+    pub date: String,
+    pub min_temp: Celsius,
+    pub max_temp: Celsius,
+    pub description: String,
+    /// The chance of rain in percent.
+    pub chance_of_rain: u8,
+    /// The chance of snow in percent.
+    pub chance_of_snow: u8,
+    /// The expected amount of precipitation in millimeters.
+    pub precipitation: f32,
+    pub hours: Vec<ForecastHour>,
+}
+
+/// Represents the forecast for an hour (or a step of several hours) of a day.
+#[derive(Serialize, Clone, Debug)]
+pub struct ForecastHour {
+    /// The local time of the forecast at the location in the `YYYY-MM-DD hh:mm` format.
+    pub time: String,
+    /// The time of the forecast as a Unix timestamp; `None` if the provider doesn't report it.
+    pub timestamp: Option<i64>,
+    pub temp: Celsius,
+    pub description: String,
+    pub wind_speed: MetersPerSecond,
+    /// The chance of rain in percent.
+    pub chance_of_rain: u8,
+    /// The chance of snow in percent.
+    pub chance_of_snow: u8,
+    /// The expected amount of precipitation in millimeters.
+    pub precipitation: f32,
+}
+
+/// Converts forecast data from Weather API to a list of `ForecastDay`.
+impl From<WeatherApiForecastData> for Vec<ForecastDay> {
+    fn from(weatherapi_forecast_data: WeatherApiForecastData) -> Self {
+        weatherapi_forecast_data
+            .forecast
+            .forecastday
+            .into_iter()
+            .map(|forecast_day| ForecastDay {
+                date: forecast_day.date,
+                min_temp: Celsius(forecast_day.day.mintemp_c),
+                max_temp: Celsius(forecast_day.day.maxtemp_c),
+                description: forecast_day.day.condition.text,
+                chance_of_rain: forecast_day.day.daily_chance_of_rain,
+                chance_of_snow: forecast_day.day.daily_chance_of_snow,
+                precipitation: forecast_day.day.totalprecip_mm,
+                hours: forecast_day
+                    .hour
+                    .into_iter()
+                    .map(|hour| ForecastHour {
+                        time: hour.time,
+                        timestamp: hour.time_epoch,
+                        temp: Celsius(hour.temp_c),
+                        description: hour.condition.text,
+                        wind_speed: MetersPerSecond::from_kph(hour.wind_kph),
+                        chance_of_rain: hour.chance_of_rain,
+                        chance_of_snow: hour.chance_of_snow,
+                        precipitation: hour.precip_mm,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Converts forecast data from OpenWeather API to a list of `ForecastDay`.
+///
+/// OpenWeather provides the forecast in 3 hour steps, so the steps are grouped by date and the daily values
+/// are aggregated (temperature range, highest chance of precipitation and total precipitation amount).
+/// The steps are given in UTC, so they are shifted to the local time of the location first when the response
+/// contains its offset from UTC.
+impl From<OpenWeatherForecastData> for Vec<ForecastDay> {
+    fn from(openweather_forecast_data: OpenWeatherForecastData) -> Self {
+        let utc_offset = openweather_forecast_data
+            .city
+            .and_then(|city| city.timezone)
+            .and_then(|seconds| FixedOffset::east_opt(i32::try_from(seconds).ok()?));
+        let mut days: Vec<ForecastDay> = Vec::new();
+
+        for item in openweather_forecast_data.list {
+            let mut hour = ForecastHour::from(item);
+            if let Some(local_time) =
+                utc_offset
+                    .zip(hour.timestamp)
+                    .and_then(|(offset, timestamp)| {
+                        DateTime::from_timestamp(timestamp, 0).map(|time| {
+                            time.with_timezone(&offset)
+                                .format("%Y-%m-%d %H:%M")
+                                .to_string()
+                        })
+                    })
+            {
+                hour.time = local_time;
+            }
+            let date = hour.time.chars().take(10).collect::<String>();
+
+            match days.last_mut() {
+                Some(day) if day.date == date => day.hours.push(hour),
+                _ => days.push(ForecastDay {
+                    date,
+                    min_temp: hour.temp,
+                    max_temp: hour.temp,
+                    description: String::new(),
+                    chance_of_rain: 0,
+                    chance_of_snow: 0,
+                    precipitation: 0.0,
+                    hours: vec![hour],
+                }),
+            }
         }
+
+        for day in &mut days {
+            for hour in &day.hours {
+                day.min_temp = Celsius(day.min_temp.value().min(hour.temp.value()));
+                day.max_temp = Celsius(day.max_temp.value().max(hour.temp.value()));
+                day.chance_of_rain = day.chance_of_rain.max(hour.chance_of_rain);
+                day.chance_of_snow = day.chance_of_snow.max(hour.chance_of_snow);
+                day.precipitation += hour.precipitation;
+            }
+
+            // the midday step describes the day best
+            day.description = day
+                .hours
+                .iter()
+                .find(|hour| hour.time.ends_with("12:00"))
+                .or(day.hours.first())
+                .map_or_else(String::new, |hour| hour.description.clone());
+        }
+
+        days
     }
 }
 
-/// Converts kilometers per hour to meters per second.
-fn km_per_hour_to_m_per_sec(km_per_hour: f32) -> f32 {
-    km_per_hour * (1000.0 / 3600.0)
+/// Converts a 3 hour step of OpenWeather forecast data to `WeatherData` (the forecasted weather of a date).
+impl From<ForecastItem> for WeatherData {
+    fn from(mut item: ForecastItem) -> Self {
+        let main = item.main;
+        let wind = item.wind;
+
+        let temp = Celsius(main.temp);
+        let wind_speed = MetersPerSecond(wind.speed);
+
+        WeatherData {
+            temp,
+            feels_like: main.feels_like.map_or_else(
+                || derived::feels_like(temp, main.humidity, wind_speed),
+                Celsius,
+            ),
+            humidity: main.humidity,
+            dew_point: derived::dew_point(temp, main.humidity),
+            pressure: HectoPascal(main.pressure),
+            station_pressure: main.grnd_level.map(HectoPascal),
+            wind_speed,
+            visibility: item.visibility.unwrap_or(OPENWEATHER_MAX_VISIBILITY),
+            description: item
+                .weather
+                .pop()
+                .map_or_else(String::new, |w| w.description),
+            uv_index: None,
+            is_day: item.sys.is_none_or(|sys| sys.pod != "n"),
+            observed_at: item.dt,
+            source: DataSource {
+                provider: "OpenWeather".to_owned(),
+                station: None,
+                attribution: Some(OPENWEATHER_ATTRIBUTION.to_owned()),
+            },
+        }
+    }
 }
 
-/// Converts kilometers to meters.
-fn km_to_m(km: f32) -> u16 {
-    (km * 1000.0) as u16
+/// Converts a 3 hour step of OpenWeather forecast data to `ForecastHour`.
+impl From<ForecastItem> for ForecastHour {
+    fn from(mut item: ForecastItem) -> Self {
+        let rain = item.rain.map_or(0.0, |rain| rain.three_hours);
+        let snow = item.snow.map_or(0.0, |snow| snow.three_hours);
+        let chance = (item.pop * 100.0).round() as u8;
+
+        let timestamp = item.dt.or_else(|| {
+            NaiveDateTime::parse_from_str(&item.dt_txt, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|time| time.and_utc().timestamp())
+        });
+
+        ForecastHour {
+            time: item.dt_txt.chars().take(16).collect(),
+            timestamp,
+            temp: Celsius(item.main.temp),
+            description: item
+                .weather
+                .pop()
+                .map_or_else(String::new, |w| w.description),
+            wind_speed: MetersPerSecond(item.wind.speed),
+            chance_of_rain: if snow > 0.0 { 0 } else { chance },
+            chance_of_snow: if snow > 0.0 { chance } else { 0 },
+            precipitation: rain + snow,
+        }
+    }
+}
+
+/// Represents marine weather data (tides, swell and water temperature) for several days.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarineData {
+    pub days: Vec<MarineDay>,
+}
+
+/// Represents marine weather data for a day.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarineDay {
+    /// The date in the `YYYY-MM-DD` format.
+    pub date: String,
+    pub tides: Vec<Tide>,
+    pub hours: Vec<MarineHour>,
+}
+
+/// Represents a high or low tide.
+#[derive(Serialize, Clone, Debug)]
+pub struct Tide {
+    /// The time of the tide in the `YYYY-MM-DD hh:mm` format.
+    pub time: String,
+    /// The height of the tide in meters.
+    pub height: f32,
+    /// The type of the tide (`HIGH` or `LOW`).
+    pub tide_type: String,
+}
+
+/// Represents marine weather data for an hour of a day.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarineHour {
+    /// The time in the `YYYY-MM-DD hh:mm` format.
+    pub time: String,
+    /// The significant wave height in meters.
+    pub wave_height: f32,
+    /// The swell height in meters.
+    pub swell_height: f32,
+    /// The swell period in seconds.
+    pub swell_period: f32,
+    /// The swell direction as a 16 point compass direction (e.g. `WSW`).
+    pub swell_direction: String,
+    pub water_temp: Celsius,
+}
+
+/// Converts marine data from Weather API to `MarineData`.
+impl From<WeatherApiMarineData> for MarineData {
+    fn from(weatherapi_marine_data: WeatherApiMarineData) -> Self {
+        let days = weatherapi_marine_data
+            .forecast
+            .forecastday
+            .into_iter()
+            .map(|marine_day| MarineDay {
+                date: marine_day.date,
+                tides: marine_day
+                    .day
+                    .tides
+                    .into_iter()
+                    .flat_map(|tides| tides.tide)
+                    .map(|tide| Tide {
+                        time: tide.tide_time,
+                        height: tide.tide_height_mt.trim().parse().unwrap_or_default(),
+                        tide_type: tide.tide_type,
+                    })
+                    .collect(),
+                hours: marine_day
+                    .hour
+                    .into_iter()
+                    .map(|hour| MarineHour {
+                        time: hour.time,
+                        wave_height: hour.sig_ht_mt,
+                        swell_height: hour.swell_ht_mt,
+                        swell_period: hour.swell_period_secs,
+                        swell_direction: hour.swell_dir_16_point,
+                        water_temp: Celsius(hour.water_temp_c),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        MarineData { days }
+    }
+}
+
+/// Represents a snow report (e.g. for a ski resort) for several days.
+#[derive(Serialize, Clone, Debug)]
+pub struct SnowReport {
+    /// The total snowfall of all days in centimeters.
+    pub total_snowfall: f32,
+    pub days: Vec<SnowDay>,
+}
+
+/// Represents snow conditions for a day.
+#[derive(Serialize, Clone, Debug)]
+pub struct SnowDay {
+    /// The date in the `YYYY-MM-DD` format.
+    pub date: String,
+    /// The snowfall in centimeters.
+    pub snowfall: f32,
+    pub min_temp: Celsius,
+    pub max_temp: Celsius,
+    /// The chance of snow in percent.
+    pub chance_of_snow: u8,
+}
+
+/// Converts forecast data from Weather API to `SnowReport` (daily snowfall totals).
+impl From<WeatherApiForecastData> for SnowReport {
+    fn from(weatherapi_forecast_data: WeatherApiForecastData) -> Self {
+        let days: Vec<SnowDay> = weatherapi_forecast_data
+            .forecast
+            .forecastday
+            .into_iter()
+            .map(|forecast_day| SnowDay {
+                date: forecast_day.date,
+                snowfall: forecast_day.day.totalsnow_cm,
+                min_temp: Celsius(forecast_day.day.mintemp_c),
+                max_temp: Celsius(forecast_day.day.maxtemp_c),
+                chance_of_snow: forecast_day.day.daily_chance_of_snow,
+            })
+            .collect();
+
+        SnowReport {
+            total_snowfall: days.iter().map(|day| day.snowfall).sum(),
+            days,
+        }
+    }
+}
+
+/// Represents a weather observation station near a location.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Station {
+    /// The identifier of the station at the provider (e.g. `EGLL` or `03772`).
+    pub id: String,
+    /// The name of the station; `None` if the provider doesn't supply it.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The distance from the location in kilometers.
+    pub distance: f32,
+    /// The elevation of the station in meters; `None` if it's unknown.
+    #[serde(default)]
+    pub elevation: Option<f32>,
+}
+
+/// Represents the precipitation forecast for a minute of the next hour (a nowcast).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MinutePrecipitation {
+    /// The time of the minute as a Unix timestamp.
+    pub time: i64,
+    /// The precipitation intensity in millimeters per hour.
+    pub precipitation: f32,
+}
+
+/// Converts the minutely forecast from the OpenWeather One Call API to a list of `MinutePrecipitation`.
+impl From<OpenWeatherOneCallData> for Vec<MinutePrecipitation> {
+    fn from(data: OpenWeatherOneCallData) -> Self {
+        data.minutely
+            .into_iter()
+            .map(|minute| MinutePrecipitation {
+                time: minute.dt,
+                precipitation: minute.precipitation,
+            })
+            .collect()
+    }
+}
+
+/// Represents a weather alert (e.g. a storm warning) issued for a location by a weather agency.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WeatherAlert {
+    /// The type of the event (e.g. `Wind Advisory`).
+    pub event: String,
+    pub headline: String,
+    /// The severity as reported by the agency (e.g. `Moderate` or `Severe`); empty if it's unknown.
+    pub severity: String,
+    /// The start of the alert as an ISO 8601 date-time.
+    pub effective: String,
+    /// The end of the alert as an ISO 8601 date-time.
+    pub expires: String,
+}
+
+/// Converts the weather alerts from Weather API to a list of `WeatherAlert`.
+impl From<WeatherApiAlertsData> for Vec<WeatherAlert> {
+    fn from(data: WeatherApiAlertsData) -> Self {
+        data.alerts
+            .alert
+            .into_iter()
+            .map(|alert| WeatherAlert {
+                event: alert.event,
+                headline: alert.headline,
+                severity: alert.severity,
+                effective: alert.effective,
+                expires: alert.expires,
+            })
+            .collect()
+    }
+}
+
+/// Converts kilometers to whole meters, rounded to the nearest meter.
+///
+/// The conversion saturates at the bounds of `u32` (e.g. a negative distance is 0 meters) instead of wrapping,
+/// so an implausible value sent by a provider is caught by `validation::validate`.
+fn km_to_m(km: f32) -> u32 {
+    (f64::from(km) * 1000.0).round() as u32
 }
 
 #[cfg(test)]
@@ -107,18 +850,52 @@ mod tests {
         weatherapi_model::{HistoryForecast, HistoryForecastDay, WeatherCondition, WeatherCurrent},
         *,
     };
+    use float_cmp::approx_eq;
     use openweather_model::*;
+    use proptest::prelude::*;
     use rstest::*;
+    use serde_json::{json, Value};
+
+    /// Generates a JSON number the way drifting providers send it: as a number, a numeric string or `null`.
+    fn drifting_number(range: std::ops::Range<f64>) -> impl Strategy<Value = Value> {
+        prop_oneof![
+            range.clone().prop_map(|number| json!(number)),
+            range.prop_map(|number| json!(number.to_string())),
+            Just(Value::Null),
+        ]
+    }
+
+    /// Generates an optional JSON block that may be missing (`None`) or `null`.
+    fn drifting_block(block: Value) -> impl Strategy<Value = Option<Value>> {
+        prop_oneof![Just(Some(block)), Just(Some(Value::Null)), Just(None)]
+    }
+
+    /// Removes fields whose generated value is `None` from a JSON object.
+    fn object(fields: Vec<(&str, Option<Value>)>) -> Value {
+        Value::Object(
+            fields
+                .into_iter()
+                .filter_map(|(name, value)| value.map(|value| (name.to_owned(), value)))
+                .collect(),
+        )
+    }
 
     #[fixture]
     fn expected_weather_data() -> WeatherData {
         WeatherData {
-            temp: 25.5,
+            temp: Celsius(25.5),
+            feels_like: Celsius(25.5),
             humidity: 50,
-            pressure: 1010,
-            wind_speed: 10.0,
+            dew_point: Celsius(14.3),
+            pressure: HectoPascal(1010),
+            station_pressure: None,
+            wind_speed: MetersPerSecond(10.0),
             visibility: 10000,
             description: "Partly Cloudy".to_string(),
+            uv_index: None,
+            is_day: true,
+            observed_at: None,
+            source: DataSource::default(),
         }
     }
 
@@ -127,22 +904,34 @@ mod tests {
         OpenWeatherData {
             main: WeatherMain {
                 temp: 25.5,
+                feels_like: None,
                 humidity: 50,
                 pressure: 1010,
+                grnd_level: Some(954),
             },
             weather: vec![Weather {
                 description: "Partly Cloudy".to_string(),
             }],
-            visibility: 10000,
+            visibility: Some(10000),
             wind: Wind { speed: 10.0 },
+            dt: Some(1_700_000_000),
+            sys: Some(Sys {
+                sunrise: 1_699_990_000,
+                sunset: 1_700_020_000,
+            }),
+
+            coord: None,
+            timezone: None,
         }
     }
 
     #[fixture]
     fn input_weather_api_data() -> WeatherApiData {
         WeatherApiData {
+            location: None,
             current: WeatherCurrent {
                 temp_c: 25.5,
+                feelslike_c: Some(25.5),
                 condition: WeatherCondition {
                     text: "Partly Cloudy".to_string(),
                 },
@@ -150,6 +939,11 @@ mod tests {
                 pressure_mb: 1010.0,
                 humidity: 50,
                 vis_km: 10.0,
+                uv: Some(6.0),
+                dewpoint_c: Some(14.3),
+                is_day: 1,
+                last_updated_epoch: Some(1_700_000_000),
+                time_epoch: None,
             },
         }
     }
@@ -161,6 +955,7 @@ mod tests {
                 forecastday: vec![HistoryForecastDay {
                     hour: vec![WeatherCurrent {
                         temp_c: 25.5,
+                        feelslike_c: None,
                         condition: WeatherCondition {
                             text: "Partly Cloudy".to_string(),
                         },
@@ -168,12 +963,183 @@ mod tests {
                         pressure_mb: 1010.0,
                         humidity: 50,
                         vis_km: 10.0,
+                        uv: Some(6.0),
+                        dewpoint_c: None,
+                        is_day: 1,
+                        last_updated_epoch: None,
+                        time_epoch: Some(1_700_000_000),
                     }],
                 }],
             },
         }
     }
 
+    #[fixture]
+    fn input_open_weather_forecast_data() -> OpenWeatherForecastData {
+        let item = |dt_txt: &str, temp: f32, pop: f32, rain: Option<f32>, snow: Option<f32>| {
+            ForecastItem {
+                dt: None,
+                dt_txt: dt_txt.to_string(),
+                main: WeatherMain {
+                    temp,
+                    feels_like: None,
+                    humidity: 50,
+                    pressure: 1010,
+                    grnd_level: None,
+                },
+                weather: vec![Weather {
+                    description: format!("Weather at {dt_txt}"),
+                }],
+                visibility: None,
+                wind: Wind { speed: 4.0 },
+                pop,
+                rain: rain.map(|three_hours| Precipitation { three_hours }),
+                snow: snow.map(|three_hours| Precipitation { three_hours }),
+                sys: None,
+            }
+        };
+
+        OpenWeatherForecastData {
+            list: vec![
+                item("2023-12-10 09:00:00", 2.0, 0.2, None, None),
+                item("2023-12-10 12:00:00", 5.5, 0.6, Some(1.5), None),
+                item("2023-12-10 15:00:00", 4.0, 0.4, Some(0.5), None),
+                item("2023-12-11 00:00:00", -3.0, 0.8, None, Some(2.0)),
+            ],
+            city: None,
+        }
+    }
+
+    #[rstest]
+    fn test_forecast_conversion_open_weather(
+        input_open_weather_forecast_data: OpenWeatherForecastData,
+    ) {
+        let result: Vec<ForecastDay> = input_open_weather_forecast_data.into();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].date, "2023-12-10");
+        assert_eq!(result[0].min_temp, Celsius(2.0));
+        assert_eq!(result[0].max_temp, Celsius(5.5));
+        assert_eq!(result[0].description, "Weather at 2023-12-10 12:00:00");
+        assert_eq!(result[0].chance_of_rain, 60);
+        assert_eq!(result[0].chance_of_snow, 0);
+        assert_eq!(result[0].precipitation, 2.0);
+        assert_eq!(result[0].hours.len(), 3);
+        assert_eq!(result[0].hours[0].time, "2023-12-10 09:00");
+        assert_eq!(result[0].hours[0].timestamp, Some(1_702_198_800));
+        assert_eq!(result[1].chance_of_rain, 0);
+        assert_eq!(result[1].chance_of_snow, 80);
+        assert_eq!(result[1].precipitation, 2.0);
+    }
+
+    #[rstest]
+    fn test_forecast_conversion_open_weather_local_time(
+        mut input_open_weather_forecast_data: OpenWeatherForecastData,
+    ) {
+        input_open_weather_forecast_data.city = Some(ForecastCity {
+            timezone: Some(-10 * 3600),
+        });
+
+        let result: Vec<ForecastDay> = input_open_weather_forecast_data.into();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].date, "2023-12-09");
+        assert_eq!(result[0].hours[0].time, "2023-12-09 23:00");
+        assert_eq!(result[1].date, "2023-12-10");
+        assert_eq!(result[1].hours[0].time, "2023-12-10 02:00");
+        assert_eq!(result[1].hours.len(), 3);
+    }
+
+    #[rstest]
+    fn test_forecast_item_weather_data_conversion_open_weather() {
+        let input: ForecastItem = serde_json::from_value(serde_json::json!({
+            "dt": 1_702_227_600,
+            "dt_txt": "2023-12-10 17:00:00",
+            "main": {"temp": 4.0, "humidity": 80, "pressure": 1008},
+            "weather": [{"description": "light rain"}],
+            "visibility": 8000,
+            "wind": {"speed": 5.5},
+            "sys": {"pod": "n"}
+        }))
+        .unwrap();
+
+        let result: WeatherData = input.into();
+
+        assert_eq!(result.temp, Celsius(4.0));
+        assert_eq!(result.visibility, 8000);
+        assert_eq!(result.description, "light rain");
+        assert!(!result.is_day);
+        assert_eq!(result.observed_at, Some(1_702_227_600));
+    }
+
+    #[rstest]
+    fn test_snow_report_conversion_weather_api() {
+        let day = |date: &str, totalsnow_cm: f32| {
+            serde_json::json!({
+                "date": date,
+                "day": {
+                    "maxtemp_c": -1.0,
+                    "mintemp_c": -9.5,
+                    "totalprecip_mm": 3.0,
+                    "totalsnow_cm": totalsnow_cm,
+                    "daily_chance_of_rain": 0,
+                    "daily_chance_of_snow": 90,
+                    "condition": {"text": "Heavy snow"}
+                },
+                "hour": []
+            })
+        };
+        let input: WeatherApiForecastData = serde_json::from_value(serde_json::json!({
+            "forecast": {"forecastday": [day("2023-12-10", 12.5), day("2023-12-11", 7.5)]}
+        }))
+        .unwrap();
+
+        let result: SnowReport = input.into();
+
+        assert_eq!(result.total_snowfall, 20.0);
+        assert_eq!(result.days.len(), 2);
+        assert_eq!(result.days[0].snowfall, 12.5);
+        assert_eq!(result.days[1].chance_of_snow, 90);
+    }
+
+    #[rstest]
+    fn test_marine_data_conversion_weather_api() {
+        let input: WeatherApiMarineData = serde_json::from_value(serde_json::json!({
+            "forecast": {
+                "forecastday": [{
+                    "date": "2023-12-10",
+                    "day": {
+                        "tides": [{
+                            "tide": [
+                                {"tide_time": "2023-12-10 03:14", "tide_height_mt": "1.20", "tide_type": "HIGH"},
+                                {"tide_time": "2023-12-10 09:31", "tide_height_mt": "-0.05", "tide_type": "LOW"}
+                            ]
+                        }]
+                    },
+                    "hour": [{
+                        "time": "2023-12-10 00:00",
+                        "sig_ht_mt": 1.4,
+                        "swell_ht_mt": 0.9,
+                        "swell_period_secs": 11.2,
+                        "swell_dir_16_point": "WSW",
+                        "water_temp_c": 12.5
+                    }]
+                }]
+            }
+        }))
+        .unwrap();
+
+        let result: MarineData = input.into();
+
+        assert_eq!(result.days.len(), 1);
+        assert_eq!(result.days[0].tides.len(), 2);
+        assert_eq!(result.days[0].tides[0].height, 1.2);
+        assert_eq!(result.days[0].tides[1].height, -0.05);
+        assert_eq!(result.days[0].tides[1].tide_type, "LOW");
+        assert_eq!(result.days[0].hours[0].swell_direction, "WSW");
+        assert_eq!(result.days[0].hours[0].water_temp, Celsius(12.5));
+    }
+
     #[rstest]
     #[case(input_open_weather_data(), expected_weather_data())]
     fn test_weather_data_conversion_open_weather(
@@ -182,11 +1148,27 @@ mod tests {
     ) {
         let result: WeatherData = input_open_weather_data.into();
         assert_eq!(result.temp, expected_weather_data.temp);
+        assert_eq!(result.station_pressure, Some(HectoPascal(954)));
+        assert_eq!(result.feels_like, expected_weather_data.feels_like);
         assert_eq!(result.humidity, expected_weather_data.humidity);
+        assert!(approx_eq!(
+            f32,
+            result.dew_point.value(),
+            expected_weather_data.dew_point.value(),
+            epsilon = 0.1
+        ));
         assert_eq!(result.pressure, expected_weather_data.pressure);
         assert_eq!(result.wind_speed, expected_weather_data.wind_speed);
         assert_eq!(result.visibility, expected_weather_data.visibility);
         assert_eq!(result.description, expected_weather_data.description);
+        assert_eq!(result.is_day, expected_weather_data.is_day);
+        assert_eq!(result.uv_index, expected_weather_data.uv_index);
+        assert_eq!(result.observed_at, Some(1_700_000_000));
+        assert_eq!(result.source.provider, "OpenWeather");
+        assert_eq!(
+            result.source.attribution.as_deref(),
+            Some(OPENWEATHER_ATTRIBUTION)
+        );
     }
 
     #[rstest]
@@ -197,11 +1179,22 @@ mod tests {
     ) {
         let result: WeatherData = input_weather_api_data.into();
         assert_eq!(result.temp, expected_weather_data.temp);
+        assert_eq!(result.feels_like, expected_weather_data.feels_like);
         assert_eq!(result.humidity, expected_weather_data.humidity);
+        assert!(approx_eq!(
+            f32,
+            result.dew_point.value(),
+            expected_weather_data.dew_point.value(),
+            epsilon = 0.1
+        ));
         assert_eq!(result.pressure, expected_weather_data.pressure);
         assert_eq!(result.wind_speed, expected_weather_data.wind_speed);
         assert_eq!(result.visibility, expected_weather_data.visibility);
         assert_eq!(result.description, expected_weather_data.description);
+        assert_eq!(result.is_day, expected_weather_data.is_day);
+        assert_eq!(result.uv_index, Some(6.0));
+        assert_eq!(result.observed_at, Some(1_700_000_000));
+        assert_eq!(result.source.provider, "Weather API");
     }
 
     #[rstest]
@@ -210,12 +1203,179 @@ mod tests {
         #[case] input_weather_api_history_data: WeatherApiHistoryData,
         #[case] expected_weather_data: WeatherData,
     ) {
-        let result: WeatherData = input_weather_api_history_data.into();
+        let result: WeatherData = input_weather_api_history_data.try_into().unwrap();
         assert_eq!(result.temp, expected_weather_data.temp);
+        assert_eq!(result.feels_like, expected_weather_data.feels_like);
         assert_eq!(result.humidity, expected_weather_data.humidity);
+        assert!(approx_eq!(
+            f32,
+            result.dew_point.value(),
+            expected_weather_data.dew_point.value(),
+            epsilon = 0.1
+        ));
         assert_eq!(result.pressure, expected_weather_data.pressure);
         assert_eq!(result.wind_speed, expected_weather_data.wind_speed);
         assert_eq!(result.visibility, expected_weather_data.visibility);
         assert_eq!(result.description, expected_weather_data.description);
+        assert_eq!(result.is_day, expected_weather_data.is_day);
+        assert_eq!(result.uv_index, Some(6.0));
+        assert_eq!(result.observed_at, Some(1_700_000_000));
+        assert_eq!(result.source.provider, "Weather API");
+    }
+
+    proptest! {
+        #[test]
+        fn test_open_weather_data_drift(
+            temp in drifting_number(-60.0..60.0),
+            humidity in drifting_number(-10.0..300.0),
+            visibility in drifting_number(0.0..100_000.0),
+            speed in drifting_number(0.0..100.0),
+            wind in drifting_block(json!({})),
+            weather in drifting_block(json!([{"description": "rain", "icon": "10d"}])),
+            sys in drifting_block(json!({"sunrise": "1699990000", "sunset": 1_700_020_000})),
+        ) {
+            let mut wind = wind;
+            if let Some(Value::Object(wind)) = &mut wind {
+                wind.insert("speed".to_owned(), speed);
+            }
+            let payload = object(vec![
+                ("main", Some(json!({"temp": temp, "humidity": humidity, "pressure": "1013", "sea_level": 1013, "grnd_level": "954"}))),
+                ("visibility", Some(visibility)),
+                ("wind", wind),
+                ("weather", weather),
+                ("sys", sys),
+                ("dt", Some(json!(1_700_000_000))),
+            ]);
+
+            let result = serde_json::from_value::<OpenWeatherData>(payload.clone());
+
+            // only the temperature and the humidity are required and can't be `null`
+            let is_required_null = payload["main"]["temp"].is_null() || payload["main"]["humidity"].is_null();
+            prop_assert_eq!(result.is_ok(), !is_required_null);
+            if let Ok(data) = result {
+                let weather_data = WeatherData::from(data);
+                prop_assert!(weather_data.dew_point.value().is_finite());
+            }
+        }
+
+        #[test]
+        fn test_weather_api_forecast_data_drift(
+            temp in drifting_number(-60.0..60.0),
+            chance in drifting_number(-10.0..300.0),
+            condition in drifting_block(json!({"text": "Sunny", "code": 1000})),
+            hour in drifting_block(json!([])),
+        ) {
+            let payload = json!({
+                "location": {"name": "London"},
+                "forecast": {"forecastday": [object(vec![
+                    ("date", Some(json!("2023-12-10"))),
+                    ("day", Some(object(vec![
+                        ("maxtemp_c", Some(json!("5.5"))),
+                        ("mintemp_c", Some(temp)),
+                        ("totalprecip_mm", Some(json!(null))),
+                        ("daily_chance_of_rain", Some(chance.clone())),
+                        ("daily_chance_of_snow", Some(chance)),
+                        ("condition", condition),
+                    ]))),
+                    ("hour", hour),
+                ])]}
+            });
+
+            let result = serde_json::from_value::<WeatherApiForecastData>(payload.clone());
+
+            prop_assert_eq!(result.is_ok(), !payload["forecast"]["forecastday"][0]["day"]["mintemp_c"].is_null());
+            if let Ok(data) = result {
+                let forecast: Vec<ForecastDay> = data.into();
+                prop_assert_eq!(forecast.len(), 1);
+            }
+        }
+
+        #[test]
+        fn test_arbitrary_json_never_panics(json in "\\PC{0,64}") {
+            let _ = serde_json::from_str::<OpenWeatherData>(&json).map(WeatherData::from);
+            let _ = serde_json::from_str::<WeatherApiData>(&json).map(WeatherData::from);
+            let _ = serde_json::from_str::<WeatherApiHistoryData>(&json).map(WeatherData::try_from);
+        }
+    }
+
+    #[rstest]
+    fn test_weather_data_conversion_weather_api_history_without_hours() {
+        let input: WeatherApiHistoryData =
+            serde_json::from_value(json!({"forecast": {"forecastday": [{"hour": null}]}})).unwrap();
+
+        let result = WeatherData::try_from(input);
+
+        assert!(matches!(result, Err(WeatherDataError::Missing(_))));
+    }
+
+    #[rstest]
+    #[case(10.0, 10_000)]
+    #[case(0.3, 300)]
+    #[case(70.0, 70_000)]
+    #[case(-1.0, 0)]
+    #[case(1.0e10, u32::MAX)]
+    fn test_km_to_m(#[case] km: f32, #[case] expected: u32) {
+        assert_eq!(km_to_m(km), expected);
+    }
+
+    #[rstest]
+    fn test_weather_data_builder() {
+        let result = WeatherData::builder()
+            .temp(Celsius(32.0))
+            .humidity(70)
+            .wind_speed(MetersPerSecond(2.0))
+            .description("sunny")
+            .uv_index(Some(9.0))
+            .build();
+
+        assert_eq!(result.temp(), Celsius(32.0));
+        assert!(approx_eq!(
+            f32,
+            result.feels_like().value(),
+            40.5,
+            epsilon = 0.1
+        ));
+        assert!(approx_eq!(
+            f32,
+            result.dew_point().value(),
+            25.9,
+            epsilon = 0.1
+        ));
+        assert_eq!(result.description(), "sunny");
+        assert_eq!(result.uv_index(), Some(9.0));
+        assert!(result.is_day());
+        assert_eq!(result.source(), &DataSource::default());
+
+        let result = WeatherData::builder()
+            .temp(Celsius(20.0))
+            .feels_like(Celsius(18.0))
+            .dew_point(Celsius(5.0))
+            .is_day(false)
+            .build();
+
+        assert_eq!(result.feels_like(), Celsius(18.0));
+        assert_eq!(result.dew_point(), Celsius(5.0));
+        assert!(!result.is_day());
+    }
+
+    #[rstest]
+    fn test_weather_data_serde_round_trip() {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(14.5))
+            .humidity(70)
+            .pressure(HectoPascal(1012))
+            .description("partly cloudy")
+            .observed_at(Some(1_700_000_000))
+            .source(DataSource {
+                provider: "Weather API".to_owned(),
+                station: None,
+                attribution: Some(WEATHERAPI_ATTRIBUTION.to_owned()),
+            })
+            .build();
+
+        let json = serde_json::to_string(&weather_data).unwrap();
+        let result: WeatherData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(result, weather_data);
     }
 }
@@ -0,0 +1,290 @@
+use serde::de::DeserializeOwned;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::cell::{Cell, RefCell};
+use std::str::FromStr;
+
+/// Represents how values of unexpected types in payloads of providers (e.g. a number sent as a string) are parsed.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParsingMode {
+    /// Values of unexpected types are errors (e.g. to catch changes of provider APIs in CI).
+    Strict,
+    /// Values of unexpected types are converted if possible or skipped if they're optional, with a warning.
+    #[default]
+    Lenient,
+}
+
+thread_local! {
+    /// The parsing mode of the payload parsed by `parse` on this thread; `None` outside of `parse`.
+    static MODE: Cell<Option<ParsingMode>> = const { Cell::new(None) };
+    /// The warnings about values of unexpected types of the payload parsed by `parse` on this thread.
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Parses a JSON payload of a provider in a parsing mode.
+///
+/// Outside of this function the deserializers of this module are lenient and don't collect warnings.
+///
+/// # Arguments
+///
+/// * `json` - The JSON payload.
+/// * `mode` - The parsing mode of values of unexpected types.
+///
+/// # Returns
+///
+/// A `Result` containing the parsed data with the warnings about converted or skipped values (always empty in
+/// the strict mode) or an error if the payload can't be parsed.
+pub fn parse<T: DeserializeOwned>(
+    json: &str,
+    mode: ParsingMode,
+) -> serde_json::Result<(T, Vec<String>)> {
+    let previous_mode = MODE.replace(Some(mode));
+    let previous_warnings = WARNINGS.take();
+
+    let result = serde_json::from_str(json);
+
+    MODE.set(previous_mode);
+    let warnings = WARNINGS.replace(previous_warnings);
+
+    result.map(|data| (data, warnings))
+}
+
+/// Handles a value of an unexpected type: an error in the strict mode, otherwise a warning.
+fn unexpected<E: de::Error>(message: String) -> Result<(), E> {
+    match MODE.get() {
+        Some(ParsingMode::Strict) => Err(E::custom(message)),
+        Some(ParsingMode::Lenient) => {
+            WARNINGS.with_borrow_mut(|warnings| warnings.push(message));
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Trait for numbers that can be deserialized leniently from JSON numbers and numeric strings.
+pub trait LenientNumber: Sized + Default {
+    /// Converts a JSON number; integers are rounded and saturated to their range (e.g. `-1` becomes `0`).
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_lenient_float {
+    ($($float:ty),*) => {
+        $(
+            impl LenientNumber for $float {
+                fn from_f64(value: f64) -> Self {
+                    value as $float
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_lenient_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl LenientNumber for $integer {
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $integer
+                }
+            }
+        )*
+    };
+}
+
+impl_lenient_float!(f32, f64);
+impl_lenient_integer!(u8, u16, u32, i64);
+
+/// Represents a JSON value that may carry a number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberLike {
+    Number(f64),
+    Text(String),
+}
+
+/// Converts a number-like value, returning `None` for strings that aren't numbers (e.g. `""` or `"N/A"`).
+///
+/// Strings are values of unexpected types (see `ParsingMode`).
+fn to_number<T: LenientNumber, E: de::Error>(value: NumberLike) -> Result<Option<T>, E> {
+    match value {
+        NumberLike::Number(number) => Ok(Some(T::from_f64(number))),
+        NumberLike::Text(text) => match f64::from_str(text.trim()) {
+            Ok(number) => {
+                unexpected(format!("the number '{text}' is sent as a string"))?;
+                Ok(Some(T::from_f64(number)))
+            }
+            Err(_) => {
+                unexpected(format!("'{text}' isn't a number"))?;
+                Ok(None)
+            }
+        },
+    }
+}
+
+/// Deserializes a required number from a JSON number or a numeric string.
+pub fn number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: LenientNumber,
+{
+    to_number(NumberLike::deserialize(deserializer)?)?
+        .ok_or_else(|| de::Error::custom("expected a number or a numeric string"))
+}
+
+/// Deserializes an optional number; `null` and strings that aren't numbers become `None`.
+///
+/// The field must also be marked with `#[serde(default)]` to be optional.
+pub fn option_number<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: LenientNumber,
+{
+    match Option::<NumberLike>::deserialize(deserializer)? {
+        Some(value) => to_number(value),
+        None => Ok(None),
+    }
+}
+
+/// Deserializes a number that defaults to zero if it's `null` or isn't a number.
+///
+/// The field must also be marked with `#[serde(default)]` to be optional.
+pub fn number_or_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: LenientNumber,
+{
+    option_number(deserializer).map(Option::unwrap_or_default)
+}
+
+/// Deserializes a value (e.g. a block, a list or a string) that defaults to its default value if it's `null`.
+///
+/// The field must also be marked with `#[serde(default)]` to be optional.
+pub fn or_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Deserializes a string from a JSON string or number; `null` becomes an empty string.
+///
+/// The field must also be marked with `#[serde(default)]` to be optional.
+pub fn text<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<NumberLike>::deserialize(deserializer)? {
+        Some(NumberLike::Number(number)) => {
+            unexpected(format!("the text '{number}' is sent as a number"))?;
+            number.to_string()
+        }
+        Some(NumberLike::Text(text)) => text,
+        None => String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[derive(Deserialize, Debug)]
+    struct Sample {
+        #[serde(deserialize_with = "number")]
+        required: u16,
+        #[serde(default, deserialize_with = "option_number")]
+        optional: Option<f32>,
+        #[serde(default, deserialize_with = "number_or_default")]
+        defaulted: u8,
+        #[serde(default, deserialize_with = "text")]
+        label: String,
+    }
+
+    #[rstest]
+    #[case(r#"{"required": 1013}"#, 1013, None, 0, "")]
+    #[case(
+        r#"{"required": "1013", "optional": "2.5", "defaulted": "50", "label": 1.2}"#,
+        1013,
+        Some(2.5),
+        50,
+        "1.2"
+    )]
+    #[case(
+        r#"{"required": 1012.6, "optional": null, "defaulted": null, "label": null}"#,
+        1013,
+        None,
+        0,
+        ""
+    )]
+    #[case(
+        r#"{"required": " 7 ", "optional": "N/A", "defaulted": -5, "unknown": {"a": 1}}"#,
+        7,
+        None,
+        0,
+        ""
+    )]
+    #[case(
+        r#"{"required": 70000, "defaulted": 300}"#,
+        u16::MAX,
+        None,
+        u8::MAX,
+        ""
+    )]
+    fn test_lenient_deserializers(
+        #[case] json: &str,
+        #[case] expected_required: u16,
+        #[case] expected_optional: Option<f32>,
+        #[case] expected_defaulted: u8,
+        #[case] expected_label: &str,
+    ) {
+        let result: Sample = serde_json::from_str(json).unwrap();
+
+        assert_eq!(result.required, expected_required);
+        assert_eq!(result.optional, expected_optional);
+        assert_eq!(result.defaulted, expected_defaulted);
+        assert_eq!(result.label, expected_label);
+    }
+
+    #[rstest]
+    #[case(
+        r#"{"required": 1013, "optional": 2.5, "label": "Rain"}"#,
+        ParsingMode::Strict,
+        0
+    )]
+    #[case(
+        r#"{"required": 1013, "optional": null, "defaulted": null}"#,
+        ParsingMode::Strict,
+        0
+    )]
+    #[case(
+        r#"{"required": 1013, "optional": "2.5", "label": 1.2}"#,
+        ParsingMode::Lenient,
+        2
+    )]
+    #[case(r#"{"required": "1013", "defaulted": "N/A"}"#, ParsingMode::Lenient, 2)]
+    fn test_parse(#[case] json: &str, #[case] mode: ParsingMode, #[case] expected_warnings: usize) {
+        let (result, warnings) = parse::<Sample>(json, mode).unwrap();
+
+        assert_eq!(result.required, 1013);
+        assert_eq!(warnings.len(), expected_warnings);
+    }
+
+    #[rstest]
+    #[case(r#"{"required": "1013"}"#)]
+    #[case(r#"{"required": 1013, "optional": "N/A"}"#)]
+    #[case(r#"{"required": 1013, "label": 1.2}"#)]
+    fn test_parse_strict_error(#[case] json: &str) {
+        assert!(parse::<Sample>(json, ParsingMode::Strict).is_err());
+        assert!(parse::<Sample>(json, ParsingMode::Lenient).is_ok());
+    }
+
+    #[rstest]
+    #[case(r#"{}"#)]
+    #[case(r#"{"required": null}"#)]
+    #[case(r#"{"required": "high"}"#)]
+    #[case(r#"{"required": true}"#)]
+    fn test_lenient_required_number_error(#[case] json: &str) {
+        assert!(serde_json::from_str::<Sample>(json).is_err());
+    }
+}
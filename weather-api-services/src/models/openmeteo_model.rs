@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+// Geocoding Section
+
+/// Represents geocoding search results from the Open-Meteo geocoding API.
+#[derive(Deserialize)]
+pub struct OpenMeteoGeocodingData {
+    /// The matching locations; missing if nothing is found.
+    #[serde(default)]
+    pub results: Vec<GeocodingResult>,
+}
+
+/// Represents a location found by the Open-Meteo geocoding API.
+#[derive(Deserialize)]
+pub struct GeocodingResult {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// The first-level administrative division (e.g. a state or region).
+    #[serde(default)]
+    pub admin1: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    /// The ISO 3166-1 alpha-2 code of the country.
+    #[serde(default)]
+    pub country_code: Option<String>,
+    /// The elevation in meters.
+    #[serde(default)]
+    pub elevation: Option<f32>,
+}
+
+// Archive Section
+
+/// Represents historical weather data from the Open-Meteo archive API.
+#[derive(Deserialize)]
+pub struct OpenMeteoArchiveData {
+    pub daily: ArchiveDaily,
+}
+
+/// Represents daily historical values from the Open-Meteo archive API.
+///
+/// Values are listed in the order of `time`; a value is `null` if it's missing for the day.
+#[derive(Deserialize)]
+pub struct ArchiveDaily {
+    pub time: Vec<String>,
+    pub temperature_2m_mean: Vec<Option<f32>>,
+}
+
+// Error Section
+
+/// Represents error data from the Open-Meteo APIs.
+#[derive(Deserialize)]
+pub struct OpenMeteoErrorData {
+    pub reason: String,
+}
@@ -1,33 +1,78 @@
 use serde::Deserialize;
 
+use super::lenient;
+
 // Weather Data Section
 
 /// Represents weather data from the OpenWeather API.
 #[derive(Deserialize)]
 pub struct OpenWeatherData {
     pub main: WeatherMain,
+    #[serde(default, deserialize_with = "lenient::or_default")]
     pub weather: Vec<Weather>,
-    pub visibility: u16,
+    /// The visibility in meters; it's missing in some responses.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub visibility: Option<u32>,
+    #[serde(default, deserialize_with = "lenient::or_default")]
     pub wind: Wind,
+    /// The time of the data calculation (unix timestamp, UTC).
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub dt: Option<i64>,
+    #[serde(default)]
+    pub sys: Option<Sys>,
+    #[serde(default)]
+    pub coord: Option<Coord>,
+    /// The shift of the local time at the location from UTC in seconds.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub timezone: Option<i64>,
+}
+
+/// Represents the coordinates of the location from OpenWeather data.
+#[derive(Deserialize)]
+pub struct Coord {
+    #[serde(deserialize_with = "lenient::number")]
+    pub lat: f64,
+    #[serde(deserialize_with = "lenient::number")]
+    pub lon: f64,
 }
 
 /// Represents main weather parameters from OpenWeather data.
 #[derive(Deserialize)]
 pub struct WeatherMain {
+    #[serde(deserialize_with = "lenient::number")]
     pub temp: f32,
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub feels_like: Option<f32>,
+    #[serde(deserialize_with = "lenient::number")]
     pub humidity: u8,
+    /// The sea-level pressure in hectopascals.
+    #[serde(deserialize_with = "lenient::number")]
     pub pressure: u16,
+    /// The pressure at the ground level (station pressure) in hectopascals.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub grnd_level: Option<u16>,
 }
 
 /// Represents weather conditions from OpenWeather data.
 #[derive(Deserialize)]
 pub struct Weather {
+    #[serde(default, deserialize_with = "lenient::text")]
     pub description: String,
 }
 
-/// Represents wind data from OpenWeather data.
+/// Represents sunrise and sunset times (unix timestamps, UTC) from OpenWeather data.
 #[derive(Deserialize)]
+pub struct Sys {
+    #[serde(deserialize_with = "lenient::number")]
+    pub sunrise: i64,
+    #[serde(deserialize_with = "lenient::number")]
+    pub sunset: i64,
+}
+
+/// Represents wind data from OpenWeather data.
+#[derive(Deserialize, Default)]
 pub struct Wind {
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
     pub speed: f32,
 }
 
@@ -35,12 +80,106 @@ pub struct Wind {
 
 //--------------------------------
 
+// Weather Forecast Data Section
+
+/// Represents the 5 day / 3 hour weather forecast from the OpenWeather API.
+#[derive(Deserialize)]
+pub struct OpenWeatherForecastData {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub list: Vec<ForecastItem>,
+    #[serde(default)]
+    pub city: Option<ForecastCity>,
+}
+
+/// Represents the location of the OpenWeather forecast.
+#[derive(Deserialize)]
+pub struct ForecastCity {
+    /// The shift of the local time at the location from UTC in seconds.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub timezone: Option<i64>,
+}
+
+/// Represents a 3 hour step of the OpenWeather forecast.
+#[derive(Deserialize)]
+pub struct ForecastItem {
+    /// The time of the forecasted data (unix timestamp, UTC).
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub dt: Option<i64>,
+    /// The time of the forecasted data in the `YYYY-MM-DD hh:mm:ss` format (UTC).
+    pub dt_txt: String,
+    pub main: WeatherMain,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub weather: Vec<Weather>,
+    /// The visibility in meters; it's missing in some responses.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub visibility: Option<u32>,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub wind: Wind,
+    /// The probability of precipitation from `0.0` to `1.0`.
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub pop: f32,
+    pub rain: Option<Precipitation>,
+    pub snow: Option<Precipitation>,
+    #[serde(default)]
+    pub sys: Option<ForecastSys>,
+}
+
+/// Represents the part of the day of a 3 hour step of the OpenWeather forecast.
+#[derive(Deserialize)]
+pub struct ForecastSys {
+    /// `d` for day and `n` for night.
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub pod: String,
+}
+
+/// Represents the precipitation volume from OpenWeather forecast data.
+#[derive(Deserialize)]
+pub struct Precipitation {
+    /// The precipitation volume for the last 3 hours in millimeters.
+    #[serde(
+        rename = "3h",
+        default,
+        deserialize_with = "lenient::number_or_default"
+    )]
+    pub three_hours: f32,
+}
+
+// End of Weather Forecast Data Section
+
+//--------------------------------
+
+// Minutely Forecast Data Section
+
+/// Represents the minutely precipitation forecast for the next hour from the OpenWeather One Call API.
+#[derive(Deserialize)]
+pub struct OpenWeatherOneCallData {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub minutely: Vec<MinutelyItem>,
+}
+
+/// Represents a minute of the OpenWeather minutely forecast.
+#[derive(Deserialize)]
+pub struct MinutelyItem {
+    /// The time of the minute (unix timestamp, UTC).
+    #[serde(deserialize_with = "lenient::number")]
+    pub dt: i64,
+    /// The precipitation intensity in millimeters per hour.
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub precipitation: f32,
+}
+
+// End of Minutely Forecast Data Section
+
+//--------------------------------
+
 // Weather Server Error Section
 
 /// Represents error data from the OpenWeather API server.
+///
+/// The `cod` field is a string for some errors (e.g. `"404"`) and a number for others (e.g. `401`).
 #[derive(Deserialize)]
 pub struct OpenWeatherErrorData {
-    pub cod: String,
+    pub cod: serde_json::Value,
     pub message: String,
 }
 
@@ -1,27 +1,69 @@
 use serde::Deserialize;
 
+use super::lenient;
+
 // Weather Data Section
 
 /// Represents weather data from the Weather API.
 #[derive(Deserialize)]
 pub struct WeatherApiData {
+    /// The location of the data; it's missing in some responses.
+    #[serde(default)]
+    pub location: Option<WeatherLocation>,
     pub current: WeatherCurrent,
 }
 
+/// Represents the location of weather data from the Weather API.
+#[derive(Deserialize)]
+pub struct WeatherLocation {
+    /// The local time at the location in the `YYYY-MM-DD hh:mm` format.
+    pub localtime: String,
+    /// The local time at the location as a Unix timestamp.
+    #[serde(deserialize_with = "lenient::number")]
+    pub localtime_epoch: i64,
+}
+
 /// Represents current weather data from the Weather API.
 #[derive(Deserialize)]
 pub struct WeatherCurrent {
+    #[serde(deserialize_with = "lenient::number")]
     pub temp_c: f32,
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub feelslike_c: Option<f32>,
+    #[serde(default, deserialize_with = "lenient::or_default")]
     pub condition: WeatherCondition,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
     pub wind_kph: f32,
+    #[serde(deserialize_with = "lenient::number")]
     pub pressure_mb: f32,
+    #[serde(deserialize_with = "lenient::number")]
     pub humidity: u8,
+    #[serde(deserialize_with = "lenient::number")]
     pub vis_km: f32,
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub uv: Option<f32>,
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub dewpoint_c: Option<f32>,
+    /// `1` for day and `0` for night (based on sunrise and sunset at the location).
+    #[serde(default = "default_is_day", deserialize_with = "lenient::number")]
+    pub is_day: u8,
+    /// The time of the last update of the current weather as a Unix timestamp.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub last_updated_epoch: Option<i64>,
+    /// The time of an hour of the history or the forecast as a Unix timestamp.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub time_epoch: Option<i64>,
+}
+
+/// Returns the default value of the `is_day` flag (day) when it's missing in the response.
+fn default_is_day() -> u8 {
+    1
 }
 
 /// Represents weather condition from the Weather API.
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 pub struct WeatherCondition {
+    #[serde(default, deserialize_with = "lenient::text")]
     pub text: String,
 }
 
@@ -40,12 +82,14 @@ pub struct WeatherApiHistoryData {
 /// Represents weather forecast for a specific date in history data from the Weather API.
 #[derive(Deserialize)]
 pub struct HistoryForecast {
+    #[serde(default, deserialize_with = "lenient::or_default")]
     pub forecastday: Vec<HistoryForecastDay>,
 }
 
 /// Represents a day's weather data in a historical forecast.
 #[derive(Deserialize)]
 pub struct HistoryForecastDay {
+    #[serde(default, deserialize_with = "lenient::or_default")]
     pub hour: Vec<WeatherCurrent>,
 }
 
@@ -53,6 +97,183 @@ pub struct HistoryForecastDay {
 
 //---------------------------------------
 
+// Weather Forecast Data Section
+
+/// Represents the weather forecast from the Weather API.
+#[derive(Deserialize)]
+pub struct WeatherApiForecastData {
+    pub forecast: Forecast,
+}
+
+/// Represents the forecasted days from the Weather API.
+#[derive(Deserialize)]
+pub struct Forecast {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub forecastday: Vec<ForecastDayData>,
+}
+
+/// Represents a day of the Weather API forecast.
+#[derive(Deserialize)]
+pub struct ForecastDayData {
+    pub date: String,
+    pub day: DaySummary,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub hour: Vec<ForecastHourData>,
+}
+
+/// Represents the summary of a forecasted day from the Weather API.
+#[derive(Deserialize)]
+pub struct DaySummary {
+    #[serde(deserialize_with = "lenient::number")]
+    pub maxtemp_c: f32,
+    #[serde(deserialize_with = "lenient::number")]
+    pub mintemp_c: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub totalprecip_mm: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub totalsnow_cm: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub daily_chance_of_rain: u8,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub daily_chance_of_snow: u8,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub condition: WeatherCondition,
+}
+
+/// Represents an hour of the Weather API forecast.
+#[derive(Deserialize)]
+pub struct ForecastHourData {
+    /// The local time of the hour at the location in the `YYYY-MM-DD hh:mm` format.
+    pub time: String,
+    /// The time of the hour as a Unix timestamp.
+    #[serde(default, deserialize_with = "lenient::option_number")]
+    pub time_epoch: Option<i64>,
+    #[serde(deserialize_with = "lenient::number")]
+    pub temp_c: f32,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub condition: WeatherCondition,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub wind_kph: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub precip_mm: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub chance_of_rain: u8,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub chance_of_snow: u8,
+}
+
+// End of Weather Forecast Data Section
+
+//---------------------------------------
+
+// Marine Data Section
+
+/// Represents marine weather data (tides, swell and water temperature) from the Weather API.
+#[derive(Deserialize)]
+pub struct WeatherApiMarineData {
+    pub forecast: MarineForecast,
+}
+
+/// Represents the forecasted days of marine data from the Weather API.
+#[derive(Deserialize)]
+pub struct MarineForecast {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub forecastday: Vec<MarineForecastDay>,
+}
+
+/// Represents a day of marine data from the Weather API.
+#[derive(Deserialize)]
+pub struct MarineForecastDay {
+    pub date: String,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub day: MarineDaySummary,
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub hour: Vec<MarineHourData>,
+}
+
+/// Represents the summary of a day of marine data from the Weather API.
+#[derive(Deserialize, Default)]
+pub struct MarineDaySummary {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub tides: Vec<Tides>,
+}
+
+/// Represents the tides of a day from the Weather API.
+#[derive(Deserialize)]
+pub struct Tides {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub tide: Vec<TideData>,
+}
+
+/// Represents a high or low tide from the Weather API.
+///
+/// The tide height is a string in the Weather API responses (e.g. `"1.20"`).
+#[derive(Deserialize)]
+pub struct TideData {
+    pub tide_time: String,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub tide_height_mt: String,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub tide_type: String,
+}
+
+/// Represents an hour of marine data from the Weather API.
+#[derive(Deserialize)]
+pub struct MarineHourData {
+    pub time: String,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub sig_ht_mt: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub swell_ht_mt: f32,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub swell_period_secs: f32,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub swell_dir_16_point: String,
+    #[serde(default, deserialize_with = "lenient::number_or_default")]
+    pub water_temp_c: f32,
+}
+
+// End of Marine Data Section
+
+//---------------------------------------
+
+// Alerts Section
+
+/// Represents the weather alerts of a location from the Weather API.
+#[derive(Deserialize)]
+pub struct WeatherApiAlertsData {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub alerts: Alerts,
+}
+
+/// Represents the list of weather alerts from the Weather API.
+#[derive(Deserialize, Default)]
+pub struct Alerts {
+    #[serde(default, deserialize_with = "lenient::or_default")]
+    pub alert: Vec<AlertData>,
+}
+
+/// Represents a weather alert issued by a weather agency from the Weather API.
+///
+/// The times are local ISO 8601 date-times with an offset (e.g. `2023-12-10T06:00:00-05:00`).
+#[derive(Deserialize)]
+pub struct AlertData {
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub event: String,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub headline: String,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub severity: String,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub effective: String,
+    #[serde(default, deserialize_with = "lenient::text")]
+    pub expires: String,
+}
+
+// End of Alerts Section
+
+//---------------------------------------
+
 // Weather Server Error Section
 
 /// Represents error data from the Weather API.
@@ -0,0 +1,39 @@
+use serde::Deserialize;
+
+/// Represents a what3words address converted to coordinates by the what3words API.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct What3WordsData {
+    /// The ISO 3166-1 alpha-2 code of the country of the square.
+    #[serde(default)]
+    pub country: Option<String>,
+    /// The nearest place to the square (e.g. `Bayswater, London`).
+    #[serde(default)]
+    pub nearest_place: Option<String>,
+    /// The center of the 3 m square.
+    pub coordinates: What3WordsCoordinates,
+    pub words: String,
+}
+
+/// Represents the coordinates of a what3words square.
+#[derive(Deserialize)]
+pub struct What3WordsCoordinates {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+// Error Section
+
+/// Represents error data from the what3words API.
+#[derive(Deserialize)]
+pub struct What3WordsErrorData {
+    pub error: What3WordsError,
+}
+
+/// Represents an error of the what3words API.
+#[derive(Deserialize)]
+pub struct What3WordsError {
+    /// The code of the error (e.g. `BadWords` or `InvalidKey`).
+    pub code: String,
+    pub message: String,
+}
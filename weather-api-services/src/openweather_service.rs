@@ -1,120 +1,503 @@
 use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Utc};
 use owo_colors::OwoColorize;
-use reqwest::{Client, StatusCode};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use super::{models::openweather_model::OpenWeatherErrorData, *};
-use models::WeatherDataError;
-use openweather_model::OpenWeatherData;
+use dates::DateRoute;
+use geocoding::PostalCode;
+use http::{Middleware, Pipeline};
+use openweather_model::{
+    ForecastItem, OpenWeatherData, OpenWeatherForecastData, OpenWeatherOneCallData,
+};
+
+/// The default URL of the OpenWeather current weather API.
+pub const DEFAULT_URL: &str = "https://api.openweathermap.org/data/2.5/weather";
+
+/// The interval between the steps of the OpenWeather forecast in seconds (3 hours).
+const FORECAST_STEP_SECONDS: i64 = 3 * 3600;
+
+/// Represents the weather of a date routed to the endpoint that has it (see `dates::route`).
+enum DatedWeather<T> {
+    /// The date is now: the response of the current weather.
+    Current(T),
+    /// The date is in the future: the date and time of the forecasted weather.
+    Forecast(DateTime<Utc>),
+}
 
 /// Struct that implement the `WeatherApi` trait and interacts with the OpenWeather API.
 #[derive(Debug)]
 pub struct OpenWeatherApiService {
     url: String,
-    api_key: String,
+    api_key: Secret,
+    language: Option<String>,
+    units: Units,
+    options: ServiceOptions,
     client: Client,
+    pipeline: Pipeline,
 }
 
 /// `OpenWeatherApiService` constructors and methods
 impl OpenWeatherApiService {
-    /// Creates a new instance of `OpenWeatherApiService`.
+    /// Creates a builder of `OpenWeatherApiService`.
+    ///
+    /// # Returns
+    ///
+    /// A `OpenWeatherApiServiceBuilder` with the default URL and metric units; the API key must be set before building.
+    pub fn builder() -> OpenWeatherApiServiceBuilder {
+        OpenWeatherApiServiceBuilder::default()
+    }
+
+    /// Retrieves the URL of the OpenWeather API service.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the URL string.
+    #[allow(dead_code)]
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    /// Retrieves the URL of the OpenWeather 5 day / 3 hour forecast API.
+    ///
+    /// The forecast URL is derived from the current weather URL (`.../weather` is replaced with `.../forecast`).
+    ///
+    /// # Returns
+    ///
+    /// The URL string of the forecast API.
+    pub fn get_forecast_url(&self) -> String {
+        match self.url.strip_suffix("/weather") {
+            Some(base_url) => format!("{base_url}/forecast"),
+            None => format!("{}/forecast", self.url),
+        }
+    }
+
+    /// Retrieves the URL of the OpenWeather One Call API 3.0 providing the minutely forecast.
+    ///
+    /// The One Call URL is derived from the current weather URL (`.../2.5/weather` is replaced with
+    /// `.../3.0/onecall`).
+    ///
+    /// # Returns
+    ///
+    /// The URL string of the One Call API.
+    pub fn get_one_call_url(&self) -> String {
+        match self.url.strip_suffix("/2.5/weather") {
+            Some(base_url) => format!("{base_url}/3.0/onecall"),
+            None => format!("{}/onecall", self.url),
+        }
+    }
+
+    /// Builds a request of the weather of an address with the common parameters (the location, metric units,
+    /// the key, the language and the extra options).
     ///
     /// # Arguments
     ///
-    /// * `client` - The HTTP client (reqwest) to use for making requests.
-    /// * `url` - The base URL for the OpenWeather API.
-    /// * `api_key` - The API key required for authentication.
+    /// * `url` - The URL of the endpoint (the current weather or the forecast).
+    /// * `address` - A string representing the address for which weather data is requested.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the initialized `OpenWeatherApiService` or an error if initialization fails.
-    pub fn new(client: Client, mut url: String, api_key: String) -> Result<Self> {
-        if url.is_empty() || api_key.is_empty() {
-            return Err(WeatherApiError::Creation.into());
+    /// The request.
+    fn weather_request(&self, url: &str, address: &str) -> RequestBuilder {
+        let mut params: HashMap<&str, String> = options::query_params(&self.options).collect();
+
+        let (name, value) = location_param(address);
+        params.insert(name, value);
+        params.insert("units", "metric".to_owned());
+        params.insert("appid", self.api_key.expose().to_owned());
+        if let Some(language) = &self.language {
+            params.insert("lang", language.to_owned());
         }
 
-        // url cleaning
-        if url.ends_with('/') {
-            url.pop();
+        self.client.get(url).query(&params)
+    }
+
+    /// Sends a request to OpenWeather through the request pipeline and parses the JSON response.
+    async fn request_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T> {
+        self.pipeline
+            .request_json(request, "Open Weather API", to_api_error)
+            .await
+    }
+
+    /// Routes the weather of an address at a date to the endpoint that has it.
+    ///
+    /// The current weather is requested first: the date is interpreted in the time zone of the location (e.g.
+    /// `tomorrow 18:00` is the evening at the location) and it answers dates around now itself; later dates are
+    /// answered from the 5 day / 3 hour forecast. OpenWeather provides the history only with a One Call API 3.0
+    /// subscription, so earlier dates aren't supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - The date (e.g. `2023-10-11 18:00`, `tomorrow 18:00` or `in 3 hours`).
+    /// * `utc_offset` - A function returning the offset from UTC of the location of a current weather response.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the routed weather or an error if the date can't be parsed, is in the past or the
+    /// request fails.
+    async fn dated_weather<T: serde::de::DeserializeOwned>(
+        &self,
+        address: &str,
+        date: &str,
+        utc_offset: impl FnOnce(&T) -> Option<FixedOffset>,
+    ) -> Result<DatedWeather<T>> {
+        // invalid dates are rejected before any request is sent
+        dates::parse_datetime(date)?;
+
+        let current: T = self
+            .request_json(self.weather_request(&self.url, address))
+            .await?;
+        let datetime = dates::parse_datetime_in(date, utc_offset(&current))?;
+
+        match dates::route(datetime, Utc::now()) {
+            DateRoute::Current => Ok(DatedWeather::Current(current)),
+            DateRoute::Forecast => Ok(DatedWeather::Forecast(datetime)),
+            DateRoute::History => Err(WeatherApiError::Feature(
+                "historical data (weather for specific date)"
+                    .yellow()
+                    .to_string(),
+            )
+            .into()),
         }
+    }
+}
 
-        Ok(OpenWeatherApiService {
-            client,
-            url,
-            api_key,
-        })
+/// Builder of `OpenWeatherApiService`, so optional parameters can be added without breaking the constructor.
+#[derive(Debug, Default)]
+pub struct OpenWeatherApiServiceBuilder {
+    client: Option<Client>,
+    url: Option<String>,
+    api_key: Secret,
+    language: Option<String>,
+    units: Units,
+    options: ServiceOptions,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    pipeline: Pipeline,
+}
+
+/// `OpenWeatherApiServiceBuilder` methods
+impl OpenWeatherApiServiceBuilder {
+    /// Sets the HTTP client (reqwest) to use for making requests; the timeout and the user agent are ignored then.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
     }
 
-    /// Retrieves the URL of the OpenWeather API service.
+    /// Sets the base URL for the OpenWeather current weather API without a trailing slash; `DEFAULT_URL` if not set.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the API key required for authentication.
+    pub fn api_key(mut self, api_key: impl Into<Secret>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    /// Sets the language of condition descriptions (e.g. `de`, `fr`); `None` for the provider default (English).
+    pub fn language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets the unit system of the precipitation, snow and heights in the returned weather data; metric if not set.
+    ///
+    /// Temperatures and speeds are typed and always metric; they are converted only for the output.
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets extra provider-specific options forwarded to the OpenWeather API as query parameters (e.g. `exclude = ["minutely"]` for a One Call endpoint);
+    /// parameters set by the service itself take precedence.
+    pub fn options(mut self, options: ServiceOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the timeout of a whole request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Adds a middleware (e.g. `http::Logging`) to the request pipeline after the default `http::Retry` middleware.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.pipeline = self.pipeline.with(middleware);
+        self
+    }
+
+    /// Replaces the whole request pipeline (e.g. one shared by several services).
+    pub fn pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Sets the `User-Agent` header of requests; `http::DEFAULT_USER_AGENT` if not set.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Creates the `OpenWeatherApiService`.
     ///
     /// # Returns
     ///
-    /// A reference to the URL string.
-    #[allow(dead_code)]
-    pub fn get_url(&self) -> &str {
-        &self.url
+    /// A `Result` containing the initialized `OpenWeatherApiService` or an error if the URL or the API key is empty
+    /// or the HTTP client can't be created.
+    pub fn build(self) -> Result<OpenWeatherApiService> {
+        let url = self.url.unwrap_or_else(|| DEFAULT_URL.to_owned());
+        if url.is_empty() || self.api_key.expose().is_empty() {
+            return Err(WeatherApiError::Creation.into());
+        }
+
+        let client = match self.client {
+            Some(client) => client,
+            None => http::build_client(self.timeout, self.user_agent.as_deref())?,
+        };
+
+        Ok(OpenWeatherApiService {
+            url,
+            api_key: self.api_key,
+            language: self.language,
+            units: self.units,
+            options: self.options,
+            client,
+            pipeline: self.pipeline,
+        })
     }
 }
 
 /// An implementation of the `WeatherApi` trait for OpenWeather API service.
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl WeatherApi for OpenWeatherApiService {
     /// Asynchronously retrieves weather data for a specific address and date (if provided).
     ///
+    /// A date is routed with `dates::route` in the time zone of the location: dates around now are answered with
+    /// the current weather and future dates with the nearest step of the forecast; past dates aren't supported.
+    ///
     /// # Arguments
     ///
     /// * `address` - A string representing the address for which weather data is requested.
-    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    /// * `date` - An optional string containing the date of forecasted weather data. Pass `None` for current weather.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the retrieved weather data or an error if the request fails.
+    /// A `Result` containing the retrieved weather data or an error if the request fails or the date is outside of
+    /// the forecast.
     async fn get_weather_data(&self, address: &str, date: &Option<String>) -> Result<WeatherData> {
-        if date.is_some() {
-            return Err(WeatherApiError::Feature(
-                "historical data (weather for specific date)"
-                    .yellow()
-                    .to_string(),
-            )
-            .into());
+        let weather_data: WeatherData = match date {
+            None => self
+                .request_json::<OpenWeatherData>(self.weather_request(&self.url, address))
+                .await?
+                .into(),
+            Some(date) => match self
+                .dated_weather(address, date, |current: &OpenWeatherData| {
+                    current.timezone.and_then(utc_offset)
+                })
+                .await?
+            {
+                DatedWeather::Current(current) => current.into(),
+                DatedWeather::Forecast(datetime) => {
+                    let forecast: OpenWeatherForecastData = self
+                        .request_json(self.weather_request(&self.get_forecast_url(), address))
+                        .await?;
+
+                    forecast_at(forecast, datetime)
+                        .ok_or_else(|| WeatherApiError::DateOutOfRange(date.yellow().to_string()))?
+                        .into()
+                }
+            },
+        };
+
+        Ok(weather_data)
+    }
+
+    /// Asynchronously retrieves the original response of OpenWeather to the weather data request.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the JSON response or an error if the request fails.
+    async fn get_raw_weather_data(&self, address: &str, date: &Option<String>) -> Result<Value> {
+        let Some(date) = date else {
+            return self
+                .request_json(self.weather_request(&self.url, address))
+                .await;
+        };
+
+        match self
+            .dated_weather(address, date, |current: &Value| {
+                current
+                    .get("timezone")
+                    .and_then(Value::as_i64)
+                    .and_then(utc_offset)
+            })
+            .await?
+        {
+            DatedWeather::Current(current) => Ok(current),
+            DatedWeather::Forecast(_) => {
+                self.request_json(self.weather_request(&self.get_forecast_url(), address))
+                    .await
+            }
         }
+    }
 
-        let mut params = HashMap::new();
+    /// OpenWeather resolves postal codes natively with the `zip` parameter.
+    fn supports_postal_codes(&self) -> bool {
+        true
+    }
 
-        params.insert("q", address.to_owned());
-        params.insert("units", "metric".to_owned());
-        params.insert("appid", self.api_key.to_owned());
+    /// Asynchronously retrieves the daily and hourly weather forecast for a specific address.
+    ///
+    /// OpenWeather provides the forecast for up to 5 days in 3 hour steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which the forecast is requested.
+    /// * `days` - The number of forecasted days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the forecasted days or an error if the request fails.
+    async fn get_forecast(&self, address: &str, days: u8) -> Result<Vec<ForecastDay>> {
+        let openweather_forecast_data: OpenWeatherForecastData = self
+            .request_json(self.weather_request(&self.get_forecast_url(), address))
+            .await?;
 
-        let client = &self.client;
-        let url = &self.url;
+        let mut forecast: Vec<ForecastDay> = openweather_forecast_data.into();
+        forecast.truncate(days as usize);
 
-        let response = client.get(url).query(&params).send().await.map_err(|err| {
-            WeatherApiError::Request(err, "Open Weather API".yellow().to_string())
-        })?;
+        Ok(forecast.into_units(self.units))
+    }
 
-        let status_code = response.status();
+    /// Asynchronously retrieves the minutely precipitation forecast for the next hour for a specific address.
+    ///
+    /// The One Call API only accepts coordinates, so they are taken from the current weather of the address
+    /// first; the minutely forecast needs a One Call API 3.0 subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which the nowcast is requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the forecasted minutes or an error if the request fails.
+    async fn get_nowcast(&self, address: &str) -> Result<Vec<MinutePrecipitation>> {
+        let (name, value) = location_param(address);
+        let params = [(name, value), ("appid", self.api_key.expose().to_owned())];
+        let request = self.client.get(&self.url).query(&params);
+        let openweather_data: OpenWeatherData = self
+            .pipeline
+            .request_json(request, "Open Weather API", to_api_error)
+            .await?;
+        let coord = openweather_data
+            .coord
+            .ok_or(WeatherDataError::Missing("the coordinates of the location"))?;
 
-        let response_body = &response.text().await.map_err(WeatherApiError::BodyText)?;
+        let mut params: HashMap<&str, String> = options::query_params(&self.options).collect();
+        params.insert("lat", coord.lat.to_string());
+        params.insert("lon", coord.lon.to_string());
+        params.insert("exclude", "current,hourly,daily,alerts".to_owned());
+        params.insert("appid", self.api_key.expose().to_owned());
 
-        if status_code == StatusCode::OK {
-            let openweather_data: OpenWeatherData =
-                serde_json::from_str(response_body).map_err(WeatherDataError::JsonParse)?;
+        let request = self.client.get(self.get_one_call_url()).query(&params);
+        let one_call_data: OpenWeatherOneCallData = self
+            .pipeline
+            .request_json(request, "Open Weather API", to_api_error)
+            .await?;
 
-            let weather_data = openweather_data.into();
+        let nowcast: Vec<MinutePrecipitation> = one_call_data.into();
 
-            Ok(weather_data)
-        } else {
-            let weather_error_data: OpenWeatherErrorData =
-                serde_json::from_str(response_body).map_err(WeatherDataError::JsonParse)?;
+        Ok(nowcast.into_units(self.units))
+    }
+}
 
-            Err(WeatherApiError::Server(weather_error_data.message.yellow().to_string()).into())
-        }
+/// Converts the shift of the local time at a location from UTC in seconds (the `timezone` of responses) to an offset.
+fn utc_offset(seconds: i64) -> Option<FixedOffset> {
+    FixedOffset::east_opt(i32::try_from(seconds).ok()?)
+}
+
+/// Finds the step of the forecast nearest to a date and time.
+///
+/// # Arguments
+///
+/// * `forecast` - The 5 day / 3 hour forecast.
+/// * `datetime` - The date and time of the forecasted weather.
+///
+/// # Returns
+///
+/// The nearest step or `None` if no step is within `FORECAST_STEP_SECONDS` of the date (it's beyond the forecast).
+fn forecast_at(forecast: OpenWeatherForecastData, datetime: DateTime<Utc>) -> Option<ForecastItem> {
+    let timestamp = datetime.timestamp();
+
+    forecast
+        .list
+        .into_iter()
+        .filter_map(|item| Some(((item.dt? - timestamp).abs(), item)))
+        .filter(|(distance, _)| *distance <= FORECAST_STEP_SECONDS)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, item)| item)
+}
+
+/// Selects the location parameter of a request: `zip` for postal code queries (e.g. `zip:10115,DE`),
+/// otherwise `q` with the address as it is.
+///
+/// # Arguments
+///
+/// * `address` - The address passed to the service.
+///
+/// # Returns
+///
+/// The name and the value of the parameter.
+fn location_param(address: &str) -> (&'static str, String) {
+    match PostalCode::from_query(address) {
+        Some(postal_code) => ("zip", postal_code.to_string()),
+        None => ("q", address.to_owned()),
+    }
+}
+
+/// Converts an error response of the OpenWeather API to a typed `WeatherApiError`.
+///
+/// # Arguments
+///
+/// * `status_code` - The HTTP status code of the response.
+/// * `error_data` - The error data from the response body.
+///
+/// # Returns
+///
+/// The `WeatherApiError` variant matching the error.
+fn to_api_error(status_code: StatusCode, error_data: OpenWeatherErrorData) -> WeatherApiError {
+    let is_date_error = error_data.message.to_lowercase().contains("range");
+    let message = error_data.message.yellow().to_string();
+
+    match status_code {
+        StatusCode::UNAUTHORIZED => WeatherApiError::Unauthorized(message),
+        StatusCode::TOO_MANY_REQUESTS => WeatherApiError::QuotaExceeded(message),
+        StatusCode::NOT_FOUND => WeatherApiError::LocationNotFound(message),
+        StatusCode::BAD_REQUEST if is_date_error => WeatherApiError::DateOutOfRange(message),
+        _ => WeatherApiError::Server(message),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::quantities::{Celsius, HectoPascal, MetersPerSecond};
     use rstest::rstest;
 
     mod tests_openweatherapi_struct {
@@ -122,9 +505,9 @@ mod tests {
 
         #[rstest]
         #[case(
-            "https://api.openweathermap.org/",
+            "https://api.openweathermap.org/data/2.5/weather",
             "my_openweather_api_key",
-            "https://api.openweathermap.org"
+            "https://api.openweathermap.org/data/2.5/weather"
         )]
         #[case("https://example.com", "my_example_api_key", "https://example.com")]
         fn test_openweather_api_creation(
@@ -133,11 +516,16 @@ mod tests {
             #[case] expected_url: &str,
         ) {
             let client = Client::new();
-            let api =
-                OpenWeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             assert_eq!(api.url, expected_url);
-            assert_eq!(api.api_key, api_key);
+            assert_eq!(api.api_key.expose(), api_key);
+            assert!(!format!("{api:?}").contains(api_key));
         }
 
         #[rstest]
@@ -152,12 +540,52 @@ mod tests {
             #[case] expected_url: &str,
         ) {
             let client = Client::new();
-            let api =
-                OpenWeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             assert_eq!(api.get_url(), expected_url);
         }
 
+        #[rstest]
+        #[case(
+            "https://api.openweathermap.org/data/2.5/weather",
+            "https://api.openweathermap.org/data/2.5/forecast"
+        )]
+        #[case("https://example.com", "https://example.com/forecast")]
+        fn test_get_forecast_url_method(#[case] url: &str, #[case] expected_url: &str) {
+            let client = Client::new();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key("key")
+                .build()
+                .unwrap();
+
+            assert_eq!(api.get_forecast_url(), expected_url);
+        }
+
+        #[rstest]
+        #[case(
+            "https://api.openweathermap.org/data/2.5/weather",
+            "https://api.openweathermap.org/data/3.0/onecall"
+        )]
+        #[case("https://example.com", "https://example.com/onecall")]
+        fn test_get_one_call_url_method(#[case] url: &str, #[case] expected_url: &str) {
+            let client = Client::new();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key("key")
+                .build()
+                .unwrap();
+
+            assert_eq!(api.get_one_call_url(), expected_url);
+        }
+
         #[rstest]
         #[case("", "")]
         #[case("", "some key")]
@@ -167,7 +595,11 @@ mod tests {
             #[case] api_key: &str,
         ) {
             let client = Client::new();
-            let api = OpenWeatherApiService::new(client, url.to_string(), api_key.to_string())
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
                 .unwrap_err()
                 .downcast()
                 .unwrap();
@@ -181,13 +613,13 @@ mod tests {
         use serde_json::json;
 
         #[allow(clippy::too_many_arguments)]
-        fn mock_openweather_server(
+        async fn mock_openweather_server(
             address: &str,
             temp: f32,
             humidity: u8,
             pressure: u16,
             wind_speed: f32,
-            visibility: u16,
+            visibility: u32,
             description: &str,
             api_key: &str,
         ) -> (mockito::ServerGuard, mockito::Mock) {
@@ -200,7 +632,7 @@ mod tests {
                 }
             );
 
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
 
             let mock_endpoint = mock_server
                 .mock("GET", "/data/2.5/weather")
@@ -213,7 +645,8 @@ mod tests {
                 .with_status(200)
                 .with_header("content-type", "text/json")
                 .with_body(mock_response.to_string())
-                .create();
+                .create_async()
+                .await;
 
             (mock_server, mock_endpoint)
         }
@@ -229,7 +662,7 @@ mod tests {
             #[case] humidity: u8,
             #[case] pressure: u16,
             #[case] wind_speed: f32,
-            #[case] visibility: u16,
+            #[case] visibility: u32,
             #[case] description: &str,
         ) {
             let api_key = "SomeApiKey";
@@ -242,28 +675,114 @@ mod tests {
                 visibility,
                 description,
                 api_key,
-            );
+            )
+            .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = OpenWeatherApiService::new(
-                client,
-                url.to_string() + "/data/2.5/weather",
-                api_key.to_string(),
-            )
-            .unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result = api.get_weather_data(address, &None).await.unwrap();
 
-            mock_endpoint.assert();
-            assert_eq!(result.temp, temp);
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.temp, Celsius(temp));
             assert_eq!(result.humidity, humidity);
-            assert_eq!(result.pressure, pressure);
-            assert_eq!(result.wind_speed, wind_speed);
+            assert_eq!(result.pressure, HectoPascal(pressure));
+            assert_eq!(result.wind_speed, MetersPerSecond(wind_speed));
             assert_eq!(result.visibility, visibility);
             assert_eq!(result.description, description);
         }
 
+        #[rstest]
+        #[case("de", "Bedeckt")]
+        #[case("fr", "Couvert")]
+        #[tokio::test]
+        async fn test_get_weather_data_with_language(
+            #[case] language: &str,
+            #[case] description: &str,
+        ) {
+            let address = "CityName";
+            let api_key = "SomeApiKey";
+            let mock_response = json!(
+                {
+                    "main": {"temp": 10.0, "humidity": 50, "pressure": 1013},
+                    "wind": {"speed": 5.0},
+                    "visibility": 10000,
+                    "weather": [{"description": description}]
+                }
+            );
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/data/2.5/weather")
+                .match_query(mockito::Matcher::UrlEncoded("lang".into(), language.into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .language(Some(language.to_owned()))
+                .build()
+                .unwrap();
+
+            let result = api.get_weather_data(address, &None).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.description, description);
+        }
+
+        #[rstest]
+        #[case("zip:10115,DE", "zip", "10115,DE")]
+        #[case("Berlin", "q", "Berlin")]
+        #[tokio::test]
+        async fn test_get_weather_data_with_postal_code(
+            #[case] address: &str,
+            #[case] param: &str,
+            #[case] value: &str,
+        ) {
+            let mock_response = json!(
+                {
+                    "main": {"temp": 10.0, "humidity": 50, "pressure": 1013},
+                    "wind": {"speed": 5.0},
+                    "visibility": 10000,
+                    "weather": [{"description": "Cloudy"}]
+                }
+            );
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/data/2.5/weather")
+                .match_query(mockito::Matcher::UrlEncoded(param.into(), value.into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let api = OpenWeatherApiService::builder()
+                .client(Client::new())
+                .url(mock_server.url() + "/data/2.5/weather")
+                .api_key("SomeApiKey")
+                .build()
+                .unwrap();
+
+            let result = api.get_weather_data(address, &None).await;
+
+            mock_endpoint.assert_async().await;
+            assert!(result.is_ok());
+            assert!(api.supports_postal_codes());
+        }
+
         #[rstest]
         #[case("AnotherCity", Some("2023-10-10"), 22.0, 60, 1005, 12.0, 8000, "Rainy")]
         #[tokio::test]
@@ -275,7 +794,7 @@ mod tests {
             #[case] humidity: u8,
             #[case] pressure: u16,
             #[case] wind_speed: f32,
-            #[case] visibility: u16,
+            #[case] visibility: u32,
             #[case] description: &str,
         ) {
             let api_key = "SomeApiKey";
@@ -288,16 +807,17 @@ mod tests {
                 visibility,
                 description,
                 api_key,
-            );
+            )
+            .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = OpenWeatherApiService::new(
-                client,
-                url.to_string() + "/data/2.5/weather",
-                api_key.to_string(),
-            )
-            .unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherApiError = api
                 .get_weather_data(address, &date.map(|d| d.to_string()))
@@ -309,6 +829,90 @@ mod tests {
             assert!(matches!(result, WeatherApiError::Feature(_)));
         }
 
+        #[rstest]
+        #[case("in 7 hours", Some("light rain"))]
+        #[case("in 9 days", None)]
+        #[tokio::test]
+        async fn test_get_weather_data_forecasted_date(
+            #[case] date: &str,
+            #[case] expected: Option<&str>,
+        ) {
+            let now = chrono::Utc::now().timestamp();
+            let step = |hours: i64, description: &str| {
+                json!({
+                    "dt": now + hours * 3600,
+                    "dt_txt": "2023-12-10 12:00:00",
+                    "main": {"temp": 4.0, "humidity": 80, "pressure": 1008},
+                    "weather": [{"description": description}]
+                })
+            };
+            let (mut mock_server, _current_endpoint) =
+                mock_openweather_server("SomeCity", 5.0, 70, 1010, 3.0, 10000, "clear sky", "123")
+                    .await;
+            let forecast_endpoint = mock_server
+                .mock("GET", "/data/2.5/forecast")
+                .match_query(mockito::Matcher::Any)
+                .with_status(200)
+                .with_body(
+                    json!({"list": [step(3, "overcast clouds"), step(6, "light rain")]})
+                        .to_string(),
+                )
+                .create_async()
+                .await;
+
+            let api = OpenWeatherApiService::builder()
+                .url(mock_server.url() + "/data/2.5/weather")
+                .api_key("123")
+                .build()
+                .unwrap();
+
+            let result = api
+                .get_weather_data("SomeCity", &Some(date.to_owned()))
+                .await;
+
+            forecast_endpoint.assert_async().await;
+            match expected {
+                Some(expected) => assert_eq!(result.unwrap().description, expected),
+                None => assert!(matches!(
+                    result.unwrap_err().downcast().unwrap(),
+                    WeatherApiError::DateOutOfRange(_)
+                )),
+            }
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_raw_weather_data() {
+            let mock_response = json!({
+                "main": {"temp": 12.5, "humidity": 80, "pressure": 1012},
+                "rain": {"1h": 0.3},
+                "weather": [{"description": "light rain"}]
+            });
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/data/2.5/weather")
+                .match_query(mockito::Matcher::UrlEncoded("q".into(), "London".into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+            let api = OpenWeatherApiService::builder()
+                .client(Client::new())
+                .url(mock_server.url() + "/data/2.5/weather")
+                .api_key("SomeApiKey")
+                .build()
+                .unwrap();
+
+            let result = api.get_raw_weather_data("London", &None).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result, mock_response);
+            assert!(api
+                .get_raw_weather_data("London", &Some("yesterday".to_owned()))
+                .await
+                .is_err());
+        }
+
         #[rstest]
         #[tokio::test]
         async fn test_get_weather_data_request_error() {
@@ -317,12 +921,12 @@ mod tests {
 
             let url = "http://invalid-url";
             let client = Client::new();
-            let api = OpenWeatherApiService::new(
-                client,
-                url.to_string() + "/data/2.5/weather",
-                api_key.to_string(),
-            )
-            .unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherApiError = api
                 .get_weather_data(address, &None)
@@ -340,7 +944,7 @@ mod tests {
             let address = "SomeCity";
             let api_key = "123";
 
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
             let mock_endpoint = mock_server
                 .mock("GET", "/data/2.5/weather")
                 .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
@@ -351,16 +955,17 @@ mod tests {
                 .match_query(mockito::Matcher::UrlEncoded("appid".into(), api_key.into()))
                 .with_status(200)
                 .with_body("invalid json")
-                .create();
+                .create_async()
+                .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = OpenWeatherApiService::new(
-                client,
-                url.to_string() + "/data/2.5/weather",
-                api_key.to_string(),
-            )
-            .unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherDataError = api
                 .get_weather_data(address, &None)
@@ -369,22 +974,26 @@ mod tests {
                 .downcast()
                 .unwrap();
 
-            mock_endpoint.assert();
+            mock_endpoint.assert_async().await;
             assert!(matches!(result, WeatherDataError::JsonParse(_)));
         }
 
         #[rstest]
+        #[case(404, r#"{"cod": "404", "message": "city not found"}"#, |err: &WeatherApiError| matches!(err, WeatherApiError::LocationNotFound(_)))]
+        #[case(401, r#"{"cod": 401, "message": "Invalid API key"}"#, |err: &WeatherApiError| matches!(err, WeatherApiError::Unauthorized(_)))]
+        #[case(429, r#"{"cod": 429, "message": "Your account is temporary blocked"}"#, |err: &WeatherApiError| matches!(err, WeatherApiError::QuotaExceeded(_)))]
+        #[case(400, r#"{"cod": "400", "message": "requested time is out of allowed range"}"#, |err: &WeatherApiError| matches!(err, WeatherApiError::DateOutOfRange(_)))]
+        #[case(500, r#"{"cod": "500", "message": "internal error"}"#, |err: &WeatherApiError| matches!(err, WeatherApiError::Server(_)))]
         #[tokio::test]
-        async fn test_get_weather_data_server_response_error() {
+        async fn test_get_weather_data_server_response_error(
+            #[case] status: usize,
+            #[case] mock_response: &str,
+            #[case] is_expected_error: fn(&WeatherApiError) -> bool,
+        ) {
             let address = "Invalid City";
             let api_key = "123";
-            let mock_response = json!(
-            {
-                "cod": "404",
-                "message": "city not found"
-            });
 
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
             let mock_endpoint = mock_server
                 .mock("GET", "/data/2.5/weather")
                 .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
@@ -393,18 +1002,19 @@ mod tests {
                     "metric".into(),
                 ))
                 .match_query(mockito::Matcher::UrlEncoded("appid".into(), api_key.into()))
-                .with_status(404)
-                .with_body(mock_response.to_string())
-                .create();
+                .with_status(status)
+                .with_body(mock_response)
+                .create_async()
+                .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = OpenWeatherApiService::new(
-                client,
-                url.to_string() + "/data/2.5/weather",
-                api_key.to_string(),
-            )
-            .unwrap();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherApiError = api
                 .get_weather_data(address, &None)
@@ -413,8 +1023,129 @@ mod tests {
                 .downcast()
                 .unwrap();
 
-            mock_endpoint.assert();
-            assert!(matches!(result, WeatherApiError::Server(_)));
+            mock_endpoint.assert_async().await;
+            assert!(is_expected_error(&result));
+        }
+
+        #[rstest]
+        #[case(2, 2)]
+        #[case(5, 2)]
+        #[tokio::test]
+        async fn test_get_forecast(#[case] days: u8, #[case] expected_days: usize) {
+            let address = "CityName";
+            let api_key = "SomeApiKey";
+            let mock_response = json!(
+                {
+                    "list": [
+                        {
+                            "dt_txt": "2023-12-10 12:00:00",
+                            "main": {"temp": 5.5, "humidity": 50, "pressure": 1010},
+                            "weather": [{"description": "light rain"}],
+                            "wind": {"speed": 4.5},
+                            "pop": 0.6,
+                            "rain": {"3h": 1.5}
+                        },
+                        {
+                            "dt_txt": "2023-12-11 00:00:00",
+                            "main": {"temp": -3.0, "humidity": 80, "pressure": 1005},
+                            "weather": [{"description": "snow"}],
+                            "wind": {"speed": 7.0},
+                            "pop": 0.8,
+                            "snow": {"3h": 2.0}
+                        }
+                    ]
+                }
+            );
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/data/2.5/forecast")
+                .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
+                .match_query(mockito::Matcher::UrlEncoded("appid".into(), api_key.into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .build()
+                .unwrap();
+
+            let result = api.get_forecast(address, days).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.len(), expected_days);
+            assert_eq!(result[0].description, "light rain");
+            assert_eq!(result[0].chance_of_rain, 60);
+            assert_eq!(result[0].precipitation, 1.5);
+            assert_eq!(result[1].chance_of_snow, 80);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_nowcast() {
+            let address = "CityName";
+            let api_key = "SomeApiKey";
+            let weather_response = json!(
+                {
+                    "coord": {"lat": 51.51, "lon": -0.13},
+                    "main": {"temp": 12.5, "humidity": 80, "pressure": 1004}
+                }
+            );
+            let one_call_response = json!(
+                {
+                    "lat": 51.51,
+                    "lon": -0.13,
+                    "minutely": [
+                        {"dt": 1_702_209_600, "precipitation": 0},
+                        {"dt": 1_702_209_660, "precipitation": 1.25}
+                    ]
+                }
+            );
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let weather_endpoint = mock_server
+                .mock("GET", "/data/2.5/weather")
+                .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
+                .with_status(200)
+                .with_body(weather_response.to_string())
+                .create_async()
+                .await;
+            let one_call_endpoint = mock_server
+                .mock("GET", "/data/3.0/onecall")
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::UrlEncoded("lat".into(), "51.51".into()),
+                    mockito::Matcher::UrlEncoded("lon".into(), "-0.13".into()),
+                    mockito::Matcher::UrlEncoded("appid".into(), api_key.into()),
+                ]))
+                .with_status(200)
+                .with_body(one_call_response.to_string())
+                .create_async()
+                .await;
+
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = OpenWeatherApiService::builder()
+                .client(client)
+                .url(url.to_string() + "/data/2.5/weather")
+                .api_key(api_key)
+                .units(Units::Imperial)
+                .build()
+                .unwrap();
+
+            let result = api.get_nowcast(address).await.unwrap();
+
+            weather_endpoint.assert_async().await;
+            one_call_endpoint.assert_async().await;
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].time, 1_702_209_600);
+            assert_eq!(result[0].precipitation, 0.0);
+            assert!((result[1].precipitation - 0.049).abs() < 0.001);
         }
     }
 }
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Extra provider-specific options keyed by the option name (e.g. `exclude`, `aqi`).
+///
+/// Weather API services forward the options as additional query parameters of their requests;
+/// parameters set by the services themselves (e.g. the API key or units) take precedence.
+pub type ServiceOptions = BTreeMap<String, ServiceOption>;
+
+/// Represents the value of an extra provider-specific option.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ServiceOption {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    /// A list of values, sent as a comma-separated query parameter (e.g. `exclude = ["minutely", "alerts"]`).
+    List(Vec<String>),
+}
+
+/// `ServiceOption` methods
+impl ServiceOption {
+    /// Converts the option value to the value of a query parameter.
+    ///
+    /// # Returns
+    ///
+    /// The value as a string; list values are joined with commas.
+    pub fn to_query_value(&self) -> String {
+        match self {
+            ServiceOption::Bool(value) => value.to_string(),
+            ServiceOption::Integer(value) => value.to_string(),
+            ServiceOption::Float(value) => value.to_string(),
+            ServiceOption::Text(value) => value.to_owned(),
+            ServiceOption::List(values) => values.join(","),
+        }
+    }
+
+    /// Gets the option value as a string slice.
+    ///
+    /// # Returns
+    ///
+    /// The text value or `None` if the option isn't a text.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ServiceOption::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Converts service options to query parameters.
+///
+/// # Arguments
+///
+/// * `options` - The extra provider-specific options.
+///
+/// # Returns
+///
+/// An iterator over the names and values of the query parameters.
+pub fn query_params(options: &ServiceOptions) -> impl Iterator<Item = (&str, String)> {
+    options
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.to_query_value()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(ServiceOption::Bool(true), "true")]
+    #[case(ServiceOption::Integer(3), "3")]
+    #[case(ServiceOption::Float(0.5), "0.5")]
+    #[case(ServiceOption::Text("yes".to_owned()), "yes")]
+    #[case(ServiceOption::List(vec!["minutely".to_owned(), "alerts".to_owned()]), "minutely,alerts")]
+    fn test_to_query_value(#[case] option: ServiceOption, #[case] expected: &str) {
+        assert_eq!(option.to_query_value(), expected);
+    }
+
+    #[rstest]
+    fn test_deserialize_options() {
+        let options: ServiceOptions = serde_json::from_str(
+            r#"{"aqi": "yes", "days": 3, "exclude": ["minutely", "alerts"], "strict": false}"#,
+        )
+        .unwrap();
+
+        assert_eq!(options["aqi"].as_str(), Some("yes"));
+        assert_eq!(options["days"], ServiceOption::Integer(3));
+        assert_eq!(options["strict"], ServiceOption::Bool(false));
+        assert_eq!(
+            query_params(&options).collect::<Vec<_>>(),
+            [
+                ("aqi", "yes".to_owned()),
+                ("days", "3".to_owned()),
+                ("exclude", "minutely,alerts".to_owned()),
+                ("strict", "false".to_owned())
+            ]
+        );
+    }
+}
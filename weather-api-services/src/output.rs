@@ -0,0 +1,325 @@
+use serde::Serialize;
+
+use super::climatology::ClimateNormal;
+use super::models::{
+    DataSource, ForecastDay, ForecastHour, MarineData, MarineDay, MarineHour, SnowDay, SnowReport,
+    Tide, WeatherData,
+};
+use super::quantities::HectoPascal;
+use super::units::Units;
+
+/// Trait for weather data that can be turned into its output (e.g. JSON) representation, with the typed temperatures
+/// and speeds in a unit system.
+///
+/// The models themselves always serialize metric values, so they round-trip through caches and stores; the
+/// untyped amounts (precipitation, snow and heights) are converted separately with
+/// [`ConvertUnits`](crate::units::ConvertUnits).
+pub trait IntoOutput {
+    /// The serializable output representation.
+    type Output: Serialize;
+
+    /// Turns the data into its output representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The unit system of the temperatures and speeds in the output.
+    ///
+    /// # Returns
+    ///
+    /// The output representation of the data.
+    fn into_output(self, units: Units) -> Self::Output;
+}
+
+impl<T: IntoOutput> IntoOutput for Vec<T> {
+    type Output = Vec<T::Output>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        self.into_iter()
+            .map(|item| item.into_output(units))
+            .collect()
+    }
+}
+
+impl<T: IntoOutput> IntoOutput for Option<T> {
+    type Output = Option<T::Output>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        self.map(|item| item.into_output(units))
+    }
+}
+
+/// Represents the output of `WeatherData` with temperatures and speeds in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct WeatherDataOutput {
+    pub temp: f32,
+    pub feels_like: f32,
+    pub humidity: u8,
+    pub dew_point: f32,
+    pub pressure: HectoPascal,
+    pub station_pressure: Option<HectoPascal>,
+    pub wind_speed: f32,
+    pub visibility: u32,
+    pub description: String,
+    pub uv_index: Option<f32>,
+    pub is_day: bool,
+    pub observed_at: Option<i64>,
+    pub source: DataSource,
+}
+
+impl IntoOutput for WeatherData {
+    type Output = WeatherDataOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        WeatherDataOutput {
+            temp: units.temperature(self.temp),
+            feels_like: units.temperature(self.feels_like),
+            humidity: self.humidity,
+            dew_point: units.temperature(self.dew_point),
+            pressure: self.pressure,
+            station_pressure: self.station_pressure,
+            wind_speed: units.speed(self.wind_speed),
+            visibility: self.visibility,
+            description: self.description,
+            uv_index: self.uv_index,
+            is_day: self.is_day,
+            observed_at: self.observed_at,
+            source: self.source,
+        }
+    }
+}
+
+/// Represents the output of `ForecastDay` with temperatures and speeds in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct ForecastDayOutput {
+    pub date: String,
+    pub min_temp: f32,
+    pub max_temp: f32,
+    pub description: String,
+    pub chance_of_rain: u8,
+    pub chance_of_snow: u8,
+    pub precipitation: f32,
+    pub hours: Vec<ForecastHourOutput>,
+}
+
+impl IntoOutput for ForecastDay {
+    type Output = ForecastDayOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        ForecastDayOutput {
+            date: self.date,
+            min_temp: units.temperature(self.min_temp),
+            max_temp: units.temperature(self.max_temp),
+            description: self.description,
+            chance_of_rain: self.chance_of_rain,
+            chance_of_snow: self.chance_of_snow,
+            precipitation: self.precipitation,
+            hours: self.hours.into_output(units),
+        }
+    }
+}
+
+/// Represents the output of `ForecastHour` with the temperature and wind speed in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct ForecastHourOutput {
+    pub time: String,
+    pub timestamp: Option<i64>,
+    pub temp: f32,
+    pub description: String,
+    pub wind_speed: f32,
+    pub chance_of_rain: u8,
+    pub chance_of_snow: u8,
+    pub precipitation: f32,
+}
+
+impl IntoOutput for ForecastHour {
+    type Output = ForecastHourOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        ForecastHourOutput {
+            time: self.time,
+            timestamp: self.timestamp,
+            temp: units.temperature(self.temp),
+            description: self.description,
+            wind_speed: units.speed(self.wind_speed),
+            chance_of_rain: self.chance_of_rain,
+            chance_of_snow: self.chance_of_snow,
+            precipitation: self.precipitation,
+        }
+    }
+}
+
+/// Represents the output of `MarineData` with water temperatures in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarineDataOutput {
+    pub days: Vec<MarineDayOutput>,
+}
+
+/// Represents the output of `MarineDay` with water temperatures in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarineDayOutput {
+    pub date: String,
+    pub tides: Vec<Tide>,
+    pub hours: Vec<MarineHourOutput>,
+}
+
+/// Represents the output of `MarineHour` with the water temperature in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct MarineHourOutput {
+    pub time: String,
+    pub wave_height: f32,
+    pub swell_height: f32,
+    pub swell_period: f32,
+    pub swell_direction: String,
+    pub water_temp: f32,
+}
+
+impl IntoOutput for MarineData {
+    type Output = MarineDataOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        MarineDataOutput {
+            days: self.days.into_output(units),
+        }
+    }
+}
+
+impl IntoOutput for MarineDay {
+    type Output = MarineDayOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        MarineDayOutput {
+            date: self.date,
+            tides: self.tides,
+            hours: self.hours.into_output(units),
+        }
+    }
+}
+
+impl IntoOutput for MarineHour {
+    type Output = MarineHourOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        MarineHourOutput {
+            time: self.time,
+            wave_height: self.wave_height,
+            swell_height: self.swell_height,
+            swell_period: self.swell_period,
+            swell_direction: self.swell_direction,
+            water_temp: units.temperature(self.water_temp),
+        }
+    }
+}
+
+/// Represents the output of `SnowReport` with temperatures in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct SnowReportOutput {
+    pub total_snowfall: f32,
+    pub days: Vec<SnowDayOutput>,
+}
+
+/// Represents the output of `SnowDay` with temperatures in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct SnowDayOutput {
+    pub date: String,
+    pub snowfall: f32,
+    pub min_temp: f32,
+    pub max_temp: f32,
+    pub chance_of_snow: u8,
+}
+
+impl IntoOutput for SnowReport {
+    type Output = SnowReportOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        SnowReportOutput {
+            total_snowfall: self.total_snowfall,
+            days: self.days.into_output(units),
+        }
+    }
+}
+
+impl IntoOutput for SnowDay {
+    type Output = SnowDayOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        SnowDayOutput {
+            date: self.date,
+            snowfall: self.snowfall,
+            min_temp: units.temperature(self.min_temp),
+            max_temp: units.temperature(self.max_temp),
+            chance_of_snow: self.chance_of_snow,
+        }
+    }
+}
+
+/// Represents the output of `ClimateNormal` with the mean temperature in a unit system.
+#[derive(Serialize, Clone, Debug)]
+pub struct ClimateNormalOutput {
+    pub month_day: String,
+    pub mean_temp: f32,
+    pub first_year: i32,
+    pub last_year: i32,
+    pub years: usize,
+}
+
+impl IntoOutput for ClimateNormal {
+    type Output = ClimateNormalOutput;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        ClimateNormalOutput {
+            month_day: self.month_day,
+            mean_temp: units.temperature(self.mean_temp),
+            first_year: self.first_year,
+            last_year: self.last_year,
+            years: self.years,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantities::{Celsius, MetersPerSecond};
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Units::Metric, 20.0, 10.0)]
+    #[case(Units::Imperial, 68.0, 22.37)]
+    fn test_weather_data_into_output(
+        #[case] units: Units,
+        #[case] expected_temp: f64,
+        #[case] expected_wind_speed: f64,
+    ) {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(20.0))
+            .humidity(50)
+            .pressure(HectoPascal(1013))
+            .wind_speed(MetersPerSecond(10.0))
+            .visibility(10000)
+            .description("Sunny")
+            .build();
+
+        let output = serde_json::to_value(weather_data.clone().into_output(units)).unwrap();
+        let metric = serde_json::to_value(&weather_data).unwrap();
+
+        assert!(approx_eq!(
+            f64,
+            output["temp"].as_f64().unwrap(),
+            expected_temp,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(
+            f64,
+            output["wind_speed"].as_f64().unwrap(),
+            expected_wind_speed,
+            epsilon = 0.01
+        ));
+        assert_eq!(output["pressure"], 1013);
+        assert_eq!(metric["temp"], 20.0);
+        assert_eq!(
+            serde_json::from_value::<WeatherData>(metric).unwrap(),
+            weather_data
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Represents a temperature in degrees Celsius.
+///
+/// It's always serialized in degrees Celsius; see [`crate::output::IntoOutput`] for the output in other units.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Celsius(pub f32);
+
+/// `Celsius` constructors and conversions
+impl Celsius {
+    /// Creates a temperature from degrees Fahrenheit.
+    pub fn from_fahrenheit(fahrenheit: f32) -> Self {
+        Celsius((fahrenheit - 32.0) * 5.0 / 9.0)
+    }
+
+    /// Returns the temperature in degrees Celsius.
+    pub fn value(self) -> f32 {
+        self.0
+    }
+
+    /// Returns the temperature in degrees Fahrenheit.
+    pub fn fahrenheit(self) -> f32 {
+        self.0 * 9.0 / 5.0 + 32.0
+    }
+
+    /// Returns the temperature in kelvins.
+    pub fn kelvin(self) -> f32 {
+        self.0 + 273.15
+    }
+}
+
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} °C", self.0)
+    }
+}
+
+/// Represents a speed (e.g. of the wind) in meters per second.
+///
+/// It's always serialized in meters per second; see [`crate::output::IntoOutput`] for the output in other units.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct MetersPerSecond(pub f32);
+
+/// `MetersPerSecond` constructors and conversions
+impl MetersPerSecond {
+    /// Creates a speed from kilometers per hour.
+    pub fn from_kph(kph: f32) -> Self {
+        MetersPerSecond(kph / 3.6)
+    }
+
+    /// Creates a speed from miles per hour.
+    pub fn from_mph(mph: f32) -> Self {
+        MetersPerSecond(mph / 2.236_936)
+    }
+
+    /// Returns the speed in meters per second.
+    pub fn value(self) -> f32 {
+        self.0
+    }
+
+    /// Returns the speed in kilometers per hour.
+    pub fn kph(self) -> f32 {
+        self.0 * 3.6
+    }
+
+    /// Returns the speed in miles per hour.
+    pub fn mph(self) -> f32 {
+        self.0 * 2.236_936
+    }
+}
+
+impl fmt::Display for MetersPerSecond {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} m/s", self.0)
+    }
+}
+
+/// Represents a pressure in whole hectopascals (millibars), as reported by providers.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(transparent)]
+pub struct HectoPascal(pub u16);
+
+/// `HectoPascal` constructors and conversions
+impl HectoPascal {
+    /// Creates a pressure from fractional hectopascals (e.g. millibars of the Weather API or a computed pressure),
+    /// rounded to whole hectopascals; values out of range are clamped.
+    pub fn rounded(hpa: f32) -> Self {
+        HectoPascal(hpa.round().clamp(0.0, f32::from(u16::MAX)) as u16)
+    }
+
+    /// Returns the pressure in hectopascals.
+    pub fn value(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the pressure in inches of mercury.
+    pub fn inhg(self) -> f32 {
+        f32::from(self.0) * 0.029_53
+    }
+
+    /// Returns the pressure in millimeters of mercury.
+    pub fn mmhg(self) -> f32 {
+        f32::from(self.0) * 0.750_062
+    }
+}
+
+impl From<HectoPascal> for f32 {
+    fn from(pressure: HectoPascal) -> Self {
+        f32::from(pressure.0)
+    }
+}
+
+impl fmt::Display for HectoPascal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} hPa", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, 32.0)]
+    #[case(100.0, 212.0)]
+    #[case(-40.0, -40.0)]
+    fn test_celsius(#[case] celsius: f32, #[case] fahrenheit: f32) {
+        assert!(approx_eq!(
+            f32,
+            Celsius(celsius).fahrenheit(),
+            fahrenheit,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(
+            f32,
+            Celsius::from_fahrenheit(fahrenheit).value(),
+            celsius,
+            epsilon = 0.01
+        ));
+    }
+
+    #[rstest]
+    #[case(36.0, 10.0, 22.37)]
+    #[case(0.0, 0.0, 0.0)]
+    fn test_meters_per_second(#[case] kph: f32, #[case] mps: f32, #[case] mph: f32) {
+        let speed = MetersPerSecond::from_kph(kph);
+
+        assert!(approx_eq!(f32, speed.value(), mps, epsilon = 0.01));
+        assert!(approx_eq!(f32, speed.kph(), kph, epsilon = 0.01));
+        assert!(approx_eq!(f32, speed.mph(), mph, epsilon = 0.01));
+        assert!(approx_eq!(
+            f32,
+            MetersPerSecond::from_mph(mph).value(),
+            mps,
+            epsilon = 0.01
+        ));
+    }
+
+    #[rstest]
+    #[case(1013.4, HectoPascal(1013))]
+    #[case(1012.5, HectoPascal(1013))]
+    #[case(-5.0, HectoPascal(0))]
+    #[case(1.0e6, HectoPascal(u16::MAX))]
+    fn test_hectopascal_rounded(#[case] hpa: f32, #[case] expected: HectoPascal) {
+        assert_eq!(HectoPascal::rounded(hpa), expected);
+    }
+
+    #[rstest]
+    fn test_display_and_serde() {
+        assert_eq!(Celsius(21.46).to_string(), "21.5 °C");
+        assert_eq!(MetersPerSecond(3.0).to_string(), "3.0 m/s");
+        assert_eq!(HectoPascal(1013).to_string(), "1013 hPa");
+        assert_eq!(serde_json::to_string(&HectoPascal(1013)).unwrap(), "1013");
+        assert_eq!(
+            serde_json::from_str::<Celsius>("21.5").unwrap(),
+            Celsius(21.5)
+        );
+    }
+}
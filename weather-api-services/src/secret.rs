@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The placeholder of redacted credentials in logs, request plans and error messages.
+pub const REDACTED: &str = "<redacted>";
+
+/// Represents a credential (e.g. an API key) that is redacted when it's printed, so it can't leak into debug logs
+/// or error messages; the value is read explicitly with `expose`.
+///
+/// It's (de)serialized as the plain value, so configuration files keep their format.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Secret<T = String>(T);
+
+/// `Secret` constructors and methods
+impl<T> Secret<T> {
+    /// Creates a new secret.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The credential.
+    ///
+    /// # Returns
+    ///
+    /// The secret holding the credential.
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Reads the credential, e.g. to add it to a request.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the credential.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({REDACTED})")
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_secret_redaction() {
+        let secret = Secret::from("my_api_key");
+
+        assert_eq!(secret.to_string(), "<redacted>");
+        assert_eq!(format!("{secret:?}"), "Secret(<redacted>)");
+        assert_eq!(
+            format!("{:?}", Some(vec![secret.clone()])),
+            "Some([Secret(<redacted>)])"
+        );
+        assert_eq!(secret.expose(), "my_api_key");
+    }
+
+    #[rstest]
+    fn test_secret_serde() {
+        let secret: Secret = serde_json::from_str("\"my_api_key\"").unwrap();
+
+        assert_eq!(secret.expose(), "my_api_key");
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"my_api_key\"");
+    }
+}
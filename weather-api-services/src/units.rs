@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+
+use super::models::{
+    ForecastDay, ForecastHour, MarineData, MinutePrecipitation, SnowReport, Station,
+};
+use super::quantities::{Celsius, HectoPascal, MetersPerSecond};
+
+/// Represents the unit system in which weather data is displayed and serialized.
+///
+/// Temperatures and speeds are always stored in degrees Celsius and meters per second and converted only for the
+/// output (see [`Units::temperature`], [`Units::speed`] and [`crate::output::IntoOutput`]).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Units {
+    /// Degrees Celsius, meters per second, millimeters of precipitation, centimeters of snow and meters of height.
+    #[default]
+    Metric,
+    /// Degrees Fahrenheit, miles per hour, inches of precipitation and snow and feet of height.
+    ///
+    /// The pressure (hPa) and the visibility (meters) are kept as is.
+    Imperial,
+}
+
+/// `Units` methods returning the symbols of the units
+impl Units {
+    /// Returns the symbol of the temperature unit (`°C` or `°F`).
+    pub fn temperature_symbol(&self) -> &'static str {
+        match self {
+            Units::Metric => "°C",
+            Units::Imperial => "°F",
+        }
+    }
+
+    /// Returns the symbol of the wind speed unit (`m/s` or `mph`).
+    pub fn speed_symbol(&self) -> &'static str {
+        match self {
+            Units::Metric => "m/s",
+            Units::Imperial => "mph",
+        }
+    }
+
+    /// Returns the symbol of the precipitation unit (`mm` or `in`).
+    pub fn precipitation_symbol(&self) -> &'static str {
+        match self {
+            Units::Metric => "mm",
+            Units::Imperial => "in",
+        }
+    }
+
+    /// Returns the symbol of the snow unit (`cm` or `in`).
+    pub fn snow_symbol(&self) -> &'static str {
+        match self {
+            Units::Metric => "cm",
+            Units::Imperial => "in",
+        }
+    }
+
+    /// Returns the symbol of the height unit (`m` or `ft`).
+    pub fn height_symbol(&self) -> &'static str {
+        match self {
+            Units::Metric => "m",
+            Units::Imperial => "ft",
+        }
+    }
+}
+
+/// `Units` methods converting typed quantities for the output
+impl Units {
+    /// Returns a temperature in degrees of the unit system (Celsius or Fahrenheit).
+    pub fn temperature(&self, temp: Celsius) -> f32 {
+        match self {
+            Units::Metric => temp.value(),
+            Units::Imperial => temp.fahrenheit(),
+        }
+    }
+
+    /// Returns a temperature difference (e.g. an anomaly) in degrees Celsius in degrees of the unit system.
+    pub fn temperature_difference(&self, difference: f32) -> f32 {
+        match self {
+            Units::Metric => difference,
+            Units::Imperial => difference * 9.0 / 5.0,
+        }
+    }
+
+    /// Returns a speed in the speed unit of the unit system (meters per second or miles per hour).
+    pub fn speed(&self, speed: MetersPerSecond) -> f32 {
+        match self {
+            Units::Metric => speed.value(),
+            Units::Imperial => speed.mph(),
+        }
+    }
+}
+
+/// Represents the units of pressure in the output; services always return hectopascals.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PressureUnit {
+    /// Hectopascals (millibars).
+    #[default]
+    Hpa,
+    /// Inches of mercury.
+    InHg,
+    /// Millimeters of mercury.
+    MmHg,
+}
+
+/// `PressureUnit` methods
+impl PressureUnit {
+    /// Returns the symbol of the unit (`hPa`, `inHg` or `mmHg`).
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            PressureUnit::Hpa => "hPa",
+            PressureUnit::InHg => "inHg",
+            PressureUnit::MmHg => "mmHg",
+        }
+    }
+
+    /// Converts a pressure to the unit.
+    pub fn from_hpa(&self, pressure: HectoPascal) -> f32 {
+        match self {
+            PressureUnit::Hpa => f32::from(pressure),
+            PressureUnit::InHg => pressure.inhg(),
+            PressureUnit::MmHg => pressure.mmhg(),
+        }
+    }
+
+    /// Formats a pressure in the unit with its symbol (e.g. `29.91 inHg`).
+    pub fn format(&self, pressure: HectoPascal) -> String {
+        let precision = match self {
+            PressureUnit::InHg => 2,
+            PressureUnit::Hpa | PressureUnit::MmHg => 0,
+        };
+
+        format!("{:.precision$} {}", self.from_hpa(pressure), self.symbol())
+    }
+}
+
+/// Represents the units of visibility in the output; services always return meters.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VisibilityUnit {
+    #[default]
+    Meters,
+    Kilometers,
+    Miles,
+}
+
+/// `VisibilityUnit` methods
+impl VisibilityUnit {
+    /// Returns the symbol of the unit (`m`, `km` or `mi`).
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            VisibilityUnit::Meters => "m",
+            VisibilityUnit::Kilometers => "km",
+            VisibilityUnit::Miles => "mi",
+        }
+    }
+
+    /// Converts a visibility from meters to the unit.
+    pub fn from_meters(&self, meters: u32) -> f32 {
+        let meters = meters as f32;
+
+        match self {
+            VisibilityUnit::Meters => meters,
+            VisibilityUnit::Kilometers => meters / 1000.0,
+            VisibilityUnit::Miles => meters_to_miles(meters),
+        }
+    }
+
+    /// Formats a visibility in meters in the unit with its symbol (e.g. `6.2 mi`).
+    pub fn format(&self, meters: u32) -> String {
+        let precision = match self {
+            VisibilityUnit::Meters => 0,
+            VisibilityUnit::Kilometers | VisibilityUnit::Miles => 1,
+        };
+
+        format!("{:.precision$} {}", self.from_meters(meters), self.symbol())
+    }
+}
+
+/// Converts millimeters to inches.
+pub fn mm_to_inches(mm: f32) -> f32 {
+    mm / 25.4
+}
+
+/// Converts centimeters to inches.
+pub fn cm_to_inches(cm: f32) -> f32 {
+    cm / 2.54
+}
+
+/// Converts meters to feet.
+pub fn meters_to_feet(meters: f32) -> f32 {
+    meters * 3.280_84
+}
+
+/// Converts meters to miles.
+pub fn meters_to_miles(meters: f32) -> f32 {
+    meters / 1609.344
+}
+
+/// Trait for weather data whose untyped amounts (precipitation, snow and heights) can be converted from metric units
+/// (as returned by services) to other units; typed temperatures and speeds always stay metric.
+pub trait ConvertUnits {
+    /// Converts the untyped amounts of the data from metric units to the given unit system.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The target unit system; `Units::Metric` returns the data unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The data in the target unit system.
+    fn into_units(self, units: Units) -> Self;
+}
+
+impl ConvertUnits for ForecastHour {
+    fn into_units(mut self, units: Units) -> Self {
+        if units == Units::Imperial {
+            self.precipitation = mm_to_inches(self.precipitation);
+        }
+        self
+    }
+}
+
+impl ConvertUnits for ForecastDay {
+    fn into_units(mut self, units: Units) -> Self {
+        if units == Units::Imperial {
+            self.precipitation = mm_to_inches(self.precipitation);
+            self.hours = self.hours.into_units(units);
+        }
+        self
+    }
+}
+
+impl<T: ConvertUnits> ConvertUnits for Vec<T> {
+    fn into_units(self, units: Units) -> Self {
+        self.into_iter()
+            .map(|item| item.into_units(units))
+            .collect()
+    }
+}
+
+impl ConvertUnits for MarineData {
+    fn into_units(mut self, units: Units) -> Self {
+        if units == Units::Imperial {
+            for day in &mut self.days {
+                for tide in &mut day.tides {
+                    tide.height = meters_to_feet(tide.height);
+                }
+
+                for hour in &mut day.hours {
+                    hour.wave_height = meters_to_feet(hour.wave_height);
+                    hour.swell_height = meters_to_feet(hour.swell_height);
+                }
+            }
+        }
+        self
+    }
+}
+
+impl ConvertUnits for SnowReport {
+    fn into_units(mut self, units: Units) -> Self {
+        if units == Units::Imperial {
+            self.total_snowfall = cm_to_inches(self.total_snowfall);
+
+            for day in &mut self.days {
+                day.snowfall = cm_to_inches(day.snowfall);
+            }
+        }
+        self
+    }
+}
+
+impl ConvertUnits for Station {
+    fn into_units(mut self, units: Units) -> Self {
+        if units == Units::Imperial {
+            self.elevation = self.elevation.map(meters_to_feet);
+        }
+        self
+    }
+}
+
+impl ConvertUnits for MinutePrecipitation {
+    fn into_units(mut self, units: Units) -> Self {
+        if units == Units::Imperial {
+            self.precipitation = mm_to_inches(self.precipitation);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Units::Metric, 20.0, 10.0)]
+    #[case(Units::Imperial, 68.0, 22.37)]
+    fn test_units_temperature_and_speed(
+        #[case] units: Units,
+        #[case] expected_temp: f32,
+        #[case] expected_wind_speed: f32,
+    ) {
+        assert!(approx_eq!(
+            f32,
+            units.temperature(Celsius(20.0)),
+            expected_temp,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(
+            f32,
+            units.speed(MetersPerSecond(10.0)),
+            expected_wind_speed,
+            epsilon = 0.01
+        ));
+    }
+
+    #[rstest]
+    #[case(PressureUnit::Hpa, "1013 hPa")]
+    #[case(PressureUnit::InHg, "29.91 inHg")]
+    #[case(PressureUnit::MmHg, "760 mmHg")]
+    fn test_pressure_unit_format(#[case] unit: PressureUnit, #[case] expected: &str) {
+        assert_eq!(unit.format(HectoPascal(1013)), expected);
+    }
+
+    #[rstest]
+    #[case(VisibilityUnit::Meters, "10000 m")]
+    #[case(VisibilityUnit::Kilometers, "10.0 km")]
+    #[case(VisibilityUnit::Miles, "6.2 mi")]
+    fn test_visibility_unit_format(#[case] unit: VisibilityUnit, #[case] expected: &str) {
+        assert_eq!(unit.format(10000), expected);
+    }
+}
@@ -0,0 +1,162 @@
+use serde::Serialize;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::models::WeatherData;
+
+/// The plausible temperature (and dew point) in degrees Celsius, a bit beyond the recorded extremes on Earth.
+const TEMP_RANGE: RangeInclusive<f32> = -95.0..=65.0;
+
+/// The plausible "feels like" temperature in degrees Celsius (the wind chill and heat index exceed the temperature).
+const FEELS_LIKE_RANGE: RangeInclusive<f32> = -110.0..=80.0;
+
+/// The plausible relative humidity in percent.
+const HUMIDITY_RANGE: RangeInclusive<f32> = 0.0..=100.0;
+
+/// The plausible sea-level pressure in hectopascals, a bit beyond the recorded extremes (870 hPa and 1084 hPa).
+const PRESSURE_RANGE: RangeInclusive<f32> = 850.0..=1100.0;
+
+/// The plausible wind speed in meters per second (the strongest recorded gust is 113 m/s).
+const WIND_SPEED_RANGE: RangeInclusive<f32> = 0.0..=120.0;
+
+/// The plausible visibility in meters (the longest observed line of sight is about 540 km).
+const VISIBILITY_RANGE: RangeInclusive<f32> = 0.0..=600_000.0;
+
+/// The plausible UV index.
+const UV_INDEX_RANGE: RangeInclusive<f32> = 0.0..=30.0;
+
+/// Represents a physically implausible value in weather data (e.g. humidity above 100 % or zero pressure),
+/// usually caused by a provider bug or a broken station.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Implausibility {
+    /// The field of the `WeatherData` with the value (e.g. `humidity`).
+    pub field: &'static str,
+    /// The value in metric units.
+    pub value: f32,
+    /// The lowest plausible value.
+    pub min: f32,
+    /// The highest plausible value.
+    pub max: f32,
+}
+
+impl fmt::Display for Implausibility {
+    /// Formats the implausibility as a human-readable message (e.g. `humidity 120 is out of range 0..100`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is out of range {}..{}",
+            self.field, self.value, self.min, self.max
+        )
+    }
+}
+
+/// Checks weather data for physically implausible values.
+///
+/// # Arguments
+///
+/// * `weather_data` - The weather data in metric units (as parsed from a provider response).
+///
+/// # Returns
+///
+/// The implausible values; empty if all values are plausible.
+pub fn validate(weather_data: &WeatherData) -> Vec<Implausibility> {
+    let values = [
+        ("temp", Some(weather_data.temp.value()), TEMP_RANGE),
+        (
+            "feels_like",
+            Some(weather_data.feels_like.value()),
+            FEELS_LIKE_RANGE,
+        ),
+        (
+            "humidity",
+            Some(weather_data.humidity as f32),
+            HUMIDITY_RANGE,
+        ),
+        (
+            "dew_point",
+            Some(weather_data.dew_point.value()),
+            TEMP_RANGE,
+        ),
+        (
+            "pressure",
+            Some(f32::from(weather_data.pressure)),
+            PRESSURE_RANGE,
+        ),
+        (
+            "wind_speed",
+            Some(weather_data.wind_speed.value()),
+            WIND_SPEED_RANGE,
+        ),
+        (
+            "visibility",
+            Some(weather_data.visibility as f32),
+            VISIBILITY_RANGE,
+        ),
+        ("uv_index", weather_data.uv_index, UV_INDEX_RANGE),
+    ];
+
+    values
+        .into_iter()
+        .filter_map(|(field, value, range)| {
+            let value = value?;
+            (!range.contains(&value)).then(|| Implausibility {
+                field,
+                value,
+                min: *range.start(),
+                max: *range.end(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DataSource;
+    use crate::quantities::{Celsius, HectoPascal, MetersPerSecond};
+    use rstest::rstest;
+
+    fn weather_data(temp: f32, humidity: u8, pressure: u16, uv_index: Option<f32>) -> WeatherData {
+        WeatherData {
+            temp: Celsius(temp),
+            feels_like: Celsius(temp),
+            humidity,
+            dew_point: Celsius(9.3),
+            pressure: HectoPascal(pressure),
+            station_pressure: None,
+            wind_speed: MetersPerSecond(5.0),
+            visibility: 10000,
+            description: "Cloudy".to_owned(),
+            uv_index,
+            is_day: true,
+            observed_at: None,
+            source: DataSource::default(),
+        }
+    }
+
+    #[rstest]
+    #[case(weather_data(20.0, 50, 1013, Some(3.0)), vec![])]
+    #[case(weather_data(-89.2, 0, 870, None), vec![])]
+    #[case(weather_data(20.0, 120, 1013, None), vec!["humidity"])]
+    #[case(weather_data(20.0, 50, 0, None), vec!["pressure"])]
+    #[case(weather_data(200.0, 50, 1013, Some(-1.0)), vec!["temp", "feels_like", "uv_index"])]
+    #[case(WeatherData { visibility: 1_000_000, ..weather_data(20.0, 50, 1013, None) }, vec!["visibility"])]
+    fn test_validate(#[case] weather_data: WeatherData, #[case] expected: Vec<&str>) {
+        let result: Vec<&str> = validate(&weather_data)
+            .iter()
+            .map(|implausibility| implausibility.field)
+            .collect();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    fn test_implausibility_display() {
+        let result = validate(&weather_data(20.0, 50, 0, None));
+
+        assert_eq!(
+            result[0].to_string(),
+            "pressure 0 is out of range 850..1100"
+        );
+    }
+}
@@ -1,50 +1,146 @@
 use anyhow::Result;
-use dateparser::parse as parse_datetime_from_str;
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Timelike, Utc};
 use owo_colors::OwoColorize;
-use reqwest::{Client, StatusCode};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use super::{
-    models::weatherapi_model::{WeatherApiData, WeatherApiErrorData, WeatherApiHistoryData},
+    models::weatherapi_model::{
+        WeatherApiAlertsData, WeatherApiData, WeatherApiErrorData, WeatherApiForecastData,
+        WeatherApiHistoryData, WeatherApiMarineData, WeatherLocation,
+    },
     *,
 };
+use dates::DateRoute;
+use http::{Middleware, Pipeline};
+
+/// The default URL of the Weather API.
+pub const DEFAULT_URL: &str = "https://api.weatherapi.com/v1";
+
+/// Represents the weather of a date routed to the endpoint that has it (see `dates::route`).
+enum DatedWeather<T> {
+    /// The date is now: the response of the current weather.
+    Current(T),
+    /// The request of the hour of the date from the history or the forecast.
+    Hour(Box<RequestBuilder>),
+}
 
 /// Struct that implement the `WeatherApi` trait and interacts with the Weather API.
 #[derive(Debug)]
 pub struct WeatherApiService {
     url: String,
-    api_key: String,
+    api_key: Secret,
+    language: Option<String>,
+    units: Units,
+    options: ServiceOptions,
     client: Client,
+    pipeline: Pipeline,
 }
 
 /// `WeatherApiService` constructors and methods
 impl WeatherApiService {
-    /// Creates a new instance of `WeatherApiService`.
+    /// Creates a builder of `WeatherApiService`.
+    ///
+    /// # Returns
+    ///
+    /// A `WeatherApiServiceBuilder` with the default URL and metric units; the API key must be set before building.
+    pub fn builder() -> WeatherApiServiceBuilder {
+        WeatherApiServiceBuilder::default()
+    }
+
+    /// Sends a request to the Weather API through the request pipeline and parses the JSON response.
+    async fn request_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        self.pipeline
+            .request_json(request, "Weather API", to_api_error)
+            .await
+    }
+
+    /// Builds a request of the weather of an address with the common parameters (the address, the key, the language
+    /// and the extra options).
     ///
     /// # Arguments
     ///
-    /// * `client` - The HTTP client (reqwest) to use for making requests.
-    /// * `url` - The base URL for the weather data service.
-    /// * `api_key` - The API key required for authentication.
+    /// * `endpoint` - The endpoint of the request (e.g. `current.json`).
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `params` - The parameters of the endpoint.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the initialized `WeatherApiService` or an error if initialization fails.
-    pub fn new(client: Client, mut url: String, api_key: String) -> Result<Self> {
-        if url.is_empty() || api_key.is_empty() {
-            return Err(WeatherApiError::Creation.into());
-        }
+    /// The request.
+    fn weather_request(
+        &self,
+        endpoint: &str,
+        address: &str,
+        params: impl IntoIterator<Item = (&'static str, String)>,
+    ) -> RequestBuilder {
+        let mut query: HashMap<&str, String> = options::query_params(&self.options).collect();
 
-        // url cleaning
-        if url.ends_with('/') {
-            url.pop();
+        query.insert("q", address.to_owned());
+        query.insert("key", self.api_key.expose().to_owned());
+        if let Some(language) = &self.language {
+            query.insert("lang", language.to_owned());
+        }
+        for (name, value) in params {
+            query.insert(name, value);
         }
 
-        Ok(WeatherApiService {
-            client,
-            url,
-            api_key,
-        })
+        self.client
+            .get(format!("{}/{endpoint}", self.url))
+            .query(&query)
+    }
+
+    /// Routes the weather of an address at a date to the endpoint that has it.
+    ///
+    /// The current weather is requested first: the date is interpreted in the time zone of the location (e.g.
+    /// `tomorrow 18:00` is the evening at the location) and it answers dates around now itself; earlier dates are
+    /// requested from the history endpoint and later dates from the forecast endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - The date (e.g. `2023-10-11 18:00`, `tomorrow 18:00` or `in 3 hours`).
+    /// * `utc_offset` - A function returning the offset from UTC of the location of a current weather response.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the routed weather or an error if the date can't be parsed or the request fails.
+    async fn dated_weather<T: serde::de::DeserializeOwned>(
+        &self,
+        address: &str,
+        date: &str,
+        utc_offset: impl FnOnce(&T) -> Option<FixedOffset>,
+    ) -> Result<DatedWeather<T>> {
+        // invalid dates are rejected before any request is sent
+        dates::parse_datetime(date)?;
+
+        let current: T = self
+            .request_json(self.weather_request("current.json", address, []))
+            .await?;
+        let utc_offset = utc_offset(&current);
+        let datetime = dates::parse_datetime_in(date, utc_offset)?;
+        let endpoint = match dates::route(datetime, Utc::now()) {
+            DateRoute::Current => return Ok(DatedWeather::Current(current)),
+            DateRoute::History => "history.json",
+            DateRoute::Forecast => "forecast.json",
+        };
+        let hour = match utc_offset {
+            Some(utc_offset) => datetime.with_timezone(&utc_offset).hour(),
+            None => datetime.with_timezone(&Local).hour(),
+        };
+
+        Ok(DatedWeather::Hour(Box::new(self.weather_request(
+            endpoint,
+            address,
+            [
+                ("unixdt", datetime.timestamp().to_string()),
+                ("hour", hour.to_string()),
+            ],
+        ))))
     }
 
     /// Retrieves the URL of the Weather API service.
@@ -58,74 +154,364 @@ impl WeatherApiService {
     }
 }
 
+/// Builder of `WeatherApiService`, so optional parameters can be added without breaking the constructor.
+#[derive(Debug, Default)]
+pub struct WeatherApiServiceBuilder {
+    client: Option<Client>,
+    url: Option<String>,
+    api_key: Secret,
+    language: Option<String>,
+    units: Units,
+    options: ServiceOptions,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    pipeline: Pipeline,
+}
+
+/// `WeatherApiServiceBuilder` methods
+impl WeatherApiServiceBuilder {
+    /// Sets the HTTP client (reqwest) to use for making requests; the timeout and the user agent are ignored then.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets the base URL for the Weather API without a trailing slash; `DEFAULT_URL` if not set.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the API key required for authentication.
+    pub fn api_key(mut self, api_key: impl Into<Secret>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    /// Sets the language of condition descriptions (e.g. `de`, `fr`); `None` for the provider default (English).
+    pub fn language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets the unit system of the precipitation, snow and heights in the returned weather data; metric if not set.
+    ///
+    /// Temperatures and speeds are typed and always metric; they are converted only for the output.
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets extra provider-specific options forwarded to the Weather API as query parameters (e.g. `aqi = "yes"`);
+    /// parameters set by the service itself take precedence.
+    pub fn options(mut self, options: ServiceOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the timeout of a whole request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Adds a middleware (e.g. `http::Logging`) to the request pipeline after the default `http::Retry` middleware.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.pipeline = self.pipeline.with(middleware);
+        self
+    }
+
+    /// Replaces the whole request pipeline (e.g. one shared by several services).
+    pub fn pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Sets the `User-Agent` header of requests; `http::DEFAULT_USER_AGENT` if not set.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Creates the `WeatherApiService`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the initialized `WeatherApiService` or an error if the URL or the API key is empty
+    /// or the HTTP client can't be created.
+    pub fn build(self) -> Result<WeatherApiService> {
+        let url = self.url.unwrap_or_else(|| DEFAULT_URL.to_owned());
+        if url.is_empty() || self.api_key.expose().is_empty() {
+            return Err(WeatherApiError::Creation.into());
+        }
+
+        let client = match self.client {
+            Some(client) => client,
+            None => http::build_client(self.timeout, self.user_agent.as_deref())?,
+        };
+
+        Ok(WeatherApiService {
+            url,
+            api_key: self.api_key,
+            language: self.language,
+            units: self.units,
+            options: self.options,
+            client,
+            pipeline: self.pipeline,
+        })
+    }
+}
+
 /// An implementation of the `WeatherApi` trait for Weather API service.
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl WeatherApi for WeatherApiService {
     /// Asynchronously retrieves weather data for a specific address and date (if provided).
     ///
+    /// The date is parsed with `dates::parse_datetime` (e.g. `2023-10-11 18:00`, `tomorrow 18:00` or `in 3 hours`)
+    /// in the time zone of the location and routed with `dates::route`: dates around now are answered with the
+    /// current weather, past dates from the history endpoint and future dates from the forecast endpoint.
+    ///
     /// # Arguments
     ///
     /// * `address` - A string representing the address for which weather data is requested.
-    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    /// * `date` - An optional string containing the date for historical or forecasted weather data. Pass `None` for current weather.
     ///
     /// # Returns
     ///
     /// A `Result` containing the retrieved weather data or an error if the request fails.
     async fn get_weather_data(&self, address: &str, date: &Option<String>) -> Result<WeatherData> {
-        let mut params = HashMap::new();
+        let dated_weather = match date {
+            Some(date) => {
+                self.dated_weather(address, date, |current: &WeatherApiData| {
+                    current.location.as_ref().and_then(location_utc_offset)
+                })
+                .await?
+            }
+            None => DatedWeather::Hour(Box::new(self.weather_request("current.json", address, []))),
+        };
+        let weather_data: WeatherData = match (dated_weather, date) {
+            (DatedWeather::Current(current), _) => current.into(),
+            (DatedWeather::Hour(request), None) => {
+                self.request_json::<WeatherApiData>(*request).await?.into()
+            }
+            // The forecast and the history responses have the same structure of hours
+            (DatedWeather::Hour(request), Some(_)) => self
+                .request_json::<WeatherApiHistoryData>(*request)
+                .await?
+                .try_into()?,
+        };
+
+        Ok(weather_data)
+    }
+
+    /// Asynchronously retrieves the original response of the Weather API to the weather data request.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the JSON response or an error if the request fails.
+    async fn get_raw_weather_data(&self, address: &str, date: &Option<String>) -> Result<Value> {
+        let dated_weather = match date {
+            Some(date) => {
+                self.dated_weather(address, date, |current: &Value| {
+                    serde_json::from_value::<WeatherLocation>(current.get("location")?.clone())
+                        .ok()
+                        .as_ref()
+                        .and_then(location_utc_offset)
+                })
+                .await?
+            }
+            None => DatedWeather::Hour(Box::new(self.weather_request("current.json", address, []))),
+        };
+
+        match dated_weather {
+            DatedWeather::Current(current) => Ok(current),
+            DatedWeather::Hour(request) => self.request_json(*request).await,
+        }
+    }
+
+    /// Asynchronously retrieves the daily and hourly weather forecast for a specific address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which the forecast is requested.
+    /// * `days` - The number of forecasted days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the forecasted days or an error if the request fails.
+    async fn get_forecast(&self, address: &str, days: u8) -> Result<Vec<ForecastDay>> {
+        let mut params: HashMap<&str, String> = options::query_params(&self.options).collect();
 
         params.insert("q", address.to_owned());
-        params.insert("key", self.api_key.to_owned());
-        if let Some(date) = date {
-            let timestamp = parse_datetime_from_str(date)
-                .map_err(|_| DateTimeError::Parse(date.yellow().to_string()))?
-                .timestamp();
-            params.insert("unixdt", timestamp.to_string());
+        params.insert("key", self.api_key.expose().to_owned());
+        params.insert("days", days.to_string());
+        if let Some(language) = &self.language {
+            params.insert("lang", language.to_owned());
         }
 
-        let client = &self.client;
-        let url = match date {
-            Some(_) => format!("{}/history.json", &self.url),
-            None => format!("{}/current.json", &self.url),
-        };
+        let request = self
+            .client
+            .get(format!("{}/forecast.json", self.url))
+            .query(&params);
+        let forecast: Vec<ForecastDay> = self
+            .request_json::<WeatherApiForecastData>(request)
+            .await?
+            .into();
 
-        let response = client
-            .get(url)
-            .query(&params)
-            .send()
-            .await
-            .map_err(|err| WeatherApiError::Request(err, "Weather API".yellow().to_string()))?;
-
-        let status_code = response.status();
-
-        let response_body = &response.text().await.map_err(WeatherApiError::BodyText)?;
-
-        if status_code == StatusCode::OK {
-            let weather_data = match date {
-                Some(_) => serde_json::from_str::<WeatherApiHistoryData>(response_body)
-                    .map_err(WeatherDataError::JsonParse)?
-                    .into(),
-                None => serde_json::from_str::<WeatherApiData>(response_body)
-                    .map_err(WeatherDataError::JsonParse)?
-                    .into(),
-            };
-
-            Ok(weather_data)
-        } else {
-            let weather_error_data: WeatherApiErrorData =
-                serde_json::from_str(response_body).map_err(WeatherDataError::JsonParse)?;
-
-            Err(
-                WeatherApiError::Server(weather_error_data.error.message.yellow().to_string())
-                    .into(),
-            )
+        Ok(forecast.into_units(self.units))
+    }
+
+    /// Asynchronously retrieves marine weather data (tides, swell and water temperature) for a specific address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address (a coastal location) for which marine data is requested.
+    /// * `days` - The number of days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the marine data or an error if the request fails.
+    async fn get_marine_data(&self, address: &str, days: u8) -> Result<MarineData> {
+        let mut params: HashMap<&str, String> = options::query_params(&self.options).collect();
+
+        params.insert("q", address.to_owned());
+        params.insert("key", self.api_key.expose().to_owned());
+        params.insert("days", days.to_string());
+        params.insert("tides", "yes".to_owned());
+
+        let request = self
+            .client
+            .get(format!("{}/marine.json", self.url))
+            .query(&params);
+        let marine_data: MarineData = self
+            .request_json::<WeatherApiMarineData>(request)
+            .await?
+            .into();
+
+        Ok(marine_data.into_units(self.units))
+    }
+
+    /// Asynchronously retrieves the snow report (daily snowfall totals) for a specific address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address (e.g. a ski resort) for which the snow report is requested.
+    /// * `days` - The number of days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the snow report or an error if the request fails.
+    async fn get_snow_report(&self, address: &str, days: u8) -> Result<SnowReport> {
+        let mut params: HashMap<&str, String> = options::query_params(&self.options).collect();
+
+        params.insert("q", address.to_owned());
+        params.insert("key", self.api_key.expose().to_owned());
+        params.insert("days", days.to_string());
+        if let Some(language) = &self.language {
+            params.insert("lang", language.to_owned());
         }
+
+        let request = self
+            .client
+            .get(format!("{}/forecast.json", self.url))
+            .query(&params);
+        let snow_report: SnowReport = self
+            .request_json::<WeatherApiForecastData>(request)
+            .await?
+            .into();
+
+        Ok(snow_report.into_units(self.units))
+    }
+
+    /// Asynchronously retrieves the active weather alerts issued by government agencies for a specific address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which alerts are requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the alerts (empty if there are none) or an error if the request fails.
+    async fn get_alerts(&self, address: &str) -> Result<Vec<WeatherAlert>> {
+        let mut params: HashMap<&str, String> = options::query_params(&self.options).collect();
+
+        params.insert("q", address.to_owned());
+        params.insert("key", self.api_key.expose().to_owned());
+        if let Some(language) = &self.language {
+            params.insert("lang", language.to_owned());
+        }
+
+        let request = self
+            .client
+            .get(format!("{}/alerts.json", self.url))
+            .query(&params);
+
+        Ok(self
+            .request_json::<WeatherApiAlertsData>(request)
+            .await?
+            .into())
+    }
+}
+
+/// Computes the offset from UTC of the local time at a location, rounded to 15 minutes (the local time has no
+/// seconds).
+///
+/// # Arguments
+///
+/// * `location` - The location of a Weather API response.
+///
+/// # Returns
+///
+/// The offset or `None` if the local time can't be parsed.
+fn location_utc_offset(location: &WeatherLocation) -> Option<FixedOffset> {
+    let localtime = NaiveDateTime::parse_from_str(&location.localtime, "%Y-%m-%d %H:%M").ok()?;
+    let utc = DateTime::from_timestamp(location.localtime_epoch, 0)?.naive_utc();
+    let quarters = ((localtime - utc).num_seconds() as f64 / 900.0).round() as i32;
+
+    FixedOffset::east_opt(quarters * 900)
+}
+
+/// Converts an error response of the Weather API to a typed `WeatherApiError`.
+///
+/// The classification is based on the Weather API error codes
+/// (e.g. `2006` - invalid API key, `2007` - exceeded quota, `1006` - no matching location).
+///
+/// # Arguments
+///
+/// * `status_code` - The HTTP status code of the response.
+/// * `error_data` - The error data from the response body.
+///
+/// # Returns
+///
+/// The `WeatherApiError` variant matching the error.
+fn to_api_error(status_code: StatusCode, error_data: WeatherApiErrorData) -> WeatherApiError {
+    let code = error_data.error.code;
+    let is_date_error = error_data.error.message.to_lowercase().contains("date");
+    let message = error_data.error.message.yellow().to_string();
+
+    match code {
+        1002 | 2006 | 2008 => WeatherApiError::Unauthorized(message),
+        2007 => WeatherApiError::QuotaExceeded(message),
+        1006 => WeatherApiError::LocationNotFound(message),
+        _ if status_code == StatusCode::UNAUTHORIZED => WeatherApiError::Unauthorized(message),
+        _ if status_code == StatusCode::TOO_MANY_REQUESTS => {
+            WeatherApiError::QuotaExceeded(message)
+        }
+        _ if is_date_error => WeatherApiError::DateOutOfRange(message),
+        _ => WeatherApiError::Server(message),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::quantities::{Celsius, HectoPascal, MetersPerSecond};
     use rstest::rstest;
 
     mod tests_weatherapi_struct {
@@ -133,7 +519,7 @@ mod tests {
 
         #[rstest]
         #[case(
-            "https://api.weatherapi.com/v1/",
+            "https://api.weatherapi.com/v1",
             "my_weather_api_key",
             "https://api.weatherapi.com/v1"
         )]
@@ -144,10 +530,15 @@ mod tests {
             #[case] expected_url: &str,
         ) {
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             assert_eq!(api.url, expected_url);
-            assert_eq!(api.api_key, api_key);
+            assert_eq!(api.api_key.expose(), api_key);
         }
 
         #[rstest]
@@ -162,18 +553,41 @@ mod tests {
             #[case] expected_url: &str,
         ) {
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             assert_eq!(api.get_url(), expected_url);
         }
 
+        #[rstest]
+        fn test_weather_api_builder_defaults() {
+            let api = WeatherApiService::builder()
+                .api_key("my_weather_api_key")
+                .timeout(std::time::Duration::from_secs(5))
+                .user_agent("weather-test/1.0")
+                .build()
+                .unwrap();
+
+            assert_eq!(api.get_url(), DEFAULT_URL);
+            assert_eq!(api.units, Units::Metric);
+            assert_eq!(api.language, None);
+        }
+
         #[rstest]
         #[case("", "")]
         #[case("", "some key")]
         #[case("some url", "")]
         fn test_weather_api_with_empty_url_and_api_key(#[case] url: &str, #[case] api_key: &str) {
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string())
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
                 .unwrap_err()
                 .downcast()
                 .unwrap();
@@ -184,17 +598,18 @@ mod tests {
 
     mod tests_get_weather_data {
         use super::*;
+        use crate::options::ServiceOption;
         use float_cmp::approx_eq;
         use serde_json::json;
 
         #[allow(clippy::too_many_arguments)]
-        fn mock_weather_api_server(
+        async fn mock_weather_api_server(
             address: &str,
             temp: f32,
             humidity: u8,
             pressure: u16,
             wind_speed: f32,
-            visibility: u16,
+            visibility: u32,
             description: &str,
             api_key: &str,
         ) -> (mockito::ServerGuard, mockito::Mock) {
@@ -210,7 +625,7 @@ mod tests {
                     "vis_km": visibility as f32 / 1000.0
                 }
             });
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
 
             let mock_endpoint = mock_server
                 .mock("GET", "/current.json")
@@ -219,7 +634,8 @@ mod tests {
                 .with_status(200)
                 .with_header("content-type", "text/json")
                 .with_body(mock_response.to_string())
-                .create();
+                .create_async()
+                .await;
 
             (mock_server, mock_endpoint)
         }
@@ -227,6 +643,7 @@ mod tests {
         #[rstest]
         #[case("CityName", 200.0, 50, 1013, 5.0, 10000, "Cloudy")]
         #[case("FourthCity", 18.5, 45, 1015, 6.5, 9500, "Partly Cloudy")]
+        #[case("SixthCity", 12.0, 30, 1021, 5.0, 70000, "Clear")]
         #[tokio::test]
         #[allow(clippy::too_many_arguments)]
         async fn test_get_weather_data(
@@ -235,7 +652,7 @@ mod tests {
             #[case] humidity: u8,
             #[case] pressure: u16,
             #[case] wind_speed: f32,
-            #[case] visibility: u16,
+            #[case] visibility: u32,
             #[case] description: &str,
         ) {
             let api_key = "SomeApiKey";
@@ -248,35 +665,167 @@ mod tests {
                 visibility,
                 description,
                 api_key,
-            );
+            )
+            .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result = api.get_weather_data(address, &None).await.unwrap();
 
-            mock_endpoint.assert();
-            assert_eq!(result.temp, temp);
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.temp, Celsius(temp));
             assert_eq!(result.humidity, humidity);
-            assert_eq!(result.pressure, pressure);
-            assert_eq!(result.wind_speed, wind_speed);
+            assert_eq!(result.pressure, HectoPascal(pressure));
+            assert_eq!(result.wind_speed, MetersPerSecond(wind_speed));
             assert_eq!(result.visibility, visibility);
             assert_eq!(result.description, description);
         }
 
+        #[rstest]
+        #[case("de", "Bedeckt")]
+        #[case("uk", "Хмарно")]
+        #[tokio::test]
+        async fn test_get_weather_data_with_language(
+            #[case] language: &str,
+            #[case] description: &str,
+        ) {
+            let address = "CityName";
+            let api_key = "SomeApiKey";
+            let mock_response = json!({
+                "current": {
+                    "temp_c": 10.0,
+                    "condition": {
+                        "text": description
+                    },
+                    "wind_kph": 18.0,
+                    "pressure_mb": 1013.0,
+                    "humidity": 50,
+                    "vis_km": 10.0
+                }
+            });
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/current.json")
+                .match_query(mockito::Matcher::UrlEncoded("lang".into(), language.into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .language(Some(language.to_owned()))
+                .build()
+                .unwrap();
+
+            let result = api.get_weather_data(address, &None).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.description, description);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_weather_data_with_options_and_units() {
+            let api_key = "SomeApiKey";
+            let mock_response = json!({
+                "current": {
+                    "temp_c": 10.0,
+                    "condition": {
+                        "text": "Cloudy"
+                    },
+                    "wind_kph": 18.0,
+                    "pressure_mb": 1013.0,
+                    "humidity": 50,
+                    "vis_km": 10.0
+                }
+            });
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/current.json")
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::UrlEncoded("aqi".into(), "yes".into()),
+                    mockito::Matcher::UrlEncoded("key".into(), api_key.into()),
+                ]))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let options = ServiceOptions::from([
+                ("aqi".to_owned(), ServiceOption::Text("yes".to_owned())),
+                ("key".to_owned(), ServiceOption::Text("OtherKey".to_owned())),
+            ]);
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .options(options)
+                .units(Units::Imperial)
+                .build()
+                .unwrap();
+
+            let result = api.get_weather_data("CityName", &None).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.description, "Cloudy");
+            assert_eq!(result.temp, Celsius(10.0));
+        }
+
+        /// Mocks the current weather of the location queried first for the time zone of dates.
+        async fn mock_current_weather(
+            mock_server: &mut mockito::ServerGuard,
+            location: Option<serde_json::Value>,
+        ) -> mockito::Mock {
+            let mut mock_response = serde_json::json!({
+                "current": {
+                    "temp_c": 15.0,
+                    "condition": { "text": "Now" },
+                    "pressure_mb": 1012.0,
+                    "humidity": 55,
+                    "vis_km": 10.0
+                }
+            });
+            if let Some(location) = location {
+                mock_response["location"] = location;
+            }
+
+            mock_server
+                .mock("GET", "/current.json")
+                .match_query(mockito::Matcher::Any)
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await
+        }
+
         #[allow(clippy::too_many_arguments)]
-        fn mock_weather_api_history_server(
+        async fn mock_weather_api_history_server(
             address: &str,
             date: &str,
             temp: f32,
             humidity: u8,
             pressure: u16,
             wind_speed: f32,
-            visibility: u16,
+            visibility: u32,
             description: &str,
             api_key: &str,
-        ) -> (mockito::ServerGuard, mockito::Mock) {
+        ) -> (mockito::ServerGuard, mockito::Mock, mockito::Mock) {
             let mock_response = serde_json::json!({
                 "forecast": {
                     "forecastday": [
@@ -297,7 +846,8 @@ mod tests {
                     ]
                 }
             });
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
+            let current_endpoint = mock_current_weather(&mut mock_server, None).await;
 
             let mock_endpoint = mock_server
                 .mock("GET", "/history.json")
@@ -307,9 +857,10 @@ mod tests {
                 .with_status(200)
                 .with_header("content-type", "text/json")
                 .with_body(mock_response.to_string())
-                .create();
+                .create_async()
+                .await;
 
-            (mock_server, mock_endpoint)
+            (mock_server, mock_endpoint, current_endpoint)
         }
 
         #[rstest]
@@ -325,16 +876,13 @@ mod tests {
             #[case] humidity: u8,
             #[case] pressure: u16,
             #[case] wind_speed: f32,
-            #[case] visibility: u16,
+            #[case] visibility: u32,
             #[case] description: &str,
         ) {
             let api_key = "SomeApiKey";
-            let (mock_server, mock_endpoint) = mock_weather_api_history_server(
+            let (mock_server, mock_endpoint, _current_endpoint) = mock_weather_api_history_server(
                 address,
-                &parse_datetime_from_str(date)
-                    .unwrap()
-                    .timestamp()
-                    .to_string(),
+                &dates::parse_datetime(date).unwrap().timestamp().to_string(),
                 temp,
                 humidity,
                 pressure,
@@ -342,26 +890,170 @@ mod tests {
                 visibility,
                 description,
                 api_key,
-            );
+            )
+            .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result = api
                 .get_weather_data(address, &Some(date.to_owned()))
                 .await
                 .unwrap();
 
-            mock_endpoint.assert();
-            assert_eq!(result.temp, temp);
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.temp, Celsius(temp));
             assert_eq!(result.humidity, humidity);
-            assert_eq!(result.pressure, pressure);
-            assert!(approx_eq!(f32, result.wind_speed, wind_speed, ulps = 2));
+            assert_eq!(result.pressure, HectoPascal(pressure));
+            assert!(approx_eq!(
+                f32,
+                result.wind_speed.value(),
+                wind_speed,
+                ulps = 2
+            ));
             assert_eq!(result.visibility, visibility);
             assert_eq!(result.description, description);
         }
 
+        #[rstest]
+        #[case("in 3 hours", "/forecast.json")]
+        #[case("tomorrow 18:00", "/forecast.json")]
+        #[case("yesterday 18:00", "/history.json")]
+        #[case("2023-10-15 12:00", "/history.json")]
+        #[tokio::test]
+        async fn test_get_weather_data_date_endpoint(#[case] date: &str, #[case] path: &str) {
+            let mut mock_server = mockito::Server::new_async().await;
+            let _current_endpoint = mock_current_weather(&mut mock_server, None).await;
+            let mock_endpoint = mock_server
+                .mock("GET", path)
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::Regex(r"unixdt=\d+".into()),
+                    mockito::Matcher::Regex(r"hour=\d+".into()),
+                ]))
+                .with_status(200)
+                .with_body(
+                    serde_json::json!({
+                        "forecast": {
+                            "forecastday": [{
+                                "hour": [{
+                                    "temp_c": 18.0,
+                                    "condition": { "text": "Clear" },
+                                    "pressure_mb": 1012.0,
+                                    "humidity": 55,
+                                    "vis_km": 10.0
+                                }]
+                            }]
+                        }
+                    })
+                    .to_string(),
+                )
+                .create_async()
+                .await;
+
+            let api = WeatherApiService::builder()
+                .url(mock_server.url())
+                .api_key("SomeApiKey")
+                .build()
+                .unwrap();
+
+            let result = api
+                .get_weather_data("SomeCity", &Some(date.to_owned()))
+                .await
+                .unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.description, "Clear");
+        }
+
+        #[rstest]
+        #[case("now")]
+        #[case("in 10 min")]
+        #[tokio::test]
+        async fn test_get_weather_data_date_now(#[case] date: &str) {
+            let mut mock_server = mockito::Server::new_async().await;
+            let current_endpoint = mock_current_weather(&mut mock_server, None).await;
+            let dated_endpoint = mock_server
+                .mock("GET", mockito::Matcher::Regex("(history|forecast)".into()))
+                .expect(0)
+                .create_async()
+                .await;
+
+            let api = WeatherApiService::builder()
+                .url(mock_server.url())
+                .api_key("SomeApiKey")
+                .build()
+                .unwrap();
+
+            let result = api
+                .get_weather_data("SomeCity", &Some(date.to_owned()))
+                .await
+                .unwrap();
+
+            current_endpoint.assert_async().await;
+            dated_endpoint.assert_async().await;
+            assert_eq!(result.description, "Now");
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_weather_data_date_location_time_zone() {
+            let mut mock_server = mockito::Server::new_async().await;
+            // 21:30 in Tokyo (UTC+9) is 12:30 UTC
+            let _current_endpoint = mock_current_weather(
+                &mut mock_server,
+                Some(serde_json::json!({
+                    "localtime": "2024-07-10 21:30",
+                    "localtime_epoch": 1_720_614_600
+                })),
+            )
+            .await;
+            let history_endpoint = mock_server
+                .mock("GET", "/history.json")
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::UrlEncoded("unixdt".into(), "1719824400".into()),
+                    mockito::Matcher::UrlEncoded("hour".into(), "18".into()),
+                ]))
+                .with_status(200)
+                .with_body(
+                    serde_json::json!({
+                        "forecast": {
+                            "forecastday": [{
+                                "hour": [{
+                                    "temp_c": 24.0,
+                                    "condition": { "text": "Clear" },
+                                    "pressure_mb": 1008.0,
+                                    "humidity": 70,
+                                    "vis_km": 10.0
+                                }]
+                            }]
+                        }
+                    })
+                    .to_string(),
+                )
+                .create_async()
+                .await;
+
+            let api = WeatherApiService::builder()
+                .url(mock_server.url())
+                .api_key("SomeApiKey")
+                .build()
+                .unwrap();
+
+            let result = api
+                .get_weather_data("Tokyo", &Some("2024-07-01 18:00".to_owned()))
+                .await
+                .unwrap();
+
+            history_endpoint.assert_async().await;
+            assert_eq!(result.temp, Celsius(24.0));
+        }
+
         #[rstest]
         #[case(
             "AnotherCity",
@@ -382,11 +1074,11 @@ mod tests {
             #[case] humidity: u8,
             #[case] pressure: u16,
             #[case] wind_speed: f32,
-            #[case] visibility: u16,
+            #[case] visibility: u32,
             #[case] description: &str,
         ) {
             let api_key = "SomeApiKey";
-            let (mock_server, _) = mock_weather_api_history_server(
+            let (mock_server, ..) = mock_weather_api_history_server(
                 address,
                 date.unwrap_or_default(),
                 temp,
@@ -396,11 +1088,17 @@ mod tests {
                 visibility,
                 description,
                 api_key,
-            );
+            )
+            .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: DateTimeError = api
                 .get_weather_data(address, &date.map(|d| d.to_string()))
@@ -420,7 +1118,12 @@ mod tests {
 
             let url = "http://invalid-url";
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherApiError = api
                 .get_weather_data(address, &None)
@@ -438,18 +1141,24 @@ mod tests {
             let address = "SomeCity";
             let api_key = "123";
 
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
             let mock_endpoint = mock_server
                 .mock("GET", "/current.json")
                 .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
                 .match_query(mockito::Matcher::UrlEncoded("key".into(), api_key.into()))
                 .with_status(200)
                 .with_body("invalid json")
-                .create();
+                .create_async()
+                .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherDataError = api
                 .get_weather_data(address, &None)
@@ -458,7 +1167,7 @@ mod tests {
                 .downcast()
                 .unwrap();
 
-            mock_endpoint.assert();
+            mock_endpoint.assert_async().await;
             assert!(matches!(result, WeatherDataError::JsonParse(_)));
         }
 
@@ -469,25 +1178,29 @@ mod tests {
             let api_key = "123";
             let date = "2023-10-17 00:00";
 
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
+            let _current_endpoint = mock_current_weather(&mut mock_server, None).await;
             let mock_endpoint = mock_server
                 .mock("GET", "/history.json")
                 .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
                 .match_query(mockito::Matcher::UrlEncoded("key".into(), api_key.into()))
                 .match_query(mockito::Matcher::UrlEncoded(
                     "unixdt".into(),
-                    parse_datetime_from_str(date)
-                        .unwrap()
-                        .timestamp()
-                        .to_string(),
+                    dates::parse_datetime(date).unwrap().timestamp().to_string(),
                 ))
                 .with_status(200)
                 .with_body("invalid json")
-                .create();
+                .create_async()
+                .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherDataError = api
                 .get_weather_data(address, &Some(date.to_owned()))
@@ -496,35 +1209,52 @@ mod tests {
                 .downcast()
                 .unwrap();
 
-            mock_endpoint.assert();
+            mock_endpoint.assert_async().await;
             assert!(matches!(result, WeatherDataError::JsonParse(_)));
         }
 
         #[rstest]
+        #[case(400, 1006, "No matching location found.", |err: &WeatherApiError| matches!(err, WeatherApiError::LocationNotFound(_)))]
+        #[case(401, 2006, "API key provided is invalid", |err: &WeatherApiError| matches!(err, WeatherApiError::Unauthorized(_)))]
+        #[case(403, 2008, "API key has been disabled.", |err: &WeatherApiError| matches!(err, WeatherApiError::Unauthorized(_)))]
+        #[case(403, 2007, "API key has exceeded calls per month quota.", |err: &WeatherApiError| matches!(err, WeatherApiError::QuotaExceeded(_)))]
+        #[case(400, 1008, "The date is out of the allowed range.", |err: &WeatherApiError| matches!(err, WeatherApiError::DateOutOfRange(_)))]
+        #[case(400, 9999, "Internal application error.", |err: &WeatherApiError| matches!(err, WeatherApiError::Server(_)))]
         #[tokio::test]
-        async fn test_get_weather_data_server_response_error() {
+        async fn test_get_weather_data_server_response_error(
+            #[case] status: usize,
+            #[case] code: u16,
+            #[case] message: &str,
+            #[case] is_expected_error: fn(&WeatherApiError) -> bool,
+        ) {
             let address = "Invalid City";
             let api_key = "123";
             let mock_response = json!(
             {
                 "error": {
-                    "code": 1006,
-                    "message": "No matching location found."
+                    "code": code,
+                    "message": message
                 }
             });
 
-            let mut mock_server = mockito::Server::new();
+            let mut mock_server = mockito::Server::new_async().await;
             let mock_endpoint = mock_server
                 .mock("GET", "/current.json")
                 .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
                 .match_query(mockito::Matcher::UrlEncoded("key".into(), api_key.into()))
-                .with_status(404)
+                .with_status(status)
                 .with_body(mock_response.to_string())
-                .create();
+                .create_async()
+                .await;
 
             let url = mock_server.url();
             let client = Client::new();
-            let api = WeatherApiService::new(client, url.to_string(), api_key.to_string()).unwrap();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
 
             let result: WeatherApiError = api
                 .get_weather_data(address, &None)
@@ -533,8 +1263,221 @@ mod tests {
                 .downcast()
                 .unwrap();
 
-            mock_endpoint.assert();
-            assert!(matches!(result, WeatherApiError::Server(_)));
+            mock_endpoint.assert_async().await;
+            assert!(is_expected_error(&result));
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_forecast() {
+            let address = "CityName";
+            let api_key = "SomeApiKey";
+            let mock_response = json!({
+                "forecast": {
+                    "forecastday": [
+                        {
+                            "date": "2023-12-10",
+                            "day": {
+                                "maxtemp_c": 7.5,
+                                "mintemp_c": 1.0,
+                                "totalprecip_mm": 4.2,
+                                "daily_chance_of_rain": 85,
+                                "daily_chance_of_snow": 0,
+                                "condition": {"text": "Moderate rain"}
+                            },
+                            "hour": [
+                                {
+                                    "time": "2023-12-10 00:00",
+                                    "temp_c": 1.0,
+                                    "condition": {"text": "Light rain"},
+                                    "wind_kph": 18.0,
+                                    "precip_mm": 0.3,
+                                    "chance_of_rain": 70,
+                                    "chance_of_snow": 0
+                                }
+                            ]
+                        }
+                    ]
+                }
+            });
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/forecast.json")
+                .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
+                .match_query(mockito::Matcher::UrlEncoded("key".into(), api_key.into()))
+                .match_query(mockito::Matcher::UrlEncoded("days".into(), "1".into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
+
+            let result = api.get_forecast(address, 1).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].date, "2023-12-10");
+            assert_eq!(result[0].max_temp, Celsius(7.5));
+            assert_eq!(result[0].chance_of_rain, 85);
+            assert_eq!(result[0].precipitation, 4.2);
+            assert_eq!(result[0].hours[0].chance_of_rain, 70);
+            assert_eq!(result[0].hours[0].precipitation, 0.3);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_marine_data() {
+            let address = "Brighton";
+            let api_key = "SomeApiKey";
+            let mock_response = json!({
+                "forecast": {
+                    "forecastday": [{
+                        "date": "2023-12-10",
+                        "day": {
+                            "tides": [{
+                                "tide": [
+                                    {"tide_time": "2023-12-10 03:14", "tide_height_mt": "1.20", "tide_type": "HIGH"}
+                                ]
+                            }]
+                        },
+                        "hour": [{
+                            "time": "2023-12-10 00:00",
+                            "sig_ht_mt": 1.4,
+                            "swell_ht_mt": 0.9,
+                            "swell_period_secs": 11.2,
+                            "swell_dir_16_point": "WSW",
+                            "water_temp_c": 12.5
+                        }]
+                    }]
+                }
+            });
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/marine.json")
+                .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
+                .match_query(mockito::Matcher::UrlEncoded("tides".into(), "yes".into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let url = mock_server.url();
+            let client = Client::new();
+            let api = WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .unwrap();
+
+            let result = api.get_marine_data(address, 1).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.days[0].tides[0].tide_type, "HIGH");
+            assert_eq!(result.days[0].tides[0].height, 1.2);
+            assert_eq!(result.days[0].hours[0].swell_height, 0.9);
+        }
+
+        #[rstest]
+        #[case(json!({"alerts": {"alert": []}}), 0)]
+        #[case(json!({"location": {"name": "Miami"}}), 0)]
+        #[case(
+            json!({
+                "alerts": {
+                    "alert": [{
+                        "headline": "Wind Advisory issued December 10 at 4:02AM EST until December 10 at 6:00PM EST",
+                        "severity": "Moderate",
+                        "event": "Wind Advisory",
+                        "effective": "2023-12-10T04:02:00-05:00",
+                        "expires": "2023-12-10T18:00:00-05:00"
+                    }]
+                }
+            }),
+            1
+        )]
+        #[tokio::test]
+        async fn test_get_alerts(#[case] mock_response: Value, #[case] expected_count: usize) {
+            let address = "Miami";
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/alerts.json")
+                .match_query(mockito::Matcher::UrlEncoded("q".into(), address.into()))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let api = WeatherApiService::builder()
+                .client(Client::new())
+                .url(mock_server.url())
+                .api_key("SomeApiKey")
+                .build()
+                .unwrap();
+
+            let result = api.get_alerts(address).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.len(), expected_count);
+            if let Some(alert) = result.first() {
+                assert_eq!(alert.event, "Wind Advisory");
+                assert_eq!(alert.severity, "Moderate");
+            }
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_get_snow_report_with_language() {
+            let address = "Zermatt";
+            let mock_response = json!({
+                "forecast": {
+                    "forecastday": [{
+                        "date": "2024-01-10",
+                        "day": {
+                            "maxtemp_c": -2.0,
+                            "mintemp_c": -9.0,
+                            "totalsnow_cm": 12.5,
+                            "daily_chance_of_snow": 90,
+                            "condition": {"text": "Starker Schneefall"}
+                        }
+                    }]
+                }
+            });
+
+            let mut mock_server = mockito::Server::new_async().await;
+            let mock_endpoint = mock_server
+                .mock("GET", "/forecast.json")
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::UrlEncoded("q".into(), address.into()),
+                    mockito::Matcher::UrlEncoded("lang".into(), "de".into()),
+                ]))
+                .with_status(200)
+                .with_body(mock_response.to_string())
+                .create_async()
+                .await;
+
+            let api = WeatherApiService::builder()
+                .client(Client::new())
+                .url(mock_server.url())
+                .api_key("SomeApiKey")
+                .language(Some("de".to_owned()))
+                .build()
+                .unwrap();
+
+            let result = api.get_snow_report(address, 1).await.unwrap();
+
+            mock_endpoint.assert_async().await;
+            assert_eq!(result.total_snowfall, 12.5);
+            assert_eq!(result.days[0].chance_of_snow, 90);
         }
     }
 }
@@ -0,0 +1,56 @@
+use reqwest::Client;
+use wasm_bindgen::prelude::*;
+
+use super::{
+    openweather_service::OpenWeatherApiService, weatherapi_service::WeatherApiService, WeatherApi,
+};
+
+/// Retrieves weather data from a provider and returns it as a JSON string (JavaScript binding).
+///
+/// # Arguments
+///
+/// * `provider` - The name of the provider (`open-weather` or `weather-api`).
+/// * `url` - The base URL of the provider API.
+/// * `api_key` - The API key required for authentication.
+/// * `address` - A string representing the address for which weather data is requested.
+/// * `date` - An optional string containing the date for historical weather data.
+///
+/// # Returns
+///
+/// A `Result` containing the retrieved weather data serialized to JSON or a JavaScript error if the request fails.
+#[wasm_bindgen(js_name = getWeatherData)]
+pub async fn get_weather_data(
+    provider: String,
+    url: String,
+    api_key: String,
+    address: String,
+    date: Option<String>,
+) -> Result<String, JsError> {
+    let client = Client::new();
+    let weather_api: Box<dyn WeatherApi> = match provider.as_str() {
+        "open-weather" => Box::new(
+            OpenWeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .map_err(|err| JsError::new(&err.to_string()))?,
+        ),
+        "weather-api" => Box::new(
+            WeatherApiService::builder()
+                .client(client)
+                .url(url)
+                .api_key(api_key)
+                .build()
+                .map_err(|err| JsError::new(&err.to_string()))?,
+        ),
+        _ => return Err(JsError::new(&format!("Unknown provider '{provider}'"))),
+    };
+
+    let weather_data = weather_api
+        .get_weather_data(&address, &date)
+        .await
+        .map_err(|err| JsError::new(&err.to_string()))?;
+
+    serde_json::to_string(&weather_data).map_err(|err| JsError::new(&err.to_string()))
+}
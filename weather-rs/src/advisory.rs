@@ -0,0 +1,125 @@
+use narrate::colored::{Color, ColoredString};
+
+use crate::theme::Paint;
+
+/// The minimal erythemal dose (J/m²) of fair skin (Fitzpatrick skin type II) used to estimate the time to sunburn.
+const MINIMAL_ERYTHEMAL_DOSE: f32 = 250.0;
+
+/// The erythemally weighted irradiance (W/m²) of one unit of the UV index.
+const UV_INDEX_IRRADIANCE: f32 = 0.025;
+
+/// Represents the exposure categories of the UV index defined by the WHO.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UvCategory {
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+    Extreme,
+}
+
+/// `UvCategory` constructors and methods
+impl UvCategory {
+    /// Classifies the UV index into the WHO exposure category.
+    ///
+    /// # Arguments
+    ///
+    /// * `uv_index` - The UV index.
+    ///
+    /// # Returns
+    ///
+    /// The `UvCategory` of the UV index (`0-2` low, `3-5` moderate, `6-7` high, `8-10` very high, `11+` extreme).
+    pub fn from_index(uv_index: f32) -> Self {
+        match uv_index.round() as i32 {
+            i32::MIN..=2 => UvCategory::Low,
+            3..=5 => UvCategory::Moderate,
+            6..=7 => UvCategory::High,
+            8..=10 => UvCategory::VeryHigh,
+            _ => UvCategory::Extreme,
+        }
+    }
+
+    /// Returns the name of the category colored with the WHO color scheme.
+    ///
+    /// # Returns
+    ///
+    /// The colored name (green, yellow, orange, red or violet).
+    pub fn colored_name(&self) -> ColoredString {
+        match self {
+            UvCategory::Low => "Low".paint_color(Color::Green),
+            UvCategory::Moderate => "Moderate".paint_color(Color::Yellow),
+            UvCategory::High => "High".paint_color(Color::TrueColor {
+                r: 255,
+                g: 140,
+                b: 0,
+            }),
+            UvCategory::VeryHigh => "Very high".paint_color(Color::Red),
+            UvCategory::Extreme => "Extreme".paint_color(Color::TrueColor {
+                r: 148,
+                g: 0,
+                b: 211,
+            }),
+        }
+    }
+
+    /// Returns the WHO sun protection recommendation for the category.
+    ///
+    /// # Returns
+    ///
+    /// The recommendation text.
+    pub fn recommendation(&self) -> &'static str {
+        match self {
+            UvCategory::Low => "No protection required",
+            UvCategory::Moderate | UvCategory::High => {
+                "Protection required: seek shade during midday hours, wear a shirt, sunscreen and a hat"
+            }
+            UvCategory::VeryHigh | UvCategory::Extreme => {
+                "Extra protection required: avoid being outside during midday hours, shirt, sunscreen and hat are a must"
+            }
+        }
+    }
+}
+
+/// Estimates the time outdoors after which unprotected fair skin gets sunburnt.
+///
+/// # Arguments
+///
+/// * `uv_index` - The UV index.
+///
+/// # Returns
+///
+/// The recommended time in minutes after which sun protection is needed or `None` if no protection is required.
+pub fn protection_time(uv_index: f32) -> Option<u32> {
+    if UvCategory::from_index(uv_index) == UvCategory::Low {
+        return None;
+    }
+
+    let minutes = MINIMAL_ERYTHEMAL_DOSE / (uv_index * UV_INDEX_IRRADIANCE * 60.0);
+
+    Some(minutes.round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, UvCategory::Low)]
+    #[case(2.4, UvCategory::Low)]
+    #[case(2.5, UvCategory::Moderate)]
+    #[case(6.0, UvCategory::High)]
+    #[case(10.0, UvCategory::VeryHigh)]
+    #[case(11.0, UvCategory::Extreme)]
+    fn test_uv_category_from_index(#[case] uv_index: f32, #[case] expected: UvCategory) {
+        assert_eq!(UvCategory::from_index(uv_index), expected);
+    }
+
+    #[rstest]
+    #[case(1.0, None)]
+    #[case(5.0, Some(33))]
+    #[case(10.0, Some(17))]
+    fn test_protection_time(#[case] uv_index: f32, #[case] expected: Option<u32>) {
+        assert_eq!(protection_time(uv_index), expected);
+    }
+}
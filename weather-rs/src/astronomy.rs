@@ -0,0 +1,258 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use std::f64::consts::PI;
+
+/// The mean length of the synodic month (from new moon to new moon) in days.
+const SYNODIC_MONTH: f64 = 29.530_588_853;
+
+/// The J2000.0 epoch (2000-01-01 12:00 UTC) as a unix timestamp.
+const J2000: i64 = 946_728_000;
+
+/// Represents the eight principal and intermediate phases of the moon.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonPhase {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+/// `MoonPhase` constructors and methods
+impl MoonPhase {
+    /// Classifies the age of the moon into a phase.
+    ///
+    /// # Arguments
+    ///
+    /// * `age` - The age of the moon (days since the last new moon).
+    ///
+    /// # Returns
+    ///
+    /// The `MoonPhase` whose eighth of the synodic month contains the age.
+    pub fn from_age(age: f64) -> Self {
+        let eighth = ((age / SYNODIC_MONTH * 8.0).round() as i64).rem_euclid(8);
+
+        match eighth {
+            0 => MoonPhase::NewMoon,
+            1 => MoonPhase::WaxingCrescent,
+            2 => MoonPhase::FirstQuarter,
+            3 => MoonPhase::WaxingGibbous,
+            4 => MoonPhase::FullMoon,
+            5 => MoonPhase::WaningGibbous,
+            6 => MoonPhase::LastQuarter,
+            _ => MoonPhase::WaningCrescent,
+        }
+    }
+
+    /// Checks whether the phase is a principal one (new moon, first quarter, full moon or last quarter).
+    pub fn is_principal(&self) -> bool {
+        matches!(
+            self,
+            MoonPhase::NewMoon
+                | MoonPhase::FirstQuarter
+                | MoonPhase::FullMoon
+                | MoonPhase::LastQuarter
+        )
+    }
+
+    /// Returns the age of the moon (days since the last new moon) at the exact phase.
+    fn exact_age(&self) -> f64 {
+        *self as u8 as f64 * SYNODIC_MONTH / 8.0
+    }
+
+    /// Returns the emoji icon of the phase (as seen from the northern hemisphere).
+    pub fn icon(&self) -> &'static str {
+        match self {
+            MoonPhase::NewMoon => "🌑",
+            MoonPhase::WaxingCrescent => "🌒",
+            MoonPhase::FirstQuarter => "🌓",
+            MoonPhase::WaxingGibbous => "🌔",
+            MoonPhase::FullMoon => "🌕",
+            MoonPhase::WaningGibbous => "🌖",
+            MoonPhase::LastQuarter => "🌗",
+            MoonPhase::WaningCrescent => "🌘",
+        }
+    }
+
+    /// Returns the human-readable name of the phase (e.g. `First quarter`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoonPhase::NewMoon => "New moon",
+            MoonPhase::WaxingCrescent => "Waxing crescent",
+            MoonPhase::FirstQuarter => "First quarter",
+            MoonPhase::WaxingGibbous => "Waxing gibbous",
+            MoonPhase::FullMoon => "Full moon",
+            MoonPhase::WaningGibbous => "Waning gibbous",
+            MoonPhase::LastQuarter => "Last quarter",
+            MoonPhase::WaningCrescent => "Waning crescent",
+        }
+    }
+}
+
+/// Represents the moon on a day.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct MoonDay {
+    /// The date in the `YYYY-MM-DD` format.
+    pub date: String,
+    pub phase: MoonPhase,
+    /// The age of the moon in days since the last new moon.
+    pub age: f64,
+    /// The illuminated fraction of the moon disk in percent.
+    pub illumination: u8,
+}
+
+/// `MoonDay` constructors
+impl MoonDay {
+    /// Computes the moon phase of a date at noon UTC.
+    ///
+    /// The elongation of the moon from the sun is computed from the mean elongation with the largest periodic terms
+    /// of the lunar and solar orbits, which is accurate to a few hours and enough for a calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Returns
+    ///
+    /// The `MoonDay` of the date.
+    pub fn from_date(date: NaiveDate) -> Self {
+        let noon = NaiveDateTime::new(date, NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default());
+        let days = (noon.and_utc().timestamp() - J2000) as f64 / 86_400.0;
+        let elongation = elongation(days);
+        let age = elongation / (2.0 * PI) * SYNODIC_MONTH;
+        let illumination = (1.0 - elongation.cos()) / 2.0 * 100.0;
+
+        MoonDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            phase: MoonPhase::from_age(age),
+            age,
+            illumination: illumination.round() as u8,
+        }
+    }
+}
+
+/// Computes the elongation of the moon from the sun in radians (`0` at the new moon, `PI` at the full moon).
+fn elongation(days_since_j2000: f64) -> f64 {
+    let d = days_since_j2000;
+    let mean_elongation = (297.850_192_1 + 12.190_749_12 * d).to_radians();
+    let sun_anomaly = (357.529_109_2 + 0.985_600_28 * d).to_radians();
+    let moon_anomaly = (134.963_396_4 + 13.064_992_95 * d).to_radians();
+
+    let elongation = mean_elongation
+        + (6.289 * moon_anomaly.sin() - 2.100 * sun_anomaly.sin()
+            + 1.274 * (2.0 * mean_elongation - moon_anomaly).sin()
+            + 0.658 * (2.0 * mean_elongation).sin()
+            + 0.214 * (2.0 * moon_anomaly).sin())
+        .to_radians();
+
+    elongation.rem_euclid(2.0 * PI)
+}
+
+/// Computes the moon phases of every day of a month.
+///
+/// # Arguments
+///
+/// * `first_day` - The first day of the month.
+///
+/// # Returns
+///
+/// The `MoonDay` of every day of the month.
+pub fn moon_calendar(first_day: NaiveDate) -> Vec<MoonDay> {
+    first_day
+        .iter_days()
+        .take_while(|date| date.month() == first_day.month())
+        .map(MoonDay::from_date)
+        .collect()
+}
+
+/// Finds the days of the principal phases (new moon, first quarter, full moon and last quarter) in a calendar.
+///
+/// Every phase lasts several days in the calendar; the day with the moon age closest to the exact phase is chosen.
+///
+/// # Arguments
+///
+/// * `calendar` - The moon phases of consecutive days.
+///
+/// # Returns
+///
+/// The days of the principal phases in the calendar order.
+pub fn principal_phase_days(calendar: &[MoonDay]) -> Vec<&MoonDay> {
+    let distance = |moon_day: &MoonDay| {
+        let distance = (moon_day.age - moon_day.phase.exact_age()).abs();
+        distance.min(SYNODIC_MONTH - distance)
+    };
+
+    calendar
+        .chunk_by(|day, next_day| day.phase == next_day.phase)
+        .filter(|days| days[0].phase.is_principal())
+        .filter_map(|days| {
+            days.iter()
+                .min_by(|day, other_day| distance(day).total_cmp(&distance(other_day)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, MoonPhase::NewMoon)]
+    #[case(4.0, MoonPhase::WaxingCrescent)]
+    #[case(7.4, MoonPhase::FirstQuarter)]
+    #[case(14.8, MoonPhase::FullMoon)]
+    #[case(22.1, MoonPhase::LastQuarter)]
+    #[case(29.3, MoonPhase::NewMoon)]
+    fn test_moon_phase_from_age(#[case] age: f64, #[case] expected: MoonPhase) {
+        assert_eq!(MoonPhase::from_age(age), expected);
+    }
+
+    #[rstest]
+    #[case(2024, 8, 4, MoonPhase::NewMoon)]
+    #[case(2024, 8, 19, MoonPhase::FullMoon)]
+    #[case(2024, 8, 12, MoonPhase::FirstQuarter)]
+    #[case(2024, 8, 26, MoonPhase::LastQuarter)]
+    fn test_moon_day_from_date(
+        #[case] year: i32,
+        #[case] month: u32,
+        #[case] day: u32,
+        #[case] expected: MoonPhase,
+    ) {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        assert_eq!(MoonDay::from_date(date).phase, expected);
+    }
+
+    #[rstest]
+    fn test_principal_phase_days() {
+        let first_day = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let calendar = moon_calendar(first_day);
+
+        let dates: Vec<&str> = principal_phase_days(&calendar)
+            .into_iter()
+            .map(|moon_day| moon_day.date.as_str())
+            .collect();
+
+        assert_eq!(
+            dates,
+            ["2024-08-04", "2024-08-12", "2024-08-19", "2024-08-26"]
+        );
+    }
+
+    #[rstest]
+    #[case(2024, 2, 29)]
+    #[case(2024, 8, 31)]
+    fn test_moon_calendar(#[case] year: i32, #[case] month: u32, #[case] days: usize) {
+        let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+
+        let calendar = moon_calendar(first_day);
+
+        assert_eq!(calendar.len(), days);
+        assert_eq!(calendar[0].date, format!("{year}-{month:02}-01"));
+    }
+}
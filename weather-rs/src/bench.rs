@@ -0,0 +1,277 @@
+use async_trait::async_trait;
+use narrate::anyhow::Result;
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use weather_api_services::http::{HttpResponse, Middleware, Next};
+
+/// The default number of requests sent to every provider by the benchmark.
+pub const DEFAULT_RUNS: u8 = 10;
+
+/// Represents errors related to the benchmark of providers.
+#[derive(Error, Debug)]
+pub enum BenchError {
+    /// An error indicating that some providers didn't return weather data in any run of the benchmark.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string listing the failed providers.
+    #[error("Providers failed every run of the benchmark - {0}")]
+    Failed(String),
+}
+
+/// Represents a response that passed through the `TransferProbe` middleware.
+#[derive(Clone, Copy, Debug)]
+struct Transfer {
+    /// The time from sending the request to reading the response body.
+    duration: Duration,
+    /// The size of the response body in bytes.
+    bytes: usize,
+}
+
+/// Middleware that records the time and the payload size of the responses passing through it, so the time spent
+/// on the network can be told apart from the time spent parsing.
+#[derive(Clone, Debug, Default)]
+pub struct TransferProbe {
+    transfers: Arc<Mutex<Vec<Transfer>>>,
+}
+
+/// `TransferProbe` methods
+impl TransferProbe {
+    /// Takes the responses recorded since the last call, e.g. those of a single run.
+    ///
+    /// # Returns
+    ///
+    /// The total time of the responses and the total size of their bodies in bytes.
+    pub fn take(&self) -> (Duration, usize) {
+        self.transfers
+            .lock()
+            .map(|mut transfers| {
+                transfers
+                    .drain(..)
+                    .fold((Duration::ZERO, 0), |(duration, bytes), transfer| {
+                        (duration + transfer.duration, bytes + transfer.bytes)
+                    })
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Middleware for TransferProbe {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let started = Instant::now();
+        let result = next.run(request).await;
+
+        if let (Ok(response), Ok(mut transfers)) = (&result, self.transfers.lock()) {
+            transfers.push(Transfer {
+                duration: started.elapsed(),
+                bytes: response.body.len(),
+            });
+        }
+
+        result
+    }
+}
+
+/// Represents a successful run of the benchmark: a request of the current weather of the address.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchRun {
+    /// The time of the run, from sending the requests to the parsed weather data.
+    pub latency: Duration,
+    /// The part of the time spent on sending the requests and reading the responses.
+    pub network: Duration,
+    /// The total size of the response bodies in bytes.
+    pub payload_bytes: usize,
+}
+
+/// `BenchRun` methods
+impl BenchRun {
+    /// Returns the part of the time spent on parsing the responses into weather data.
+    pub fn parse_time(&self) -> Duration {
+        self.latency.saturating_sub(self.network)
+    }
+}
+
+/// Represents the results of the benchmark of a provider.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct BenchResult {
+    pub provider: String,
+    /// The number of runs.
+    pub runs: usize,
+    /// The number of runs that didn't return weather data.
+    pub failures: usize,
+    /// The median latency of successful runs in milliseconds.
+    pub p50_ms: Option<u64>,
+    /// The 95th percentile of the latency of successful runs in milliseconds.
+    pub p95_ms: Option<u64>,
+    /// The median payload size of successful runs in bytes.
+    pub payload_bytes: Option<usize>,
+    /// The median parse time of successful runs in milliseconds.
+    pub parse_ms: Option<f64>,
+    /// The error of the last failed run; `None` if all runs returned weather data.
+    pub error: Option<String>,
+}
+
+/// `BenchResult` constructors and methods
+impl BenchResult {
+    /// Summarizes the runs of the benchmark of a provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The name of the provider.
+    /// * `runs` - The successful runs or the errors of failed runs.
+    ///
+    /// # Returns
+    ///
+    /// The results of the provider; the statistics are `None` if no run succeeded.
+    pub fn new(provider: String, runs: &[std::result::Result<BenchRun, String>]) -> Self {
+        let successful: Vec<&BenchRun> = runs.iter().filter_map(|run| run.as_ref().ok()).collect();
+        let latencies = sorted(successful.iter().map(|run| run.latency));
+        let parse_times = sorted(successful.iter().map(|run| run.parse_time()));
+        let payloads = sorted(successful.iter().map(|run| run.payload_bytes));
+
+        BenchResult {
+            provider,
+            runs: runs.len(),
+            failures: runs.len() - successful.len(),
+            p50_ms: percentile(&latencies, 50).map(|latency| latency.as_millis() as u64),
+            p95_ms: percentile(&latencies, 95).map(|latency| latency.as_millis() as u64),
+            payload_bytes: percentile(&payloads, 50),
+            parse_ms: percentile(&parse_times, 50)
+                .map(|parse_time| parse_time.as_secs_f64() * 1000.0),
+            error: runs.iter().rev().find_map(|run| run.clone().err()),
+        }
+    }
+}
+
+/// Sorts the values of the runs.
+fn sorted<T: Ord>(values: impl Iterator<Item = T>) -> Vec<T> {
+    let mut values: Vec<T> = values.collect();
+    values.sort();
+    values
+}
+
+/// Picks a percentile of sorted values by the nearest-rank method.
+///
+/// # Arguments
+///
+/// * `sorted` - The sorted values.
+/// * `percentile` - The percentile (from 1 to 100).
+///
+/// # Returns
+///
+/// The value of the percentile or `None` if there are no values.
+fn percentile<T: Copy>(sorted: &[T], percentile: usize) -> Option<T> {
+    let rank = (sorted.len() * percentile).div_ceil(100);
+
+    sorted.get(rank.max(1) - 1).copied()
+}
+
+/// Orders the results of the benchmark by the median latency, the fastest provider first; providers without
+/// successful runs go last.
+///
+/// # Arguments
+///
+/// * `results` - The results of the providers.
+pub fn rank(results: &mut [BenchResult]) {
+    results.sort_by_key(|result| (result.p50_ms.is_none(), result.p50_ms));
+}
+
+/// Checks the results of a benchmark.
+///
+/// # Arguments
+///
+/// * `results` - The results of the providers.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `BenchError` listing the providers that failed every run.
+pub fn check_results(results: &[BenchResult]) -> std::result::Result<(), BenchError> {
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|result| result.failures == result.runs)
+        .map(|result| result.provider.as_str())
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(BenchError::Failed(failed.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn run(latency_ms: u64, network_ms: u64, payload_bytes: usize) -> Result<BenchRun, String> {
+        Ok(BenchRun {
+            latency: Duration::from_millis(latency_ms),
+            network: Duration::from_millis(network_ms),
+            payload_bytes,
+        })
+    }
+
+    #[rstest]
+    #[case(&[], 50, None)]
+    #[case(&[7], 95, Some(7))]
+    #[case(&[1, 2, 3, 4], 50, Some(2))]
+    #[case(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 50, Some(5))]
+    #[case(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 95, Some(10))]
+    fn test_percentile(#[case] values: &[u64], #[case] rank: usize, #[case] expected: Option<u64>) {
+        assert_eq!(percentile(values, rank), expected);
+    }
+
+    #[rstest]
+    fn test_bench_result() {
+        let runs = vec![
+            run(120, 100, 900),
+            Err("Unauthorized".to_owned()),
+            run(300, 290, 1000),
+            run(100, 96, 800),
+        ];
+
+        let result = BenchResult::new("open-weather".to_owned(), &runs);
+
+        assert_eq!(
+            result,
+            BenchResult {
+                provider: "open-weather".to_owned(),
+                runs: 4,
+                failures: 1,
+                p50_ms: Some(120),
+                p95_ms: Some(300),
+                payload_bytes: Some(900),
+                parse_ms: Some(10.0),
+                error: Some("Unauthorized".to_owned()),
+            }
+        );
+    }
+
+    #[rstest]
+    fn test_rank_and_check_results() {
+        let mut results = vec![
+            BenchResult::new("open-weather".to_owned(), &[run(300, 290, 1000)]),
+            BenchResult::new("local".to_owned(), &[Err("Plugin failed".to_owned())]),
+            BenchResult::new("weather-api".to_owned(), &[run(100, 96, 800)]),
+        ];
+
+        rank(&mut results);
+
+        let providers: Vec<&str> = results
+            .iter()
+            .map(|result| result.provider.as_str())
+            .collect();
+        assert_eq!(providers, vec!["weather-api", "open-weather", "local"]);
+        assert!(matches!(
+            check_results(&results),
+            Err(BenchError::Failed(failed)) if failed == "local"
+        ));
+        assert!(check_results(&results[..2]).is_ok());
+    }
+}
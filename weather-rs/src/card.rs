@@ -0,0 +1,243 @@
+use chrono::NaiveDate;
+use convert_case::{Case, Casing};
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Stdio;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::report::escape_html;
+use crate::theme::{Paint, Role};
+use crate::views;
+use weather_api_services::models::{ForecastDay, WeatherData};
+use weather_api_services::units::Units;
+
+/// The executable used to rasterize cards to PNG (part of librsvg).
+const RASTERIZER: &str = "rsvg-convert";
+
+/// The size of a card in pixels.
+const CARD_WIDTH: u32 = 400;
+const CARD_HEIGHT: u32 = 200;
+
+/// The maximum number of days in the forecast strip of a card.
+const MAX_STRIP_DAYS: usize = 5;
+
+/// Represents errors related to weather cards.
+#[derive(Error, Debug)]
+pub enum CardError {
+    /// An error indicating a failure to write the card file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the card file.
+    #[error("Failed to write the card '{1}'")]
+    Io(std::io::Error, String),
+
+    /// An error indicating that the card can't be rasterized to PNG.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the reason.
+    #[error("Failed to rasterize the card to PNG - {0}. Please install 'rsvg-convert' (librsvg) or write an SVG card")]
+    Rasterize(String),
+}
+
+/// Represents image formats of weather cards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CardFormat {
+    Svg,
+    Png,
+}
+
+/// `CardFormat` methods
+impl CardFormat {
+    /// Selects the format of a card by the extension of its file (PNG for `.png`, otherwise SVG).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("png") => CardFormat::Png,
+            _ => CardFormat::Svg,
+        }
+    }
+}
+
+/// Renders a compact weather card as SVG: the condition icon, the temperature, the location and a forecast strip.
+///
+/// # Arguments
+///
+/// * `location` - The location shown on the card.
+/// * `weather_data` - The current weather.
+/// * `forecast` - The forecasted days; the first days are shown in the strip.
+/// * `units` - The units of the temperatures on the card.
+///
+/// # Returns
+///
+/// The SVG document of the card.
+pub fn render_card(
+    location: &str,
+    weather_data: &WeatherData,
+    forecast: &[ForecastDay],
+    units: Units,
+) -> String {
+    let temp_symbol = units.temperature_symbol();
+    let (background_from, background_to) = if weather_data.is_day {
+        ("#4facfe", "#00c6fb")
+    } else {
+        ("#141e30", "#243b55")
+    };
+    let icon = views::condition_icon(&weather_data.description, weather_data.is_day).unwrap_or("");
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{CARD_WIDTH}" height="{CARD_HEIGHT}" viewBox="0 0 {CARD_WIDTH} {CARD_HEIGHT}" font-family="system-ui, -apple-system, 'Segoe UI', sans-serif" fill="#ffffff">
+<defs><linearGradient id="background" x1="0" y1="0" x2="1" y2="1"><stop offset="0" stop-color="{background_from}"/><stop offset="1" stop-color="{background_to}"/></linearGradient></defs>
+<rect width="{CARD_WIDTH}" height="{CARD_HEIGHT}" rx="16" fill="url(#background)"/>
+<text x="20" y="36" font-size="18" font-weight="600">{}</text>
+<text x="20" y="58" font-size="13" opacity="0.85">{}</text>
+<text x="20" y="110" font-size="44" font-weight="700">{:.0}{temp_symbol}</text>
+<text x="{}" y="108" font-size="56" text-anchor="end">{}</text>
+"##,
+        escape_html(location),
+        escape_html(&weather_data.description.to_case(Case::Title)),
+        units.temperature(weather_data.temp),
+        CARD_WIDTH - 24,
+        icon
+    );
+
+    let days: Vec<&ForecastDay> = forecast.iter().take(MAX_STRIP_DAYS).collect();
+    let column_width = (CARD_WIDTH - 40) as f32 / days.len().max(1) as f32;
+    for (index, day) in days.iter().enumerate() {
+        let x = 20.0 + column_width * (index as f32 + 0.5);
+        let weekday = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+            .map(|date| date.format("%a").to_string())
+            .unwrap_or_else(|_| day.date.clone());
+        let icon = views::condition_icon(&day.description, true).unwrap_or("·");
+        let _ = write!(
+            svg,
+            r#"<text x="{x:.1}" y="146" font-size="12" text-anchor="middle" opacity="0.85">{}</text>
+<text x="{x:.1}" y="168" font-size="16" text-anchor="middle">{icon}</text>
+<text x="{x:.1}" y="188" font-size="12" text-anchor="middle">{:.0}° / {:.0}°</text>
+"#,
+            escape_html(&weekday),
+            units.temperature(day.max_temp),
+            units.temperature(day.min_temp)
+        );
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/// Writes a card to a file, rasterizing it with `rsvg-convert` if the format is PNG.
+///
+/// # Arguments
+///
+/// * `svg` - The SVG document of the card.
+/// * `path` - The path to the card file.
+/// * `format` - The image format of the card file.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `CardError` if the card can't be written or rasterized.
+pub async fn write_card(svg: &str, path: &Path, format: CardFormat) -> Result<(), CardError> {
+    if format == CardFormat::Svg {
+        return std::fs::write(path, svg)
+            .map_err(|err| CardError::Io(err, path.display().to_string()));
+    }
+
+    let mut child = Command::new(RASTERIZER)
+        .arg("--format=png")
+        .arg("--output")
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| CardError::Rasterize(err.to_string().paint(Role::Warning).to_string()))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(svg.as_bytes()).await.map_err(|err| {
+            CardError::Rasterize(err.to_string().paint(Role::Warning).to_string())
+        })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|err| CardError::Rasterize(err.to_string().paint(Role::Warning).to_string()))?;
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+        return Err(CardError::Rasterize(
+            message.paint(Role::Warning).to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use weather_api_services::quantities::{Celsius, HectoPascal, MetersPerSecond};
+
+    fn weather_data(is_day: bool) -> WeatherData {
+        WeatherData::builder()
+            .temp(Celsius(21.4))
+            .humidity(40)
+            .pressure(HectoPascal(1018))
+            .wind_speed(MetersPerSecond(2.0))
+            .visibility(10000)
+            .description("sunny")
+            .is_day(is_day)
+            .build()
+    }
+
+    fn forecast_day(date: &str) -> ForecastDay {
+        ForecastDay {
+            date: date.to_owned(),
+            min_temp: Celsius(12.2),
+            max_temp: Celsius(22.6),
+            description: "light rain".to_owned(),
+            chance_of_rain: 70,
+            chance_of_snow: 0,
+            precipitation: 1.0,
+            hours: Vec::new(),
+        }
+    }
+
+    #[rstest]
+    fn test_render_card() {
+        let forecast: Vec<ForecastDay> = (10..=16)
+            .map(|day| forecast_day(&format!("2024-07-{day}")))
+            .collect();
+
+        let result = render_card("Rock & Roll", &weather_data(true), &forecast, Units::Metric);
+
+        assert!(result.starts_with("<svg"));
+        assert!(result.trim_end().ends_with("</svg>"));
+        assert!(result.contains(">Rock &amp; Roll</text>"));
+        assert!(result.contains(">21°C</text>"));
+        assert!(result.contains(">☀</text>"));
+        assert!(result.contains(">Wed</text>"));
+        assert!(result.contains(">23° / 12°</text>"));
+        assert_eq!(result.matches("° / ").count(), MAX_STRIP_DAYS);
+    }
+
+    #[rstest]
+    fn test_render_card_night_without_forecast() {
+        let result = render_card("London", &weather_data(false), &[], Units::Imperial);
+
+        assert!(result.contains("#141e30"));
+        assert!(result.contains(">71°F</text>"));
+        assert!(!result.contains("° / "));
+    }
+
+    #[rstest]
+    #[case("card.svg", CardFormat::Svg)]
+    #[case("card.PNG", CardFormat::Png)]
+    #[case("card", CardFormat::Svg)]
+    fn test_card_format_from_path(#[case] path: &str, #[case] expected: CardFormat) {
+        assert_eq!(CardFormat::from_path(Path::new(path)), expected);
+    }
+}
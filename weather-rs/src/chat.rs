@@ -0,0 +1,393 @@
+use chrono::NaiveDate;
+use convert_case::{Case, Casing};
+use narrate::anyhow::Result;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Client;
+use serde_json::{json, Value};
+use thiserror::Error;
+use url::Url;
+
+use crate::report::{escape_html, escape_markdown};
+use crate::theme::{Paint, Role};
+use crate::views;
+use weather_api_services::models::{ForecastDay, WeatherData};
+use weather_api_services::secret::Secret;
+use weather_api_services::units::Units;
+
+/// The URL of the Telegram Bot API.
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Represents errors related to posting weather messages to chats.
+#[derive(Error, Debug)]
+pub enum ChatError {
+    /// An error indicating that the chat service rejected the message.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the name of the chat service.
+    /// * `1` - A string representing the response of the chat service.
+    #[error("{0} rejected the message - {1}")]
+    Rejected(String, String),
+}
+
+/// Represents a chat the weather is posted to.
+///
+/// The webhook URLs and the bot token are credentials, so they're redacted when the target is printed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChatTarget {
+    /// A Slack channel with an incoming webhook.
+    Slack(Secret<Url>),
+    /// A Discord channel with a webhook.
+    Discord(Secret<Url>),
+    /// A Telegram chat of a bot.
+    Telegram { token: Secret, chat_id: String },
+}
+
+/// `ChatTarget` methods
+impl ChatTarget {
+    /// Returns the name of the chat service.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChatTarget::Slack(_) => "Slack",
+            ChatTarget::Discord(_) => "Discord",
+            ChatTarget::Telegram { .. } => "Telegram",
+        }
+    }
+
+    /// Returns the URL the message is posted to.
+    fn url(&self) -> String {
+        match self {
+            ChatTarget::Slack(url) | ChatTarget::Discord(url) => url.expose().to_string(),
+            ChatTarget::Telegram { token, .. } => {
+                format!("{TELEGRAM_API_URL}/bot{}/sendMessage", token.expose())
+            }
+        }
+    }
+
+    /// Returns the JSON payload of a message in the format of the chat service.
+    fn payload(&self, message: &ChatMessage) -> Value {
+        match self {
+            ChatTarget::Slack(_) => message.slack_payload(),
+            ChatTarget::Discord(_) => message.discord_payload(),
+            ChatTarget::Telegram { chat_id, .. } => message.telegram_payload(chat_id),
+        }
+    }
+}
+
+/// Represents a weather message of an address with the current conditions and the forecast.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChatMessage {
+    pub title: String,
+    /// The current conditions in a line.
+    pub current: String,
+    /// The forecasted days, a line per day.
+    pub forecast: Vec<String>,
+    /// The source of the weather data; `None` if it's unknown.
+    pub footer: Option<String>,
+}
+
+/// `ChatMessage` constructors and methods
+impl ChatMessage {
+    /// Creates a weather message.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - The location shown in the title.
+    /// * `weather_data` - The current weather.
+    /// * `forecast` - The forecasted days.
+    /// * `units` - The units of the temperatures and wind speed in the message.
+    ///
+    /// # Returns
+    ///
+    /// The `ChatMessage` with plain text lines.
+    pub fn new(
+        location: &str,
+        weather_data: &WeatherData,
+        forecast: &[ForecastDay],
+        units: Units,
+    ) -> Self {
+        let temp_symbol = units.temperature_symbol();
+        let icon = views::condition_icon(&weather_data.description, weather_data.is_day)
+            .map_or_else(String::new, |icon| format!("{icon} "));
+
+        let current = format!(
+            "{icon}{}, {:.0} {temp_symbol} (feels like {:.0} {temp_symbol}), humidity {} %, wind {:.1} {}",
+            weather_data.description.to_case(Case::Title),
+            units.temperature(weather_data.temp),
+            units.temperature(weather_data.feels_like),
+            weather_data.humidity,
+            units.speed(weather_data.wind_speed),
+            units.speed_symbol()
+        );
+        let forecast = forecast
+            .iter()
+            .map(|day| {
+                let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+                    .map(|date| date.format("%a %d %b").to_string())
+                    .unwrap_or_else(|_| day.date.clone());
+                format!(
+                    "{date}: {}, {:.0}..{:.0} {temp_symbol}, {} % chance of precipitation",
+                    day.description.to_case(Case::Title),
+                    units.temperature(day.min_temp),
+                    units.temperature(day.max_temp),
+                    day.chance_of_rain.max(day.chance_of_snow)
+                )
+            })
+            .collect();
+
+        ChatMessage {
+            title: format!("Weather in {location}"),
+            current,
+            forecast,
+            footer: views::source_footer(&weather_data.source),
+        }
+    }
+
+    /// Renders the message as a Slack message with blocks (a header, sections and a context footer).
+    ///
+    /// The `text` field holds the title and the current conditions for notifications.
+    pub fn slack_payload(&self) -> Value {
+        let escape = |text: &str| {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+        };
+
+        let mut blocks = vec![
+            json!({"type": "header", "text": {"type": "plain_text", "text": self.title}}),
+            json!({"type": "section", "text": {"type": "mrkdwn", "text": escape(&self.current)}}),
+        ];
+        if !self.forecast.is_empty() {
+            let forecast: Vec<String> = self
+                .forecast
+                .iter()
+                .map(|day| format!("• {}", escape(day)))
+                .collect();
+            blocks.push(
+                json!({"type": "section", "text": {"type": "mrkdwn", "text": forecast.join("\n")}}),
+            );
+        }
+        if let Some(footer) = &self.footer {
+            blocks.push(
+                json!({"type": "context", "elements": [{"type": "mrkdwn", "text": escape(footer)}]}),
+            );
+        }
+
+        json!({
+            "text": format!("{}: {}", self.title, self.current),
+            "blocks": blocks,
+        })
+    }
+
+    /// Renders the message as a Discord message with Markdown content.
+    pub fn discord_payload(&self) -> Value {
+        let mut content = format!(
+            "**{}**\n{}\n",
+            escape_markdown(&self.title),
+            escape_markdown(&self.current)
+        );
+        for day in &self.forecast {
+            content.push_str(&format!("- {}\n", escape_markdown(day)));
+        }
+        if let Some(footer) = &self.footer {
+            content.push_str(&format!("-# {}\n", escape_markdown(footer)));
+        }
+
+        json!({ "content": content.trim_end() })
+    }
+
+    /// Renders the message as a Telegram `sendMessage` request with HTML formatting.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The identifier of the chat (e.g. `-1001234567890` or `@channel`).
+    pub fn telegram_payload(&self, chat_id: &str) -> Value {
+        let mut text = format!(
+            "<b>{}</b>\n{}\n",
+            escape_html(&self.title),
+            escape_html(&self.current)
+        );
+        for day in &self.forecast {
+            text.push_str(&format!("• {}\n", escape_html(day)));
+        }
+        if let Some(footer) = &self.footer {
+            text.push_str(&format!("<i>{}</i>\n", escape_html(footer)));
+        }
+
+        json!({
+            "chat_id": chat_id,
+            "text": text.trim_end(),
+            "parse_mode": "HTML",
+            "disable_web_page_preview": true,
+        })
+    }
+}
+
+/// Asynchronously posts a weather message to a chat.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client used for the request.
+/// * `target` - The chat the message is posted to.
+/// * `message` - The weather message.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the request fails or the chat service rejects the message; the
+/// URL (with the webhook or the bot token) is removed from request errors.
+pub async fn post_message(
+    client: &Client,
+    target: &ChatTarget,
+    message: &ChatMessage,
+) -> Result<()> {
+    let response = client
+        .post(target.url())
+        .header(CONTENT_TYPE, "application/json")
+        .body(target.payload(message).to_string())
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let reason = match body.trim() {
+            "" => status.to_string(),
+            body => format!("{status} {body}"),
+        };
+        return Err(ChatError::Rejected(
+            target.name().to_owned(),
+            reason.paint(Role::Warning).to_string(),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use weather_api_services::models::DataSource;
+    use weather_api_services::quantities::{Celsius, HectoPascal, MetersPerSecond};
+
+    #[fixture]
+    fn message() -> ChatMessage {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(7.6))
+            .feels_like(Celsius(5.2))
+            .humidity(81)
+            .pressure(HectoPascal(1004))
+            .wind_speed(MetersPerSecond(5.5))
+            .visibility(10000)
+            .description("light rain")
+            .is_day(true)
+            .source(DataSource {
+                provider: "Weather API".to_owned(),
+                station: None,
+                attribution: None,
+            })
+            .build();
+        let forecast = vec![ForecastDay {
+            date: "2024-03-08".to_owned(),
+            min_temp: Celsius(3.4),
+            max_temp: Celsius(9.1),
+            description: "patchy rain nearby".to_owned(),
+            chance_of_rain: 80,
+            chance_of_snow: 10,
+            precipitation: 2.4,
+            hours: Vec::new(),
+        }];
+
+        ChatMessage::new("Rock & Roll", &weather_data, &forecast, Units::Metric)
+    }
+
+    #[rstest]
+    fn test_chat_message_new(message: ChatMessage) {
+        assert_eq!(message.title, "Weather in Rock & Roll");
+        assert_eq!(
+            message.current,
+            "🌧 Light Rain, 8 °C (feels like 5 °C), humidity 81 %, wind 5.5 m/s"
+        );
+        assert_eq!(
+            message.forecast,
+            vec!["Fri 08 Mar: Patchy Rain Nearby, 3..9 °C, 80 % chance of precipitation"]
+        );
+        assert_eq!(message.footer.as_deref(), Some("Weather API"));
+    }
+
+    #[rstest]
+    fn test_slack_payload(message: ChatMessage) {
+        let result = message.slack_payload();
+
+        assert_eq!(
+            result["blocks"][0]["text"]["text"],
+            "Weather in Rock & Roll"
+        );
+        assert_eq!(result["blocks"].as_array().unwrap().len(), 4);
+        assert!(result["blocks"][2]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("• Fri 08 Mar"));
+        assert!(result["text"].as_str().unwrap().contains("Light Rain"));
+    }
+
+    #[rstest]
+    fn test_telegram_and_discord_payloads(message: ChatMessage) {
+        let telegram = message.telegram_payload("@weather");
+        let discord = message.discord_payload();
+
+        assert_eq!(telegram["chat_id"], "@weather");
+        assert_eq!(telegram["parse_mode"], "HTML");
+        assert!(telegram["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("<b>Weather in Rock &amp; Roll</b>\n"));
+        assert!(telegram["text"]
+            .as_str()
+            .unwrap()
+            .ends_with("<i>Weather API</i>"));
+        assert!(discord["content"]
+            .as_str()
+            .unwrap()
+            .starts_with("**Weather in Rock & Roll**\n"));
+        assert!(discord["content"]
+            .as_str()
+            .unwrap()
+            .contains("\n- Fri 08 Mar"));
+    }
+
+    #[rstest]
+    fn test_chat_target_redacts_credentials() {
+        let telegram = ChatTarget::Telegram {
+            token: Secret::from("123:secret-token"),
+            chat_id: "@weather".to_owned(),
+        };
+        let slack = ChatTarget::Slack(Secret::new(
+            Url::parse("https://hooks.slack.com/services/T0/B0/secret-hook").unwrap(),
+        ));
+
+        assert!(!format!("{telegram:?} {slack:?}").contains("secret"));
+        assert_eq!(
+            telegram.url(),
+            "https://api.telegram.org/bot123:secret-token/sendMessage"
+        );
+        assert_eq!(
+            slack.url(),
+            "https://hooks.slack.com/services/T0/B0/secret-hook"
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_post_message_error_without_url(message: ChatMessage) {
+        let target = ChatTarget::Discord(Secret::new(
+            Url::parse("http://127.0.0.1:1/api/webhooks/secret-hook").unwrap(),
+        ));
+
+        let result = post_message(&Client::new(), &target, &message).await;
+
+        assert!(!format!("{:#}", result.unwrap_err()).contains("secret-hook"));
+    }
+}
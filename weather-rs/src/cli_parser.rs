@@ -1,13 +1,458 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use url::Url;
 
-use crate::providers::Provider;
+use weather_api_services::models::lenient::ParsingMode;
+use weather_api_services::options::{ServiceOption, ServiceOptions};
+use weather_api_services::units::{PressureUnit, Units, VisibilityUnit};
+
+use crate::{
+    bench,
+    config::{parse_provider_url, MainConfig},
+    favorites,
+    interaction::Interaction,
+    maps, ping,
+    providers::{Provider, ProviderValueParser},
+    query::Query,
+    terminal::ProgressMode,
+    theme::Theme,
+};
 
 /// The `WeatherCli` struct represents a command-line interface for weather-related operations.
+///
+/// Without a subcommand the arguments of the `get` command are accepted (e.g. `weather-rs Kyiv`).
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true,
+    arg_required_else_help = true
+)]
 pub struct WeatherCli {
     #[command(subcommand)]
-    command: Command,
+    command: Option<Command>,
+
+    #[command(flatten)]
+    get: GetArgs,
+
+    #[command(flatten)]
+    options: GlobalOptions,
+
+    /// Save raw provider responses to the directory (e.g. for bug reports) (optional)
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Answer requests with provider responses saved by '--record' instead of sending them (optional)
+    #[arg(long, global = true, value_name = "DIR")]
+    replay: Option<PathBuf>,
+
+    /// Print the provider, URL and query parameters of requests (API keys redacted) instead of sending them
+    #[arg(long, global = true, conflicts_with_all = ["record", "replay"])]
+    dry_run: bool,
+
+    /// Extra query parameter forwarded to the provider request, repeatable (Example: '--param aqi=yes') (optional)
+    #[arg(long = "param", global = true, value_name = "KEY=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Assume the default answer of any prompt instead of asking (e.g. the best matching location), for scripts
+    #[arg(short, long, global = true)]
+    yes: bool,
+
+    /// Suppress spinners and informational messages, so only data and errors are printed
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// When the progress of requests is displayed: 'never', 'auto' (if the output is a terminal) or 'always'
+    #[arg(long, global = true, value_enum, default_value_t)]
+    progress: ProgressMode,
+}
+
+/// Represents the options accepted by all commands; unset options fall back to the configuration.
+#[derive(Args, Clone, Debug, Default, PartialEq)]
+pub struct GlobalOptions {
+    /// Provider for weather data (optional)
+    #[arg(
+        short,
+        long = "provider",
+        id = "query_provider",
+        value_name = "PROVIDER",
+        global = true,
+        value_parser = ProviderValueParser
+    )]
+    pub provider: Option<Provider>,
+
+    /// Units of the output: 'metric' or 'imperial' (optional)
+    #[arg(long, global = true, value_parser = parse_units)]
+    pub units: Option<Units>,
+
+    /// Units of pressure: 'hpa', 'inhg' or 'mmhg' (optional)
+    #[arg(long, global = true, value_parser = parse_pressure_unit)]
+    pub pressure_unit: Option<PressureUnit>,
+
+    /// Units of visibility: 'm', 'km' or 'mi' (optional)
+    #[arg(long, global = true, value_parser = parse_visibility_unit)]
+    pub visibility_unit: Option<VisibilityUnit>,
+
+    /// Language of labels and condition descriptions (Example: 'de', 'fr', 'uk') (optional)
+    #[arg(short, long, global = true)]
+    pub lang: Option<String>,
+
+    /// Color theme of the terminal output (optional)
+    #[arg(long, global = true, value_enum)]
+    pub theme: Option<Theme>,
+
+    /// Take the best matching location of an ambiguous address (e.g. 'Springfield') instead of asking
+    #[arg(long, global = true)]
+    pub first: bool,
+
+    /// Country addresses are resolved in as an ISO 3166-1 alpha-2 code (Example: 'US', 'GB') (optional)
+    #[arg(long, global = true, value_parser = parse_country_code)]
+    pub country: Option<String>,
+
+    /// Treat addresses as postal codes with an optional country code (Example: '--zip 10115,DE')
+    #[arg(long, global = true)]
+    pub zip: bool,
+
+    /// Maximum number of simultaneous requests of batch operations ('trip', 'route' and 'export') (optional, a default per provider)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u8).range(1..))]
+    pub concurrency: Option<u8>,
+
+    /// Reject weather data with physically implausible values instead of warning about them (unlike '--parsing strict', which rejects values of unexpected types)
+    #[arg(long, global = true)]
+    pub reject_implausible: bool,
+
+    /// Parsing mode of provider responses: 'strict' (values of unexpected types are errors) or 'lenient' (converted with a warning) (optional)
+    #[arg(long, global = true, value_parser = parse_parsing_mode)]
+    pub parsing: Option<ParsingMode>,
+
+    /// Path to a Handlebars template rendering the output of 'get' and 'forecast' instead of the tables (optional)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub template: Option<PathBuf>,
+
+    /// Print only the value selected by a jq-like path from the JSON output of weather data, e.g. '.temp' or '.[0].max_temp' (optional)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub query: Option<Query>,
+
+    /// Wrap the JSON output of weather data in an object with its provenance: {"meta": {provider, fetched_at, cache_hit, latency_ms, request_units}, "data": ...}
+    #[arg(long, global = true)]
+    pub json_envelope: bool,
+
+    /// Print a footer with the provenance of the data (provider, fetch time, cache and latency) after the tables
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+}
+
+/// Represents the options of a query resolved from the command line and the configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryOptions {
+    /// The weather data provider.
+    pub provider: Provider,
+    /// The units of the output.
+    pub units: Units,
+    /// The units of pressure in the output.
+    pub pressure_unit: PressureUnit,
+    /// The units of visibility in the output.
+    pub visibility_unit: VisibilityUnit,
+    /// The language of labels and condition descriptions; English if not set.
+    pub language: Option<String>,
+    /// A flag to indicate if the best matching location of an ambiguous address is taken without asking.
+    pub first: bool,
+    /// The ISO 3166-1 alpha-2 code of the country addresses are resolved in.
+    pub country: Option<String>,
+    /// A flag to indicate if addresses are postal codes with an optional country code.
+    pub zip: bool,
+    /// The maximum number of simultaneous requests of batch operations.
+    pub concurrency: usize,
+    /// A flag to indicate if weather data with implausible values should be rejected rather than only warned about.
+    pub reject_implausible: bool,
+    /// The parsing mode of provider responses.
+    pub parsing: ParsingMode,
+    /// The path to the Handlebars template rendering the output; the tables if not set.
+    pub template: Option<PathBuf>,
+    /// A flag to indicate if the provenance footer should be printed after the tables.
+    pub provenance: bool,
+    /// A flag to indicate if the JSON output of the weather data should be wrapped in an envelope with its
+    /// provenance.
+    pub json_envelope: bool,
+    /// The query selecting the printed value of the JSON output of the weather data; the whole output if not set.
+    pub query: Option<Query>,
+}
+
+/// Represents the arguments of the `get` command that select what is fetched and displayed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeatherOptions {
+    /// An optional date for historical or forecasted weather data; the current weather if not set.
+    pub date: Option<String>,
+    /// A flag to indicate if the output format should be JSON.
+    pub json: bool,
+    /// A flag to indicate if the UV index advisory should be displayed.
+    pub uv: bool,
+    /// A flag to indicate if the temperature should be compared with the climate normal of the date
+    /// (today if no date is given).
+    pub vs_normal: bool,
+    /// A flag to indicate if the output (plain text or JSON) should be copied to the clipboard.
+    pub copy: bool,
+    /// A flag to indicate if the station pressure should be computed from the elevation of the address when
+    /// the provider reports only the sea-level pressure.
+    pub altitude_pressure: bool,
+}
+
+/// Represents the arguments of the `post` command: the message and the chats it's posted to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostOptions {
+    /// The name of the location shown in the message; the address if not set.
+    pub alias: Option<String>,
+    /// The number of forecasted days in the message, starting from today.
+    pub days: u8,
+    /// The incoming webhook URL of a Slack channel.
+    pub slack_webhook: Option<Url>,
+    /// The webhook URL of a Discord channel.
+    pub discord_webhook: Option<Url>,
+    /// The identifier of a Telegram chat the configured Telegram bot posts to.
+    pub telegram_chat: Option<String>,
+}
+
+/// Represents the arguments of the `forecast` command that select what is displayed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForecastOptions {
+    /// The number of forecasted days, starting from today.
+    pub days: u8,
+    /// A flag to indicate if the hourly forecast should be displayed instead of the daily one.
+    pub hourly: bool,
+    /// A flag to indicate if only the summary of today's forecast should be displayed.
+    pub summary: bool,
+    /// A flag to indicate if the output format should be JSON.
+    pub json: bool,
+}
+
+/// `GlobalOptions` methods
+impl GlobalOptions {
+    /// Merges the options with the configuration; options set on the command line take precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The application's main configuration.
+    ///
+    /// # Returns
+    ///
+    /// The resolved `QueryOptions`.
+    pub fn merge(&self, config: &MainConfig) -> QueryOptions {
+        let provider = self
+            .provider
+            .clone()
+            .unwrap_or_else(|| config.selected_provider.clone());
+        let language = self
+            .lang
+            .clone()
+            .or_else(|| config.language.clone())
+            .or_else(|| {
+                config
+                    .locale_language
+                    .clone()
+                    .filter(|_| config.detect_language && provider.is_localized())
+            });
+
+        QueryOptions {
+            concurrency: self
+                .concurrency
+                .map_or_else(|| provider.default_concurrency(), usize::from),
+            provider,
+            units: self.units.unwrap_or(config.units),
+            pressure_unit: self.pressure_unit.unwrap_or(config.pressure_unit),
+            visibility_unit: self.visibility_unit.unwrap_or(config.visibility_unit),
+            language,
+            first: self.first,
+            country: self.country.clone().or_else(|| {
+                config
+                    .default_country
+                    .as_deref()
+                    .and_then(|code| parse_country_code(code).ok())
+            }),
+            zip: self.zip,
+            reject_implausible: self.reject_implausible,
+            parsing: self.parsing.unwrap_or(config.parsing),
+            template: self.template.clone().or_else(|| config.template.clone()),
+            provenance: self.verbose || config.show_provenance,
+            json_envelope: self.json_envelope,
+            query: self.query.clone(),
+        }
+    }
+}
+
+/// Validates the interval of scheduled runs of a favorite query from the command line.
+///
+/// # Arguments
+///
+/// * `interval` - The number of minutes (`m`), hours (`h`) or days (`d`).
+///
+/// # Returns
+///
+/// A `Result` containing the trimmed interval or an error if it's invalid.
+fn parse_interval(interval: &str) -> Result<String, favorites::FavoriteError> {
+    favorites::parse_interval(interval).map(|_| interval.trim().to_owned())
+}
+
+/// Parses units of the output from the command line.
+///
+/// # Arguments
+///
+/// * `units` - The name of the units (`metric` or `imperial`).
+///
+/// # Returns
+///
+/// A `Result` containing the `Units` or an error message if the name is unknown.
+fn parse_units(units: &str) -> Result<Units, String> {
+    match units.trim().to_lowercase().as_str() {
+        "metric" => Ok(Units::Metric),
+        "imperial" => Ok(Units::Imperial),
+        _ => Err("expected 'metric' or 'imperial'".to_owned()),
+    }
+}
+
+/// Parses units of pressure from the command line.
+///
+/// # Arguments
+///
+/// * `unit` - The name of the unit (`hpa`, `inhg` or `mmhg`).
+///
+/// # Returns
+///
+/// A `Result` containing the `PressureUnit` or an error message if the name is unknown.
+fn parse_pressure_unit(unit: &str) -> Result<PressureUnit, String> {
+    match unit.trim().to_lowercase().as_str() {
+        "hpa" | "mbar" => Ok(PressureUnit::Hpa),
+        "inhg" => Ok(PressureUnit::InHg),
+        "mmhg" => Ok(PressureUnit::MmHg),
+        _ => Err("expected 'hpa', 'inhg' or 'mmhg'".to_owned()),
+    }
+}
+
+/// Parses units of visibility from the command line.
+///
+/// # Arguments
+///
+/// * `unit` - The name or the symbol of the unit (`m`, `km` or `mi`).
+///
+/// # Returns
+///
+/// A `Result` containing the `VisibilityUnit` or an error message if the name is unknown.
+fn parse_visibility_unit(unit: &str) -> Result<VisibilityUnit, String> {
+    match unit.trim().to_lowercase().as_str() {
+        "m" | "meters" => Ok(VisibilityUnit::Meters),
+        "km" | "kilometers" => Ok(VisibilityUnit::Kilometers),
+        "mi" | "miles" => Ok(VisibilityUnit::Miles),
+        _ => Err("expected 'm', 'km' or 'mi'".to_owned()),
+    }
+}
+
+/// Parses the parsing mode of provider responses from the command line.
+///
+/// # Arguments
+///
+/// * `mode` - The name of the parsing mode (`strict` or `lenient`).
+///
+/// # Returns
+///
+/// A `Result` containing the `ParsingMode` or an error message if the name is unknown.
+fn parse_parsing_mode(mode: &str) -> Result<ParsingMode, String> {
+    match mode.trim().to_lowercase().as_str() {
+        "strict" => Ok(ParsingMode::Strict),
+        "lenient" => Ok(ParsingMode::Lenient),
+        _ => Err("expected 'strict' or 'lenient'".to_owned()),
+    }
+}
+
+/// Parses the code of a country from the command line.
+///
+/// # Arguments
+///
+/// * `code` - The ISO 3166-1 alpha-2 code of the country (e.g. `us`, `GB`).
+///
+/// # Returns
+///
+/// A `Result` containing the uppercase code or an error message if it isn't a two-letter code.
+fn parse_country_code(code: &str) -> Result<String, String> {
+    let code = code.trim();
+
+    if code.len() == 2 && code.chars().all(|char| char.is_ascii_alphabetic()) {
+        Ok(code.to_uppercase())
+    } else {
+        Err("expected a two-letter country code (e.g. 'US')".to_owned())
+    }
+}
+
+/// Parses an extra query parameter of provider requests from the command line.
+///
+/// # Arguments
+///
+/// * `param` - The parameter as `key=value` (e.g. `aqi=yes`); the value may be empty.
+///
+/// # Returns
+///
+/// A `Result` containing the name and the value of the parameter or an error message if there is no name.
+fn parse_param(param: &str) -> Result<(String, String), String> {
+    match param.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_owned(), value.to_owned()))
+        }
+        _ => Err("expected a parameter as 'key=value' (e.g. 'aqi=yes')".to_owned()),
+    }
+}
+
+/// Represents the mode of recording or replaying raw provider responses.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FixtureMode {
+    /// Save responses to the directory.
+    Record(PathBuf),
+    /// Read responses from the directory.
+    Replay(PathBuf),
+    /// Print the plan of requests instead of sending them.
+    DryRun,
+}
+
+/// Represents the output format of commands that fetch data for several locations (e.g. `trip` and `route`).
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// A table printed once all results are fetched.
+    #[default]
+    Table,
+    /// A JSON array printed once all results are fetched.
+    Json,
+    /// One JSON object per line, streamed as each result arrives (e.g. to pipe long exports into 'jq').
+    Ndjson,
+}
+
+/// Represents the format of weather reports.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReportFormat {
+    /// A styled HTML page with an inline SVG chart.
+    #[default]
+    Html,
+    /// A Markdown document with tables and sparklines.
+    Md,
+}
+
+/// `OutputFormat` methods
+impl OutputFormat {
+    /// Resolves the output format from the '--output' option and the '--json' flag (a shortcut for '--output json').
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The '--json' flag.
+    /// * `output` - The '--output' option.
+    ///
+    /// # Returns
+    ///
+    /// `OutputFormat::Json` if the flag is set, otherwise the option.
+    pub fn resolve(json: bool, output: OutputFormat) -> Self {
+        if json {
+            OutputFormat::Json
+        } else {
+            output
+        }
+    }
 }
 
 /// Methods for `WeatherCLI` for working with commands
@@ -16,60 +461,527 @@ impl WeatherCli {
     ///
     /// # Returns
     ///
-    /// A reference to the `Command` enum stored in the `WeatherCli`.
+    /// A reference to the `Command` enum stored in the `WeatherCli` or `None` if no subcommand is given
+    /// (the default `get` command).
     #[allow(dead_code)]
-    pub fn get_command(&self) -> &Command {
-        &self.command
+    pub fn get_command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
+    /// Gets the options accepted by all commands.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `GlobalOptions` set on the command line.
+    pub fn options(&self) -> &GlobalOptions {
+        &self.options
+    }
+
+    /// Gets the mode of recording or replaying raw provider responses.
+    ///
+    /// # Returns
+    ///
+    /// The `FixtureMode` or `None` if none of '--record', '--replay' and '--dry-run' is set.
+    pub fn fixture_mode(&self) -> Option<FixtureMode> {
+        match (&self.record, &self.replay) {
+            (Some(dir), _) => Some(FixtureMode::Record(dir.clone())),
+            (_, Some(dir)) => Some(FixtureMode::Replay(dir.clone())),
+            _ if self.dry_run => Some(FixtureMode::DryRun),
+            _ => None,
+        }
+    }
+
+    /// Gets the interaction with the user set with '--yes' and '--quiet'.
+    ///
+    /// # Returns
+    ///
+    /// The `Interaction` of the application.
+    pub fn interaction(&self) -> Interaction {
+        Interaction {
+            assume_yes: self.yes,
+            quiet: self.quiet,
+        }
+    }
+
+    /// Gets when the progress of requests is displayed, set with '--progress'.
+    ///
+    /// # Returns
+    ///
+    /// The `ProgressMode` of the application.
+    pub fn progress_mode(&self) -> ProgressMode {
+        self.progress
+    }
+
+    /// Gets the extra query parameters of provider requests set with '--param'.
+    ///
+    /// # Returns
+    ///
+    /// The parameters as provider-specific options; a later value of the same parameter wins.
+    pub fn provider_params(&self) -> ServiceOptions {
+        self.params
+            .iter()
+            .map(|(key, value)| (key.clone(), ServiceOption::Text(value.clone())))
+            .collect()
     }
 
     /// Takes ownership of the `Command` enum stored in the `WeatherCli`.
     ///
     /// # Returns
     ///
-    /// The `Command` enum previously stored in the `WeatherCli`.
+    /// The `Command` enum previously stored in the `WeatherCli` or the `get` command with the top-level arguments
+    /// if no subcommand is given.
     pub fn take_command(self) -> Command {
-        self.command
+        self.command.unwrap_or(Command::Get(self.get))
     }
 }
 
+/// Arguments of the `get` command, also accepted without a subcommand.
+#[derive(Args, Debug, Default, PartialEq)]
+pub struct GetArgs {
+    /// The address for which weather information is requested
+    #[arg(required_unless_present_any = ["last", "tag"])]
+    pub address: Option<String>,
+
+    /// Repeat the most recent query (its address, and its provider unless '--provider' is given) flag (optional)
+    #[arg(long, conflicts_with = "address")]
+    pub last: bool,
+
+    /// Get weather information of all favorite locations with the tag (e.g. 'family') instead of an address (optional)
+    #[arg(long, value_parser = favorites::parse_tag, conflicts_with_all = ["address", "last", "date", "copy", "raw"])]
+    pub tag: Option<String>,
+
+    /// Date and time for past or forecasted weather, e.g. '2023-10-11', 'tomorrow 18:00' or 'in 3 hours' (optional)
+    #[arg(short, long)]
+    pub date: Option<String>,
+
+    /// Get weather data in JSON format flag (optional)
+    #[arg(short, long)]
+    pub json: bool,
+
+    /// Show UV index with exposure category and sun protection advice flag (optional)
+    #[arg(long)]
+    pub uv: bool,
+
+    /// Compare the temperature with the 10-year normal of the calendar day of the date (today by default) flag (optional)
+    #[arg(long)]
+    pub vs_normal: bool,
+
+    /// Copy the output (plain text or JSON, as selected) to the system clipboard flag (optional)
+    #[arg(long)]
+    pub copy: bool,
+
+    /// Compute the station pressure from the elevation of the address if the provider reports only the sea-level pressure flag (optional)
+    #[arg(long)]
+    pub altitude_pressure: bool,
+
+    /// Print the original JSON response of the provider (pretty-printed) instead of the normalized weather data flag (optional)
+    #[arg(long, conflicts_with_all = ["json", "uv", "vs_normal", "copy", "altitude_pressure"])]
+    pub raw: bool,
+}
+
 /// Enum for CLI commands
 #[derive(Subcommand, Debug, PartialEq)]
 pub enum Command {
     /// Get a full list of supported providers
+    #[command(visible_alias = "p-l")]
     ProviderList,
     /// Configure a provider with the given credentials
     Configure {
         /// The provider to be configured (Example: 'open-weather', 'weather-api')
-        provider: Provider,
+        #[arg(value_parser = ProviderValueParser, required_unless_present = "from_file")]
+        provider: Option<Provider>,
 
         /// API Service URL (Example: Open Weather API - 'https://api.openweathermap.org/data/2.5/weather') (optional)
-        #[arg(short, long)]
-        url: Option<String>,
+        #[arg(short, long, value_parser = parse_provider_url)]
+        url: Option<Url>,
+
+        /// The API key for a service provider; '-' reads it from the standard input (optional, asked with a hidden input if not set)
+        #[arg(conflicts_with_all = ["api_key_option", "api_key_file"])]
+        api_key: Option<String>,
+
+        /// The API key for a service provider, the same as the positional API key (Example: '-' to read it from the standard input) (optional)
+        #[arg(
+            long = "api-key",
+            value_name = "API_KEY",
+            conflicts_with = "api_key_file"
+        )]
+        api_key_option: Option<String>,
+
+        /// Read the API key from a file, so it doesn't land in the shell history or process listings (Example: '~/.keys/owm') (optional)
+        #[arg(long, value_name = "FILE")]
+        api_key_file: Option<PathBuf>,
 
-        /// The API key for a service provider
-        api_key: String,
+        /// Import the API keys and URLs of several providers from a TOML or JSON file (Example: 'creds.toml') (optional)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["provider", "url", "api_key", "api_key_option", "api_key_file"])]
+        from_file: Option<PathBuf>,
     },
     /// Select an available provider
     SelectProvider {
         /// The provider to be selected
+        #[arg(value_parser = ProviderValueParser)]
         provider: Provider,
     },
-    /// Get weather information
-    Get {
-        /// The address for which weather information is requested
+    /// Check the HTTP status and latency of configured providers
+    Ping {
+        /// The provider to be checked (optional, all configured providers by default)
+        #[arg(value_parser = ProviderValueParser)]
+        provider: Option<Provider>,
+
+        /// Get results in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Measure the latency (p50/p95), payload size and parse time of configured providers to pick the fastest one
+    Bench {
+        /// The provider to be measured (optional, all configured providers by default)
+        #[arg(value_parser = ProviderValueParser)]
+        provider: Option<Provider>,
+
+        /// The address whose current weather is requested, e.g. one in your region (optional, 'London' by default)
+        #[arg(short, long, default_value = ping::PING_ADDRESS)]
+        address: String,
+
+        /// Number of requests sent to every provider (from 1 to 100)
+        #[arg(short = 'n', long, default_value_t = bench::DEFAULT_RUNS, value_parser = clap::value_parser!(u8).range(1..=100))]
+        runs: u8,
+
+        /// Get results in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get weather information (the default command)
+    #[command(visible_alias = "g")]
+    Get(GetArgs),
+    /// List the recent queries (address, provider and time), most recent first
+    Recent {
+        /// Get recent queries in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get the current weather and alerts of all favorite locations in a single overview table
+    Dashboard {
+        /// Show only the favorite locations with the tag (e.g. 'travel') (optional)
+        #[arg(short, long, value_parser = favorites::parse_tag)]
+        tag: Option<String>,
+
+        /// Get the overview in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Save, run and list favorite queries (named presets of the address, provider, units and output format)
+    Favorite {
+        #[command(subcommand)]
+        command: FavoriteCommand,
+    },
+    /// Get weather forecast with chance and amount of precipitation
+    #[command(visible_alias = "f")]
+    Forecast {
+        /// The address for which weather forecast is requested
+        address: String,
+
+        /// Number of forecasted days, starting from today (optional)
+        #[arg(short = 'n', long, default_value_t = 3)]
+        days: u8,
+
+        /// Show hourly forecast flag (optional)
+        #[arg(long)]
+        hourly: bool,
+
+        /// Show only the one-sentence summary of today's forecast flag (optional)
+        #[arg(long)]
+        summary: bool,
+
+        /// Get forecast data in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get marine weather data (tides, swell and water temperature) for sailors and surfers
+    Marine {
+        /// The coastal address for which marine data is requested
+        address: String,
+
+        /// Number of days, starting from today (optional)
+        #[arg(short = 'n', long, default_value_t = 1)]
+        days: u8,
+
+        /// Get marine data in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get snow report (daily snowfall and temperatures) for a ski resort
+    Snow {
+        /// The ski resort (or any address) for which the snow report is requested
+        resort: String,
+
+        /// Number of days, starting from today (optional)
+        #[arg(short = 'n', long, default_value_t = 3)]
+        days: u8,
+
+        /// Get snow report in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// List observation stations near an address (id, distance and elevation)
+    Stations {
+        /// The address near which stations are listed
+        address: String,
+
+        /// Get stations in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get the minutely rain forecast of the next hour as a timeline with a verdict
+    Nowcast {
+        /// The address for which the nowcast is requested
+        address: String,
+
+        /// Get the nowcast in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get weather forecast for every stop of a trip on the given dates
+    Trip {
+        /// Comma-separated stops of the trip (Example: 'Kyiv,Warsaw,Berlin')
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        stops: Vec<String>,
+
+        /// Comma-separated dates of the stops in the 'YYYY-MM-DD' format; the last date applies to the remaining stops (optional, today by default)
+        #[arg(short, long, value_delimiter = ',')]
+        date: Vec<String>,
+
+        /// Get trip forecast in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+
+        /// Output format; 'ndjson' streams one JSON object per line as each result arrives (optional)
+        #[arg(short, long, value_enum, default_value_t, conflicts_with = "json")]
+        output: OutputFormat,
+    },
+    /// Get a monthly moon phase calendar
+    Moon {
+        /// Month of the calendar in the 'YYYY-MM' format (optional, the current month by default)
+        #[arg(short, long)]
+        month: Option<String>,
+
+        /// Get moon phases in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Get weather along a route (GPX or GeoJSON track) at estimated arrival times
+    Route {
+        /// Path to the GPX or GeoJSON track file
+        track: PathBuf,
+
+        /// Departure time in the 'HH:MM' (today) or 'YYYY-MM-DD HH:MM' format (optional, now by default)
+        #[arg(short, long)]
+        start: Option<String>,
+
+        /// Average speed in km/h used to estimate arrival times (optional)
+        #[arg(long, default_value_t = 15.0)]
+        speed: f64,
+
+        /// Distance in km between the sampled points of the route (optional)
+        #[arg(long, default_value_t = 10.0)]
+        every: f64,
+
+        /// Get route weather in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+
+        /// Output format; 'ndjson' streams one JSON object per line as each result arrives (optional)
+        #[arg(short, long, value_enum, default_value_t, conflicts_with = "json")]
+        output: OutputFormat,
+    },
+    /// Export the daily weather history of an address for a date range to an NDJSON file
+    Export {
+        /// The address for which the weather history is exported
+        address: String,
+
+        /// The first date of the range in the 'YYYY-MM-DD' format
+        #[arg(long)]
+        from: String,
+
+        /// The last date of the range in the 'YYYY-MM-DD' format (optional, yesterday by default)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Path to the NDJSON file the history is written to
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Resume an interrupted export from its checkpoint instead of starting over (optional)
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Get statistics (temperature range, rainy days and trend) of the locally stored observations of an address
+    Stats {
+        /// The address for which the statistics are requested
+        address: String,
+
+        /// Period of the statistics ending now as a number of days or weeks (Example: '30d' or '4w') (optional)
+        #[arg(long, default_value = "30d")]
+        period: String,
+
+        /// Get statistics in JSON format flag (optional)
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Generate a shareable report (current conditions, forecast table and chart) of an address in HTML or Markdown
+    Report {
+        /// The address for which the report is generated
+        address: String,
+
+        /// Number of forecasted days, starting from today (optional)
+        #[arg(short = 'n', long, default_value_t = 3)]
+        days: u8,
+
+        /// Format of the report (optional)
+        #[arg(short, long, value_enum, default_value_t)]
+        format: ReportFormat,
+
+        /// Path to the file the report is written to (optional, printed to the standard output by default)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Render a compact weather card (icon, temperature, location and forecast strip) of an address as SVG or PNG
+    Card {
+        /// The address for which the card is rendered
+        address: String,
+
+        /// Name of the location shown on the card (optional, the address by default)
+        #[arg(short, long)]
+        alias: Option<String>,
+
+        /// Number of days in the forecast strip, starting from today (optional)
+        #[arg(short = 'n', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(0..=5))]
+        days: u8,
+
+        /// Path to the card file; a '.png' extension rasterizes the card with 'rsvg-convert'
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Show the current precipitation radar map around an address in the terminal or save it as a PNG image
+    Radar {
+        /// The address around which the radar map is downloaded
+        address: String,
+
+        /// Zoom level of the map; a 3x3 grid of tiles is stitched (optional)
+        #[arg(short, long, default_value_t = maps::DEFAULT_ZOOM, value_parser = clap::value_parser!(u8).range(1..=maps::MAX_ZOOM as i64))]
+        zoom: u8,
+
+        /// Path to the PNG image file (optional, a coarse map is printed in the terminal by default)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print the current weather of an address as MQTT messages (one JSON object per line) for an MQTT client
+    Mqtt {
+        /// The address for which the weather is published
+        address: String,
+
+        /// Name of the location used in topics and Home Assistant device names (optional, the address by default)
+        #[arg(short, long)]
+        alias: Option<String>,
+
+        /// Base topic of the state messages (optional)
+        #[arg(long, default_value = "weather-rs")]
+        topic: String,
+
+        /// Also print Home Assistant MQTT Discovery config messages of the sensors flag (optional)
+        #[arg(long)]
+        discovery: bool,
+
+        /// Discovery prefix of Home Assistant (optional)
+        #[arg(long, default_value = "homeassistant")]
+        discovery_prefix: String,
+    },
+    /// Send a morning digest (current conditions and today's forecast) of an address by email
+    Digest {
+        /// The address for which the digest is sent
+        address: String,
+
+        /// Email address of the recipient (e.g. 'me@example.com')
+        #[arg(short, long)]
+        email: String,
+
+        /// Connection URL of the SMTP server (optional, 'smtp_url' of the config by default)
+        #[arg(long)]
+        smtp: Option<String>,
+
+        /// Email address of the sender (optional, 'email_from' of the config or the recipient by default)
+        #[arg(long)]
+        from: Option<String>,
+    },
+    /// Post the current weather and the forecast of an address to Slack, Discord or Telegram chats
+    #[command(group(ArgGroup::new("chats").required(true).multiple(true)))]
+    Post {
+        /// The address for which the weather is posted
         address: String,
 
-        /// Date for specific weather information (optional)
+        /// Name of the location shown in the message (optional, the address by default)
         #[arg(short, long)]
-        date: Option<String>,
+        alias: Option<String>,
+
+        /// Number of forecasted days in the message, starting from today (optional)
+        #[arg(short = 'n', long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..=3))]
+        days: u8,
+
+        /// Incoming webhook URL of a Slack channel
+        #[arg(long, group = "chats")]
+        slack_webhook: Option<Url>,
+
+        /// Webhook URL of a Discord channel
+        #[arg(long, group = "chats")]
+        discord_webhook: Option<Url>,
+
+        /// Identifier of a Telegram chat (e.g. '-1001234567890' or '@channel'); needs 'telegram_bot_token' in the config
+        #[arg(long, group = "chats")]
+        telegram_chat: Option<String>,
+    },
+}
+
+/// Enum for the subcommands of the `favorite` command
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum FavoriteCommand {
+    /// Save a favorite query; the provider and the units are taken from '--provider' and '--units'
+    Add {
+        /// The name of the favorite (Example: 'commute')
+        #[arg(value_parser = favorites::parse_name)]
+        name: String,
+
+        /// The address for which weather information is requested
+        address: String,
 
         /// Get weather data in JSON format flag (optional)
         #[arg(short, long)]
         json: bool,
 
-        /// Provider for weather data (optional)
+        /// Interval of scheduled runs for schedulers, e.g. '30m', '6h' or '1d' (optional)
+        #[arg(long, value_parser = parse_interval)]
+        every: Option<String>,
+
+        /// Tag grouping the location with others, e.g. 'home', 'travel' or 'family' (optional, repeatable)
+        #[arg(short, long = "tag", value_parser = favorites::parse_tag)]
+        tags: Vec<String>,
+    },
+    /// Run a favorite query; options given on the command line take precedence over the preset
+    Run {
+        /// The name of the favorite
+        name: String,
+    },
+    /// List the favorite queries
+    List {
+        /// List only the favorites with the tag (optional)
+        #[arg(short, long, value_parser = favorites::parse_tag)]
+        tag: Option<String>,
+
+        /// Get favorites in JSON format flag (optional)
         #[arg(short, long)]
-        provider: Option<Provider>,
+        json: bool,
+    },
+    /// Remove a favorite query
+    Remove {
+        /// The name of the favorite
+        name: String,
     },
 }
 
@@ -81,20 +993,462 @@ mod tests {
     #[rstest]
     fn test_get_command() {
         let command = Command::ProviderList;
-        let weather_cli = WeatherCli { command };
+        let weather_cli = WeatherCli {
+            command: Some(command),
+            get: GetArgs::default(),
+            options: GlobalOptions::default(),
+            record: None,
+            replay: None,
+            dry_run: false,
+            params: Vec::new(),
+            yes: false,
+            quiet: false,
+            progress: ProgressMode::Auto,
+        };
 
         let result = weather_cli.get_command();
 
-        assert_eq!(result, &Command::ProviderList);
+        assert_eq!(result, Some(&Command::ProviderList));
     }
 
     #[rstest]
     fn test_take_command() {
         let command = Command::ProviderList;
-        let weather_cli = WeatherCli { command };
+        let weather_cli = WeatherCli {
+            command: Some(command),
+            get: GetArgs::default(),
+            options: GlobalOptions::default(),
+            record: None,
+            replay: None,
+            dry_run: false,
+            params: Vec::new(),
+            yes: false,
+            quiet: false,
+            progress: ProgressMode::Auto,
+        };
 
         let result = weather_cli.take_command();
 
         assert_eq!(result, Command::ProviderList);
     }
+
+    #[rstest]
+    fn test_parse_trip_command() {
+        let weather_cli = WeatherCli::parse_from([
+            "weather-rs",
+            "trip",
+            "--stops",
+            "Kyiv,Warsaw,Berlin",
+            "--date",
+            "2024-07-01,2024-07-02",
+        ]);
+
+        assert_eq!(
+            weather_cli.take_command(),
+            Command::Trip {
+                stops: vec!["Kyiv".into(), "Warsaw".into(), "Berlin".into()],
+                date: vec!["2024-07-01".into(), "2024-07-02".into()],
+                json: false,
+                output: OutputFormat::Table,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "trip", "-s", "Kyiv"], OutputFormat::Table)]
+    #[case(&["weather-rs", "trip", "-s", "Kyiv", "--json"], OutputFormat::Json)]
+    #[case(&["weather-rs", "trip", "-s", "Kyiv", "--output", "ndjson"], OutputFormat::Ndjson)]
+    fn test_output_format(#[case] args: &[&str], #[case] expected: OutputFormat) {
+        let Command::Trip { json, output, .. } = WeatherCli::parse_from(args).take_command() else {
+            panic!("expected the trip command");
+        };
+
+        assert_eq!(OutputFormat::resolve(json, output), expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], None)]
+    #[case(&["weather-rs", "get", "London", "--record", "fixtures"], Some(FixtureMode::Record("fixtures".into())))]
+    #[case(&["weather-rs", "--replay", "fixtures", "get", "London"], Some(FixtureMode::Replay("fixtures".into())))]
+    #[case(&["weather-rs", "forecast", "London", "--dry-run"], Some(FixtureMode::DryRun))]
+    fn test_fixture_mode(#[case] args: &[&str], #[case] expected: Option<FixtureMode>) {
+        let weather_cli = WeatherCli::parse_from(args);
+
+        assert_eq!(weather_cli.fixture_mode(), expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], Interaction::default())]
+    #[case(&["weather-rs", "Springfield", "--yes"], Interaction { assume_yes: true, quiet: false })]
+    #[case(&["weather-rs", "-q", "forecast", "London"], Interaction { assume_yes: false, quiet: true })]
+    #[case(&["weather-rs", "configure", "owm", "-y", "--quiet"], Interaction { assume_yes: true, quiet: true })]
+    fn test_interaction(#[case] args: &[&str], #[case] expected: Interaction) {
+        let weather_cli = WeatherCli::parse_from(args);
+
+        assert_eq!(weather_cli.interaction(), expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], ProgressMode::Auto)]
+    #[case(&["weather-rs", "trip", "--stops", "Kyiv,Lviv", "--progress", "always"], ProgressMode::Always)]
+    #[case(&["weather-rs", "--progress", "never", "London"], ProgressMode::Never)]
+    fn test_progress_mode(#[case] args: &[&str], #[case] expected: ProgressMode) {
+        let weather_cli = WeatherCli::parse_from(args);
+
+        assert_eq!(weather_cli.progress_mode(), expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "--record", "a", "--replay", "b", "get", "London"])]
+    #[case(&["weather-rs", "--dry-run", "--replay", "b", "get", "London"])]
+    fn test_fixture_mode_conflicts(#[case] args: &[&str]) {
+        let result = WeatherCli::try_parse_from(args);
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_cli_debug_assert() {
+        use clap::CommandFactory;
+
+        WeatherCli::command().debug_assert();
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], Provider::OpenWeather, Units::Metric, None, false)]
+    #[case(&["weather-rs", "forecast", "London", "-p", "weather-api", "--units", "imperial", "-l", "de"], Provider::WeatherApi, Units::Imperial, Some("de"), false)]
+    #[case(&["weather-rs", "--units", "Imperial", "trip", "-s", "Kyiv"], Provider::OpenWeather, Units::Imperial, None, false)]
+    #[case(&["weather-rs", "get", "London", "--reject-implausible"], Provider::OpenWeather, Units::Metric, None, true)]
+    fn test_merge_options(
+        #[case] args: &[&str],
+        #[case] expected_provider: Provider,
+        #[case] expected_units: Units,
+        #[case] expected_language: Option<&str>,
+        #[case] expected_reject_implausible: bool,
+    ) {
+        let config = MainConfig::default();
+        let expected_concurrency = expected_provider.default_concurrency();
+
+        let result = WeatherCli::parse_from(args).options().merge(&config);
+
+        assert_eq!(
+            result,
+            QueryOptions {
+                provider: expected_provider,
+                units: expected_units,
+                pressure_unit: PressureUnit::Hpa,
+                visibility_unit: VisibilityUnit::Meters,
+                language: expected_language.map(str::to_owned),
+                first: false,
+                country: None,
+                zip: false,
+                concurrency: expected_concurrency,
+                reject_implausible: expected_reject_implausible,
+                parsing: ParsingMode::Lenient,
+                template: None,
+                provenance: false,
+                json_envelope: false,
+                query: None,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("us", Some("US"))]
+    #[case(" GB ", Some("GB"))]
+    #[case("USA", None)]
+    #[case("1a", None)]
+    fn test_parse_country_code(#[case] code: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_country_code(code).ok().as_deref(), expected);
+    }
+
+    #[rstest]
+    fn test_merge_options_from_config() {
+        let config = MainConfig {
+            selected_provider: Provider::WeatherApi,
+            units: Units::Imperial,
+            language: Some("uk".to_owned()),
+            ..MainConfig::default()
+        };
+
+        let result = WeatherCli::parse_from(["weather-rs", "get", "London", "-l", "fr"])
+            .options()
+            .merge(&config);
+
+        assert_eq!(result.provider, Provider::WeatherApi);
+        assert_eq!(result.units, Units::Imperial);
+        assert_eq!(result.language.as_deref(), Some("fr"));
+        assert_eq!(result.concurrency, 4);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], true, Some("de"))]
+    #[case(&["weather-rs", "get", "London", "-l", "fr"], true, Some("fr"))]
+    #[case(&["weather-rs", "get", "London"], false, None)]
+    #[case(&["weather-rs", "get", "London", "-p", "aeris-weather"], true, None)]
+    fn test_merge_locale_language(
+        #[case] args: &[&str],
+        #[case] detect_language: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let config = MainConfig {
+            detect_language,
+            locale_language: Some("de".to_owned()),
+            ..MainConfig::default()
+        };
+
+        let result = WeatherCli::try_parse_from(args)
+            .unwrap()
+            .options()
+            .merge(&config)
+            .language;
+
+        assert_eq!(result.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], ParsingMode::Strict)]
+    #[case(&["weather-rs", "get", "London", "--parsing", "lenient"], ParsingMode::Lenient)]
+    fn test_merge_parsing(#[case] args: &[&str], #[case] expected: ParsingMode) {
+        let config = MainConfig {
+            parsing: ParsingMode::Strict,
+            ..MainConfig::default()
+        };
+
+        let result = WeatherCli::parse_from(args).options().merge(&config);
+
+        assert_eq!(result.parsing, expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], false, false)]
+    #[case(&["weather-rs", "get", "London", "--verbose"], false, true)]
+    #[case(&["weather-rs", "forecast", "London", "-v"], false, true)]
+    #[case(&["weather-rs", "get", "London"], true, true)]
+    fn test_merge_provenance(
+        #[case] args: &[&str],
+        #[case] show_provenance: bool,
+        #[case] expected: bool,
+    ) {
+        let config = MainConfig {
+            show_provenance,
+            ..MainConfig::default()
+        };
+
+        let result = WeatherCli::parse_from(args).options().merge(&config);
+
+        assert_eq!(result.provenance, expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], None)]
+    #[case(&["weather-rs", "forecast", "London", "--query", ".[0].max_temp"], Some(".[0].max_temp"))]
+    #[case(&["weather-rs", "London", "--query", ".temp"], Some(".temp"))]
+    fn test_parse_query(#[case] args: &[&str], #[case] expected: Option<&str>) {
+        let result = WeatherCli::try_parse_from(args).unwrap();
+
+        assert_eq!(
+            result.options().query,
+            expected.map(|query| query.parse().unwrap())
+        );
+    }
+
+    #[rstest]
+    fn test_parse_query_invalid() {
+        assert!(WeatherCli::try_parse_from(["weather-rs", "London", "--query", "temp"]).is_err());
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "London"], Some("weather.hbs"))]
+    #[case(&["weather-rs", "forecast", "London", "--template", "forecast.hbs"], Some("forecast.hbs"))]
+    fn test_merge_template(#[case] args: &[&str], #[case] expected: Option<&str>) {
+        let config = MainConfig {
+            template: Some(PathBuf::from("weather.hbs")),
+            ..MainConfig::default()
+        };
+
+        let result = WeatherCli::parse_from(args).options().merge(&config);
+
+        assert_eq!(result.template, expected.map(PathBuf::from));
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "Springfield"], Some("us"), Some("US"))]
+    #[case(&["weather-rs", "get", "Springfield", "--country", "ca"], Some("us"), Some("CA"))]
+    #[case(&["weather-rs", "get", "Springfield"], Some("USA"), None)]
+    #[case(&["weather-rs", "get", "Springfield"], None, None)]
+    fn test_merge_country(
+        #[case] args: &[&str],
+        #[case] default_country: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let config = MainConfig {
+            default_country: default_country.map(str::to_owned),
+            ..MainConfig::default()
+        };
+
+        let result = WeatherCli::parse_from(args).options().merge(&config);
+
+        assert_eq!(result.country.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "get", "--zip", "10115,DE"], true, "10115,DE")]
+    #[case(&["weather-rs", "forecast", "10115,DE", "--zip", "-n", "3"], true, "10115,DE")]
+    #[case(&["weather-rs", "get", "10115"], false, "10115")]
+    fn test_zip_option(
+        #[case] args: &[&str],
+        #[case] expected: bool,
+        #[case] expected_address: &str,
+    ) {
+        let cli = WeatherCli::parse_from(args);
+        let result = cli.options().merge(&MainConfig::default()).zip;
+
+        assert_eq!(result, expected);
+        let address = match cli.take_command() {
+            Command::Get(get) => get.address,
+            Command::Forecast { address, .. } => Some(address),
+            command => panic!("unexpected command {command:?}"),
+        };
+        assert_eq!(address.as_deref(), Some(expected_address));
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "export", "London", "--from", "2024-01-01", "-o", "a.ndjson", "--concurrency", "8"], Some(8))]
+    #[case(&["weather-rs", "--concurrency", "0", "trip", "-s", "Kyiv"], None)]
+    fn test_concurrency_option(#[case] args: &[&str], #[case] expected: Option<usize>) {
+        let result = WeatherCli::try_parse_from(args)
+            .map(|cli| cli.options().merge(&MainConfig::default()).concurrency);
+
+        assert_eq!(result.ok(), expected);
+    }
+
+    #[rstest]
+    fn test_merge_measure_units() {
+        let config = MainConfig {
+            pressure_unit: PressureUnit::MmHg,
+            visibility_unit: VisibilityUnit::Kilometers,
+            ..MainConfig::default()
+        };
+
+        let result =
+            WeatherCli::parse_from(["weather-rs", "get", "London", "--visibility-unit", "mi"])
+                .options()
+                .merge(&config);
+
+        assert_eq!(result.pressure_unit, PressureUnit::MmHg);
+        assert_eq!(result.visibility_unit, VisibilityUnit::Miles);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "Kyiv"])]
+    #[case(&["weather-rs", "get", "Kyiv"])]
+    #[case(&["weather-rs", "g", "Kyiv"])]
+    #[case(&["weather-rs", "--units", "imperial", "Kyiv"])]
+    fn test_default_get_command(#[case] args: &[&str]) {
+        let result = WeatherCli::parse_from(args).take_command();
+
+        assert_eq!(
+            result,
+            Command::Get(GetArgs {
+                address: Some("Kyiv".to_owned()),
+                ..GetArgs::default()
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "p-l"], Command::ProviderList)]
+    #[case(&["weather-rs", "ping"], Command::Ping { provider: None, json: false })]
+    #[case(&["weather-rs", "ping", "weather-api", "-j"], Command::Ping { provider: Some(Provider::WeatherApi), json: true })]
+    #[case(&["weather-rs", "f", "Kyiv", "-n", "2"], Command::Forecast { address: "Kyiv".to_owned(), days: 2, hourly: false, summary: false, json: false })]
+    #[case(&["weather-rs", "--last"], Command::Get(GetArgs { last: true, ..GetArgs::default() }))]
+    #[case(&["weather-rs", "g", "--last", "-j"], Command::Get(GetArgs { last: true, json: true, ..GetArgs::default() }))]
+    #[case(&["weather-rs", "recent", "-j"], Command::Recent { json: true })]
+    #[case(
+        &["weather-rs", "favorite", "add", "commute", "London", "-j", "--every", "30m", "-t", "Travel", "--tag", "family"],
+        Command::Favorite { command: FavoriteCommand::Add { name: "commute".to_owned(), address: "London".to_owned(), json: true, every: Some("30m".to_owned()), tags: vec!["travel".to_owned(), "family".to_owned()] } }
+    )]
+    #[case(&["weather-rs", "favorite", "run", "commute"], Command::Favorite { command: FavoriteCommand::Run { name: "commute".to_owned() } })]
+    #[case(&["weather-rs", "dashboard", "--tag", "travel"], Command::Dashboard { tag: Some("travel".to_owned()), json: false })]
+    #[case(&["weather-rs", "get", "--tag", "family"], Command::Get(GetArgs { tag: Some("family".to_owned()), ..GetArgs::default() }))]
+    fn test_command_aliases(#[case] args: &[&str], #[case] expected: Command) {
+        let result = WeatherCli::parse_from(args).take_command();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "Kyiv"], &[])]
+    #[case(&["weather-rs", "Kyiv", "--param", "aqi=yes"], &[("aqi", "yes")])]
+    #[case(
+        &["weather-rs", "forecast", "Kyiv", "--param", "alerts=no", "--param", " aqi = yes", "--param", "alerts="],
+        &[("alerts", ""), ("aqi", " yes")]
+    )]
+    fn test_provider_params(#[case] args: &[&str], #[case] expected: &[(&str, &str)]) {
+        let result = WeatherCli::parse_from(args).provider_params();
+
+        assert_eq!(
+            result,
+            expected
+                .iter()
+                .map(|(key, value)| (key.to_string(), ServiceOption::Text(value.to_string())))
+                .collect::<ServiceOptions>()
+        );
+    }
+
+    #[rstest]
+    #[case("aqi")]
+    #[case("=yes")]
+    fn test_parse_param_invalid(#[case] param: &str) {
+        assert!(parse_param(param).is_err());
+    }
+
+    #[rstest]
+    #[case(&["weather-rs"])]
+    #[case(&["weather-rs", "Kyiv", "forecast"])]
+    #[case(&["weather-rs", "Kyiv", "--raw", "--json"])]
+    #[case(&["weather-rs", "Kyiv", "--last"])]
+    #[case(&["weather-rs", "favorite", "add", "my home", "Kyiv"])]
+    #[case(&["weather-rs", "favorite", "add", "home", "Kyiv", "--every", "1w"])]
+    #[case(&["weather-rs", "Kyiv", "--tag", "family"])]
+    fn test_default_get_command_errors(#[case] args: &[&str]) {
+        assert!(WeatherCli::try_parse_from(args).is_err());
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "London", "-p", "Open-Weather"], Provider::OpenWeather)]
+    #[case(&["weather-rs", "London", "--provider", "OWM"], Provider::OpenWeather)]
+    #[case(&["weather-rs", "select-provider", "WEATHER-API"], Provider::WeatherApi)]
+    fn test_provider_value(#[case] args: &[&str], #[case] expected: Provider) {
+        let weather_cli = WeatherCli::parse_from(args);
+
+        let provider = match weather_cli.options().provider.clone() {
+            Some(provider) => provider,
+            None => match weather_cli.take_command() {
+                Command::SelectProvider { provider } => provider,
+                command => panic!("unexpected command {command:?}"),
+            },
+        };
+        assert_eq!(provider, expected);
+    }
+
+    #[rstest]
+    fn test_provider_value_invalid() {
+        let Err(err) = WeatherCli::try_parse_from(["weather-rs", "London", "-p", "open-wether"])
+        else {
+            panic!("the misspelled provider is accepted");
+        };
+
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+        let message = err.to_string();
+        assert!(
+            message.contains("a similar value exists: 'open-weather'"),
+            "{message}"
+        );
+        assert!(
+            message.contains("[possible values: open-weather, weather-api"),
+            "{message}"
+        );
+    }
 }
@@ -0,0 +1,29 @@
+use arboard::Clipboard;
+use thiserror::Error;
+
+/// Represents errors related to the system clipboard.
+#[derive(Error, Debug)]
+pub enum ClipboardError {
+    /// An error indicating that the system clipboard can't be accessed (e.g. there is no display server).
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `arboard::Error` indicating the specific error.
+    #[error("Failed to copy the output to the clipboard")]
+    Unavailable(#[from] arboard::Error),
+}
+
+/// Places text onto the system clipboard.
+///
+/// # Arguments
+///
+/// * `text` - The text to copy.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `ClipboardError` if the clipboard can't be accessed.
+pub fn copy(text: &str) -> Result<(), ClipboardError> {
+    Clipboard::new()?.set_text(text.trim_end())?;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use std::io::{self, BufRead, Write};
+
+/// Prepares the console for the output of the application.
+///
+/// On Windows, the output code page is switched to UTF-8, so symbols like `°` aren't printed as mojibake
+/// (e.g. `Â°C`), and the processing of ANSI escape sequences is enabled for colors; colors are disabled (see
+/// `terminal::init`) if the console doesn't support escape sequences (e.g. the legacy console of older Windows
+/// versions). Nothing is done on other platforms, where terminals handle both already.
+///
+/// # Returns
+///
+/// `true` if the console processes ANSI escape sequences.
+pub fn init() -> bool {
+    #[cfg(windows)]
+    return windows::init();
+
+    #[cfg(not(windows))]
+    true
+}
+
+/// Reads a line from the console without echoing it, e.g. to enter an API key that shouldn't be seen by others.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt written to the standard error.
+///
+/// # Returns
+///
+/// An `io::Result` containing the line without the trailing newline.
+pub fn read_hidden_line(prompt: &str) -> io::Result<String> {
+    let mut stderr = io::stderr();
+    write!(stderr, "{prompt}")?;
+    stderr.flush()?;
+
+    let mut line = String::new();
+    let read_result = {
+        let _echo = echo::disable();
+        io::stdin().lock().read_line(&mut line)
+    };
+    writeln!(stderr)?;
+    read_result?;
+
+    Ok(line.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+/// Disables the echo of the standard input while the returned guard is alive.
+#[cfg(unix)]
+mod echo {
+    /// Restores the original terminal attributes of the standard input when it's dropped.
+    pub struct EchoGuard(Option<libc::termios>);
+
+    /// Disables the echo of the standard input; nothing is done if it isn't a terminal.
+    pub fn disable() -> EchoGuard {
+        // SAFETY: the attributes of the standard input are read into and written from a local `termios`
+        unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return EchoGuard(None);
+            }
+
+            let original = termios;
+            termios.c_lflag &= !libc::ECHO;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) != 0 {
+                return EchoGuard(None);
+            }
+
+            EchoGuard(Some(original))
+        }
+    }
+
+    impl Drop for EchoGuard {
+        fn drop(&mut self) {
+            if let Some(original) = self.0 {
+                // SAFETY: the original attributes of the standard input are restored from a local `termios`
+                unsafe {
+                    libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original);
+                }
+            }
+        }
+    }
+}
+
+/// Disables the echo of the standard input while the returned guard is alive.
+#[cfg(windows)]
+mod echo {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE, ENABLE_ECHO_INPUT,
+        STD_INPUT_HANDLE,
+    };
+
+    /// Restores the original console mode of the standard input when it's dropped.
+    pub struct EchoGuard(Option<CONSOLE_MODE>);
+
+    /// Disables the echo of the standard input; nothing is done if it isn't a console.
+    pub fn disable() -> EchoGuard {
+        // SAFETY: the mode of the standard handle of the process is read into and written from a local mode
+        unsafe {
+            let handle = GetStdHandle(STD_INPUT_HANDLE);
+            let mut mode: CONSOLE_MODE = 0;
+            if GetConsoleMode(handle, &mut mode) == 0
+                || SetConsoleMode(handle, mode & !ENABLE_ECHO_INPUT) == 0
+            {
+                return EchoGuard(None);
+            }
+
+            EchoGuard(Some(mode))
+        }
+    }
+
+    impl Drop for EchoGuard {
+        fn drop(&mut self) {
+            if let Some(mode) = self.0 {
+                // SAFETY: the original mode of the standard handle of the process is restored
+                unsafe {
+                    SetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), mode);
+                }
+            }
+        }
+    }
+}
+
+/// Leaves the echo of the standard input as is on platforms without terminal attributes.
+#[cfg(not(any(unix, windows)))]
+mod echo {
+    pub fn disable() {}
+}
+
+#[cfg(windows)]
+mod windows {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleCP, SetConsoleMode, SetConsoleOutputCP,
+        CONSOLE_MODE, ENABLE_VIRTUAL_TERMINAL_PROCESSING, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+
+    /// The identifier of the UTF-8 code page.
+    const CP_UTF8: u32 = 65001;
+
+    /// Switches the code pages of the console to UTF-8 and enables the processing of ANSI escape sequences
+    /// of the standard output and error.
+    ///
+    /// # Returns
+    ///
+    /// `true` if escape sequences are processed by the console or the output isn't a console (e.g. it's
+    /// redirected to a file), `false` otherwise.
+    pub fn init() -> bool {
+        // SAFETY: the functions are called with the standard handles of the process and a pointer to a local mode
+        unsafe {
+            SetConsoleOutputCP(CP_UTF8);
+            SetConsoleCP(CP_UTF8);
+
+            [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE]
+                .into_iter()
+                .all(|std_handle| {
+                    let handle = GetStdHandle(std_handle);
+                    let mut mode: CONSOLE_MODE = 0;
+                    if GetConsoleMode(handle, &mut mode) == 0 {
+                        return true;
+                    }
+
+                    mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                        || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+                })
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use thiserror::Error;
+use url::Url;
+
+use crate::config::parse_provider_url;
+use crate::console;
+use crate::interaction;
+use crate::providers::Provider;
+use crate::terminal;
+use crate::theme::{Paint, Role};
+
+/// Represents errors related to importing provider credentials from a file.
+#[derive(Error, Debug)]
+pub enum CredentialsError {
+    /// An error indicating a failure to read the credentials file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific read error.
+    /// * `1` - A string representing the path to the credentials file.
+    #[error("Failed to read the credentials file '{1}'")]
+    Read(std::io::Error, String),
+
+    /// An error indicating that the credentials file isn't a valid TOML or JSON document.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the reason of the parse failure.
+    #[error("Failed to parse the credentials; expected a table of providers with an 'api_key' and an optional 'url' each ({0})")]
+    Parse(String),
+
+    /// An error indicating that entries of the credentials file are invalid; nothing is imported then.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the invalid entries and their reasons.
+    #[error("Invalid credentials, nothing was imported: {0}")]
+    Invalid(String),
+
+    /// An error indicating that the credentials file doesn't contain any provider.
+    #[error("The credentials file doesn't contain any provider")]
+    Empty,
+
+    /// An error indicating a failure to read the API key of a provider.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific read error.
+    /// * `1` - A string representing the source of the API key (a file or the standard input).
+    #[error("Failed to read the API key from {1}")]
+    ApiKeyRead(std::io::Error, String),
+
+    /// An error indicating that the read API key is empty.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the source of the API key (a file or the standard input).
+    #[error("The API key read from {0} is empty")]
+    EmptyApiKey(String),
+
+    /// An error indicating that the API key isn't set and can't be asked, because the standard input isn't a terminal
+    /// or prompts are answered with their defaults (`--yes`).
+    #[error("The API key is missing; pass it with '--api-key <API_KEY>', '--api-key-file <FILE>' or '--api-key -' to read it from the standard input")]
+    MissingApiKey,
+}
+
+/// Represents the source of the API key of the `configure` command.
+#[derive(Debug, PartialEq)]
+pub enum ApiKeySource {
+    /// The API key given on the command line.
+    Value(String),
+    /// The standard input (the API key is `-`), so the key doesn't land in the shell history or process listings.
+    Stdin,
+    /// A file containing the API key.
+    File(PathBuf),
+    /// A hidden input of the terminal (the API key isn't set).
+    Prompt,
+}
+
+/// `ApiKeySource` constructors
+impl ApiKeySource {
+    /// Selects the source of the API key from the options of the `configure` command.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - The API key; `-` stands for the standard input.
+    /// * `api_key_file` - The path to a file containing the API key.
+    ///
+    /// # Returns
+    ///
+    /// The source of the API key; the terminal is prompted if neither is set.
+    pub fn new(api_key: Option<String>, api_key_file: Option<PathBuf>) -> Self {
+        match (api_key, api_key_file) {
+            (_, Some(path)) => ApiKeySource::File(path),
+            (Some(api_key), None) if api_key == "-" => ApiKeySource::Stdin,
+            (Some(api_key), None) => ApiKeySource::Value(api_key),
+            (None, None) => ApiKeySource::Prompt,
+        }
+    }
+}
+
+/// Reads the API key of a provider from its source.
+///
+/// # Arguments
+///
+/// * `source` - The source of the API key.
+/// * `provider` - The configured provider (named by the prompt of the terminal).
+///
+/// # Returns
+///
+/// A `Result` containing the API key without surrounding whitespace or a `CredentialsError` if it can't be read
+/// or is empty.
+pub fn read_api_key(source: ApiKeySource, provider: &Provider) -> Result<String, CredentialsError> {
+    match source {
+        ApiKeySource::Value(api_key) => Ok(api_key),
+        ApiKeySource::Stdin if terminal::capabilities().interactive => {
+            read_prompted_api_key(provider)
+        }
+        ApiKeySource::Stdin => read_key(std::io::stdin().lock(), "the standard input"),
+        ApiKeySource::File(path) => {
+            let source = format!("'{}'", path.display().to_string().paint(Role::Warning));
+            let file = File::open(&path)
+                .map_err(|err| CredentialsError::ApiKeyRead(err, source.clone()))?;
+            read_key(file, &source)
+        }
+        ApiKeySource::Prompt if is_promptable() => read_prompted_api_key(provider),
+        ApiKeySource::Prompt => Err(CredentialsError::MissingApiKey),
+    }
+}
+
+/// Checks whether the API key can be asked, i.e. the user can be asked in the terminal and prompts aren't answered
+/// with their defaults (`--yes`); an API key has no default.
+///
+/// # Returns
+///
+/// `true` if the API key can be asked.
+fn is_promptable() -> bool {
+    terminal::capabilities().interactive && !interaction::current().assume_yes
+}
+
+/// Asks the terminal for the API key of a provider without echoing it.
+///
+/// # Arguments
+///
+/// * `provider` - The configured provider.
+///
+/// # Returns
+///
+/// A `Result` containing the API key without surrounding whitespace or a `CredentialsError` if it can't be read
+/// or is empty.
+fn read_prompted_api_key(provider: &Provider) -> Result<String, CredentialsError> {
+    let source = "the terminal";
+    let api_key = console::read_hidden_line(&format!(
+        "API key of '{}' (hidden): ",
+        provider.to_string().paint(Role::Highlight)
+    ))
+    .map_err(|err| CredentialsError::ApiKeyRead(err, source.to_owned()))?;
+
+    read_key(api_key.as_bytes(), source)
+}
+
+/// Reads an API key, e.g. from a file or the standard input.
+///
+/// # Arguments
+///
+/// * `input` - The input containing the API key.
+/// * `source` - A string representing the source of the input (used in errors).
+///
+/// # Returns
+///
+/// A `Result` containing the API key without surrounding whitespace or a `CredentialsError` if it can't be read
+/// or is empty.
+fn read_key(mut input: impl Read, source: &str) -> Result<String, CredentialsError> {
+    let mut content = String::new();
+    input
+        .read_to_string(&mut content)
+        .map_err(|err| CredentialsError::ApiKeyRead(err, source.to_owned()))?;
+
+    match content.trim() {
+        "" => Err(CredentialsError::EmptyApiKey(source.to_owned())),
+        api_key => Ok(api_key.to_owned()),
+    }
+}
+
+/// Represents the credentials of a provider in the credentials file.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct CredentialsEntry {
+    /// The API key of the provider.
+    api_key: String,
+    /// The URL of the provider API; the configured (or default) URL is kept if not set.
+    #[serde(default)]
+    url: Option<String>,
+}
+
+/// Represents the validated credentials of a provider.
+#[derive(Debug, PartialEq)]
+pub struct ProviderCredentials {
+    pub provider: Provider,
+    pub url: Option<Url>,
+    pub api_key: String,
+}
+
+/// Parses and validates the credentials of several providers, e.g. for provisioning machines by scripts.
+///
+/// The document is a table of providers keyed by their names (e.g. `open-weather` or `owm`), with an `api_key`
+/// and an optional `url` each:
+///
+/// ```toml
+/// [open-weather]
+/// api_key = "<API_KEY>"
+///
+/// [weather-api]
+/// api_key = "<API_KEY>"
+/// url = "https://api.weatherapi.com/v1"
+/// ```
+///
+/// # Arguments
+///
+/// * `content` - The content of the credentials file.
+/// * `json` - Whether the content is JSON (`true`) or TOML (`false`).
+///
+/// # Returns
+///
+/// A `Result` containing the credentials in the order of the provider names or a `CredentialsError` if the document
+/// can't be parsed, is empty or any entry is invalid (an unknown provider, a malformed URL or an empty API key).
+pub fn parse(content: &str, json: bool) -> Result<Vec<ProviderCredentials>, CredentialsError> {
+    let entries: BTreeMap<String, CredentialsEntry> = if json {
+        serde_json::from_str(content).map_err(|err| CredentialsError::Parse(err.to_string()))?
+    } else {
+        toml::from_str(content).map_err(|err| CredentialsError::Parse(err.to_string()))?
+    };
+    if entries.is_empty() {
+        return Err(CredentialsError::Empty);
+    }
+
+    let mut credentials = Vec::new();
+    let mut invalid = Vec::new();
+    for (name, entry) in entries {
+        match validate(&name, entry) {
+            Ok(provider_credentials) => credentials.push(provider_credentials),
+            Err(reason) => invalid.push(format!("{} ({reason})", name.paint(Role::Warning))),
+        }
+    }
+
+    if invalid.is_empty() {
+        Ok(credentials)
+    } else {
+        Err(CredentialsError::Invalid(invalid.join("; ")))
+    }
+}
+
+/// Validates the credentials of a provider.
+///
+/// # Arguments
+///
+/// * `name` - The name of the provider.
+/// * `entry` - The credentials of the provider.
+///
+/// # Returns
+///
+/// A `Result` containing the validated credentials or the reason why they are invalid.
+fn validate(name: &str, entry: CredentialsEntry) -> Result<ProviderCredentials, String> {
+    let provider: Provider = name.parse().map_err(|_| "unknown provider".to_owned())?;
+    let url = entry
+        .url
+        .as_deref()
+        .map(parse_provider_url)
+        .transpose()
+        .map_err(|err| err.to_string())?;
+    let api_key = entry.api_key.trim();
+    if api_key.is_empty() {
+        return Err("the API key is empty".to_owned());
+    }
+
+    Ok(ProviderCredentials {
+        provider,
+        url,
+        api_key: api_key.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "[owm]\napi_key = 'key1'\n\n[weather-api]\napi_key = ' key2 '\nurl = 'https://example.com/v1'\n",
+        false
+    )]
+    #[case(
+        r#"{"owm": {"api_key": "key1"}, "weather-api": {"api_key": " key2 ", "url": "https://example.com/v1"}}"#,
+        true
+    )]
+    fn test_parse(#[case] content: &str, #[case] json: bool) {
+        let result = parse(content, json).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                ProviderCredentials {
+                    provider: Provider::OpenWeather,
+                    url: None,
+                    api_key: "key1".to_owned(),
+                },
+                ProviderCredentials {
+                    provider: Provider::WeatherApi,
+                    url: Some(Url::parse("https://example.com/v1").unwrap()),
+                    api_key: "key2".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("[open-wether]\napi_key = 'key'\n", "open-wether")]
+    #[case("[owm]\napi_key = ''\n", "the API key is empty")]
+    #[case(
+        "[owm]\napi_key = 'key'\nurl = 'ftp://example.com'\n",
+        "ftp://example.com"
+    )]
+    fn test_parse_invalid(#[case] content: &str, #[case] expected: &str) {
+        let result = parse(content, false);
+
+        assert!(
+            matches!(&result, Err(CredentialsError::Invalid(reason)) if reason.contains(expected))
+        );
+    }
+
+    #[rstest]
+    #[case(Some("key"), None, ApiKeySource::Value("key".to_owned()))]
+    #[case(Some("-"), None, ApiKeySource::Stdin)]
+    #[case(None, Some("owm.key"), ApiKeySource::File(PathBuf::from("owm.key")))]
+    #[case(None, None, ApiKeySource::Prompt)]
+    fn test_api_key_source(
+        #[case] api_key: Option<&str>,
+        #[case] api_key_file: Option<&str>,
+        #[case] expected: ApiKeySource,
+    ) {
+        assert_eq!(
+            ApiKeySource::new(api_key.map(str::to_owned), api_key_file.map(PathBuf::from)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("my_api_key\n", Some("my_api_key"))]
+    #[case("  my_api_key \r\n", Some("my_api_key"))]
+    #[case(" \n", None)]
+    fn test_read_key(#[case] input: &str, #[case] expected: Option<&str>) {
+        let result = read_key(input.as_bytes(), "the standard input");
+
+        match expected {
+            Some(expected) => assert_eq!(result.unwrap(), expected),
+            None => assert!(matches!(result, Err(CredentialsError::EmptyApiKey(_)))),
+        }
+    }
+
+    #[rstest]
+    fn test_read_api_key_file() {
+        let path = std::env::temp_dir().join("weather-rs-test-credentials-api-key");
+        std::fs::write(&path, "my_api_key\n").unwrap();
+
+        let result = read_api_key(ApiKeySource::File(path.clone()), &Provider::OpenWeather);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap(), "my_api_key");
+        assert!(matches!(
+            read_api_key(ApiKeySource::File(path), &Provider::OpenWeather),
+            Err(CredentialsError::ApiKeyRead(..))
+        ));
+    }
+
+    #[rstest]
+    #[case("", false, |err: &CredentialsError| matches!(err, CredentialsError::Empty))]
+    #[case("[owm]\napi_token = 'key'\n", false, |err: &CredentialsError| matches!(err, CredentialsError::Parse(_)))]
+    #[case("not json", true, |err: &CredentialsError| matches!(err, CredentialsError::Parse(_)))]
+    fn test_parse_error(
+        #[case] content: &str,
+        #[case] json: bool,
+        #[case] check: fn(&CredentialsError) -> bool,
+    ) {
+        assert!(check(&parse(content, json).unwrap_err()));
+    }
+}
@@ -0,0 +1,475 @@
+use narrate::anyhow::Error;
+use narrate::ExitCode;
+use std::error::Error as StdError;
+use std::sync::OnceLock;
+
+use crate::bench::BenchError;
+use crate::cli_parser::{Command, GetArgs};
+use crate::config::{ConfigError, HttpConfig};
+use crate::credentials::CredentialsError;
+use crate::export::ExportError;
+use crate::favorites::FavoriteError;
+use crate::handlers;
+use crate::locations::LocationError;
+use crate::paths::PathsError;
+use crate::ping::PingError;
+use crate::plugins::PluginError;
+use crate::providers::{Provider, ProviderError};
+use weather_api_services::fixtures::FixtureError;
+use weather_api_services::models::WeatherDataError;
+use weather_api_services::{DateTimeError, WeatherApiError};
+
+/// The context of the command, set once at startup, so hints of its errors are tailored to it.
+static CONTEXT: OnceLock<HintContext> = OnceLock::new();
+
+/// The maximum number of similar spellings suggested for an unknown address.
+const MAX_SUGGESTIONS: u8 = 3;
+
+/// Exit code for configuration errors, e.g. a missing or rejected API key or an invalid URL.
+///
+/// It isn't `2`, which clap exits with on invalid command-line arguments.
+pub const EXIT_CONFIG: i32 = 7;
+
+/// Exit code for provider and network errors, e.g. a failed request, a server error or an exceeded quota.
+pub const EXIT_PROVIDER: i32 = 3;
+
+/// Exit code for errors caused by an unknown location.
+pub const EXIT_LOCATION_NOT_FOUND: i32 = 4;
+
+/// Exit code for errors caused by an invalid date or a date outside of the provider range.
+pub const EXIT_DATE: i32 = 5;
+
+/// Exit code for errors caused by a feature that the provider doesn't support.
+pub const EXIT_UNSUPPORTED: i32 = 6;
+
+/// Represents the context of the command that hints of its errors are tailored to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HintContext {
+    /// The weather data provider of the command.
+    pub provider: Provider,
+    /// The address of the command; `None` if the command has no single address (e.g. `trip`).
+    pub address: Option<String>,
+    /// Whether the command requests the weather of a date (e.g. historical weather).
+    pub date: bool,
+    /// Whether the command requests the minutely forecast (`nowcast`).
+    pub minutely: bool,
+    /// Whether similar spellings of an unknown address are looked up with the geocoder (not when responses are
+    /// recorded, replayed or planned).
+    pub geocode: bool,
+    /// The identification of the geocoder requests.
+    pub http: HttpConfig,
+}
+
+/// `HintContext` constructors
+impl HintContext {
+    /// Creates the context of a command.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command.
+    /// * `provider` - The weather data provider of the command.
+    /// * `geocode` - Whether similar spellings of an unknown address are looked up with the geocoder.
+    /// * `http` - The identification of the geocoder requests.
+    ///
+    /// # Returns
+    ///
+    /// The `HintContext` of the command.
+    pub fn new(command: &Command, provider: &Provider, geocode: bool, http: &HttpConfig) -> Self {
+        let address = match command {
+            Command::Get(GetArgs { address, .. }) => address.as_deref(),
+            Command::Forecast { address, .. }
+            | Command::Marine { address, .. }
+            | Command::Stations { address, .. }
+            | Command::Nowcast { address, .. }
+            | Command::Export { address, .. }
+            | Command::Stats { address, .. }
+            | Command::Report { address, .. }
+            | Command::Card { address, .. }
+            | Command::Radar { address, .. }
+            | Command::Mqtt { address, .. }
+            | Command::Digest { address, .. }
+            | Command::Post { address, .. } => Some(address.as_str()),
+            Command::Snow { resort, .. } => Some(resort.as_str()),
+            _ => None,
+        };
+
+        HintContext {
+            provider: provider.clone(),
+            address: address.map(str::to_owned),
+            date: matches!(
+                command,
+                Command::Get(GetArgs { date: Some(_), .. }) | Command::Export { .. }
+            ),
+            minutely: matches!(command, Command::Nowcast { .. }),
+            geocode,
+            http: http.clone(),
+        }
+    }
+}
+
+/// Represents classes of errors that terminate the application; every class has its own exit code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorClass {
+    Config,
+    Provider,
+    LocationNotFound,
+    Date,
+    Unsupported,
+}
+
+/// `ErrorClass` methods
+impl ErrorClass {
+    /// Classifies an error (or one of its causes) by its type.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error or one of its causes.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorClass` of the error or `None` if the error isn't classified.
+    fn of(err: &(dyn StdError + 'static)) -> Option<Self> {
+        if let Some(err) = err.downcast_ref::<WeatherApiError>() {
+            return Some(match err {
+                WeatherApiError::Creation | WeatherApiError::Unauthorized(_) => ErrorClass::Config,
+                WeatherApiError::LocationNotFound(_) => ErrorClass::LocationNotFound,
+                WeatherApiError::DateOutOfRange(_) => ErrorClass::Date,
+                WeatherApiError::Feature(_) => ErrorClass::Unsupported,
+                _ => ErrorClass::Provider,
+            });
+        }
+        if let Some(err) = err.downcast_ref::<ProviderError>() {
+            return Some(match err {
+                ProviderError::ProviderNotFound | ProviderError::UnknownProvider(..) => {
+                    ErrorClass::Config
+                }
+                ProviderError::ProviderNotImplemented => ErrorClass::Unsupported,
+                ProviderError::DaysOutOfRange(..) | ProviderError::DateOutOfRange(..) => {
+                    ErrorClass::Date
+                }
+            });
+        }
+
+        if let Some(ExportError::Range(..)) = err.downcast_ref::<ExportError>() {
+            return Some(ErrorClass::Date);
+        }
+        if let Some(LocationError::PlusCodeLocality(_)) = err.downcast_ref::<LocationError>() {
+            return Some(ErrorClass::LocationNotFound);
+        }
+
+        if err.is::<ConfigError>()
+            || err.is::<PathsError>()
+            || err.is::<FavoriteError>()
+            || err.is::<CredentialsError>()
+        {
+            Some(ErrorClass::Config)
+        } else if err.is::<DateTimeError>() {
+            Some(ErrorClass::Date)
+        } else if err.is::<WeatherDataError>()
+            || err.is::<PluginError>()
+            || err.is::<FixtureError>()
+            || err.is::<PingError>()
+            || err.is::<BenchError>()
+        {
+            Some(ErrorClass::Provider)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the process exit code of the error class.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorClass::Config => EXIT_CONFIG,
+            ErrorClass::Provider => EXIT_PROVIDER,
+            ErrorClass::LocationNotFound => EXIT_LOCATION_NOT_FOUND,
+            ErrorClass::Date => EXIT_DATE,
+            ErrorClass::Unsupported => EXIT_UNSUPPORTED,
+        }
+    }
+}
+
+/// Returns the process exit code for an error.
+///
+/// Typed errors are mapped to the exit code of their `ErrorClass` (the first classified error of the chain of
+/// causes wins), so shell scripts can branch on failures; all other errors use the default exit code of `narrate`.
+///
+/// # Arguments
+///
+/// * `err` - The error that terminated the application.
+///
+/// # Returns
+///
+/// The exit code of the application.
+pub fn exit_code(err: &Error) -> i32 {
+    err.chain()
+        .find_map(ErrorClass::of)
+        .map_or_else(|| err.exit_code(), |class| class.exit_code())
+}
+
+/// Checks whether an error only stopped a dry run (a request that wasn't sent), which is not a failure.
+///
+/// # Arguments
+///
+/// * `err` - The error that terminated the application.
+///
+/// # Returns
+///
+/// `true` if the error or one of its causes is `FixtureError::DryRun`.
+pub fn is_dry_run(err: &Error) -> bool {
+    err.chain()
+        .any(|cause| matches!(cause.downcast_ref(), Some(FixtureError::DryRun)))
+}
+
+/// Sets the context of the command that hints of its errors are tailored to; only the first context takes effect.
+///
+/// # Arguments
+///
+/// * `context` - The context of the command.
+pub fn set_context(context: HintContext) {
+    let _ = CONTEXT.set(context);
+}
+
+/// Returns an actionable hint for an error, if there is one.
+///
+/// The hint is tailored to the context of the command (see `set_context`); similar spellings of an unknown
+/// address are looked up with the Open-Meteo geocoder.
+///
+/// # Arguments
+///
+/// * `err` - The error that terminated the application.
+///
+/// # Returns
+///
+/// An optional hint that helps to resolve the error.
+pub async fn find_hint(err: &Error) -> Option<String> {
+    let context = CONTEXT.get().cloned().unwrap_or_default();
+
+    let mut suggestions = Vec::new();
+    if let (Some(WeatherApiError::LocationNotFound(_)), Some(address)) =
+        (weather_api_error(err), &context.address)
+    {
+        if context.geocode {
+            suggestions =
+                handlers::similar_locations(address, MAX_SUGGESTIONS, &context.http).await;
+        }
+    }
+
+    hint(err, &context, &suggestions)
+}
+
+/// Returns the first `WeatherApiError` of the chain of causes of an error.
+fn weather_api_error(err: &Error) -> Option<&WeatherApiError> {
+    err.chain().find_map(|cause| cause.downcast_ref())
+}
+
+/// Returns an actionable hint for an error in the context of the command, if there is one.
+///
+/// # Arguments
+///
+/// * `err` - The error that terminated the application.
+/// * `context` - The context of the command.
+/// * `suggestions` - Similar spellings of the address if it's unknown, best matches first.
+///
+/// # Returns
+///
+/// An optional hint that helps to resolve the error.
+pub fn hint(err: &Error, context: &HintContext, suggestions: &[String]) -> Option<String> {
+    let open_weather = context.provider == Provider::OpenWeather;
+
+    let hint = match weather_api_error(err)? {
+        WeatherApiError::Unauthorized(_) if open_weather && context.minutely => {
+            "the minutely forecast of OpenWeather needs a One Call API 3.0 subscription, which free api keys \
+             don't include; subscribe to 'One Call by Call' or use another provider with '-p <PROVIDER>'"
+                .to_owned()
+        }
+        WeatherApiError::Unauthorized(_) => format!(
+            "configure a valid api key using the command 'weather-rs configure {} <API_KEY>'",
+            context.provider
+        ),
+        WeatherApiError::QuotaExceeded(_) => format!(
+            "wait until the quota of your api key resets, add more keys to 'api_keys' of the '{}' section of the \
+             config or select another provider using the command 'weather-rs select-provider <PROVIDER>'",
+            context.provider.config_key()
+        ),
+        WeatherApiError::LocationNotFound(_) => match suggestions {
+            [] => "check the spelling of the address or use a more specific one (e.g. 'London, GB')"
+                .to_owned(),
+            [suggestion] => format!("did you mean '{suggestion}'?"),
+            [suggestions @ .., last] => format!(
+                "did you mean '{}' or '{last}'?",
+                suggestions.join("', '")
+            ),
+        },
+        WeatherApiError::DateOutOfRange(_) if context.provider == Provider::WeatherApi => {
+            "use a date supported by your Weather API plan (the free plan provides history only for the last 7 days)"
+                .to_owned()
+        }
+        WeatherApiError::DateOutOfRange(_) => "use a date supported by your provider plan".to_owned(),
+        WeatherApiError::Feature(_) if open_weather && context.date => {
+            "the historical weather of OpenWeather needs a One Call API 3.0 subscription, which free api keys \
+             don't include; use Weather API for dates with '-p weather-api'"
+                .to_owned()
+        }
+        WeatherApiError::Feature(_) => {
+            "use a provider supporting it with '-p <PROVIDER>' (see 'weather-rs provider-list')".to_owned()
+        }
+        _ => return None,
+    };
+
+    Some(hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_parser::WeatherCli;
+    use clap::Parser;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(WeatherApiError::Unauthorized(String::new()).into(), EXIT_CONFIG)]
+    #[case(ConfigError::InvalidUrl(String::new(), String::new()).into(), EXIT_CONFIG)]
+    #[case(ProviderError::ProviderNotFound.into(), EXIT_CONFIG)]
+    #[case(WeatherApiError::QuotaExceeded(String::new()).into(), EXIT_PROVIDER)]
+    #[case(FavoriteError::NotFound(String::new()).into(), EXIT_CONFIG)]
+    #[case(CredentialsError::Empty.into(), EXIT_CONFIG)]
+    #[case(WeatherApiError::Server(String::new()).into(), EXIT_PROVIDER)]
+    #[case(WeatherDataError::Implausible(String::new()).into(), EXIT_PROVIDER)]
+    #[case(PingError::Failed(String::new()).into(), EXIT_PROVIDER)]
+    #[case(BenchError::Failed(String::new()).into(), EXIT_PROVIDER)]
+    #[case(
+        PluginError::Failed(String::new(), String::new()).into(),
+        EXIT_PROVIDER
+    )]
+    #[case(
+        WeatherApiError::LocationNotFound(String::new()).into(),
+        EXIT_LOCATION_NOT_FOUND
+    )]
+    #[case(
+        LocationError::PlusCodeLocality(String::new()).into(),
+        EXIT_LOCATION_NOT_FOUND
+    )]
+    #[case(DateTimeError::Parse(String::new()).into(), EXIT_DATE)]
+    #[case(WeatherApiError::DateOutOfRange(String::new()).into(), EXIT_DATE)]
+    #[case(ExportError::Range(String::new(), String::new()).into(), EXIT_DATE)]
+    #[case(WeatherApiError::Feature(String::new()).into(), EXIT_UNSUPPORTED)]
+    #[case(ProviderError::ProviderNotImplemented.into(), EXIT_UNSUPPORTED)]
+    #[case(ProviderError::DaysOutOfRange(String::new(), 5, 3).into(), EXIT_DATE)]
+    #[case(
+        Error::from(WeatherApiError::LocationNotFound(String::new())).context("Failed to get the trip forecast"),
+        EXIT_LOCATION_NOT_FOUND
+    )]
+    fn test_exit_code(#[case] err: Error, #[case] expected: i32) {
+        assert_eq!(exit_code(&err), expected);
+    }
+
+    #[rstest]
+    #[case(FixtureError::DryRun.into(), true)]
+    #[case(Error::from(FixtureError::DryRun).context("Failed to get the trip forecast"), true)]
+    #[case(FixtureError::Missing(String::new()).into(), false)]
+    fn test_is_dry_run(#[case] err: Error, #[case] expected: bool) {
+        assert_eq!(is_dry_run(&err), expected);
+    }
+
+    #[rstest]
+    #[case(WeatherApiError::Unauthorized(String::new()), true)]
+    #[case(WeatherApiError::Server(String::new()), false)]
+    #[case(WeatherApiError::Creation, false)]
+    fn test_hint(#[case] err: WeatherApiError, #[case] expected: bool) {
+        assert_eq!(
+            hint(&err.into(), &HintContext::default(), &[]).is_some(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(
+        WeatherApiError::Unauthorized(String::new()),
+        Provider::WeatherApi,
+        "configure a valid api key using the command 'weather-rs configure weather-api <API_KEY>'"
+    )]
+    #[case(
+        WeatherApiError::Unauthorized(String::new()),
+        Provider::OpenWeather,
+        "the minutely forecast of OpenWeather"
+    )]
+    #[case(
+        WeatherApiError::Feature(String::new()),
+        Provider::OpenWeather,
+        "the historical weather of OpenWeather"
+    )]
+    #[case(
+        WeatherApiError::Feature(String::new()),
+        Provider::WeatherApi,
+        "use a provider supporting it"
+    )]
+    #[case(
+        WeatherApiError::QuotaExceeded(String::new()),
+        Provider::WeatherApi,
+        "'weather_api' section"
+    )]
+    fn test_hint_context(
+        #[case] err: WeatherApiError,
+        #[case] provider: Provider,
+        #[case] expected: &str,
+    ) {
+        let context = HintContext {
+            provider,
+            date: true,
+            minutely: true,
+            ..HintContext::default()
+        };
+
+        let hint = hint(
+            &Error::from(err).context("Failed to get weather data"),
+            &context,
+            &[],
+        );
+
+        assert!(hint.unwrap().contains(expected));
+    }
+
+    #[rstest]
+    #[case(&[], "check the spelling of the address")]
+    #[case(&["London, England, United Kingdom"], "did you mean 'London, England, United Kingdom'?")]
+    #[case(&["Londonderry", "London", "Lindon"], "did you mean 'Londonderry', 'London' or 'Lindon'?")]
+    fn test_hint_suggestions(#[case] suggestions: &[&str], #[case] expected: &str) {
+        let suggestions: Vec<String> = suggestions
+            .iter()
+            .map(|&suggestion| suggestion.to_owned())
+            .collect();
+
+        let hint = hint(
+            &WeatherApiError::LocationNotFound(String::new()).into(),
+            &HintContext::default(),
+            &suggestions,
+        );
+
+        assert!(hint.unwrap().starts_with(expected));
+    }
+
+    #[rstest]
+    #[case(&["weather-rs", "London", "-d", "2024-03-08"], Some("London"), true, false)]
+    #[case(&["weather-rs", "nowcast", "Paris"], Some("Paris"), false, true)]
+    #[case(&["weather-rs", "trip", "-s", "London,Paris"], None, false, false)]
+    fn test_hint_context_new(
+        #[case] args: &[&str],
+        #[case] expected_address: Option<&str>,
+        #[case] expected_date: bool,
+        #[case] expected_minutely: bool,
+    ) {
+        let command = WeatherCli::parse_from(args).take_command();
+
+        let context = HintContext::new(
+            &command,
+            &Provider::OpenWeather,
+            true,
+            &HttpConfig::default(),
+        );
+
+        assert_eq!(context.address.as_deref(), expected_address);
+        assert_eq!(context.date, expected_date);
+        assert_eq!(context.minutely, expected_minutely);
+    }
+}
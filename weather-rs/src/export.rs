@@ -0,0 +1,297 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::theme::{Paint, Role};
+use weather_api_services::output::WeatherDataOutput;
+use weather_api_services::DateTimeError;
+
+/// The extension appended to the path of an export file to get the path of its checkpoint file.
+const CHECKPOINT_EXTENSION: &str = "checkpoint.json";
+
+/// Represents errors related to exporting the weather history.
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// An error indicating that the last date of the range is before the first one.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the first date of the range.
+    /// * `1` - A string representing the last date of the range.
+    #[error("Invalid date range - {0}..{1}; the last date must not be before the first one")]
+    Range(String, String),
+
+    /// An error indicating that there is no checkpoint to resume an export from.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the path to the expected checkpoint file.
+    #[error("No checkpoint '{0}' to resume the export from; start the export without '--resume'")]
+    MissingCheckpoint(String),
+
+    /// An error indicating that the checkpoint belongs to an export of another address or date range.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the path to the checkpoint file.
+    #[error("The checkpoint '{0}' belongs to an export of another address or date range")]
+    CheckpointMismatch(String),
+
+    /// An error indicating a failure to read or write the export file or its checkpoint.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the file.
+    #[error("Failed to access the export file '{1}'")]
+    Io(std::io::Error, String),
+}
+
+/// Represents the weather of a day of the exported history, a line of the NDJSON export file.
+#[derive(Serialize, Debug)]
+pub struct ExportedDay {
+    /// The date in the `YYYY-MM-DD` format.
+    pub date: String,
+    /// The weather with temperatures and speeds in the units of the export.
+    #[serde(flatten)]
+    pub weather: WeatherDataOutput,
+}
+
+/// Represents the progress of an export saved after every exported day, so an interrupted export can be resumed.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ExportCheckpoint {
+    /// The address of the export.
+    pub address: String,
+    /// The first date of the range.
+    pub from: NaiveDate,
+    /// The last date of the range.
+    pub to: NaiveDate,
+    /// The last exported date; `None` if no day is exported yet.
+    pub last_exported: Option<NaiveDate>,
+}
+
+/// `ExportCheckpoint` constructors and methods
+impl ExportCheckpoint {
+    /// Creates the checkpoint of a new export.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the export.
+    /// * `from` - The first date of the range.
+    /// * `to` - The last date of the range.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the checkpoint or an `ExportError` if the range is invalid.
+    pub fn new(address: &str, from: NaiveDate, to: NaiveDate) -> Result<Self, ExportError> {
+        if to < from {
+            return Err(ExportError::Range(
+                from.to_string().paint(Role::Warning).to_string(),
+                to.to_string().paint(Role::Warning).to_string(),
+            ));
+        }
+
+        Ok(ExportCheckpoint {
+            address: address.to_owned(),
+            from,
+            to,
+            last_exported: None,
+        })
+    }
+
+    /// Returns the dates that aren't exported yet in chronological order.
+    pub fn remaining_dates(&self) -> Vec<NaiveDate> {
+        let first = self
+            .last_exported
+            .map_or(self.from, |date| date + Duration::days(1));
+
+        first
+            .iter_days()
+            .take_while(|date| *date <= self.to)
+            .collect()
+    }
+
+    /// Checks whether the checkpoint belongs to an export of the given address and date range.
+    pub fn matches(&self, other: &ExportCheckpoint) -> bool {
+        self.address.to_lowercase() == other.address.to_lowercase()
+            && self.from == other.from
+            && self.to == other.to
+    }
+}
+
+/// Parses a date of the export range in the `YYYY-MM-DD` format.
+///
+/// # Arguments
+///
+/// * `date` - The date to parse.
+///
+/// # Returns
+///
+/// A `Result` containing the date or a `DateTimeError` if it's invalid.
+pub fn parse_date(date: &str) -> Result<NaiveDate, DateTimeError> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| DateTimeError::Parse(date.paint(Role::Warning).to_string()))
+}
+
+/// Returns the path of the checkpoint file of an export file (e.g. `london.ndjson.checkpoint.json`).
+pub fn checkpoint_path(output: &Path) -> PathBuf {
+    let mut name = output.as_os_str().to_owned();
+    name.push(format!(".{CHECKPOINT_EXTENSION}"));
+
+    PathBuf::from(name)
+}
+
+/// Loads the checkpoint of an interrupted export.
+///
+/// # Arguments
+///
+/// * `path` - The path to the checkpoint file.
+///
+/// # Returns
+///
+/// A `Result` containing the checkpoint or an `ExportError` if it's missing or can't be read.
+pub fn load_checkpoint(path: &Path) -> Result<ExportCheckpoint, ExportError> {
+    let content = fs::read_to_string(path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => ExportError::MissingCheckpoint(
+            path.display().to_string().paint(Role::Warning).to_string(),
+        ),
+        _ => ExportError::Io(err, path.display().to_string()),
+    })?;
+
+    serde_json::from_str(&content)
+        .map_err(|err| ExportError::Io(err.into(), path.display().to_string()))
+}
+
+/// Saves the checkpoint of an export, replacing the previous one.
+///
+/// The checkpoint is written to a temporary file first, so an interruption can't leave a truncated checkpoint.
+///
+/// # Arguments
+///
+/// * `path` - The path to the checkpoint file.
+/// * `checkpoint` - The checkpoint to save.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an `ExportError` if the checkpoint can't be written.
+pub fn save_checkpoint(path: &Path, checkpoint: &ExportCheckpoint) -> Result<(), ExportError> {
+    let temp_path = path.with_extension("tmp");
+    let content = serde_json::to_string_pretty(checkpoint)
+        .map_err(|err| ExportError::Io(err.into(), path.display().to_string()))?;
+
+    fs::write(&temp_path, content)
+        .and_then(|_| fs::rename(&temp_path, path))
+        .map_err(|err| ExportError::Io(err, path.display().to_string()))
+}
+
+/// Opens the export file for appending exported days.
+///
+/// # Arguments
+///
+/// * `path` - The path to the export file.
+/// * `resume` - A flag to indicate if the export is resumed; a new export truncates the file.
+///
+/// # Returns
+///
+/// A `Result` containing the opened file or an `ExportError` if it can't be opened.
+pub fn open_output(path: &Path, resume: bool) -> Result<File, ExportError> {
+    OpenOptions::new()
+        .create(true)
+        .append(resume)
+        .write(true)
+        .truncate(!resume)
+        .open(path)
+        .map_err(|err| ExportError::Io(err, path.display().to_string()))
+}
+
+/// Appends an exported day to the export file as a JSON line.
+///
+/// # Arguments
+///
+/// * `file` - The export file.
+/// * `path` - The path to the export file (for error messages).
+/// * `day` - The exported day.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an `ExportError` if the line can't be written.
+pub fn append_day(file: &mut File, path: &Path, day: &ExportedDay) -> Result<(), ExportError> {
+    let line = serde_json::to_string(day)
+        .map_err(|err| ExportError::Io(err.into(), path.display().to_string()))?;
+
+    writeln!(file, "{line}")
+        .and_then(|_| file.flush())
+        .map_err(|err| ExportError::Io(err, path.display().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn date(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    #[rstest]
+    #[case(None, vec!["2024-07-01", "2024-07-02", "2024-07-03"])]
+    #[case(Some("2024-07-01"), vec!["2024-07-02", "2024-07-03"])]
+    #[case(Some("2024-07-03"), vec![])]
+    fn test_remaining_dates(#[case] last_exported: Option<&str>, #[case] expected: Vec<&str>) {
+        let mut checkpoint =
+            ExportCheckpoint::new("London", date("2024-07-01"), date("2024-07-03")).unwrap();
+        checkpoint.last_exported = last_exported.map(date);
+
+        let result = checkpoint.remaining_dates();
+
+        assert_eq!(result, expected.into_iter().map(date).collect::<Vec<_>>());
+    }
+
+    #[rstest]
+    fn test_checkpoint_invalid_range() {
+        let result = ExportCheckpoint::new("London", date("2024-07-03"), date("2024-07-01"));
+
+        assert!(matches!(result, Err(ExportError::Range(..))));
+    }
+
+    #[rstest]
+    #[case("london", "2024-07-01", "2024-07-03", true)]
+    #[case("Paris", "2024-07-01", "2024-07-03", false)]
+    #[case("London", "2024-07-02", "2024-07-03", false)]
+    fn test_checkpoint_matches(
+        #[case] address: &str,
+        #[case] from: &str,
+        #[case] to: &str,
+        #[case] expected: bool,
+    ) {
+        let checkpoint =
+            ExportCheckpoint::new("London", date("2024-07-01"), date("2024-07-03")).unwrap();
+        let other = ExportCheckpoint::new(address, date(from), date(to)).unwrap();
+
+        assert_eq!(checkpoint.matches(&other), expected);
+    }
+
+    #[rstest]
+    fn test_save_and_load_checkpoint() {
+        let dir = std::env::temp_dir().join("weather-rs-test-export");
+        fs::create_dir_all(&dir).unwrap();
+        let path = checkpoint_path(&dir.join("london.ndjson"));
+        let mut checkpoint =
+            ExportCheckpoint::new("London", date("2024-07-01"), date("2024-07-03")).unwrap();
+        checkpoint.last_exported = Some(date("2024-07-02"));
+
+        save_checkpoint(&path, &checkpoint).unwrap();
+        let result = load_checkpoint(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(path.ends_with("london.ndjson.checkpoint.json"));
+        assert_eq!(result, checkpoint);
+        assert!(matches!(
+            load_checkpoint(&path),
+            Err(ExportError::MissingCheckpoint(_))
+        ));
+    }
+}
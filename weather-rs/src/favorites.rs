@@ -0,0 +1,336 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::cli_parser::GlobalOptions;
+use crate::providers::Provider;
+use crate::theme::{Paint, Role};
+use weather_api_services::units::Units;
+
+/// Represents errors related to favorite queries.
+#[derive(Error, Debug)]
+pub enum FavoriteError {
+    /// An error indicating that a favorite query isn't saved.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the name of the favorite.
+    #[error("Favorite '{0}' not found; use the command 'weather-rs favorite list' to get a list of saved favorites")]
+    NotFound(String),
+
+    /// An error indicating that there are no favorite queries, e.g. no locations for the dashboard.
+    #[error("There are no favorites; save a location with the command 'weather-rs favorite add <NAME> <ADDRESS>'")]
+    NoFavorites,
+
+    /// An error indicating an invalid name of a favorite query.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the invalid name.
+    #[error(
+        "Invalid favorite name - '{0}'. Please use letters, digits, '-' and '_' (e.g., 'commute')"
+    )]
+    Name(String),
+
+    /// An error indicating an invalid interval of scheduled runs.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the invalid interval.
+    #[error("Invalid interval - {0}. Please use a number of minutes, hours or days (e.g., '30m', '6h' or '1d')")]
+    Interval(String),
+
+    /// An error indicating an invalid tag of a favorite query.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the invalid tag.
+    #[error("Invalid tag - '{0}'. Please use letters, digits, '-' and '_' (e.g., 'travel')")]
+    Tag(String),
+
+    /// An error indicating that no favorite query has the tag.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the tag.
+    #[error("No favorites are tagged '{0}'; tag a favorite with the command 'weather-rs favorite add <NAME> <ADDRESS> --tag <TAG>'")]
+    NoTagged(String),
+}
+
+/// Represents a favorite query: a named preset of the `get` command.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Favorite {
+    /// The address of the query.
+    pub address: String,
+    /// The units of the output; the units of the configuration if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<Units>,
+    /// Whether the weather data is printed in JSON format instead of the table.
+    #[serde(default)]
+    pub json: bool,
+    /// The interval of scheduled runs (e.g. `30m`, `6h` or `1d`) for schedulers running the favorite; not run
+    /// on a schedule if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every: Option<String>,
+    /// The tags grouping the location of the favorite with others (e.g. `home`, `travel` or `family`), in lowercase.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The weather data provider of the query; the selected provider if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<Provider>,
+}
+
+/// `Favorite` methods
+impl Favorite {
+    /// Applies the preset of the favorite to the options of the command line; options set on the command line
+    /// take precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options of the command line.
+    ///
+    /// # Returns
+    ///
+    /// The options of the query.
+    pub fn apply(&self, options: &GlobalOptions) -> GlobalOptions {
+        GlobalOptions {
+            provider: options.provider.clone().or_else(|| self.provider.clone()),
+            units: options.units.or(self.units),
+            ..options.clone()
+        }
+    }
+}
+
+/// Finds a favorite query by its name.
+///
+/// # Arguments
+///
+/// * `favorites` - The favorite queries keyed by their names.
+/// * `name` - The name of the favorite.
+///
+/// # Returns
+///
+/// A `Result` containing the favorite or `FavoriteError::NotFound` if it isn't saved.
+pub fn find<'a>(
+    favorites: &'a BTreeMap<String, Favorite>,
+    name: &str,
+) -> Result<&'a Favorite, FavoriteError> {
+    favorites
+        .get(name)
+        .ok_or_else(|| FavoriteError::NotFound(name.paint(Role::Warning).to_string()))
+}
+
+/// Selects the favorite queries with a tag.
+///
+/// # Arguments
+///
+/// * `favorites` - The favorite queries keyed by their names.
+/// * `tag` - The tag (case-insensitive); all favorites are selected if `None`.
+///
+/// # Returns
+///
+/// A `Result` containing the names and the selected favorites in the order of their names or `FavoriteError` if no
+/// favorite is selected.
+pub fn tagged<'a>(
+    favorites: &'a BTreeMap<String, Favorite>,
+    tag: Option<&str>,
+) -> Result<Vec<(&'a String, &'a Favorite)>, FavoriteError> {
+    let selected: Vec<_> = favorites
+        .iter()
+        .filter(|(_, favorite)| {
+            tag.is_none_or(|tag| {
+                favorite
+                    .tags
+                    .iter()
+                    .any(|other| other.eq_ignore_ascii_case(tag))
+            })
+        })
+        .collect();
+
+    match (selected.is_empty(), tag) {
+        (false, _) => Ok(selected),
+        (true, None) => Err(FavoriteError::NoFavorites),
+        (true, Some(tag)) => Err(FavoriteError::NoTagged(
+            tag.paint(Role::Warning).to_string(),
+        )),
+    }
+}
+
+/// Validates the name of a favorite query (used as a value parser of the `favorite add` command).
+///
+/// # Arguments
+///
+/// * `name` - The name of the favorite.
+///
+/// # Returns
+///
+/// A `Result` containing the name or a `FavoriteError` if it's empty or has characters other than letters, digits,
+/// `-` and `_`.
+pub fn parse_name(name: &str) -> Result<String, FavoriteError> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '-' || char == '_')
+    {
+        Ok(name.to_owned())
+    } else {
+        Err(FavoriteError::Name(name.to_owned()))
+    }
+}
+
+/// Validates a tag of favorite queries (used as a value parser of the `--tag` options).
+///
+/// # Arguments
+///
+/// * `tag` - The tag.
+///
+/// # Returns
+///
+/// A `Result` containing the tag in lowercase or a `FavoriteError` if it's empty or has characters other than
+/// letters, digits, `-` and `_`.
+pub fn parse_tag(tag: &str) -> Result<String, FavoriteError> {
+    parse_name(tag)
+        .map(|tag| tag.to_lowercase())
+        .map_err(|_| FavoriteError::Tag(tag.to_owned()))
+}
+
+/// Parses the interval of scheduled runs of a favorite query (e.g. `30m`, `6h` or `1d`).
+///
+/// # Arguments
+///
+/// * `interval` - The number of minutes (`m`), hours (`h`) or days (`d`).
+///
+/// # Returns
+///
+/// A `Result` containing the duration of the interval or a `FavoriteError` if it's invalid.
+pub fn parse_interval(interval: &str) -> Result<Duration, FavoriteError> {
+    let invalid = || FavoriteError::Interval(interval.paint(Role::Warning).to_string());
+    let interval = interval.trim();
+    let unit = interval.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = interval[..interval.len() - unit.len_utf8()]
+        .parse()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(invalid)?;
+
+    match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("30m", Some(Duration::minutes(30)))]
+    #[case(" 6h", Some(Duration::hours(6)))]
+    #[case("1d", Some(Duration::days(1)))]
+    #[case("0h", None)]
+    #[case("1w", None)]
+    #[case("", None)]
+    fn test_parse_interval(#[case] interval: &str, #[case] expected: Option<Duration>) {
+        assert_eq!(parse_interval(interval).ok(), expected);
+    }
+
+    #[rstest]
+    #[case("commute", true)]
+    #[case("home_2", true)]
+    #[case("", false)]
+    #[case("my home", false)]
+    fn test_parse_name(#[case] name: &str, #[case] is_valid: bool) {
+        assert_eq!(parse_name(name).is_ok(), is_valid);
+    }
+
+    #[rstest]
+    #[case(
+        GlobalOptions::default(),
+        Some(Provider::WeatherApi),
+        Some(Units::Imperial)
+    )]
+    #[case(
+        GlobalOptions { provider: Some(Provider::OpenWeather), units: Some(Units::Metric), ..GlobalOptions::default() },
+        Some(Provider::OpenWeather),
+        Some(Units::Metric)
+    )]
+    fn test_favorite_apply(
+        #[case] options: GlobalOptions,
+        #[case] expected_provider: Option<Provider>,
+        #[case] expected_units: Option<Units>,
+    ) {
+        let favorite = Favorite {
+            address: "London".to_owned(),
+            units: Some(Units::Imperial),
+            json: false,
+            every: None,
+            tags: Vec::new(),
+            provider: Some(Provider::WeatherApi),
+        };
+
+        let result = favorite.apply(&options);
+
+        assert_eq!(result.provider, expected_provider);
+        assert_eq!(result.units, expected_units);
+    }
+
+    fn favorite(address: &str, tags: &[&str]) -> Favorite {
+        Favorite {
+            address: address.to_owned(),
+            units: None,
+            json: true,
+            every: Some("30m".to_owned()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            provider: None,
+        }
+    }
+
+    #[rstest]
+    fn test_find() {
+        let favorites = BTreeMap::from([("commute".to_owned(), favorite("London", &[]))]);
+
+        assert_eq!(find(&favorites, "commute").unwrap().address, "London");
+        assert!(matches!(
+            find(&favorites, "home"),
+            Err(FavoriteError::NotFound(_))
+        ));
+    }
+
+    #[rstest]
+    #[case(None, Some(vec!["grandma", "home", "hotel"]))]
+    #[case(Some("family"), Some(vec!["grandma", "home"]))]
+    #[case(Some("TRAVEL"), Some(vec!["hotel"]))]
+    #[case(Some("work"), None)]
+    fn test_tagged(#[case] tag: Option<&str>, #[case] expected: Option<Vec<&str>>) {
+        let favorites = BTreeMap::from([
+            ("home".to_owned(), favorite("Kyiv", &["home", "family"])),
+            ("hotel".to_owned(), favorite("Lisbon", &["travel"])),
+            ("grandma".to_owned(), favorite("Lviv", &["family"])),
+        ]);
+
+        let result = tagged(&favorites, tag).ok().map(|selected| {
+            selected
+                .into_iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(result, expected);
+        assert!(matches!(
+            tagged(&BTreeMap::new(), None),
+            Err(FavoriteError::NoFavorites)
+        ));
+    }
+
+    #[rstest]
+    #[case("Travel", Some("travel"))]
+    #[case("road trip", None)]
+    fn test_parse_tag(#[case] tag: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_tag(tag).ok().as_deref(), expected);
+    }
+}
@@ -1,130 +1,2423 @@
-use std::time::Duration;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
 
-use indicatif::{ProgressBar, ProgressStyle};
-use narrate::anyhow::Result;
-use narrate::colored::Colorize;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use narrate::anyhow::{Error, Result};
+use reqwest::Client;
+use serde::Serialize;
+use url::Url;
 
-use crate::config::{ConfigError, MainConfig, ProviderConfig};
+use crate::astronomy;
+use crate::bench::{self, BenchResult, BenchRun, TransferProbe};
+use crate::card::{self, CardFormat};
+use crate::chat::{self, ChatMessage, ChatTarget};
+use crate::cli_parser::{
+    FixtureMode, ForecastOptions, OutputFormat, PostOptions, QueryOptions, ReportFormat,
+    WeatherOptions,
+};
+use crate::clipboard;
+use crate::config::{ClimateNormalsCache, ConfigError, HttpConfig, LocationChoices, MainConfig};
+use crate::credentials::{self, CredentialsError};
+use crate::digest;
+use crate::export::{self, ExportCheckpoint, ExportError, ExportedDay};
+use crate::favorites::{self, Favorite};
+use crate::history::{RecentQueries, RecentQuery};
+use crate::interaction;
+use crate::locations::{self, LocationError, MAX_CANDIDATES};
+use crate::maps::{self, RainViewer};
+use crate::mqtt;
+use crate::nowcast;
+use crate::paths::{self, AppDir};
+use crate::ping::{self, PingResult, StatusProbe};
+use crate::plugins::{self, PluginService};
+use crate::progress::{self, with_spinner, BatchProgress};
+use crate::provenance;
 use crate::providers::{Provider, ProviderError};
-use crate::views;
-use weather_api_services::WeatherApi;
-use weather_api_services::{
-    openweather_service::OpenWeatherApiService, weatherapi_service::WeatherApiService,
+use crate::report::{Report, ReportError};
+use crate::route::{self, RouteError};
+use crate::stats::{self, Statistics, StatsError};
+use crate::store::{self, StoredObservation};
+use crate::summary;
+use crate::template::{self, TemplateContext, UnitSymbols};
+use crate::terminal;
+use crate::theme::{Paint, Role};
+use crate::views::{self, DashboardRow, LocationForecast, LocationWeather, RouteWeather};
+use crate::{
+    CLIMATE_CACHE_NAME, LOCATIONS_NAME, OBSERVATIONS_STORE_NAME, RECENT_QUERIES_NAME,
+    RESPONSES_CACHE_NAME,
 };
+use weather_api_services::climatology::{ClimateArchive, ClimateNormal};
+use weather_api_services::dates;
+use weather_api_services::derived;
+use weather_api_services::fixtures::{DryRun, FixtureError, Record, Replay};
+use weather_api_services::geocoding::{self, PostalCode, What3Words};
+use weather_api_services::http::{self, Cache, KeyRotation, Pipeline};
+use weather_api_services::models::lenient::ParsingMode;
+use weather_api_services::models::{WeatherData, WeatherDataError};
+use weather_api_services::output::{ClimateNormalOutput, IntoOutput, WeatherDataOutput};
+use weather_api_services::quantities::HectoPascal;
+use weather_api_services::secret::Secret;
+use weather_api_services::units::ConvertUnits;
+use weather_api_services::validation;
+use weather_api_services::{DateTimeError, WeatherApi, WeatherApiError};
+
+/// Represents weather data with the climate normal of the calendar day (the JSON output of `get --vs-normal`).
+#[derive(Serialize)]
+struct WeatherDataVsNormal {
+    #[serde(flatten)]
+    weather: WeatherDataOutput,
+    climate_normal: ClimateNormalOutput,
+    /// The difference between the temperature and the normal in degrees of the output units.
+    anomaly: f32,
+}
+
+/// Handles the 'provider-list' command to display the status of weather data providers.
+///
+/// This function displays the status of every provider in the provider registry, indicating whether each provider
+/// is not implemented, configured, or not configured. It also shows which provider is currently selected.
+///
+/// # Arguments
+///
+/// * `selected_provider` - The selected weather data provider.
+/// * `configured_providers` - A list of configured weather data providers.
+pub fn provider_list_handler(selected_provider: &Provider, configured_providers: Vec<&Provider>) {
+    print!(
+        "{}",
+        views::provider_list_terminal_view(
+            selected_provider,
+            &configured_providers,
+            &plugins::discover_plugins()
+        )
+    );
+}
+
+/// Fetches weather information from a selected provider and displays it in the terminal.
+///
+/// This function fetches weather information for a given address and optional date using the selected provider.
+/// It supports JSON output and displays the weather data using the provided `WeatherData` struct.
+///
+/// # Arguments
+///
+/// * `address` - The address for which weather information is requested.
+/// * `weather_options` - The optional date and the flags selecting what is fetched and displayed.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// The current weather is also kept in the observations store for the 'stats' command.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying weather information.
+pub async fn get_weather_info(
+    address: &str,
+    weather_options: &WeatherOptions,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let WeatherOptions {
+        ref date,
+        json,
+        uv,
+        vs_normal,
+        copy,
+        altitude_pressure,
+    } = *weather_options;
+    check_date(&options.provider, date)?;
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let (weather_data, climate_normal) = with_spinner(async {
+        let mut weather_data = weather_api.get_weather_data(&query, date).await?;
+        check_plausibility(address, &weather_data, options.reject_implausible)?;
+        if altitude_pressure && weather_data.station_pressure.is_none() {
+            weather_data.station_pressure =
+                compute_station_pressure(address, &weather_data, &config.http).await?;
+        }
+        if date.is_none() {
+            store_observations(&[StoredObservation::new(
+                address,
+                &weather_data,
+                Local::now().timestamp(),
+            )]);
+        }
+        let climate_normal = if vs_normal {
+            Some(get_climate_normal(address, requested_date(date)?, &config.http).await?)
+        } else {
+            None
+        };
+
+        Ok((weather_data, climate_normal))
+    })
+    .await?;
+
+    if let (true, Some(climate_normal)) = (json, &climate_normal) {
+        let output = WeatherDataVsNormal {
+            anomaly: options
+                .units
+                .temperature_difference(climate_normal.anomaly(weather_data.temp)),
+            climate_normal: climate_normal.clone().into_output(options.units),
+            weather: weather_data.into_output(options.units),
+        };
+        if copy {
+            copy_output(&serde_json::to_string(&output)?)?;
+        }
+
+        print!(
+            "{}",
+            views::json_data_view(
+                output,
+                options.units,
+                options.json_envelope,
+                options.query.as_ref()
+            )?
+        );
+    } else if json {
+        let output = weather_data.into_output(options.units);
+        if copy {
+            copy_output(&serde_json::to_string(&output)?)?;
+        }
+
+        print!(
+            "{}",
+            views::json_data_view(
+                output,
+                options.units,
+                options.json_envelope,
+                options.query.as_ref()
+            )?
+        );
+    } else if let Some(template) = &options.template {
+        let output = template::render_file(
+            template,
+            &TemplateContext {
+                address,
+                units: UnitSymbols::new(options.units),
+                weather: Some(weather_data.clone().into_output(options.units)),
+                forecast: None,
+                summary: None,
+            },
+        )?;
+        if copy {
+            copy_output(&output)?;
+        }
+
+        print!("{output}");
+    } else {
+        if copy {
+            copy_output(&views::plain_text_view(
+                &weather_data,
+                options.units,
+                options.pressure_unit,
+                options.visibility_unit,
+                options.language.as_deref(),
+            ))?;
+        }
+        let (temp, uv_index) = (weather_data.temp, weather_data.uv_index);
+
+        print!(
+            "{}",
+            views::table_terminal_view(
+                weather_data,
+                options.units,
+                options.pressure_unit,
+                options.visibility_unit,
+                options.language.as_deref(),
+                &config.color_scales,
+                views::terminal_width(),
+            )
+        );
+        if uv {
+            print!(
+                "{}",
+                views::uv_terminal_view(
+                    uv_index,
+                    options.language.as_deref(),
+                    &config.color_scales.uv_index,
+                )
+            );
+        }
+        if let Some(climate_normal) = climate_normal {
+            print!(
+                "{}",
+                views::normal_terminal_view(
+                    temp,
+                    &climate_normal,
+                    options.units,
+                    options.language.as_deref(),
+                )
+            );
+        }
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the original response of a selected provider to the weather data request and displays it in the
+/// terminal, skipping the normalization (and the unit conversion) of the weather data.
+///
+/// # Arguments
+///
+/// * `address` - The address for which weather information is requested.
+/// * `date` - An optional date parameter for historical weather data.
+/// * `options` - The provider and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching the response or if the provider doesn't support it.
+pub async fn get_raw_weather_data(
+    address: &str,
+    date: &Option<String>,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    check_date(&options.provider, date)?;
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let response = with_spinner(weather_api.get_raw_weather_data(&query, date)).await?;
+
+    print!(
+        "{}",
+        views::raw_json_terminal_view(&response, options.query.as_ref())?
+    );
+
+    Ok(())
+}
+
+/// Fetches the weather forecast from a selected provider and displays it in the terminal.
+///
+/// # Arguments
+///
+/// * `address` - The address for which the weather forecast is requested.
+/// * `forecast_options` - The number of days and the flags selecting what is displayed.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying the weather forecast.
+pub async fn get_forecast_info(
+    address: &str,
+    forecast_options: &ForecastOptions,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let ForecastOptions {
+        days,
+        hourly,
+        summary,
+        json,
+    } = *forecast_options;
+    options.provider.check_days(days)?;
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let forecast = with_spinner(weather_api.get_forecast(&query, days)).await?;
+
+    let summary_line = forecast
+        .first()
+        .map(|day| summary::summarize(day, options.units, Utc::now()));
+    let forecast = forecast.into_units(options.units);
+
+    if json && summary {
+        print!(
+            "{}",
+            views::json_data_view(
+                summary_line,
+                options.units,
+                options.json_envelope,
+                options.query.as_ref()
+            )?
+        );
+    } else if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                forecast.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else if let Some(template) = &options.template {
+        print!(
+            "{}",
+            template::render_file(
+                template,
+                &TemplateContext {
+                    address,
+                    units: UnitSymbols::new(options.units),
+                    weather: None,
+                    forecast: Some(forecast.into_output(options.units)),
+                    summary: summary_line.as_deref(),
+                },
+            )?
+        );
+    } else {
+        if let Some(summary_line) = summary_line {
+            print!("{}", views::summary_terminal_view(&summary_line));
+        }
+        if !summary {
+            print!(
+                "{}",
+                views::forecast_table_terminal_view(
+                    forecast,
+                    hourly,
+                    options.units,
+                    options.language.as_deref(),
+                )
+            );
+        }
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches marine weather data (tides, swell and water temperature) from a selected provider and displays it in the terminal.
+///
+/// # Arguments
+///
+/// * `address` - The coastal address for which marine data is requested.
+/// * `days` - The number of days, starting from today.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying marine data.
+pub async fn get_marine_info(
+    address: &str,
+    days: u8,
+    json: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    options.provider.check_days(days)?;
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let marine_data = with_spinner(weather_api.get_marine_data(&query, days))
+        .await?
+        .into_units(options.units);
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                marine_data.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::marine_table_terminal_view(
+                marine_data,
+                options.units,
+                options.language.as_deref()
+            )
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the snow report (daily snowfall and temperatures) from a selected provider and displays it in the terminal.
+///
+/// # Arguments
+///
+/// * `resort` - The ski resort (or any address) for which the snow report is requested.
+/// * `days` - The number of days, starting from today.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying the snow report.
+pub async fn get_snow_report(
+    resort: &str,
+    days: u8,
+    json: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    options.provider.check_days(days)?;
+    let (weather_api, query) = resolve_service(resort, options, &mut config).await?;
+    let snow_report = with_spinner(weather_api.get_snow_report(&query, days))
+        .await?
+        .into_units(options.units);
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                snow_report.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::snow_table_terminal_view(
+                snow_report,
+                options.units,
+                options.language.as_deref()
+            )
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the observation stations near an address from a selected provider and displays them in the terminal,
+/// nearest first.
+///
+/// # Arguments
+///
+/// * `address` - The address near which stations are listed.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying the stations.
+pub async fn list_stations(
+    address: &str,
+    json: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let mut stations = with_spinner(weather_api.get_stations(&query))
+        .await?
+        .into_units(options.units);
+    stations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                stations,
+                options.units,
+                options.json_envelope,
+                options.query.as_ref()
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::stations_table_terminal_view(
+                stations,
+                options.units,
+                options.language.as_deref()
+            )
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the minutely precipitation forecast of the next hour from a selected provider and displays it in the
+/// terminal as a timeline with a verdict.
+///
+/// # Arguments
+///
+/// * `address` - The address for which the nowcast is requested.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying the nowcast.
+pub async fn get_nowcast(
+    address: &str,
+    json: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let nowcast = with_spinner(weather_api.get_nowcast(&query)).await?;
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                nowcast.into_units(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        let minutes = nowcast::minutes(&nowcast);
+        print!(
+            "{}",
+            views::nowcast_terminal_view(&nowcast::timeline(&minutes), &nowcast::verdict(&minutes))
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the forecast of every stop of a trip on its date from a selected provider and displays the itinerary
+/// in the terminal.
+///
+/// Forecasts of the stops are fetched concurrently; with the NDJSON output every stop is printed as soon as its
+/// forecast (and the forecasts of the previous stops) arrives.
+///
+/// # Arguments
+///
+/// * `stops` - The stops (addresses) of the trip.
+/// * `dates` - The dates of the stops in the `YYYY-MM-DD` format; the last date applies to the remaining stops
+///   and today is used if there are no dates.
+/// * `output` - The output format.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching and displaying the trip forecast.
+pub async fn get_trip_forecast(
+    stops: &[String],
+    dates: &[String],
+    output: OutputFormat,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let itinerary = trip_itinerary(stops, dates, Local::now().date_naive())?;
+    for (_, _, days) in &itinerary {
+        options.provider.check_days(*days)?;
+    }
+
+    let progress = BatchProgress::new(itinerary.len(), batch_progress_visible(output))?;
+    let progress = &progress;
+
+    let weather_api = create_weather_api(options, &mut config)?;
+    let weather_api = &weather_api;
+    let stop_forecasts = stream::iter(itinerary).map(|(stop, date, days)| async move {
+        let date = date.format("%Y-%m-%d").to_string();
+        let _item = progress.start(format!("{stop} ({date})"));
+        let forecast = weather_api.get_forecast(stop, days).await?;
+        let day = forecast
+            .into_iter()
+            .find(|day| day.date == date)
+            .ok_or_else(|| {
+                WeatherApiError::DateOutOfRange(
+                    format!("no forecast for {stop} on {date}")
+                        .paint(Role::Warning)
+                        .to_string(),
+                )
+            })?;
+
+        Ok::<_, Error>(LocationForecast {
+            location: stop.to_owned(),
+            day: day.into_units(options.units),
+        })
+    });
+    let stop_forecasts = stream_or_collect(
+        stop_forecasts.buffered(options.concurrency),
+        output,
+        options,
+    )
+    .await;
+
+    progress.finish();
+
+    let Some(stop_forecasts) = stop_forecasts? else {
+        return Ok(());
+    };
+    if output == OutputFormat::Json {
+        print!(
+            "{}",
+            views::json_data_view(
+                stop_forecasts.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::locations_table_terminal_view(
+                stop_forecasts,
+                options.units,
+                options.language.as_deref(),
+            )
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the current weather and the number of active alerts of all saved locations (the addresses of the favorite
+/// queries) concurrently and displays them as a single overview table.
+///
+/// Locations whose weather can't be fetched are shown with the error instead of failing the whole dashboard.
+///
+/// # Arguments
+///
+/// * `tag` - An optional tag selecting a subset of the locations. Pass `None` for all locations.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `options` - The provider, units and language of the queries.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if there are no (tagged) saved locations or the provider isn't
+/// configured.
+pub async fn get_dashboard(
+    tag: Option<&str>,
+    json: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let locations = tagged_locations(&config, tag)?;
+
+    let weather_api = create_weather_api(options, &mut config)?;
+    let weather_api = &weather_api;
+    let rows = with_spinner(async {
+        let rows: Vec<DashboardRow> = stream::iter(locations)
+            .map(|(location, address)| async move {
+                let (weather_data, alerts) = futures::join!(
+                    weather_api.get_weather_data(&address, &None),
+                    weather_api.get_alerts(&address)
+                );
+
+                match weather_data {
+                    Ok(weather_data) => DashboardRow {
+                        location,
+                        address,
+                        weather: Some(weather_data),
+                        alerts: alerts.ok().map(|alerts| alerts.len()),
+                        error: None,
+                    },
+                    Err(err) => DashboardRow {
+                        location,
+                        address,
+                        weather: None,
+                        alerts: None,
+                        error: Some(err.to_string()),
+                    },
+                }
+            })
+            .buffered(options.concurrency)
+            .collect()
+            .await;
+
+        Ok(rows)
+    })
+    .await?;
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                rows.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::dashboard_table_terminal_view(&rows, options.units, options.language.as_deref())
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Fetches the current weather of the saved locations with a tag concurrently and displays the weather data table
+/// of every location under its name.
+///
+/// # Arguments
+///
+/// * `tag` - The tag selecting the locations (the addresses of the favorite queries).
+/// * `json` - A flag to indicate if the output format should be JSON (an array of the locations).
+/// * `options` - The provider, units and language of the queries.
+/// * `config` - The application's main configuration.
+///
+/// The current weather is also kept in the observations store for the 'stats' command.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if no saved location has the tag or when fetching the weather data.
+pub async fn get_tagged_weather(
+    tag: &str,
+    json: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let locations = tagged_locations(&config, Some(tag))?;
+
+    let weather_api = create_weather_api(options, &mut config)?;
+    let weather_api = &weather_api;
+    let locations_weather = with_spinner(async {
+        let locations_weather: Vec<LocationWeather> = stream::iter(locations)
+            .map(|(location, address)| async move {
+                let weather_data = weather_api.get_weather_data(&address, &None).await?;
+                check_plausibility(&address, &weather_data, options.reject_implausible)?;
+
+                Ok::<_, Error>(LocationWeather {
+                    location,
+                    address,
+                    weather: weather_data,
+                })
+            })
+            .buffered(options.concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(locations_weather)
+    })
+    .await?;
+
+    let fetched_at = Local::now().timestamp();
+    store_observations(
+        &locations_weather
+            .iter()
+            .map(|location| {
+                StoredObservation::new(&location.address, &location.weather, fetched_at)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                locations_weather.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        for LocationWeather {
+            location,
+            address,
+            weather,
+        } in locations_weather
+        {
+            println!("{} ({address})", location.paint(Role::Emphasis));
+            print!(
+                "{}",
+                views::table_terminal_view(
+                    weather,
+                    options.units,
+                    options.pressure_unit,
+                    options.visibility_unit,
+                    options.language.as_deref(),
+                    &config.color_scales,
+                    views::terminal_width(),
+                )
+            );
+        }
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Selects the saved locations (the addresses of the favorite queries) with a tag.
+///
+/// # Arguments
+///
+/// * `config` - The application's main configuration.
+/// * `tag` - An optional tag. Pass `None` for all locations.
+///
+/// # Returns
+///
+/// A `Result` containing the names of the favorites and their addresses or a `FavoriteError` if no location is
+/// selected.
+fn tagged_locations(config: &MainConfig, tag: Option<&str>) -> Result<Vec<(String, String)>> {
+    Ok(favorites::tagged(&config.favorites, tag)?
+        .into_iter()
+        .map(|(name, favorite)| (name.clone(), favorite.address.clone()))
+        .collect())
+}
+
+/// Fetches the weather along a route (GPX or GeoJSON track) from a selected provider and displays it in the terminal.
+///
+/// Points are sampled along the route at regular distances and the forecasted hour nearest to the estimated arrival
+/// time is shown for every point. Forecasts of the points are fetched concurrently (see `QueryOptions::concurrency`);
+/// with the NDJSON output every point is printed as soon as its forecast arrives.
+///
+/// # Arguments
+///
+/// * `track` - The path to the GPX or GeoJSON track file.
+/// * `start` - An optional departure time (`HH:MM` or `YYYY-MM-DD HH:MM`); now if not set.
+/// * `speed` - The average speed in kilometers per hour.
+/// * `every` - The distance between the sampled points in kilometers.
+/// * `output` - The output format.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when reading the track or fetching and displaying the weather.
+pub async fn get_route_weather(
+    track: &Path,
+    start: Option<&str>,
+    speed: f64,
+    every: f64,
+    output: OutputFormat,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let content = std::fs::read_to_string(track).map_err(|err| {
+        RouteError::Read(
+            err,
+            track.display().to_string().paint(Role::Warning).to_string(),
+        )
+    })?;
+    let samples = route::sample(&route::parse_track(&content)?, every);
+    let now = Local::now().naive_local();
+    let departure = route::departure_time(start, now)?;
+
+    let progress = BatchProgress::new(samples.len(), batch_progress_visible(output))?;
+    let progress = &progress;
+
+    let weather_api = create_weather_api(options, &mut config)?;
+    let weather_api = &weather_api;
+    let route_weather = stream::iter(samples)
+        .map(|sample| async move {
+            let arrival = route::arrival_time(departure, sample.distance, speed);
+            let _item = progress.start(format!(
+                "{:.1} km ({})",
+                sample.distance,
+                arrival.format("%Y-%m-%d %H:%M")
+            ));
+            let out_of_range = || {
+                WeatherApiError::DateOutOfRange(
+                    arrival
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                        .paint(Role::Warning)
+                        .to_string(),
+                )
+            };
+            let days = u8::try_from((arrival.date() - now.date()).num_days() + 1)
+                .ok()
+                .filter(|days| *days > 0)
+                .ok_or_else(out_of_range)?;
+
+            let forecast = weather_api
+                .get_forecast(&sample.point.to_query(), days)
+                .await?;
+            // the arrival time is local to the user, so it's compared with the forecast in UTC
+            let arrival_utc = arrival
+                .and_local_timezone(Local)
+                .earliest()
+                .ok_or_else(out_of_range)?
+                .with_timezone(&Utc);
+            let hour = route::nearest_hour(forecast, arrival_utc).ok_or_else(out_of_range)?;
+
+            Ok::<_, Error>(RouteWeather {
+                point: sample,
+                arrival: arrival.format("%Y-%m-%d %H:%M").to_string(),
+                forecast: hour.into_units(options.units),
+            })
+        })
+        .buffered(options.concurrency);
+    let route_weather: Result<Option<Vec<RouteWeather>>> =
+        stream_or_collect(route_weather, output, options).await;
+
+    progress.finish();
+
+    let Some(route_weather) = route_weather? else {
+        return Ok(());
+    };
+    if output == OutputFormat::Json {
+        print!(
+            "{}",
+            views::json_data_view(
+                route_weather.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::route_table_terminal_view(
+                route_weather,
+                options.units,
+                options.language.as_deref()
+            )
+        );
+        print_provenance(options);
+    }
+
+    Ok(())
+}
+
+/// Computes the moon phase calendar of a month and displays it in the terminal.
+///
+/// # Arguments
+///
+/// * `month` - An optional month in the `YYYY-MM` format; the current month if not set.
+/// * `json` - A flag to indicate if the output format should be JSON.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the month is invalid.
+pub fn get_moon_calendar(month: Option<&str>, json: bool) -> Result<()> {
+    let first_day = match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+            .map_err(|_| DateTimeError::Parse(month.paint(Role::Warning).to_string()))?,
+        None => Local::now().date_naive().with_day(1).unwrap_or_default(),
+    };
+    let calendar = astronomy::moon_calendar(first_day);
+
+    if json {
+        print!("{}", views::json_terminal_view(calendar)?);
+    } else {
+        print!(
+            "{}",
+            views::moon_calendar_terminal_view(first_day, calendar)
+        );
+    }
+
+    Ok(())
+}
+
+/// Exports the daily weather history of an address for a date range from a selected provider to an NDJSON file.
+///
+/// Days are fetched concurrently and written in chronological order, one JSON object per line. A checkpoint file
+/// next to the export file (see `export::checkpoint_path`) is updated after every written day and removed once the
+/// export is complete, so an interrupted export can be resumed without fetching the exported days again.
+/// Exported days are also kept in the observations store for the 'stats' command.
+///
+/// # Arguments
+///
+/// * `address` - The address for which the weather history is exported.
+/// * `from` - The first date of the range in the `YYYY-MM-DD` format.
+/// * `to` - An optional last date of the range in the `YYYY-MM-DD` format; yesterday if not set.
+/// * `output` - The path to the NDJSON file.
+/// * `resume` - A flag to indicate if an interrupted export should be resumed from its checkpoint.
+/// * `options` - The provider, units, language and concurrency of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching or writing the weather history.
+pub async fn export_history(
+    address: &str,
+    from: &str,
+    to: Option<&str>,
+    output: &Path,
+    resume: bool,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let from = export::parse_date(from)?;
+    let to = match to {
+        Some(to) => export::parse_date(to)?,
+        None => Local::now().date_naive() - Duration::days(1),
+    };
+    let checkpoint_path = export::checkpoint_path(output);
+    let mut checkpoint = ExportCheckpoint::new(address, from, to)?;
+    if resume {
+        let saved = export::load_checkpoint(&checkpoint_path)?;
+        if !saved.matches(&checkpoint) {
+            return Err(ExportError::CheckpointMismatch(
+                checkpoint_path
+                    .display()
+                    .to_string()
+                    .paint(Role::Warning)
+                    .to_string(),
+            )
+            .into());
+        }
+        checkpoint = saved;
+    }
+
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let query = query.as_str();
+    let weather_api = &weather_api;
+    let mut file = export::open_output(output, resume)?;
+    export::save_checkpoint(&checkpoint_path, &checkpoint)?;
+
+    let dates = checkpoint.remaining_dates();
+    let progress = BatchProgress::new(dates.len(), progress::is_enabled())?;
+    let progress = &progress;
+    let days = stream::iter(dates)
+        .map(|date| async move {
+            let date = date.format("%Y-%m-%d").to_string();
+            let _item = progress.start(format!("{address} ({date})"));
+            let weather = weather_api
+                .get_weather_data(query, &Some(format!("{date} 12:00")))
+                .await?;
+            check_plausibility(address, &weather, options.reject_implausible)?;
+            let noon = export::parse_date(&date)?
+                .and_hms_opt(12, 0, 0)
+                .and_then(|noon| noon.and_local_timezone(Local).earliest());
+            store_observations(&[StoredObservation::new(
+                address,
+                &weather,
+                noon.map_or_else(|| Local::now().timestamp(), |noon| noon.timestamp()),
+            )]);
+
+            Ok::<_, Error>(ExportedDay {
+                date,
+                weather: weather.into_output(options.units),
+            })
+        })
+        .buffered(options.concurrency);
+    let exported = days
+        .try_fold(0, |exported, day| {
+            let result = export::append_day(&mut file, output, &day).and_then(|_| {
+                checkpoint.last_exported = export::parse_date(&day.date).ok();
+                export::save_checkpoint(&checkpoint_path, &checkpoint)
+            });
+
+            async move { result.map(|_| exported + 1).map_err(Error::from) }
+        })
+        .await;
+
+    progress.finish();
+
+    let exported = exported.map_err(|err| {
+        err.context(format!(
+            "The export was interrupted; run the same command with '--resume' to continue after {}",
+            checkpoint
+                .last_exported
+                .map_or_else(|| "the start".to_owned(), |date| date.to_string())
+                .paint(Role::Warning)
+        ))
+    })?;
+    std::fs::remove_file(&checkpoint_path)
+        .map_err(|err| ExportError::Io(err, checkpoint_path.display().to_string()))?;
+
+    interaction::inform(format!(
+        "Exported {} days to '{}'",
+        exported.to_string().paint(Role::Success),
+        output.display().to_string().paint(Role::Success)
+    ));
+
+    Ok(())
+}
+
+/// Computes statistics of the locally stored observations of an address over a period and displays them
+/// in the terminal.
+///
+/// # Arguments
+///
+/// * `address` - The address for which the statistics are requested.
+/// * `period` - The period ending now (e.g. `30d` or `4w`).
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `options` - The units and language of the output.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the period is invalid, the store can't be read or there are
+/// no observations in the period.
+pub fn get_stats(address: &str, period: &str, json: bool, options: &QueryOptions) -> Result<()> {
+    let period = stats::parse_period(period)?;
+    let since = (Local::now() - period).timestamp();
+    let observations = store::load(
+        &paths::file(AppDir::Data, OBSERVATIONS_STORE_NAME)?,
+        address,
+        since,
+    )?;
+    let statistics = Statistics::compute(&observations, &Local)
+        .ok_or_else(|| StatsError::NoObservations(address.paint(Role::Warning).to_string()))?
+        .into_units(options.units);
+
+    if json {
+        print!(
+            "{}",
+            views::json_data_view(
+                statistics.into_output(options.units),
+                options.units,
+                options.json_envelope,
+                options.query.as_ref(),
+            )?
+        );
+    } else {
+        print!(
+            "{}",
+            views::stats_table_terminal_view(
+                address,
+                statistics,
+                options.units,
+                options.language.as_deref(),
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// Adds a query of the `get` command to the history of recent queries; failures to write the history are ignored,
+/// so they don't fail the query.
+///
+/// # Arguments
+///
+/// * `address` - The queried address.
+/// * `provider` - The weather data provider of the query.
+/// * `limit` - The maximum number of queries kept in the history.
+pub fn record_query(address: &str, provider: &Provider, limit: usize) {
+    let Ok(path) = paths::file(AppDir::State, RECENT_QUERIES_NAME) else {
+        return;
+    };
+    let mut recent_queries: RecentQueries = confy::load_path(&path).unwrap_or_default();
+
+    recent_queries.push(
+        RecentQuery {
+            address: address.to_owned(),
+            queried_at: Local::now().timestamp(),
+            provider: provider.clone(),
+        },
+        limit,
+    );
+    let _ = confy::store_path(&path, recent_queries);
+}
+
+/// Gets the most recent query from the history, e.g. to repeat it with `get --last`.
+///
+/// # Returns
+///
+/// A `Result` containing the most recent query or a `HistoryError` if there are no recent queries.
+pub fn last_query() -> Result<RecentQuery> {
+    let recent_queries: RecentQueries =
+        confy::load_path(paths::file(AppDir::State, RECENT_QUERIES_NAME)?).unwrap_or_default();
+
+    Ok(recent_queries.last()?.clone())
+}
+
+/// Displays the recent queries of the `get` command, most recent first.
+///
+/// # Arguments
+///
+/// * `json` - A flag to indicate if the output format should be JSON.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the state directory can't be determined.
+pub fn list_recent_queries(json: bool) -> Result<()> {
+    let recent_queries: RecentQueries =
+        confy::load_path(paths::file(AppDir::State, RECENT_QUERIES_NAME)?).unwrap_or_default();
+
+    if json {
+        print!("{}", views::json_terminal_view(&recent_queries.queries)?);
+    } else {
+        print!(
+            "{}",
+            views::recent_queries_terminal_view(&recent_queries.queries)
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetches the current weather and the forecast of an address and renders them as a shareable report.
+///
+/// # Arguments
+///
+/// * `address` - The address for which the report is generated.
+/// * `days` - The number of forecasted days, starting from today.
+/// * `format` - The format of the report.
+/// * `output` - The path to the report file; the report is printed to the standard output if `None`.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching the weather data or writing the report.
+pub async fn generate_report(
+    address: &str,
+    days: u8,
+    format: ReportFormat,
+    output: Option<&Path>,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    options.provider.check_days(days)?;
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let (weather_data, forecast) = with_spinner(async {
+        futures::try_join!(
+            weather_api.get_weather_data(&query, &None),
+            weather_api.get_forecast(&query, days)
+        )
+    })
+    .await?;
+    check_plausibility(address, &weather_data, options.reject_implausible)?;
+
+    let report = Report {
+        address: address.to_owned(),
+        weather: weather_data,
+        forecast: forecast.into_units(options.units),
+        generated_at: Local::now().naive_local(),
+    }
+    .render(
+        format,
+        options.units,
+        options.pressure_unit,
+        options.visibility_unit,
+        options.language.as_deref(),
+    );
+
+    match output {
+        Some(output) => {
+            std::fs::write(output, report)
+                .map_err(|err| ReportError::Io(err, output.display().to_string()))?;
+
+            interaction::inform(format!(
+                "Report was written to '{}'",
+                output.display().to_string().paint(Role::Success)
+            ));
+        }
+        None => print!("{report}"),
+    }
+
+    Ok(())
+}
+
+/// Fetches the current weather and the forecast of an address and renders them as a compact weather card.
+///
+/// # Arguments
+///
+/// * `address` - The address for which the card is rendered.
+/// * `alias` - The name of the location shown on the card; the address if `None`.
+/// * `days` - The number of days in the forecast strip, starting from today.
+/// * `output` - The path to the card file; its extension selects the image format (see `CardFormat`).
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching the weather data or writing the card.
+pub async fn generate_card(
+    address: &str,
+    alias: Option<&str>,
+    days: u8,
+    output: &Path,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    options.provider.check_days(days)?;
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let (weather_data, forecast) = with_spinner(async {
+        let weather_data = weather_api.get_weather_data(&query, &None).await?;
+        check_plausibility(address, &weather_data, options.reject_implausible)?;
+        let forecast = if days > 0 {
+            weather_api.get_forecast(&query, days).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok((weather_data, forecast))
+    })
+    .await?;
+
+    let svg = card::render_card(
+        alias.unwrap_or(address),
+        &weather_data,
+        &forecast.into_units(options.units),
+        options.units,
+    );
+    card::write_card(&svg, output, CardFormat::from_path(output)).await?;
+
+    interaction::inform(format!(
+        "Card was written to '{}'",
+        output.display().to_string().paint(Role::Success)
+    ));
+
+    Ok(())
+}
+
+/// Downloads the latest precipitation radar tiles around an address and prints a coarse map of them in the
+/// terminal or saves them as a stitched PNG map.
+///
+/// # Arguments
+///
+/// * `address` - The address on which the map is centered.
+/// * `zoom` - The zoom level of the radar tiles.
+/// * `output` - The path to the PNG file; the map is printed in the terminal if `None`.
+/// * `options` - The country and the location choice of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when locating the address, downloading the tiles or writing the map.
+pub async fn get_radar_map(
+    address: &str,
+    zoom: u8,
+    output: Option<&Path>,
+    options: &QueryOptions,
+    config: MainConfig,
+) -> Result<()> {
+    let query = resolve_address(address, false, options, &config).await?;
+    let (latitude, longitude) = match maps::parse_coordinates(&query) {
+        Some(coordinates) => coordinates,
+        None => {
+            let location = ClimateArchive::new(http_client(&config.http)?)
+                .locate(&query)
+                .await?;
+            (location.latitude, location.longitude)
+        }
+    };
+    let radar_map = with_spinner(
+        RainViewer::new(http_client(&config.http)?).radar_map(latitude, longitude, zoom),
+    )
+    .await?;
+
+    let Some(output) = output else {
+        print!(
+            "{}",
+            views::radar_terminal_view(&radar_map, views::terminal_width())
+        );
+        return Ok(());
+    };
+    maps::write_radar_map(&radar_map, output)?;
+
+    let time = DateTime::from_timestamp(radar_map.time, 0)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    interaction::inform(format!(
+        "Radar map of {time} was written to '{}'",
+        output.display().to_string().paint(Role::Success)
+    ));
+    println!("{}", maps::RAINVIEWER_ATTRIBUTION.paint(Role::Muted));
+
+    Ok(())
+}
+
+/// Fetches the current weather of an address and prints it as MQTT messages, one JSON object per line, for an MQTT
+/// client to publish (e.g. `mosquitto_pub`).
+///
+/// # Arguments
+///
+/// * `address` - The address for which the weather is published.
+/// * `alias` - The name of the location used in topics and device names; the address if `None`.
+/// * `topic` - The base topic of the state messages.
+/// * `discovery_prefix` - The discovery prefix of Home Assistant; Discovery config messages are printed before
+///   the state message if it's set.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching the weather data.
+pub async fn publish_mqtt(
+    address: &str,
+    alias: Option<&str>,
+    topic: &str,
+    discovery_prefix: Option<&str>,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    let weather_data = weather_api.get_weather_data(&query, &None).await?;
+    check_plausibility(address, &weather_data, options.reject_implausible)?;
+    store_observations(&[StoredObservation::new(
+        address,
+        &weather_data,
+        Local::now().timestamp(),
+    )]);
+
+    let name = alias.unwrap_or(address);
+    if let Some(discovery_prefix) = discovery_prefix {
+        for message in mqtt::discovery_messages(discovery_prefix, topic, name, options.units) {
+            print!("{}", views::json_terminal_view(message)?);
+        }
+    }
+    print!(
+        "{}",
+        views::json_terminal_view(mqtt::state_message(
+            topic,
+            &mqtt::object_id(name),
+            weather_data,
+            options.units,
+        )?)?
+    );
+
+    Ok(())
+}
+
+/// Fetches the current weather and today's forecast of an address and sends them as an email digest, rendered from
+/// the report templates (HTML with a plain text alternative).
+///
+/// # Arguments
+///
+/// * `address` - The address for which the digest is sent.
+/// * `email` - The recipient's email address.
+/// * `smtp` - The connection URL of the SMTP server; `smtp_url` of the configuration if `None`.
+/// * `from` - The sender's email address; `email_from` of the configuration or the recipient if `None`.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching the weather data or sending the digest.
+pub async fn send_digest(
+    address: &str,
+    email: &str,
+    smtp: Option<String>,
+    from: Option<String>,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let Some(smtp_url) = smtp.or_else(|| config.smtp_url.clone()) else {
+        return Err(ConfigError::SmtpUrl(
+            paths::config_file_hint().paint(Role::Warning).to_string(),
+        )
+        .into());
+    };
+    let from = from
+        .or_else(|| config.email_from.clone())
+        .unwrap_or_else(|| email.to_owned());
+
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    with_spinner(async {
+        let (weather_data, forecast) = futures::try_join!(
+            weather_api.get_weather_data(&query, &None),
+            weather_api.get_forecast(&query, 1)
+        )?;
+        check_plausibility(address, &weather_data, options.reject_implausible)?;
+
+        let subject = digest::subject(address, forecast.first(), options.units);
+        let report = Report {
+            address: address.to_owned(),
+            weather: weather_data,
+            forecast: forecast.into_units(options.units),
+            generated_at: Local::now().naive_local(),
+        };
+        let [text, html] = [ReportFormat::Md, ReportFormat::Html].map(|format| {
+            report.render(
+                format,
+                options.units,
+                options.pressure_unit,
+                options.visibility_unit,
+                options.language.as_deref(),
+            )
+        });
+        digest::send(
+            &smtp_url,
+            digest::compose(&from, email, &subject, text, html)?,
+        )
+        .await?;
+
+        Ok(())
+    })
+    .await?;
+
+    interaction::inform(format!(
+        "Digest was sent to '{}'",
+        email.paint(Role::Success)
+    ));
+
+    Ok(())
+}
+
+/// Fetches the current weather and the forecast of an address and posts them as a message to Slack, Discord or
+/// Telegram chats (e.g. from a daily cron job).
+///
+/// # Arguments
+///
+/// * `address` - The address for which the weather is posted.
+/// * `post_options` - The alias of the location, the number of forecasted days and the chats to post to.
+/// * `options` - The provider, units and language of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error when fetching the weather data or posting the message.
+pub async fn post_weather(
+    address: &str,
+    post_options: PostOptions,
+    options: &QueryOptions,
+    mut config: MainConfig,
+) -> Result<()> {
+    let PostOptions {
+        alias,
+        days,
+        slack_webhook,
+        discord_webhook,
+        telegram_chat,
+    } = post_options;
+    let alias = alias.as_deref();
+    options.provider.check_days(days)?;
+    let telegram = match (telegram_chat, &config.telegram_bot_token) {
+        (Some(chat_id), Some(token)) => Some(ChatTarget::Telegram {
+            token: token.clone(),
+            chat_id,
+        }),
+        (Some(_), None) => {
+            return Err(ConfigError::TelegramToken(
+                paths::config_file_hint().paint(Role::Warning).to_string(),
+            )
+            .into());
+        }
+        (None, _) => None,
+    };
+    let targets: Vec<ChatTarget> = [
+        slack_webhook.map(|url| ChatTarget::Slack(Secret::new(url))),
+        discord_webhook.map(|url| ChatTarget::Discord(Secret::new(url))),
+        telegram,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let (weather_api, query) = resolve_service(address, options, &mut config).await?;
+    with_spinner(async {
+        let weather_data = weather_api.get_weather_data(&query, &None).await?;
+        check_plausibility(address, &weather_data, options.reject_implausible)?;
+        let forecast = if days > 0 {
+            weather_api.get_forecast(&query, days).await?
+        } else {
+            Vec::new()
+        };
+        let message = ChatMessage::new(
+            alias.unwrap_or(address),
+            &weather_data,
+            &forecast.into_units(options.units),
+            options.units,
+        );
+
+        let client = http_client(&config.http)?;
+        for target in &targets {
+            chat::post_message(&client, target, &message).await?;
+        }
+
+        Ok(())
+    })
+    .await?;
+
+    for target in &targets {
+        interaction::inform(format!(
+            "Weather was posted to {}",
+            target.name().paint(Role::Success)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks the health of providers by requesting the current weather of `ping::PING_ADDRESS` from all of them
+/// concurrently and displays the HTTP status and the latency of every provider.
+///
+/// # Arguments
+///
+/// * `providers` - The providers to check.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `parsing` - The parsing mode of provider responses.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `PingError` if any provider failed the health check.
+pub async fn ping_providers(
+    providers: &[Provider],
+    json: bool,
+    parsing: ParsingMode,
+    mut config: MainConfig,
+) -> Result<()> {
+    let results = with_spinner(async {
+        let checks: Vec<_> = providers
+            .iter()
+            .map(|provider| {
+                let probe = StatusProbe::default();
+                let pipeline = request_pipeline(parsing, &config).with(probe.clone());
+                let weather_api =
+                    create_weather_api_with_pipeline(provider, None, &mut config, pipeline);
+
+                (provider, probe, weather_api)
+            })
+            .collect();
+        let results = futures::future::join_all(checks.into_iter().map(
+            |(provider, probe, weather_api)| async move {
+                let started = Instant::now();
+                let result = match weather_api {
+                    Ok(weather_api) => weather_api
+                        .get_weather_data(ping::PING_ADDRESS, &None)
+                        .await
+                        .map(|_| ()),
+                    Err(err) => Err(err),
+                };
+
+                PingResult {
+                    provider: provider.to_string(),
+                    status: probe.status(),
+                    latency_ms: started.elapsed().as_millis() as u64,
+                    error: result.err().map(|err| err.to_string()),
+                }
+            },
+        ))
+        .await;
+
+        Ok(results)
+    })
+    .await?;
+
+    if json {
+        print!("{}", views::json_terminal_view(&results)?);
+    } else {
+        print!("{}", views::ping_table_terminal_view(&results));
+    }
+
+    Ok(ping::check_results(&results)?)
+}
+
+/// Measures the providers by requesting the current weather of an address from every provider several times
+/// and displays the latency distribution, the payload size and the parse time of every provider, the fastest
+/// provider first.
+///
+/// Providers are measured concurrently and their runs one after another; with '--replay', the saved responses
+/// are parsed without sending requests, so parse times can be compared offline.
+///
+/// # Arguments
+///
+/// * `providers` - The providers to measure.
+/// * `address` - The address whose current weather is requested.
+/// * `runs` - The number of requests sent to every provider.
+/// * `json` - A flag to indicate if the output format should be JSON.
+/// * `parsing` - The parsing mode of provider responses.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `BenchError` if any provider failed every run.
+pub async fn bench_providers(
+    providers: &[Provider],
+    address: &str,
+    runs: u8,
+    json: bool,
+    parsing: ParsingMode,
+    mut config: MainConfig,
+) -> Result<()> {
+    let progress = BatchProgress::new(providers.len(), progress::is_enabled())?;
+
+    let benches: Vec<_> = providers
+        .iter()
+        .map(|provider| {
+            let probe = TransferProbe::default();
+            let pipeline = with_fixture_mode(base_pipeline(parsing).with(probe.clone()), &config);
+            let weather_api =
+                create_weather_api_with_pipeline(provider, None, &mut config, pipeline);
+
+            (provider, probe, weather_api)
+        })
+        .collect();
+    let mut results =
+        futures::future::join_all(benches.into_iter().map(|(provider, probe, weather_api)| {
+            let progress = &progress;
+            async move {
+                let _item = progress.start(provider.to_string());
+                let mut bench_runs = Vec::new();
+                match weather_api {
+                    Ok(weather_api) => {
+                        for _ in 0..runs {
+                            let started = Instant::now();
+                            let result = weather_api.get_weather_data(address, &None).await;
+                            let latency = started.elapsed();
+                            let (network, payload_bytes) = probe.take();
+
+                            bench_runs.push(
+                                result
+                                    .map(|_| BenchRun {
+                                        latency,
+                                        network,
+                                        payload_bytes,
+                                    })
+                                    .map_err(|err| err.to_string()),
+                            );
+                        }
+                    }
+                    Err(err) => bench_runs.push(Err(err.to_string())),
+                }
+
+                BenchResult::new(provider.to_string(), &bench_runs)
+            }
+        }))
+        .await;
+
+    progress.finish();
+    bench::rank(&mut results);
+
+    if json {
+        print!("{}", views::json_terminal_view(&results)?);
+    } else {
+        print!("{}", views::bench_table_terminal_view(&results));
+    }
+
+    Ok(bench::check_results(&results)?)
+}
+
+/// Copies the output to the system clipboard and confirms it on the standard error, so the standard output stays
+/// unchanged for pipes.
+///
+/// # Arguments
+///
+/// * `output` - The output to copy.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the clipboard can't be accessed.
+fn copy_output(output: &str) -> Result<()> {
+    clipboard::copy(output)?;
+    if !interaction::current().quiet {
+        eprintln!("{}", "Copied to the clipboard".paint(Role::Muted));
+    }
+
+    Ok(())
+}
+
+/// Checks weather data for physically implausible values (e.g. humidity above 100 %) and warns about them on
+/// the standard error or rejects the data ('--reject-implausible').
+///
+/// # Arguments
+///
+/// * `address` - The address of the weather data (shown in warnings).
+/// * `weather_data` - The weather data in metric units.
+/// * `reject` - A flag to indicate if weather data with implausible values should be rejected.
+///
+/// # Returns
+///
+/// A `Result` indicating success or the `WeatherDataError::Implausible` error if the data is rejected.
+fn check_plausibility(address: &str, weather_data: &WeatherData, reject: bool) -> Result<()> {
+    let implausibilities = validation::validate(weather_data);
+    if implausibilities.is_empty() {
+        return Ok(());
+    }
+
+    if reject {
+        let values = implausibilities
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(WeatherDataError::Implausible(values.paint(Role::Warning).to_string()).into());
+    }
+
+    for implausibility in implausibilities {
+        eprintln!(
+            "{} implausible weather data for '{address}': {implausibility}",
+            "Warning:".paint(Role::Warning)
+        );
+    }
+
+    Ok(())
+}
+
+/// Keeps observations in the observations store for the 'stats' command.
+///
+/// Storing is best effort: a failure (e.g. a read-only data directory) doesn't fail the command that fetched
+/// the observations.
+///
+/// # Arguments
+///
+/// * `observations` - The observations in metric units.
+fn store_observations(observations: &[StoredObservation]) {
+    if let Ok(path) = paths::file(AppDir::Data, OBSERVATIONS_STORE_NAME) {
+        let _ = store::append(&path, observations);
+    }
+}
+
+/// Checks whether the progress of a batch operation should be displayed; it's hidden for the NDJSON output,
+/// which is printed while fetching, and if the output isn't a terminal.
+fn batch_progress_visible(output: OutputFormat) -> bool {
+    output != OutputFormat::Ndjson && progress::is_enabled()
+}
+
+/// Prints results as NDJSON as they arrive, or collects them for the table or JSON output.
+///
+/// # Arguments
+///
+/// * `results` - The stream of results.
+/// * `output` - The output format.
+/// * `options` - The units and the JSON envelope of the NDJSON output.
+///
+/// # Returns
+///
+/// A `Result` containing the collected results (`None` if they were printed as NDJSON) or the first error.
+async fn stream_or_collect<T: IntoOutput>(
+    results: impl Stream<Item = Result<T>>,
+    output: OutputFormat,
+    options: &QueryOptions,
+) -> Result<Option<Vec<T>>> {
+    if output == OutputFormat::Ndjson {
+        results
+            .try_for_each(|result| async move {
+                print!(
+                    "{}",
+                    views::json_data_view(
+                        result.into_output(options.units),
+                        options.units,
+                        options.json_envelope,
+                        options.query.as_ref()
+                    )?
+                );
+                Ok(())
+            })
+            .await?;
+
+        Ok(None)
+    } else {
+        results.try_collect().await.map(Some)
+    }
+}
+
+/// Pairs the stops of a trip with their dates and the number of forecasted days needed to reach the dates.
+///
+/// # Arguments
+///
+/// * `stops` - The stops (addresses) of the trip.
+/// * `dates` - The dates of the stops in the `YYYY-MM-DD` format; the last date applies to the remaining stops.
+/// * `today` - The current date used if there are no dates.
+///
+/// # Returns
+///
+/// A `Result` containing the stops with their dates and numbers of forecasted days, or an error if a date is invalid
+/// or in the past.
+fn trip_itinerary<'a>(
+    stops: &'a [String],
+    dates: &[String],
+    today: NaiveDate,
+) -> Result<Vec<(&'a str, NaiveDate, u8)>> {
+    let dates = dates
+        .iter()
+        .map(|date| {
+            NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| DateTimeError::Parse(date.paint(Role::Warning).to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    stops
+        .iter()
+        .enumerate()
+        .map(|(index, stop)| {
+            let date = dates.get(index).or(dates.last()).copied().unwrap_or(today);
+            let days = u8::try_from((date - today).num_days() + 1)
+                .ok()
+                .filter(|days| *days > 0)
+                .ok_or_else(|| {
+                    WeatherApiError::DateOutOfRange(
+                        date.to_string().paint(Role::Warning).to_string(),
+                    )
+                })?;
+
+            Ok((stop.trim(), date, days))
+        })
+        .collect()
+}
+
+/// Checks that a provider has the weather of a date in its history or forecast before the weather is requested.
+///
+/// # Arguments
+///
+/// * `provider` - The weather data provider.
+/// * `date` - An optional date (e.g. `2023-10-11 18:00` or `tomorrow 18:00`); nothing is checked if `None`.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the date can't be parsed or is outside of the range of the provider.
+fn check_date(provider: &Provider, date: &Option<String>) -> Result<()> {
+    if date.is_some() {
+        provider.check_date(requested_date(date)?, Local::now().date_naive())?;
+    }
+
+    Ok(())
+}
+
+/// Gets the local calendar day of a requested date.
+///
+/// # Arguments
+///
+/// * `date` - An optional date (e.g. `2023-01-15` or `tomorrow`).
+///
+/// # Returns
+///
+/// A `Result` containing the day of the date (today if no date is given) or an error if the date isn't recognized.
+fn requested_date(date: &Option<String>) -> Result<NaiveDate> {
+    Ok(match date {
+        Some(date) => dates::parse_datetime(date)?
+            .with_timezone(&Local)
+            .date_naive(),
+        None => Local::now().date_naive(),
+    })
+}
+
+/// Creates the weather API service of the provider of a query from its configuration.
+///
+/// # Arguments
+///
+/// * `options` - The provider, language and parsing mode of the query.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` containing the weather API service or an error if the provider is not available or not configured.
+fn create_weather_api(
+    options: &QueryOptions,
+    config: &mut MainConfig,
+) -> Result<Box<dyn WeatherApi + Send + Sync>> {
+    let pipeline = cached_pipeline(options.parsing, config);
 
-/// Handles the 'provider-list' command to display the status of weather data providers.
+    create_weather_api_with_pipeline(
+        &options.provider,
+        options.language.clone(),
+        config,
+        pipeline,
+    )
+}
+
+/// Creates the weather API service of a provider from its configuration with the given request pipeline.
 ///
-/// This function displays the status of weather data providers, indicating whether each provider
-/// is not implemented, configured, or not configured. It also shows which provider is currently selected.
+/// The extra query parameters set on the command line are added to the options of the provider.
 ///
 /// # Arguments
 ///
-/// * `selected_provider` - The selected weather data provider.
-/// * `configured_providers` - A list of configured weather data providers.
-/// * `not_implemented_providers` - A list of weather data providers that are not implemented.
-pub fn provider_list_handler(
-    selected_provider: &Provider,
-    configured_providers: Vec<&Provider>,
-    not_implemented_providers: Vec<&Provider>,
-) {
-    println!("Current status of providers: ");
+/// * `provider` - The selected weather data provider.
+/// * `language` - An optional language of condition descriptions.
+/// * `config` - The application's main configuration.
+/// * `pipeline` - The request pipeline of the service (unused by plugins).
+///
+/// # Returns
+///
+/// A `Result` containing the weather API service or an error if the provider is not available or not configured.
+fn create_weather_api_with_pipeline(
+    provider: &Provider,
+    language: Option<String>,
+    config: &mut MainConfig,
+    pipeline: Pipeline,
+) -> Result<Box<dyn WeatherApi + Send + Sync>> {
+    let client = http_client(&config.http)?;
+    let mut provider_config = std::mem::take(config.provider_config_mut(provider));
+    let keys = provider_config.keys();
+    provider_config
+        .options
+        .extend(config.provider_params.clone());
+    let weather_api: Box<dyn WeatherApi + Send + Sync> = match provider {
+        Provider::Plugin(name) => {
+            let path = plugins::find_plugin(name).ok_or(ProviderError::ProviderNotFound)?;
+            if config.fixture_mode == Some(FixtureMode::DryRun) {
+                println!("Provider: {name}\nPlugin:   {}", path.display());
+                return Err(FixtureError::DryRun.into());
+            }
 
-    for provider in Provider::get_all_variants() {
-        let provider_str = if not_implemented_providers.contains(&&provider) {
-            format!("{} (not supported)", provider).red()
-        } else if configured_providers.contains(&&provider) {
-            format!("{} (configured)", provider).green()
-        } else {
-            format!("{} (not configured)", provider).yellow()
-        };
+            Box::new(
+                PluginService::new(name.clone(), path, provider_config).with_language(language),
+            )
+        }
+        _ => {
+            let entry = provider
+                .entry()
+                .ok_or(ProviderError::ProviderNotImplemented)?;
+            let factory = entry.factory.ok_or(ProviderError::ProviderNotImplemented)?;
+            let pipeline = if keys.len() > 1 {
+                pipeline.with(KeyRotation::new(keys.clone(), provider_config.key_rotation))
+            } else {
+                pipeline
+            };
 
-        if &provider == selected_provider {
-            println!("*{} (selected)", provider_str);
-        } else {
-            println!(" {}", provider_str);
+            factory(
+                client,
+                provider_config
+                    .base_url()
+                    .unwrap_or_else(|| entry.default_url.to_owned()),
+                keys.into_iter().next().ok_or(ConfigError::ProviderConfig(
+                    provider.to_string().paint(Role::Warning).to_string(),
+                    paths::config_file_hint().paint(Role::Warning).to_string(),
+                    "weather-rs configure <PROVIDER> <API_KEY> [-u <URL>]"
+                        .paint(Role::Warning)
+                        .to_string(),
+                ))?,
+                language,
+                provider_config.options,
+                pipeline,
+            )?
         }
+    };
+
+    Ok(weather_api)
+}
+
+/// Asynchronously looks up locations with spellings similar to an unknown address with the Open-Meteo geocoder,
+/// which matches names fuzzily.
+///
+/// # Arguments
+///
+/// * `address` - The unknown address.
+/// * `count` - The maximum number of locations.
+/// * `http` - The identification of the geocoder requests.
+///
+/// # Returns
+///
+/// The names of the locations with their regions and countries, best matches first; empty if nothing is found or
+/// the geocoder can't be reached.
+pub async fn similar_locations(address: &str, count: u8, http: &HttpConfig) -> Vec<String> {
+    let Ok(client) = http_client(http) else {
+        return Vec::new();
+    };
+
+    ClimateArchive::new(client)
+        .search(address, None, count)
+        .await
+        .map(|locations| locations.iter().map(ToString::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Creates the request pipeline of provider services in a parsing mode, recording or replaying raw responses or
+/// printing the plans of requests (in a dry run) if requested.
+///
+/// The metadata of every request is recorded for the provenance footer (see `provenance::record`).
+fn request_pipeline(parsing: ParsingMode, config: &MainConfig) -> Pipeline {
+    with_fixture_mode(base_pipeline(parsing), config)
+}
+
+/// Creates the request pipeline of weather queries in a parsing mode, which caches the provider responses in the
+/// cache directory (see `HttpConfig::cache_ttl`), so one-shot invocations reuse and revalidate them.
+///
+/// The responses aren't cached in a fixture mode, so recorded, replayed and planned requests always reach
+/// its middleware.
+///
+/// # Arguments
+///
+/// * `parsing` - The parsing mode of provider responses.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// The `Pipeline` of weather queries.
+fn cached_pipeline(parsing: ParsingMode, config: &MainConfig) -> Pipeline {
+    if config.fixture_mode.is_some() {
+        return request_pipeline(parsing, config);
     }
 
-    println!("\nCurrently supported providers is\n\tOpen Weather ({}; example url: '{}'),\n\tWeather API ({}; example url: '{}')", "v2".blue(), "https://api.openweathermap.org/data/2.5/weather".green(), "v1".blue(), "https://api.weatherapi.com/v1".green());
+    let cache = Cache::new(config.http.cache_ttl());
+    let cache = match paths::dir(AppDir::Cache) {
+        Ok(dir) => cache.dir(dir.join(RESPONSES_CACHE_NAME)),
+        Err(_) => cache,
+    };
+
+    base_pipeline(parsing).with(cache)
 }
 
-/// Fetches weather information from a selected provider and displays it in the terminal.
+/// Creates the request pipeline of provider services in a parsing mode without the middleware of the fixture mode,
+/// so more middleware can be added in front of it (see `with_fixture_mode`).
+fn base_pipeline(parsing: ParsingMode) -> Pipeline {
+    Pipeline::default()
+        .parsing_mode(parsing)
+        .on_request(provenance::record)
+        .on_warning(|warning| {
+            eprintln!(
+                "{} unexpected provider response: {warning}",
+                "Warning:".paint(Role::Warning)
+            )
+        })
+}
+
+/// Adds the middleware recording or replaying raw responses or printing the plans of requests (in a dry run)
+/// to a request pipeline if requested.
 ///
-/// This function fetches weather information for a given address and optional date using the selected provider.
-/// It supports JSON output and displays the weather data using the provided `WeatherData` struct.
+/// # Arguments
+///
+/// * `pipeline` - The request pipeline.
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// The `Pipeline` with the middleware of the fixture mode.
+fn with_fixture_mode(pipeline: Pipeline, config: &MainConfig) -> Pipeline {
+    match &config.fixture_mode {
+        Some(FixtureMode::Record(dir)) => pipeline.with(Record::new(dir)),
+        Some(FixtureMode::Replay(dir)) => pipeline.with(Replay::new(dir)),
+        Some(FixtureMode::DryRun) => pipeline.with(DryRun::new(|plan| println!("{plan}"))),
+        None => pipeline,
+    }
+}
+
+/// Creates an HTTP client with the `User-Agent` header of the configuration.
 ///
 /// # Arguments
 ///
-/// * `address` - The address for which weather information is requested.
-/// * `date` - An optional date parameter for historical weather data.
-/// * `json` - A flag to indicate if the output format should be JSON.
-/// * `provider` - The selected weather data provider.
+/// * `http` - The identification of HTTP requests (see `HttpConfig::user_agent_header`).
+///
+/// # Returns
+///
+/// A `Result` containing the HTTP client or an error if it can't be created.
+fn http_client(http: &HttpConfig) -> Result<Client> {
+    http::build_client(None, Some(&http.user_agent_header()))
+}
+
+/// Prints the provenance footer of the provider requests sent so far after the tables if it's enabled
+/// (see `provenance::footer`).
+///
+/// # Arguments
+///
+/// * `options` - The options of the query.
+fn print_provenance(options: &QueryOptions) {
+    if !options.provenance {
+        return;
+    }
+
+    if let Some(footer) = provenance::footer(&provenance::requests()) {
+        println!("{}", footer.paint(Role::Muted));
+    }
+}
+
+/// Gets the climate normal of a calendar day at an address from the local cache or the Open-Meteo archive.
+///
+/// Fetched normals are stored in the cache; a cached normal is refetched once a new year is over.
+///
+/// # Arguments
+///
+/// * `address` - The address of the normal.
+/// * `date` - The date whose calendar day is looked up.
+/// * `http` - The identification of the archive requests.
+///
+/// # Returns
+///
+/// A `Result` containing the `ClimateNormal` or an error if it can't be fetched.
+async fn get_climate_normal(
+    address: &str,
+    date: NaiveDate,
+    http: &HttpConfig,
+) -> Result<ClimateNormal> {
+    let month_day = date.format("%m-%d").to_string();
+    let key = format!("{}|{month_day}", address.to_lowercase());
+    let cache_path = paths::file(AppDir::Cache, CLIMATE_CACHE_NAME)?;
+    let mut cache: ClimateNormalsCache = confy::load_path(&cache_path).unwrap_or_default();
+
+    if let Some(normal) = cache.normals.get(&key) {
+        if normal.last_year == date.year() - 1 {
+            return Ok(normal.clone());
+        }
+    }
+
+    let archive = ClimateArchive::new(http_client(http)?);
+    let location = archive.locate(address).await?;
+    let normal = archive
+        .get_normal(&location, &month_day, date.year())
+        .await?;
+
+    cache.normals.insert(key, normal.clone());
+    confy::store_path(&cache_path, cache)?;
+
+    Ok(normal)
+}
+
+/// Computes the station pressure of weather data from the elevation of an address looked up with the Open-Meteo
+/// geocoder.
+///
+/// # Arguments
+///
+/// * `address` - The address of the weather data.
+/// * `weather_data` - The weather data in metric units with the sea-level pressure.
+/// * `http` - The identification of the geocoder requests.
+///
+/// # Returns
+///
+/// A `Result` containing the station pressure (`None` if the elevation is unknown) or an error
+/// if the address can't be looked up.
+async fn compute_station_pressure(
+    address: &str,
+    weather_data: &WeatherData,
+    http: &HttpConfig,
+) -> Result<Option<HectoPascal>> {
+    let location = ClimateArchive::new(http_client(http)?)
+        .locate(address)
+        .await?;
+
+    Ok(location.elevation.map(|elevation| {
+        derived::station_pressure(weather_data.pressure, elevation, weather_data.temp)
+    }))
+}
+
+/// Creates the weather API service of the selected provider in the language of the query and resolves
+/// an address to the query sent to it (see `resolve_address`).
+///
+/// # Arguments
+///
+/// * `address` - The address given on the command line.
+/// * `options` - The provider, language and address options of the query.
 /// * `config` - The application's main configuration.
 ///
 /// # Returns
 ///
-/// A `Result` indicating success or an error when fetching and displaying weather information.
-pub async fn get_weather_info(
+/// A `Result` containing the weather API service and the query or an error if the provider is not configured
+/// or the address can't be resolved.
+async fn resolve_service(
     address: &str,
-    date: &Option<String>,
-    json: bool,
-    provider: &Provider,
-    config: MainConfig,
-) -> Result<()> {
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(ProgressStyle::default_spinner().template("{spinner} Fetching...")?);
-    pb.enable_steady_tick(Duration::from_millis(100));
+    options: &QueryOptions,
+    config: &mut MainConfig,
+) -> Result<(Box<dyn WeatherApi + Send + Sync>, String)> {
+    let weather_api = create_weather_api(options, config)?;
+    let query = resolve_address(
+        address,
+        weather_api.supports_postal_codes(),
+        options,
+        config,
+    )
+    .await?;
 
-    let client = reqwest::Client::new();
-    let weather_api: Result<Box<dyn WeatherApi>> = match provider {
-        Provider::OpenWeather => {
-            let open_weather_config = config.open_weather;
+    Ok((weather_api, query))
+}
 
-            Ok(Box::new(OpenWeatherApiService::new(
-                client,
-                open_weather_config.url,
-                open_weather_config
-                    .api_key
-                    .ok_or(ConfigError::ProviderConfig(
-                        provider.to_string().yellow().to_string(),
-                        "weather-rs/config.toml".yellow().to_string(),
-                        "weather-rs configure <PROVIDER> <API_KEY> [-u <URL>]"
-                            .yellow()
-                            .to_string(),
-                    ))?,
-            )?))
-        }
-        Provider::WeatherApi => {
-            let weather_api_config = config.weather_api;
-
-            Ok(Box::new(WeatherApiService::new(
-                client,
-                weather_api_config.url,
-                weather_api_config
-                    .api_key
-                    .ok_or(ConfigError::ProviderConfig(
-                        provider.to_string().yellow().to_string(),
-                        "weather-rs/config.toml".yellow().to_string(),
-                        "weather-rs configure <PROVIDER> <API_KEY> [-u <URL>]"
-                            .yellow()
-                            .to_string(),
-                    ))?,
-            )?))
-        }
-        Provider::AccuWeather => Err(ProviderError::ProviderNotImplemented.into()),
-        Provider::AerisWeather => Err(ProviderError::ProviderNotImplemented.into()),
-    };
-    let weather_data = weather_api?.get_weather_data(address, date).await?;
+/// Resolves an address to the query sent to providers: postal codes are passed to providers supporting them
+/// natively, plus codes and what3words addresses are decoded to coordinates and an ambiguous address
+/// (e.g. "Springfield") to the coordinates of one of its matching locations.
+///
+/// Postal codes ('--zip', e.g. `10115,DE`) default to the country set with '--country' or `default_country`;
+/// for providers without native support they are looked up with the Open-Meteo geocoder.
+/// Full plus codes are decoded locally; short plus codes are recovered near their locality (e.g. `9G8F+6X Zurich`)
+/// and what3words addresses (e.g. `///filled.count.soap`) are converted with the what3words API if its key is set.
+/// Addresses are looked up with the Open-Meteo geocoder only in an interactive terminal, with the '--first' option
+/// or with a country ('--country' or `default_country`), so scripts keep sending addresses to providers as they are.
+/// Matches are limited to the country, so short city names resolve in the user's region. If several locations match,
+/// the best match is taken with '--first' (or without a terminal), otherwise the user is asked to choose one;
+/// the choice is stored and reused for the address (unless it's in another country). Addresses with a single match
+/// (unless a country is set), without a match or that can't be looked up (e.g. offline) are returned as they are.
+///
+/// # Arguments
+///
+/// * `address` - The address given on the command line.
+/// * `postal_codes` - A flag to indicate if the provider resolves postal codes natively.
+/// * `options` - The options of the query ('--first', the country and '--zip').
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` containing the query sent to the provider (the address or `latitude,longitude`) or an error if
+/// the address can't be decoded or the choice can't be asked or stored.
+async fn resolve_address(
+    address: &str,
+    postal_codes: bool,
+    options: &QueryOptions,
+    config: &MainConfig,
+) -> Result<String> {
+    if options.zip {
+        let mut postal_code = PostalCode::parse(address)
+            .ok_or_else(|| LocationError::PostalCode(address.paint(Role::Warning).to_string()))?;
+        postal_code.country = postal_code.country.or_else(|| options.country.clone());
 
-    pb.finish_and_clear();
+        if postal_codes {
+            return Ok(postal_code.to_query());
+        }
+        if config.fixture_mode.is_some() {
+            return Ok(postal_code.to_string());
+        }
+        let location = ClimateArchive::new(http_client(&config.http)?)
+            .search(&postal_code.code, postal_code.country.as_deref(), 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                WeatherApiError::LocationNotFound(
+                    postal_code.to_string().paint(Role::Warning).to_string(),
+                )
+            })?;
 
-    if json {
-        views::json_terminal_view(weather_data)?;
-    } else {
-        views::table_terminal_view(weather_data);
+        return Ok(location.query());
     }
 
-    Ok(())
+    let plus_code = geocoding::split_plus_code(address);
+    if let Some((latitude, longitude)) = plus_code.as_ref().and_then(|(code, _)| code.decode()) {
+        return Ok(format!("{latitude:.4},{longitude:.4}"));
+    }
+    if config.fixture_mode.is_some() {
+        return Ok(address.to_owned());
+    }
+    if let Some((code, locality)) = plus_code {
+        if locality.is_empty() {
+            return Err(
+                LocationError::PlusCodeLocality(address.paint(Role::Warning).to_string()).into(),
+            );
+        }
+        let reference = ClimateArchive::new(http_client(&config.http)?)
+            .locate(locality)
+            .await?;
+        let (latitude, longitude) = code.recover(reference.latitude, reference.longitude);
+
+        return Ok(format!("{latitude:.4},{longitude:.4}"));
+    }
+    if let Some(words) = geocoding::what3words_address(address) {
+        match &config.what3words_api_key {
+            Some(api_key) => {
+                let what3words = What3Words::new(http_client(&config.http)?, api_key.clone());
+
+                return Ok(what3words.locate(&words).await?.query());
+            }
+            None if address.trim().starts_with("///") => {
+                return Err(ConfigError::What3WordsKey(
+                    paths::config_file_hint().paint(Role::Warning).to_string(),
+                )
+                .into());
+            }
+            None => {}
+        }
+    }
+
+    let interactive = terminal::capabilities().interactive;
+    let explicit = options.first || options.country.is_some();
+    if !(interactive || explicit) {
+        return Ok(address.to_owned());
+    }
+
+    let key = address.trim().to_lowercase();
+    let choices_path = paths::file(AppDir::Data, LOCATIONS_NAME)?;
+    let mut choices: LocationChoices = confy::load_path(&choices_path).unwrap_or_default();
+    if let Some(location) = choices
+        .locations
+        .get(&key)
+        .filter(|location| options.country.is_none() || location.country_code == options.country)
+    {
+        return Ok(location.query());
+    }
+
+    let archive = ClimateArchive::new(http_client(&config.http)?);
+    let Ok(candidates) = archive
+        .search(address, options.country.as_deref(), MAX_CANDIDATES)
+        .await
+    else {
+        return Ok(address.to_owned());
+    };
+
+    match candidates.as_slice() {
+        [] => Ok(address.to_owned()),
+        [location] if options.country.is_some() => Ok(location.query()),
+        [_] => Ok(address.to_owned()),
+        [location, ..] if options.first || !interactive || interaction::current().assume_yes => {
+            Ok(location.query())
+        }
+        _ => {
+            let index = locations::choose_location(
+                address,
+                &candidates,
+                std::io::stdin().lock(),
+                std::io::stderr(),
+            )?;
+            let location = candidates[index].clone();
+            let query = location.query();
+
+            choices.locations.insert(key, location);
+            confy::store_path(&choices_path, choices)?;
+
+            Ok(query)
+        }
+    }
 }
 
 /// Configures the URL and API key for a weather data provider.
@@ -136,30 +2429,59 @@ pub async fn get_weather_info(
 ///
 /// * `cfg` - A mutable reference to the main configuration.
 /// * `provider` - The selected weather data provider.
-/// * `url` - The URL for the provider's API.
+/// * `url` - The URL for the provider's API (validated by `config::parse_provider_url`).
 /// * `api_key` - The API key for the provider's API.
 pub fn configure_provider(
     cfg: &mut MainConfig,
     provider: &Provider,
-    url: Option<String>,
+    url: Option<Url>,
     api_key: String,
 ) {
-    let provider_config = ProviderConfig {
-        url: url.unwrap_or_else(|| match provider {
-            Provider::OpenWeather => cfg.open_weather.url.clone(),
-            Provider::WeatherApi => cfg.weather_api.url.clone(),
-            Provider::AccuWeather => cfg.accu_weather.url.clone(),
-            Provider::AerisWeather => cfg.aeris_weather.url.clone(),
-        }),
-        api_key: Some(api_key),
-    };
+    let provider_config = cfg.provider_config_mut(provider);
 
-    match provider {
-        Provider::OpenWeather => cfg.open_weather = provider_config,
-        Provider::WeatherApi => cfg.weather_api = provider_config,
-        Provider::AccuWeather => cfg.accu_weather = provider_config,
-        Provider::AerisWeather => cfg.aeris_weather = provider_config,
+    if let Some(url) = url {
+        provider_config.url = Some(url);
     }
+    provider_config.api_key = Some(api_key.into());
+}
+
+/// Imports the URLs and API keys of several providers from a credentials file (see `credentials::parse`).
+///
+/// All entries are validated first, so nothing is imported if any of them is invalid.
+/// But this function DOES NOT save the configuration itself!
+///
+/// # Arguments
+///
+/// * `cfg` - A mutable reference to the main configuration.
+/// * `path` - The path to the credentials file; it's parsed as JSON if its extension is `json` and as TOML otherwise.
+///
+/// # Returns
+///
+/// A `Result` containing the configured providers or an error if the file can't be read or is invalid.
+pub fn import_credentials(cfg: &mut MainConfig, path: &Path) -> Result<Vec<Provider>> {
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        CredentialsError::Read(
+            err,
+            path.display().to_string().paint(Role::Warning).to_string(),
+        )
+    })?;
+    let json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    Ok(credentials::parse(&content, json)?
+        .into_iter()
+        .map(|credentials| {
+            configure_provider(
+                cfg,
+                &credentials.provider,
+                credentials.url,
+                credentials.api_key,
+            );
+
+            credentials.provider
+        })
+        .collect())
 }
 
 /// Selects the active weather data provider.
@@ -175,32 +2497,157 @@ pub fn select_provider(cfg: &mut MainConfig, provider: Provider) {
     cfg.selected_provider = provider;
 }
 
+/// Saves a favorite query, replacing the favorite of the same name.
+///
+/// But this function DOES NOT save the configuration itself!
+///
+/// # Arguments
+///
+/// * `cfg` - A mutable reference to the main configuration.
+/// * `name` - The name of the favorite (validated by `favorites::parse_name`).
+/// * `favorite` - The preset of the query.
+pub fn add_favorite(cfg: &mut MainConfig, name: &str, favorite: Favorite) {
+    cfg.favorites.insert(name.to_owned(), favorite);
+}
+
+/// Removes a favorite query.
+///
+/// But this function DOES NOT save the configuration itself!
+///
+/// # Arguments
+///
+/// * `cfg` - A mutable reference to the main configuration.
+/// * `name` - The name of the favorite.
+///
+/// # Returns
+///
+/// A `Result` indicating success or `FavoriteError::NotFound` if the favorite isn't saved.
+pub fn remove_favorite(cfg: &mut MainConfig, name: &str) -> Result<()> {
+    favorites::find(&cfg.favorites, name)?;
+    cfg.favorites.remove(name);
+
+    Ok(())
+}
+
+/// Fetches and displays the current weather of the address of a favorite query.
+///
+/// # Arguments
+///
+/// * `name` - The name of the favorite.
+/// * `json` - A flag to indicate if the output format should be JSON even if the favorite prints the table.
+/// * `options` - The provider, units and language of the query with the preset of the favorite applied
+///   (see `Favorite::apply`).
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if the favorite isn't saved or when fetching the weather data.
+pub async fn run_favorite(
+    name: &str,
+    json: bool,
+    options: &QueryOptions,
+    config: MainConfig,
+) -> Result<()> {
+    let favorite = favorites::find(&config.favorites, name)?.clone();
+
+    let weather_options = WeatherOptions {
+        json: favorite.json || json,
+        ..WeatherOptions::default()
+    };
+
+    get_weather_info(&favorite.address, &weather_options, options, config).await
+}
+
+/// Displays the favorite queries.
+///
+/// # Arguments
+///
+/// * `favorites` - The favorite queries keyed by their names.
+/// * `tag` - An optional tag selecting the listed favorites. Pass `None` for all favorites.
+/// * `json` - A flag to indicate if the output format should be JSON.
+///
+/// # Returns
+///
+/// A `Result` indicating success or an error if no favorite has the tag or when serializing the favorites.
+pub fn list_favorites(
+    favorites: &BTreeMap<String, Favorite>,
+    tag: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let favorites: BTreeMap<&String, &Favorite> = match tag {
+        Some(tag) => favorites::tagged(favorites, Some(tag))?
+            .into_iter()
+            .collect(),
+        None => favorites.iter().collect(),
+    };
+
+    if json {
+        print!("{}", views::json_terminal_view(&favorites)?);
+    } else {
+        print!("{}", views::favorites_terminal_view(&favorites));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ProviderConfig;
     use rstest::rstest;
+    use weather_api_services::quantities::{Celsius, MetersPerSecond};
 
     #[rstest]
-    #[case(Some("https://example.com".to_owned()), "api_key")]
-    #[case(Some("".to_owned()), "api_key")]
-    fn test_configure_provider(#[case] url: Option<String>, #[case] api_key: String) {
+    #[case(Some("https://example.com"), "api_key")]
+    #[case(Some("http://localhost:8080/weather"), "api_key")]
+    #[case(None, "api_key")]
+    fn test_configure_provider(#[case] url: Option<&str>, #[case] api_key: String) {
         let mut config = MainConfig::default();
         let provider = Provider::OpenWeather;
 
-        configure_provider(&mut config, &provider, url.clone(), api_key.clone());
+        let url = url.map(|url| Url::parse(url).unwrap());
+        let expected_url = url
+            .clone()
+            .or_else(|| Url::parse(provider.entry().unwrap().default_url).ok());
 
-        match provider {
-            Provider::OpenWeather => {
-                assert_eq!(
-                    config.open_weather,
-                    ProviderConfig {
-                        url: url.unwrap(),
-                        api_key: Some(api_key.clone())
-                    }
-                );
-            }
-            _ => panic!("Unexpected provider selection"),
-        }
+        configure_provider(&mut config, &provider, url, api_key.clone());
+
+        assert_eq!(
+            config.provider_config(&provider),
+            Some(&ProviderConfig {
+                url: expected_url,
+                api_key: Some(api_key.clone().into()),
+                ..ProviderConfig::default()
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_check_plausibility() {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(200.0))
+            .feels_like(Celsius(21.0))
+            .humidity(50)
+            .pressure(HectoPascal(1013))
+            .wind_speed(MetersPerSecond(5.0))
+            .build();
+
+        assert!(check_plausibility("London", &weather_data, false).is_ok());
+        let result = check_plausibility("London", &weather_data, true).unwrap_err();
+        assert!(matches!(
+            result.downcast_ref::<WeatherDataError>(),
+            Some(WeatherDataError::Implausible(_))
+        ));
+    }
+
+    #[rstest]
+    fn test_requested_date() {
+        assert_eq!(
+            requested_date(&Some("2023-01-15".to_owned())).unwrap(),
+            NaiveDate::from_ymd_opt(2023, 1, 15).unwrap()
+        );
+        assert_eq!(requested_date(&None).unwrap(), Local::now().date_naive());
+        assert!(requested_date(&Some("someday".to_owned())).is_err());
     }
 
     #[rstest]
@@ -212,4 +2659,55 @@ mod tests {
 
         assert_eq!(config.selected_provider, provider);
     }
+
+    #[rstest]
+    fn test_add_and_remove_favorite() {
+        let mut config = MainConfig::default();
+        let favorite = Favorite {
+            address: "London".to_owned(),
+            units: None,
+            json: false,
+            every: Some("30m".to_owned()),
+            tags: vec!["travel".to_owned()],
+            provider: Some(Provider::WeatherApi),
+        };
+
+        add_favorite(&mut config, "commute", favorite.clone());
+
+        assert_eq!(config.favorites.get("commute"), Some(&favorite));
+        assert!(remove_favorite(&mut config, "commute").is_ok());
+        assert!(config.favorites.is_empty());
+        assert!(remove_favorite(&mut config, "commute").is_err());
+    }
+
+    #[rstest]
+    #[case(vec![], vec![("Kyiv", "2024-07-01", 1), ("Warsaw", "2024-07-01", 1)])]
+    #[case(vec!["2024-07-02"], vec![("Kyiv", "2024-07-02", 2), ("Warsaw", "2024-07-02", 2)])]
+    #[case(
+        vec!["2024-07-01", "2024-07-03"],
+        vec![("Kyiv", "2024-07-01", 1), ("Warsaw", "2024-07-03", 3)]
+    )]
+    fn test_trip_itinerary(#[case] dates: Vec<&str>, #[case] expected: Vec<(&str, &str, u8)>) {
+        let stops = vec!["Kyiv".to_owned(), " Warsaw".to_owned()];
+        let dates: Vec<String> = dates.into_iter().map(String::from).collect();
+        let today = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        let result = trip_itinerary(&stops, &dates, today).unwrap();
+
+        let expected: Vec<(&str, NaiveDate, u8)> = expected
+            .into_iter()
+            .map(|(stop, date, days)| (stop, date.parse().unwrap(), days))
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("2024-06-30")]
+    #[case("07/01/2024")]
+    fn test_trip_itinerary_invalid_date(#[case] date: &str) {
+        let stops = vec!["Kyiv".to_owned()];
+        let today = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        assert!(trip_itinerary(&stops, &[date.to_owned()], today).is_err());
+    }
 }
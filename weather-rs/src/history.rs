@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::providers::Provider;
+
+/// The default number of recent queries kept in the history.
+pub const DEFAULT_RECENT_QUERIES: usize = 20;
+
+/// Represents errors related to the history of recent queries.
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    /// An error indicating that the most recent query is repeated, but there are no recent queries.
+    #[error("There are no recent queries to repeat; query the weather of an address first (e.g. 'weather-rs London')")]
+    Empty,
+}
+
+/// Represents a recent query of an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecentQuery {
+    pub address: String,
+    /// The time of the query as a Unix timestamp.
+    pub queried_at: i64,
+    /// The weather data provider of the query.
+    pub provider: Provider,
+}
+
+/// Represents the history of recent queries, most recent first.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct RecentQueries {
+    #[serde(default)]
+    pub queries: Vec<RecentQuery>,
+}
+
+/// `RecentQueries` methods
+impl RecentQueries {
+    /// Adds a query to the top of the history.
+    ///
+    /// A repeated query (the same address, ignoring case, with the same provider) is moved to the top instead of
+    /// being added again, and the oldest queries are dropped beyond the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query.
+    /// * `limit` - The maximum number of queries kept.
+    pub fn push(&mut self, query: RecentQuery, limit: usize) {
+        self.queries.retain(|recent| {
+            !(recent.address.eq_ignore_ascii_case(&query.address)
+                && recent.provider == query.provider)
+        });
+        self.queries.insert(0, query);
+        self.queries.truncate(limit);
+    }
+
+    /// Returns the most recent query.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the most recent query or `HistoryError::Empty` if the history is empty.
+    pub fn last(&self) -> Result<&RecentQuery, HistoryError> {
+        self.queries.first().ok_or(HistoryError::Empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn query(address: &str, provider: Provider, queried_at: i64) -> RecentQuery {
+        RecentQuery {
+            address: address.to_owned(),
+            queried_at,
+            provider,
+        }
+    }
+
+    #[rstest]
+    #[case(
+        query("Paris", Provider::OpenWeather, 3),
+        3,
+        vec![
+            query("Paris", Provider::OpenWeather, 3),
+            query("London", Provider::OpenWeather, 2),
+            query("Kyiv", Provider::WeatherApi, 1),
+        ]
+    )]
+    #[case(
+        query("london", Provider::OpenWeather, 3),
+        3,
+        vec![query("london", Provider::OpenWeather, 3), query("Kyiv", Provider::WeatherApi, 1)]
+    )]
+    #[case(
+        query("London", Provider::WeatherApi, 3),
+        2,
+        vec![query("London", Provider::WeatherApi, 3), query("London", Provider::OpenWeather, 2)]
+    )]
+    #[case(query("Paris", Provider::OpenWeather, 3), 0, vec![])]
+    fn test_push(
+        #[case] new_query: RecentQuery,
+        #[case] limit: usize,
+        #[case] expected: Vec<RecentQuery>,
+    ) {
+        let mut recent_queries = RecentQueries {
+            queries: vec![
+                query("London", Provider::OpenWeather, 2),
+                query("Kyiv", Provider::WeatherApi, 1),
+            ],
+        };
+
+        recent_queries.push(new_query, limit);
+
+        assert_eq!(recent_queries.queries, expected);
+    }
+
+    #[rstest]
+    fn test_last() {
+        let mut recent_queries = RecentQueries::default();
+        assert!(matches!(recent_queries.last(), Err(HistoryError::Empty)));
+
+        recent_queries.push(query("Kyiv", Provider::WeatherApi, 1), 20);
+
+        assert_eq!(
+            recent_queries.last().unwrap(),
+            &query("Kyiv", Provider::WeatherApi, 1)
+        );
+    }
+}
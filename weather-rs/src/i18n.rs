@@ -0,0 +1,333 @@
+/// Represents labels of the weather data output that are translated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Label {
+    Name,
+    Value,
+    Description,
+    Temperature,
+    Humidity,
+    Pressure,
+    WindSpeed,
+    Visibility,
+    Date,
+    Time,
+    Precipitation,
+    Tide,
+    Height,
+    WaveHeight,
+    Swell,
+    WaterTemperature,
+    Snowfall,
+    Total,
+    UvIndex,
+    FeelsLike,
+    DewPoint,
+    Normal,
+    Location,
+    Distance,
+    Observed,
+    Observations,
+    Minimum,
+    Maximum,
+    Average,
+    RainyDays,
+    Trend,
+    StationPressure,
+    Alerts,
+}
+
+/// Embedded translations of labels keyed by ISO 639-1 language code.
+///
+/// Translations of every language are listed in the order of `Label` variants. The first entry (English)
+/// is used as a fallback for unsupported languages.
+static TRANSLATIONS: [(&str, [&str; 33]); 5] = [
+    (
+        "en",
+        [
+            "Name",
+            "Value",
+            "Description",
+            "Temperature",
+            "Humidity",
+            "Pressure",
+            "Wind speed",
+            "Visibility",
+            "Date",
+            "Time",
+            "Precipitation",
+            "Tide",
+            "Height",
+            "Wave height",
+            "Swell",
+            "Water temperature",
+            "Snowfall",
+            "Total",
+            "UV index",
+            "Feels like",
+            "Dew point",
+            "Normal",
+            "Location",
+            "Distance",
+            "Observed",
+            "Observations",
+            "Minimum",
+            "Maximum",
+            "Average",
+            "Rainy days",
+            "Trend",
+            "Station pressure",
+            "Alerts",
+        ],
+    ),
+    (
+        "de",
+        [
+            "Name",
+            "Wert",
+            "Beschreibung",
+            "Temperatur",
+            "Luftfeuchtigkeit",
+            "Luftdruck",
+            "Windgeschwindigkeit",
+            "Sichtweite",
+            "Datum",
+            "Zeit",
+            "Niederschlag",
+            "Gezeit",
+            "Höhe",
+            "Wellenhöhe",
+            "Dünung",
+            "Wassertemperatur",
+            "Schneefall",
+            "Gesamt",
+            "UV-Index",
+            "Gefühlt",
+            "Taupunkt",
+            "Normalwert",
+            "Ort",
+            "Entfernung",
+            "Beobachtet",
+            "Beobachtungen",
+            "Minimum",
+            "Maximum",
+            "Durchschnitt",
+            "Regentage",
+            "Trend",
+            "Stationsluftdruck",
+            "Warnungen",
+        ],
+    ),
+    (
+        "es",
+        [
+            "Nombre",
+            "Valor",
+            "Descripción",
+            "Temperatura",
+            "Humedad",
+            "Presión",
+            "Velocidad del viento",
+            "Visibilidad",
+            "Fecha",
+            "Hora",
+            "Precipitación",
+            "Marea",
+            "Altura",
+            "Altura de olas",
+            "Mar de fondo",
+            "Temperatura del agua",
+            "Nevada",
+            "Total",
+            "Índice UV",
+            "Sensación térmica",
+            "Punto de rocío",
+            "Normal",
+            "Ubicación",
+            "Distancia",
+            "Observado",
+            "Observaciones",
+            "Mínimo",
+            "Máximo",
+            "Promedio",
+            "Días de lluvia",
+            "Tendencia",
+            "Presión en la estación",
+            "Alertas",
+        ],
+    ),
+    (
+        "fr",
+        [
+            "Nom",
+            "Valeur",
+            "Description",
+            "Température",
+            "Humidité",
+            "Pression",
+            "Vitesse du vent",
+            "Visibilité",
+            "Date",
+            "Heure",
+            "Précipitations",
+            "Marée",
+            "Hauteur",
+            "Hauteur des vagues",
+            "Houle",
+            "Température de l'eau",
+            "Chute de neige",
+            "Total",
+            "Indice UV",
+            "Ressenti",
+            "Point de rosée",
+            "Normale",
+            "Lieu",
+            "Distance",
+            "Observé",
+            "Observations",
+            "Minimum",
+            "Maximum",
+            "Moyenne",
+            "Jours de pluie",
+            "Tendance",
+            "Pression à la station",
+            "Alertes",
+        ],
+    ),
+    (
+        "uk",
+        [
+            "Назва",
+            "Значення",
+            "Опис",
+            "Температура",
+            "Вологість",
+            "Тиск",
+            "Швидкість вітру",
+            "Видимість",
+            "Дата",
+            "Час",
+            "Опади",
+            "Приплив",
+            "Висота",
+            "Висота хвиль",
+            "Брижі",
+            "Температура води",
+            "Снігопад",
+            "Разом",
+            "УФ-індекс",
+            "Відчувається як",
+            "Точка роси",
+            "Норма",
+            "Місце",
+            "Відстань",
+            "Спостереження",
+            "Кількість спостережень",
+            "Мінімум",
+            "Максимум",
+            "Середнє",
+            "Дощові дні",
+            "Тенденція",
+            "Тиск на станції",
+            "Попередження",
+        ],
+    ),
+];
+
+/// Translates a label of the weather data output.
+///
+/// # Arguments
+///
+/// * `label` - The label to translate.
+/// * `language` - An optional language code or locale (e.g. `de`, `de-DE`, `uk_UA.UTF-8`). Pass `None` for English.
+///
+/// # Returns
+///
+/// The translated label or the English one if the language is not supported.
+pub fn translate(label: Label, language: Option<&str>) -> &'static str {
+    let code = language.map(language_code).unwrap_or_default();
+    let (_, labels) = TRANSLATIONS
+        .iter()
+        .find(|(language_code, _)| *language_code == code)
+        .unwrap_or(&TRANSLATIONS[0]);
+
+    labels[label as usize]
+}
+
+/// The environment variables of the locale of messages in the order of their precedence (POSIX).
+const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Detects the language of the terminal from the locale environment variables (`LC_ALL`, `LC_MESSAGES` and `LANG`).
+///
+/// # Returns
+///
+/// The ISO 639-1 code of the language (e.g. `de` for `de_DE.UTF-8`) or `None` if the locale isn't set, is English
+/// or isn't a language (e.g. `C` or `POSIX`).
+pub fn locale_language() -> Option<String> {
+    let locale = LOCALE_VARIABLES.iter().find_map(|name| {
+        std::env::var(name)
+            .ok()
+            .filter(|locale| !locale.trim().is_empty())
+    })?;
+
+    language_of_locale(&locale)
+}
+
+/// Extracts the language of a locale for localized output.
+///
+/// # Arguments
+///
+/// * `locale` - The locale (e.g. `uk_UA.UTF-8`).
+///
+/// # Returns
+///
+/// The lowercase ISO 639-1 (or 639-2) code of the language or `None` if it's English or isn't a language code.
+fn language_of_locale(locale: &str) -> Option<String> {
+    let code = language_code(locale);
+
+    ((2..=3).contains(&code.len())
+        && code.chars().all(|char| char.is_ascii_alphabetic())
+        && code != "en")
+        .then_some(code)
+}
+
+/// Extracts the lowercase ISO 639-1 language code from a language code or locale.
+fn language_code(language: &str) -> String {
+    language
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Label::Temperature, None, "Temperature")]
+    #[case(Label::Temperature, Some("de"), "Temperatur")]
+    #[case(Label::WindSpeed, Some("fr-FR"), "Vitesse du vent")]
+    #[case(Label::Humidity, Some("uk_UA.UTF-8"), "Вологість")]
+    #[case(Label::Visibility, Some("ES"), "Visibilidad")]
+    #[case(Label::Pressure, Some("xx"), "Pressure")]
+    #[case(Label::Precipitation, Some("de"), "Niederschlag")]
+    fn test_translate(
+        #[case] label: Label,
+        #[case] language: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(translate(label, language), expected);
+    }
+
+    #[rstest]
+    #[case("de_DE.UTF-8", Some("de"))]
+    #[case("uk_UA", Some("uk"))]
+    #[case("pt-BR", Some("pt"))]
+    #[case("en_US.UTF-8", None)]
+    #[case("C.UTF-8", None)]
+    #[case("POSIX", None)]
+    fn test_language_of_locale(#[case] locale: &str, #[case] expected: Option<&str>) {
+        assert_eq!(language_of_locale(locale).as_deref(), expected);
+    }
+}
@@ -0,0 +1,39 @@
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// The interaction with the user, selected once at startup.
+static INTERACTION: OnceLock<Interaction> = OnceLock::new();
+
+/// Represents how the application interacts with the user, e.g. when it runs in scripts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Interaction {
+    /// Whether the default answer of every prompt is taken instead of asking (`--yes`).
+    pub assume_yes: bool,
+    /// Whether spinners and informational messages are suppressed, so only data and errors are printed (`--quiet`).
+    pub quiet: bool,
+}
+
+/// Selects the interaction with the user; only the first selection takes effect.
+///
+/// # Arguments
+///
+/// * `interaction` - The interaction.
+pub fn set_interaction(interaction: Interaction) {
+    let _ = INTERACTION.set(interaction);
+}
+
+/// Returns the selected interaction with the user (asking and informing if none is selected).
+pub fn current() -> Interaction {
+    INTERACTION.get().copied().unwrap_or_default()
+}
+
+/// Prints an informational message (e.g. that a file was written) on the standard output unless the output is quiet.
+///
+/// # Arguments
+///
+/// * `message` - The message.
+pub fn inform(message: impl Display) {
+    if !current().quiet {
+        println!("{message}");
+    }
+}
@@ -0,0 +1,132 @@
+use std::io::{BufRead, Write};
+use thiserror::Error;
+
+use crate::theme::{Paint, Role};
+use weather_api_services::climatology::Location;
+
+/// The maximum number of locations listed for an ambiguous address.
+pub const MAX_CANDIDATES: u8 = 10;
+
+/// Represents errors related to choosing a location.
+#[derive(Error, Debug)]
+pub enum LocationError {
+    /// An error indicating a failure to ask for the location.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    #[error("Failed to ask for the location")]
+    Io(#[from] std::io::Error),
+
+    /// An error indicating that the input ended before a location was chosen.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the ambiguous address.
+    #[error("No location of '{0}' was chosen; use '--first' or '--country <CODE>' to choose it non-interactively")]
+    NotChosen(String),
+
+    /// An error indicating that a short plus code is given without a locality it's relative to.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the short plus code.
+    #[error("The short plus code '{0}' needs a locality (e.g. '9G8F+6X Zurich'); add one or use the full code")]
+    PlusCodeLocality(String),
+
+    /// An error indicating that an address given with '--zip' isn't a postal code.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the address.
+    #[error("'{0}' isn't a postal code; use '<CODE>[,<COUNTRY>]' (e.g. '10115,DE')")]
+    PostalCode(String),
+}
+
+/// Lists the locations matching an ambiguous address and asks the user to choose one of them.
+///
+/// An empty answer chooses the first (best matching) location; invalid answers are asked again.
+///
+/// # Arguments
+///
+/// * `address` - The ambiguous address (e.g. `Springfield`).
+/// * `candidates` - The matching locations, best matches first.
+/// * `input` - The input the answer is read from (e.g. the locked standard input).
+/// * `output` - The output the list and the question are written to (e.g. the standard error).
+///
+/// # Returns
+///
+/// A `Result` containing the index of the chosen location or a `LocationError` if the input ends or fails.
+pub fn choose_location(
+    address: &str,
+    candidates: &[Location],
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<usize, LocationError> {
+    writeln!(
+        output,
+        "Several locations match '{}':",
+        address.paint(Role::Highlight)
+    )?;
+    for (index, candidate) in candidates.iter().enumerate() {
+        writeln!(output, "{:>4}) {candidate}", index + 1)?;
+    }
+
+    loop {
+        write!(output, "Choose a location [1-{}] (1): ", candidates.len())?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Err(LocationError::NotChosen(
+                address.paint(Role::Warning).to_string(),
+            ));
+        }
+
+        match answer.trim() {
+            "" => return Ok(0),
+            answer => match answer.parse::<usize>() {
+                Ok(number) if (1..=candidates.len()).contains(&number) => return Ok(number - 1),
+                _ => writeln!(output, "{}", "Invalid choice".paint(Role::Warning))?,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn springfield(admin1: &str) -> Location {
+        Location {
+            name: "Springfield".to_owned(),
+            admin1: Some(admin1.to_owned()),
+            country: Some("United States".to_owned()),
+            country_code: Some("US".to_owned()),
+            latitude: 39.8,
+            longitude: -89.6,
+            elevation: None,
+        }
+    }
+
+    #[rstest]
+    #[case("2\n", Some(1))]
+    #[case("\n", Some(0))]
+    #[case("7\nabc\n3\n", Some(2))]
+    #[case("", None)]
+    fn test_choose_location(#[case] answers: &str, #[case] expected: Option<usize>) {
+        let candidates = [
+            springfield("Illinois"),
+            springfield("Missouri"),
+            springfield("Massachusetts"),
+        ];
+        let mut output = Vec::new();
+
+        let result = choose_location("Springfield", &candidates, answers.as_bytes(), &mut output);
+
+        assert_eq!(result.ok(), expected);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("   2) Springfield, Missouri, United States"));
+    }
+}
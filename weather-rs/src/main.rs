@@ -1,11 +1,78 @@
+/// The `advisory` module classifies the UV index and computes sun protection recommendations.
+mod advisory;
+/// The `astronomy` module computes moon phases locally from the mean synodic month.
+mod astronomy;
+/// The `bench` module measures the latency, payload size and parse time of providers.
+mod bench;
+/// The `card` module renders compact weather cards as SVG (rasterized to PNG by `rsvg-convert`).
+mod card;
+/// The `chat` module formats weather messages for Slack, Discord and Telegram and posts them to chats.
+mod chat;
 /// The `cli_parser` module handles the parsing of command-line arguments and options for the weather-rs application.
 mod cli_parser;
+/// The `clipboard` module places the output onto the system clipboard.
+mod clipboard;
 /// The `config` module defines data structures for handling configuration settings in the weather-rs application.
 mod config;
+/// The `console` module prepares the console for colors and UTF-8 output (on Windows).
+mod console;
+/// The `credentials` module imports the credentials of several providers from a TOML or JSON file and reads API keys
+/// from files, the standard input or a hidden prompt.
+mod credentials;
+/// The `digest` module composes email digests of the weather and sends them over SMTP.
+mod digest;
+/// The `errors` module maps application errors to exit codes and actionable hints.
+mod errors;
+/// The `export` module keeps the checkpoint of weather history exports, so interrupted exports can be resumed.
+mod export;
+/// The `favorites` module defines favorite queries: named presets of the `get` command.
+mod favorites;
 /// The `handlers` module contains functions that handle various commands and operations in the weather-rs application.
 mod handlers;
-/// The `providers` module defines enum for weather data providers implementations for the weather-rs application.
+/// The `history` module keeps the recent queries of the `get` command, so they can be listed and repeated.
+mod history;
+/// The `i18n` module contains embedded translations of the output labels.
+mod i18n;
+/// The `interaction` module selects how the user is asked and informed (`--yes` and `--quiet`).
+mod interaction;
+/// The `locations` module asks the user to choose one of the locations matching an ambiguous address.
+mod locations;
+/// The `maps` module downloads precipitation radar tiles from RainViewer and stitches them into maps.
+mod maps;
+/// The `mqtt` module creates MQTT messages of weather data, including Home Assistant MQTT Discovery configs.
+mod mqtt;
+/// The `nowcast` module sums up the minutely precipitation forecast of the next hour in a timeline and a verdict.
+mod nowcast;
+/// The `paths` module locates the config, cache, state and data directories of the weather-rs application.
+mod paths;
+/// The `ping` module checks the health (HTTP status and latency) of configured providers.
+mod ping;
+/// The `plugins` module discovers and runs external provider plugins (`weather-rs-provider-<name>` executables).
+mod plugins;
+/// The `progress` module displays the progress of requests (a spinner or per-item bars of batch operations).
+mod progress;
+/// The `provenance` module collects the metadata of provider requests for the provenance footer of the output.
+mod provenance;
+/// The `providers` module defines enum and registry for weather data providers implementations for the weather-rs application.
 mod providers;
+/// The `query` module selects a value of the JSON output with a jq-like path.
+mod query;
+/// The `report` module renders shareable HTML and Markdown weather reports from templates.
+mod report;
+/// The `route` module parses GPX and GeoJSON tracks and samples points along routes at estimated arrival times.
+mod route;
+/// The `stats` module computes statistics (e.g. the temperature trend) over stored observations.
+mod stats;
+/// The `store` module keeps fetched observations in a local NDJSON file for statistics.
+mod store;
+/// The `summary` module generates rule-based natural-language summaries of forecasts.
+mod summary;
+/// The `template` module renders custom output from Handlebars templates.
+mod template;
+/// The `terminal` module decides colors, progress and prompts by the streams connected to terminals.
+mod terminal;
+/// The `theme` module defines the color themes of the terminal output; all colors are picked by the theme.
+mod theme;
 /// The `views` module contains functions responsible for displaying weather data in different output views,
 /// such as table view and JSON view, in the weather-rs application.
 mod views;
@@ -13,10 +80,16 @@ mod views;
 use clap::Parser;
 use config::MainConfig;
 use narrate::anyhow::Result;
-use narrate::{colored::Colorize, report, ExitCode};
 
-use cli_parser::{Command, WeatherCli};
-use providers::{Provider, NOT_IMPLEMENTED_PROVIDERS};
+use cli_parser::{
+    Command, FavoriteCommand, ForecastOptions, GetArgs, OutputFormat, PostOptions, WeatherCli,
+    WeatherOptions,
+};
+use credentials::ApiKeySource;
+use favorites::Favorite;
+use providers::Provider;
+use terminal::Capabilities;
+use theme::{Paint, Role};
 
 /// The name of the application.
 const APP_NAME: &str = "weather-rs";
@@ -24,17 +97,42 @@ const APP_NAME: &str = "weather-rs";
 /// The name of the configuration file.
 const CONFIG_NAME: &str = "config";
 
+/// The name of the climate normals cache file in the cache directory.
+const CLIMATE_CACHE_NAME: &str = "climate-normals.toml";
+
+/// The name of the directory of cached provider responses in the cache directory.
+const RESPONSES_CACHE_NAME: &str = "responses";
+
+/// The name of the file of locations chosen for ambiguous addresses in the data directory.
+const LOCATIONS_NAME: &str = "locations.toml";
+
+/// The name of the observations store file in the data directory.
+const OBSERVATIONS_STORE_NAME: &str = "observations.ndjson";
+
+/// The name of the file of recent queries in the state directory.
+const RECENT_QUERIES_NAME: &str = "recent.toml";
+
 /// Main function of the weather-rs application.
 ///
 /// This is the main function of the weather-rs application. It initializes the application, runs the main logic,
 /// and handles any errors that may occur during execution.
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
-    let result = entry_point().await;
+    let escape_sequences = console::init();
+    let result = entry_point(escape_sequences).await.or_else(|err| {
+        if errors::is_dry_run(&err) {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    });
 
     if let Err(ref err) = result {
-        report::anyhow_err_full(err);
-        std::process::exit(err.exit_code());
+        narrate::report::anyhow_err_full(err);
+        if let Some(hint) = errors::find_hint(err).await {
+            eprintln!("{}: {}", "hint".paint(Role::Hint), hint);
+        }
+        std::process::exit(errors::exit_code(err));
     } else {
         std::process::exit(0);
     }
@@ -48,85 +146,423 @@ async fn main() {
 /// # Returns
 ///
 /// A `Result` indicating the success or failure of the application's main logic.
-async fn entry_point() -> Result<()> {
+async fn entry_point(escape_sequences: bool) -> Result<()> {
     let weather_cli = WeatherCli::parse();
-    let mut config: MainConfig = confy::load(APP_NAME, CONFIG_NAME)?;
+    terminal::init(Capabilities::detect(
+        escape_sequences,
+        weather_cli.progress_mode(),
+        weather_cli.interaction().quiet,
+    ));
+    let mut config: MainConfig = config::load_config(APP_NAME, CONFIG_NAME)?;
+    config.fixture_mode = weather_cli.fixture_mode();
+    config.provider_params = weather_cli.provider_params();
+    config.locale_language = i18n::locale_language();
+    theme::set_theme(weather_cli.options().theme.unwrap_or(config.theme));
+    interaction::set_interaction(weather_cli.interaction());
+    let cli_options = weather_cli.options().clone();
+    let mut options = cli_options.merge(&config);
+    // a query selects a value of the JSON output of the weather data and the envelope wraps it, so both imply
+    // the JSON output of the commands of the weather data
+    let json_data_output = options.query.is_some() || options.json_envelope;
+
+    // queries aren't recorded while recording or replaying fixtures, so the history stays the user's own
+    let recent_queries = match config.fixture_mode {
+        None => config.recent_queries,
+        Some(_) => 0,
+    };
+
+    let mut command = weather_cli.take_command();
+    if let Command::Get(GetArgs {
+        address,
+        last: true,
+        ..
+    }) = &mut command
+    {
+        let last_query = handlers::last_query()?;
+        *address = Some(last_query.address);
+        if cli_options.provider.is_none() {
+            options.provider = last_query.provider;
+        }
+    }
+    if let Command::Favorite {
+        command: FavoriteCommand::Run { name },
+    } = &command
+    {
+        options = favorites::find(&config.favorites, name)?
+            .apply(&cli_options)
+            .merge(&config);
+    }
+    errors::set_context(errors::HintContext::new(
+        &command,
+        &options.provider,
+        config.fixture_mode.is_none(),
+        &config.http,
+    ));
 
-    match weather_cli.take_command() {
+    let queried_address = match &command {
+        Command::Get(GetArgs { address, .. }) => address.clone(),
+        _ => None,
+    };
+
+    match command {
         Command::ProviderList => {
-            let selected_provider = config.selected_provider;
-            let configured_providers = vec![
-                if config.open_weather.api_key.is_some() {
-                    Some(&Provider::OpenWeather)
-                } else {
-                    None
-                },
-                if config.weather_api.api_key.is_some() {
-                    Some(&Provider::WeatherApi)
-                } else {
-                    None
-                },
-                if config.accu_weather.api_key.is_some() {
-                    Some(&Provider::AccuWeather)
-                } else {
-                    None
-                },
-                if config.aeris_weather.api_key.is_some() {
-                    Some(&Provider::AerisWeather)
-                } else {
-                    None
-                },
-            ]
-            .into_iter()
-            .flatten()
-            .collect();
-            let not_implemented_providers = NOT_IMPLEMENTED_PROVIDERS.to_vec();
+            let configured_providers = configured_providers(&config);
 
             handlers::provider_list_handler(
-                &selected_provider,
-                configured_providers,
-                not_implemented_providers,
+                &config.selected_provider,
+                configured_providers.iter().collect(),
             );
         }
         Command::Configure {
-            provider,
+            from_file: Some(path),
+            ..
+        } => {
+            let providers = handlers::import_credentials(&mut config, &path)?;
+
+            confy::store(APP_NAME, CONFIG_NAME, config)?;
+
+            interaction::inform(format!(
+                "Providers {} were successfully configured",
+                providers
+                    .iter()
+                    .map(|provider| format!("'{}'", provider.to_string().paint(Role::Success)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        Command::Configure {
+            provider: Some(provider),
             url,
             api_key,
+            api_key_option,
+            api_key_file,
+            ..
         } => {
+            let api_key = credentials::read_api_key(
+                ApiKeySource::new(api_key.or(api_key_option), api_key_file),
+                &provider,
+            )?;
             handlers::configure_provider(&mut config, &provider, url, api_key);
 
             confy::store(APP_NAME, CONFIG_NAME, config)?;
 
-            println!(
+            interaction::inform(format!(
                 "Provider '{}' was successfully configured",
-                &provider.to_string().green()
-            );
+                provider.to_string().paint(Role::Success)
+            ));
         }
+        // clap requires the provider without a credentials file
+        Command::Configure { .. } => unreachable!(),
         Command::SelectProvider { provider } => {
             handlers::select_provider(&mut config, provider.clone());
 
             confy::store(APP_NAME, CONFIG_NAME, config)?;
 
-            println!(
+            interaction::inform(format!(
                 "Provider '{}' was successfully selected",
-                provider.to_string().green()
-            );
+                provider.to_string().paint(Role::Success)
+            ));
+        }
+        Command::Ping { provider, json } => {
+            let providers = match provider {
+                Some(provider) => vec![provider],
+                None => configured_providers(&config),
+            };
+
+            handlers::ping_providers(&providers, json, options.parsing, config).await?;
         }
-        Command::Get {
+        Command::Bench {
+            provider,
             address,
-            date,
+            runs,
             json,
-            provider,
         } => {
-            let provider = if let Some(provider) = provider {
-                provider
+            let providers = match provider {
+                Some(provider) => vec![provider],
+                None => configured_providers(&config),
+            };
+
+            handlers::bench_providers(&providers, &address, runs, json, options.parsing, config)
+                .await?;
+        }
+        Command::Get(GetArgs {
+            address,
+            date,
+            json,
+            uv,
+            vs_normal,
+            copy,
+            altitude_pressure,
+            raw,
+            tag,
+            ..
+        }) => {
+            // the address is a required argument unless locations are selected by a tag or it's set from the history
+            let address = address.unwrap_or_default();
+
+            if let Some(tag) = tag {
+                handlers::get_tagged_weather(&tag, json || json_data_output, &options, config)
+                    .await?;
+            } else if raw {
+                handlers::get_raw_weather_data(&address, &date, &options, config).await?;
             } else {
-                config.selected_provider.clone()
+                let weather_options = WeatherOptions {
+                    date,
+                    json: json || json_data_output,
+                    uv,
+                    vs_normal,
+                    copy,
+                    altitude_pressure,
+                };
+                handlers::get_weather_info(&address, &weather_options, &options, config).await?;
+            }
+        }
+        Command::Recent { json } => {
+            handlers::list_recent_queries(json)?;
+        }
+        Command::Dashboard { tag, json } => {
+            handlers::get_dashboard(tag.as_deref(), json || json_data_output, &options, config)
+                .await?;
+        }
+        Command::Favorite { command } => match command {
+            FavoriteCommand::Add {
+                name,
+                address,
+                json,
+                every,
+                tags,
+            } => {
+                handlers::add_favorite(
+                    &mut config,
+                    &name,
+                    Favorite {
+                        address,
+                        units: cli_options.units,
+                        json,
+                        every,
+                        tags,
+                        provider: cli_options.provider.clone(),
+                    },
+                );
+
+                confy::store(APP_NAME, CONFIG_NAME, config)?;
+
+                interaction::inform(format!(
+                    "Favorite '{}' was successfully saved",
+                    name.paint(Role::Success)
+                ));
+            }
+            FavoriteCommand::Run { name } => {
+                handlers::run_favorite(&name, json_data_output, &options, config).await?;
+            }
+            FavoriteCommand::List { tag, json } => {
+                handlers::list_favorites(&config.favorites, tag.as_deref(), json)?;
+            }
+            FavoriteCommand::Remove { name } => {
+                handlers::remove_favorite(&mut config, &name)?;
+
+                confy::store(APP_NAME, CONFIG_NAME, config)?;
+
+                interaction::inform(format!(
+                    "Favorite '{}' was successfully removed",
+                    name.paint(Role::Success)
+                ));
+            }
+        },
+        Command::Forecast {
+            address,
+            days,
+            hourly,
+            summary,
+            json,
+        } => {
+            let forecast_options = ForecastOptions {
+                days,
+                hourly,
+                summary,
+                json: json || json_data_output,
             };
+            handlers::get_forecast_info(&address, &forecast_options, &options, config).await?;
+        }
+        Command::Marine {
+            address,
+            days,
+            json,
+        } => {
+            handlers::get_marine_info(&address, days, json || json_data_output, &options, config)
+                .await?;
+        }
+        Command::Snow { resort, days, json } => {
+            handlers::get_snow_report(&resort, days, json || json_data_output, &options, config)
+                .await?;
+        }
+        Command::Stations { address, json } => {
+            handlers::list_stations(&address, json || json_data_output, &options, config).await?;
+        }
+        Command::Nowcast { address, json } => {
+            handlers::get_nowcast(&address, json || json_data_output, &options, config).await?;
+        }
+        Command::Trip {
+            stops,
+            date,
+            json,
+            output,
+        } => {
+            handlers::get_trip_forecast(
+                &stops,
+                &date,
+                OutputFormat::resolve(json || json_data_output, output),
+                &options,
+                config,
+            )
+            .await?;
+        }
+        Command::Moon { month, json } => {
+            handlers::get_moon_calendar(month.as_deref(), json)?;
+        }
+        Command::Route {
+            track,
+            start,
+            speed,
+            every,
+            json,
+            output,
+        } => {
+            handlers::get_route_weather(
+                &track,
+                start.as_deref(),
+                speed,
+                every,
+                OutputFormat::resolve(json || json_data_output, output),
+                &options,
+                config,
+            )
+            .await?;
+        }
+        Command::Export {
+            address,
+            from,
+            to,
+            output,
+            resume,
+        } => {
+            handlers::export_history(
+                &address,
+                &from,
+                to.as_deref(),
+                &output,
+                resume,
+                &options,
+                config,
+            )
+            .await?;
+        }
+        Command::Stats {
+            address,
+            period,
+            json,
+        } => {
+            handlers::get_stats(&address, &period, json || json_data_output, &options)?;
+        }
+        Command::Report {
+            address,
+            days,
+            format,
+            output,
+        } => {
+            handlers::generate_report(&address, days, format, output.as_deref(), &options, config)
+                .await?;
+        }
+        Command::Card {
+            address,
+            alias,
+            days,
+            output,
+        } => {
+            handlers::generate_card(&address, alias.as_deref(), days, &output, &options, config)
+                .await?;
+        }
+        Command::Radar {
+            address,
+            zoom,
+            output,
+        } => {
+            handlers::get_radar_map(&address, zoom, output.as_deref(), &options, config).await?;
+        }
+        Command::Mqtt {
+            address,
+            alias,
+            topic,
+            discovery,
+            discovery_prefix,
+        } => {
+            let discovery_prefix = discovery.then_some(discovery_prefix.as_str());
 
-            handlers::get_weather_info(&address, &date, json, &provider, config).await?;
+            handlers::publish_mqtt(
+                &address,
+                alias.as_deref(),
+                &topic,
+                discovery_prefix,
+                &options,
+                config,
+            )
+            .await?;
+        }
+        Command::Digest {
+            address,
+            email,
+            smtp,
+            from,
+        } => {
+            handlers::send_digest(&address, &email, smtp, from, &options, config).await?;
+        }
+        Command::Post {
+            address,
+            alias,
+            days,
+            slack_webhook,
+            discord_webhook,
+            telegram_chat,
+        } => {
+            let post_options = PostOptions {
+                alias,
+                days,
+                slack_webhook,
+                discord_webhook,
+                telegram_chat,
+            };
+            handlers::post_weather(&address, post_options, &options, config).await?;
         }
     }
 
+    if let Some(address) = queried_address.filter(|_| recent_queries > 0) {
+        handlers::record_query(&address, &options.provider, recent_queries);
+    }
+
     Ok(())
 }
+
+/// Returns the built-in providers and the discovered plugins that have an API key in the configuration.
+///
+/// # Arguments
+///
+/// * `config` - The application's main configuration.
+///
+/// # Returns
+///
+/// The configured providers.
+fn configured_providers(config: &MainConfig) -> Vec<Provider> {
+    let plugin_providers = plugins::discover_plugins()
+        .into_iter()
+        .map(Provider::Plugin);
+
+    Provider::registry()
+        .map(|entry| entry.provider.clone())
+        .chain(plugin_providers)
+        .filter(|provider| {
+            config
+                .provider_config(provider)
+                .is_some_and(|provider_config| provider_config.api_key.is_some())
+        })
+        .collect()
+}
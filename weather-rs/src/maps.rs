@@ -0,0 +1,570 @@
+use narrate::anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use std::f64::consts::PI;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::theme::{Paint, Role};
+
+/// The URL of the RainViewer API listing the available radar frames.
+pub const RAINVIEWER_MAPS_URL: &str = "https://api.rainviewer.com/public/weather-maps.json";
+
+/// The attribution required by RainViewer for its radar data.
+pub const RAINVIEWER_ATTRIBUTION: &str = "Radar data by RainViewer (https://www.rainviewer.com)";
+
+/// The default zoom level of radar maps (a tile covers about 600 km at the equator).
+pub const DEFAULT_ZOOM: u8 = 6;
+
+/// The highest zoom level RainViewer serves radar tiles for.
+pub const MAX_ZOOM: u8 = 7;
+
+/// The width and height of a map tile in pixels.
+const TILE_SIZE: u32 = 256;
+
+/// The number of tiles in a row and a column of a radar map, centered on the tile of the location.
+const GRID_SIZE: u32 = 3;
+
+/// The color scheme (Universal Blue) and the options (smoothed, snow shown) of RainViewer radar tiles.
+const TILE_COLOR_SCHEME: u8 = 2;
+const TILE_OPTIONS: &str = "1_1";
+
+/// The width and height of the area around the location shown by terminal maps in pixels (two tiles).
+const TERMINAL_MAP_SIZE: u32 = TILE_SIZE * 2;
+
+/// The shades of the cells of terminal maps by the share of the area with precipitation (none, up to 25%, 50%, 75%
+/// and more).
+pub const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// The color of the location marker (RGBA).
+const MARKER_COLOR: [u8; 4] = [220, 20, 60, 255];
+
+/// The half length of the arms of the location marker in pixels.
+const MARKER_SIZE: i64 = 6;
+
+/// Represents errors related to radar maps.
+#[derive(Error, Debug)]
+pub enum MapError {
+    /// An error indicating that no radar frames are available.
+    #[error("No radar frames are available from RainViewer")]
+    NoFrames,
+
+    /// An error indicating a failure to decode or encode a PNG image.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the reason.
+    #[error("Failed to process the radar image - {0}")]
+    Image(String),
+
+    /// An error indicating a failure to write the radar image file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the image file.
+    #[error("Failed to write the radar image '{1}'")]
+    Io(std::io::Error, String),
+}
+
+/// Represents the radar frames listed by the RainViewer API.
+#[derive(Deserialize, Debug)]
+struct WeatherMaps {
+    host: String,
+    radar: RadarFrames,
+}
+
+/// Represents the past radar frames and the nowcast frames of RainViewer.
+#[derive(Deserialize, Debug)]
+struct RadarFrames {
+    #[serde(default)]
+    past: Vec<RadarFrame>,
+}
+
+/// Represents a radar frame of RainViewer.
+#[derive(Deserialize, Clone, Debug)]
+pub struct RadarFrame {
+    /// The time of the frame as a Unix timestamp.
+    pub time: i64,
+    /// The path of the frame's tiles on the tile host.
+    pub path: String,
+}
+
+/// Represents an image with 8-bit RGBA pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// The RGBA values of the pixels, row by row.
+    pub pixels: Vec<u8>,
+}
+
+/// `Image` constructors and methods
+impl Image {
+    /// Creates a transparent image.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width in pixels.
+    /// * `height` - The height in pixels.
+    ///
+    /// # Returns
+    ///
+    /// The transparent `Image`.
+    pub fn new(width: u32, height: u32) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    /// Decodes a PNG image, converting any color type to RGBA.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The PNG file contents.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded `Image` or a `MapError` if the data isn't a valid PNG image.
+    pub fn decode_png(data: &[u8]) -> Result<Self, MapError> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder
+            .read_info()
+            .map_err(|err| MapError::Image(err.to_string()))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|err| MapError::Image(err.to_string()))?;
+        let buffer = &buffer[..info.buffer_size()];
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buffer.to_vec(),
+            png::ColorType::Rgb => buffer
+                .as_chunks::<3>()
+                .0
+                .iter()
+                .flat_map(|&[red, green, blue]| [red, green, blue, 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .as_chunks::<2>()
+                .0
+                .iter()
+                .flat_map(|&[gray, alpha]| [gray, gray, gray, alpha])
+                .collect(),
+            png::ColorType::Grayscale => buffer
+                .iter()
+                .flat_map(|&gray| [gray, gray, gray, 255])
+                .collect(),
+            png::ColorType::Indexed => {
+                return Err(MapError::Image("unexpanded indexed colors".to_owned()))
+            }
+        };
+
+        Ok(Image {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// Encodes the image as an RGBA PNG image.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the PNG file contents or a `MapError` if the image can't be encoded.
+    pub fn encode_png(&self) -> Result<Vec<u8>, MapError> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(|err| MapError::Image(err.to_string()))?;
+
+        Ok(data)
+    }
+
+    /// Returns the RGBA value of a pixel; transparent outside the image.
+    pub fn pixel(&self, x: i64, y: i64) -> [u8; 4] {
+        if x < 0 || y < 0 || x >= i64::from(self.width) || y >= i64::from(self.height) {
+            return [0; 4];
+        }
+        let index = ((y as u32 * self.width + x as u32) * 4) as usize;
+
+        [
+            self.pixels[index],
+            self.pixels[index + 1],
+            self.pixels[index + 2],
+            self.pixels[index + 3],
+        ]
+    }
+
+    /// Sets the RGBA value of a pixel; pixels outside the image are ignored.
+    fn set_pixel(&mut self, x: i64, y: i64, rgba: [u8; 4]) {
+        if x < 0 || y < 0 || x >= i64::from(self.width) || y >= i64::from(self.height) {
+            return;
+        }
+        let index = ((y as u32 * self.width + x as u32) * 4) as usize;
+
+        self.pixels[index..index + 4].copy_from_slice(&rgba);
+    }
+
+    /// Copies another image (e.g. a tile) into the image; the parts outside the image are cut off.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to copy.
+    /// * `x` - The column of the top left corner of the copied image.
+    /// * `y` - The row of the top left corner of the copied image.
+    pub fn draw(&mut self, image: &Image, x: u32, y: u32) {
+        for row in 0..image.height {
+            for column in 0..image.width {
+                let rgba = image.pixel(i64::from(column), i64::from(row));
+                self.set_pixel(i64::from(x + column), i64::from(y + row), rgba);
+            }
+        }
+    }
+
+    /// Draws a cross marking a location.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the center of the cross.
+    /// * `y` - The row of the center of the cross.
+    pub fn draw_marker(&mut self, x: u32, y: u32) {
+        let (x, y) = (i64::from(x), i64::from(y));
+
+        for offset in -MARKER_SIZE..=MARKER_SIZE {
+            for width in -1..=1 {
+                self.set_pixel(x + offset, y + width, MARKER_COLOR);
+                self.set_pixel(x + width, y + offset, MARKER_COLOR);
+            }
+        }
+    }
+}
+
+/// Represents a stitched radar map around a location.
+#[derive(Clone, Debug)]
+pub struct RadarMap {
+    /// The stitched radar tiles; pixels without precipitation are transparent.
+    pub image: Image,
+    /// The time of the radar frame as a Unix timestamp.
+    pub time: i64,
+    /// The column and the row of the location in the image.
+    pub location: (u32, u32),
+}
+
+/// `RadarMap` methods
+impl RadarMap {
+    /// Computes the share of the area with precipitation in the cells of a coarse grid centered on the location.
+    ///
+    /// The grid covers two tiles around the location, which always lie within the stitched image.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The number of columns of the grid.
+    /// * `rows` - The number of rows of the grid.
+    ///
+    /// # Returns
+    ///
+    /// The shares (0.0 to 1.0) of the cells, row by row; the location is in the cell `(columns / 2, rows / 2)`.
+    pub fn coverage(&self, columns: u32, rows: u32) -> Vec<Vec<f32>> {
+        let left = i64::from(self.location.0) - i64::from(TERMINAL_MAP_SIZE / 2);
+        let top = i64::from(self.location.1) - i64::from(TERMINAL_MAP_SIZE / 2);
+        let bounds = |index: u32, count: u32| {
+            let start = i64::from(index * TERMINAL_MAP_SIZE / count);
+            let end = i64::from((index + 1) * TERMINAL_MAP_SIZE / count).max(start + 1);
+            start..end
+        };
+
+        (0..rows)
+            .map(|row| {
+                (0..columns)
+                    .map(|column| {
+                        let (xs, ys) = (bounds(column, columns), bounds(row, rows));
+                        let total = (xs.end - xs.start) * (ys.end - ys.start);
+                        let wet = ys
+                            .flat_map(|y| xs.clone().map(move |x| (x, y)))
+                            .filter(|&(x, y)| self.image.pixel(left + x, top + y)[3] > 0)
+                            .count();
+
+                        wet as f32 / total as f32
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Selects the shade of a cell of a terminal map.
+///
+/// # Arguments
+///
+/// * `coverage` - The share (0.0 to 1.0) of the area of the cell with precipitation.
+///
+/// # Returns
+///
+/// The shade character of the cell (see `SHADES`).
+pub fn shade(coverage: f32) -> char {
+    if coverage <= 0.0 {
+        SHADES[0]
+    } else {
+        SHADES[((coverage * 4.0).ceil() as usize).clamp(1, SHADES.len() - 1)]
+    }
+}
+
+/// Client of the free RainViewer API that downloads precipitation radar tiles.
+#[derive(Debug)]
+pub struct RainViewer {
+    url: String,
+    client: Client,
+}
+
+/// `RainViewer` constructors and methods
+impl RainViewer {
+    /// Creates a new instance of `RainViewer` using the public RainViewer API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The HTTP client used for requests.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `RainViewer`.
+    pub fn new(client: Client) -> Self {
+        RainViewer {
+            url: RAINVIEWER_MAPS_URL.to_owned(),
+            client,
+        }
+    }
+
+    /// Asynchronously downloads the radar tiles of the latest frame around a location and stitches them into
+    /// a map centered on the tile of the location.
+    ///
+    /// # Arguments
+    ///
+    /// * `latitude` - The latitude of the location.
+    /// * `longitude` - The longitude of the location.
+    /// * `zoom` - The zoom level of the tiles (up to `MAX_ZOOM`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `RadarMap` or an error if the tiles can't be downloaded or decoded.
+    pub async fn radar_map(&self, latitude: f64, longitude: f64, zoom: u8) -> Result<RadarMap> {
+        let body = self
+            .client
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let maps: WeatherMaps = serde_json::from_slice(&body)?;
+        let frame = maps.radar.past.last().ok_or(MapError::NoFrames)?;
+
+        let (tile_x, tile_y) = tile_position(latitude, longitude, zoom);
+        let tiles = 1_i64 << zoom;
+        let first_x = tile_x.floor() as i64 - i64::from(GRID_SIZE / 2);
+        let first_y = tile_y.floor() as i64 - i64::from(GRID_SIZE / 2);
+
+        let mut image = Image::new(TILE_SIZE * GRID_SIZE, TILE_SIZE * GRID_SIZE);
+        for row in 0..GRID_SIZE {
+            for column in 0..GRID_SIZE {
+                let y = first_y + i64::from(row);
+                // there are no tiles beyond the poles, but the longitude wraps around
+                if !(0..tiles).contains(&y) {
+                    continue;
+                }
+                let x = (first_x + i64::from(column)).rem_euclid(tiles);
+
+                let url = format!(
+                    "{}{}/{TILE_SIZE}/{zoom}/{x}/{y}/{TILE_COLOR_SCHEME}/{TILE_OPTIONS}.png",
+                    maps.host, frame.path
+                );
+                let data = self
+                    .client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await?;
+                image.draw(
+                    &Image::decode_png(&data)?,
+                    column * TILE_SIZE,
+                    row * TILE_SIZE,
+                );
+            }
+        }
+
+        let location = (
+            ((tile_x - first_x as f64) * f64::from(TILE_SIZE)) as u32,
+            ((tile_y - first_y as f64) * f64::from(TILE_SIZE)) as u32,
+        );
+
+        Ok(RadarMap {
+            image,
+            time: frame.time,
+            location,
+        })
+    }
+}
+
+/// Computes the position of a location in the grid of map tiles (Web Mercator projection).
+///
+/// # Arguments
+///
+/// * `latitude` - The latitude of the location.
+/// * `longitude` - The longitude of the location.
+/// * `zoom` - The zoom level; the world is `2^zoom` tiles wide and high.
+///
+/// # Returns
+///
+/// The fractional column and row of the location; the integer parts are the tile containing it.
+pub fn tile_position(latitude: f64, longitude: f64, zoom: u8) -> (f64, f64) {
+    let tiles = f64::from(1_u32 << zoom);
+    // the projection is undefined at the poles, so the latitude is limited to the extent of the tiles
+    let latitude = latitude.clamp(-85.051_128, 85.051_128).to_radians();
+
+    let x = (longitude + 180.0) / 360.0 * tiles;
+    let y = (1.0 - latitude.tan().asinh() / PI) / 2.0 * tiles;
+
+    (x, y)
+}
+
+/// Parses a `latitude,longitude` query (e.g. a location resolved from an address).
+///
+/// # Arguments
+///
+/// * `query` - The query sent to providers.
+///
+/// # Returns
+///
+/// The latitude and the longitude or `None` if the query isn't a pair of coordinates.
+pub fn parse_coordinates(query: &str) -> Option<(f64, f64)> {
+    let (latitude, longitude) = query.split_once(',')?;
+    let latitude: f64 = latitude.trim().parse().ok()?;
+    let longitude: f64 = longitude.trim().parse().ok()?;
+
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+/// Writes a radar map to a PNG file.
+///
+/// # Arguments
+///
+/// * `radar_map` - The radar map.
+/// * `path` - The path to the image file.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `MapError` if the image can't be encoded or written.
+pub fn write_radar_map(radar_map: &RadarMap, path: &Path) -> Result<(), MapError> {
+    let mut image = radar_map.image.clone();
+    image.draw_marker(radar_map.location.0, radar_map.location.1);
+
+    std::fs::write(path, image.encode_png()?).map_err(|err| {
+        MapError::Io(
+            err,
+            path.display().to_string().paint(Role::Warning).to_string(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, 0.0, 1, (1.0, 1.0))]
+    #[case(51.5074, -0.1278, 6, (31.977, 21.282))]
+    #[case(-33.8688, 151.2093, 7, (117.763, 76.812))]
+    #[case(90.0, 180.0, 2, (4.0, 0.0))]
+    fn test_tile_position(
+        #[case] latitude: f64,
+        #[case] longitude: f64,
+        #[case] zoom: u8,
+        #[case] expected: (f64, f64),
+    ) {
+        let (x, y) = tile_position(latitude, longitude, zoom);
+
+        assert!((x - expected.0).abs() < 0.001, "{x}");
+        assert!((y - expected.1).abs() < 0.001, "{y}");
+    }
+
+    #[rstest]
+    #[case("51.5074,-0.1278", Some((51.5074, -0.1278)))]
+    #[case(" -33.87 , 151.21 ", Some((-33.87, 151.21)))]
+    #[case("91.0,0.0", None)]
+    #[case("London", None)]
+    #[case("10115,DE", None)]
+    fn test_parse_coordinates(#[case] query: &str, #[case] expected: Option<(f64, f64)>) {
+        assert_eq!(parse_coordinates(query), expected);
+    }
+
+    #[rstest]
+    fn test_image_png_round_trip() {
+        let mut tile = Image::new(2, 2);
+        tile.set_pixel(1, 0, [10, 20, 30, 255]);
+        let mut image = Image::new(4, 3);
+        image.draw(&tile, 2, 1);
+        // the part outside the image is cut off
+        image.draw(&tile, 3, 2);
+
+        let result = Image::decode_png(&image.encode_png().unwrap()).unwrap();
+
+        assert_eq!(result, image);
+        assert_eq!(result.pixel(3, 1), [10, 20, 30, 255]);
+        assert_eq!(result.pixel(2, 1), [0; 4]);
+        assert_eq!(result.pixel(4, 1), [0; 4]);
+        assert!(Image::decode_png(b"not a png").is_err());
+    }
+
+    #[rstest]
+    fn test_radar_map_coverage() {
+        let mut image = Image::new(TILE_SIZE * GRID_SIZE, TILE_SIZE * GRID_SIZE);
+        // rain over the top left quarter of the area around the location
+        let mut rain = Image::new(TILE_SIZE, TILE_SIZE);
+        rain.pixels.fill(255);
+        image.draw(&rain, 128, 128);
+        let radar_map = RadarMap {
+            image,
+            time: 1_720_000_000,
+            location: (384, 384),
+        };
+
+        let result = radar_map.coverage(4, 2);
+
+        assert_eq!(result, vec![vec![1.0, 1.0, 0.0, 0.0], vec![0.0; 4]]);
+    }
+
+    #[rstest]
+    #[case(0.0, ' ')]
+    #[case(0.01, '░')]
+    #[case(0.25, '░')]
+    #[case(0.4, '▒')]
+    #[case(0.7, '▓')]
+    #[case(1.0, '█')]
+    fn test_shade(#[case] coverage: f32, #[case] expected: char) {
+        assert_eq!(shade(coverage), expected);
+    }
+
+    #[rstest]
+    fn test_draw_marker() {
+        let mut image = Image::new(20, 20);
+
+        image.draw_marker(10, 10);
+
+        assert_eq!(image.pixel(10, 10), MARKER_COLOR);
+        assert_eq!(image.pixel(16, 11), MARKER_COLOR);
+        assert_eq!(image.pixel(10, 4), MARKER_COLOR);
+        assert_eq!(image.pixel(16, 16), [0; 4]);
+    }
+}
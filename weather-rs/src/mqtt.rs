@@ -0,0 +1,264 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use weather_api_services::models::WeatherData;
+use weather_api_services::output::IntoOutput;
+use weather_api_services::units::Units;
+
+/// The identifier prefix of Home Assistant devices and entities created by the application.
+const DEVICE_PREFIX: &str = "weather_rs";
+
+/// Represents a sensor of the weather data announced to Home Assistant.
+struct Sensor {
+    /// The field of the `WeatherData` JSON with the value of the sensor.
+    field: &'static str,
+    /// The name of the entity (Home Assistant prefixes it with the device name).
+    name: &'static str,
+    /// The Home Assistant device class; `None` for sensors without a class.
+    device_class: Option<&'static str>,
+    /// Returns the unit of the sensor; `None` for sensors without a unit.
+    unit: fn(Units) -> Option<&'static str>,
+}
+
+/// The sensors of the weather data announced to Home Assistant.
+const SENSORS: [Sensor; 9] = [
+    Sensor {
+        field: "temp",
+        name: "Temperature",
+        device_class: Some("temperature"),
+        unit: |units| Some(units.temperature_symbol()),
+    },
+    Sensor {
+        field: "feels_like",
+        name: "Feels like",
+        device_class: Some("temperature"),
+        unit: |units| Some(units.temperature_symbol()),
+    },
+    Sensor {
+        field: "humidity",
+        name: "Humidity",
+        device_class: Some("humidity"),
+        unit: |_| Some("%"),
+    },
+    Sensor {
+        field: "dew_point",
+        name: "Dew point",
+        device_class: Some("temperature"),
+        unit: |units| Some(units.temperature_symbol()),
+    },
+    Sensor {
+        field: "pressure",
+        name: "Pressure",
+        device_class: Some("atmospheric_pressure"),
+        unit: |_| Some("hPa"),
+    },
+    Sensor {
+        field: "wind_speed",
+        name: "Wind speed",
+        device_class: Some("wind_speed"),
+        unit: |units| Some(units.speed_symbol()),
+    },
+    Sensor {
+        field: "visibility",
+        name: "Visibility",
+        device_class: Some("distance"),
+        unit: |_| Some("m"),
+    },
+    Sensor {
+        field: "uv_index",
+        name: "UV index",
+        device_class: None,
+        unit: |_| None,
+    },
+    Sensor {
+        field: "description",
+        name: "Condition",
+        device_class: None,
+        unit: |_| None,
+    },
+];
+
+/// Represents an MQTT message to publish.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: Value,
+    /// A flag to indicate if the broker should retain the message for new subscribers.
+    pub retain: bool,
+}
+
+/// Converts a location name (e.g. an alias) to an identifier usable in MQTT topics and Home Assistant IDs.
+///
+/// # Arguments
+///
+/// * `name` - The location name.
+///
+/// # Returns
+///
+/// The lowercase name with runs of other characters than ASCII letters and digits replaced by `_`
+/// (e.g. `new_york` for `New York, US`).
+pub fn object_id(name: &str) -> String {
+    name.to_lowercase()
+        .split(|char: char| !char.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Returns the topic of the state messages of a location (e.g. `weather-rs/london/state`).
+///
+/// # Arguments
+///
+/// * `topic` - The base topic.
+/// * `object_id` - The identifier of the location (see `object_id`).
+pub fn state_topic(topic: &str, object_id: &str) -> String {
+    format!("{}/{object_id}/state", topic.trim_end_matches('/'))
+}
+
+/// Creates the state message with the weather data of a location.
+///
+/// # Arguments
+///
+/// * `topic` - The base topic.
+/// * `object_id` - The identifier of the location (see `object_id`).
+/// * `weather_data` - The weather data of the location.
+/// * `units` - The units of the temperatures and speeds in the payload.
+///
+/// # Returns
+///
+/// A `Result` containing the message or an error if the weather data can't be serialized.
+pub fn state_message(
+    topic: &str,
+    object_id: &str,
+    weather_data: WeatherData,
+    units: Units,
+) -> serde_json::Result<MqttMessage> {
+    Ok(MqttMessage {
+        topic: state_topic(topic, object_id),
+        payload: serde_json::to_value(weather_data.into_output(units))?,
+        retain: true,
+    })
+}
+
+/// Creates the Home Assistant MQTT Discovery config messages of the sensors of a location.
+///
+/// Every sensor reads its value from the state message (see `state_message`); the sensors of a location are grouped
+/// into a device named after the location.
+///
+/// # Arguments
+///
+/// * `discovery_prefix` - The discovery prefix of Home Assistant (`homeassistant` by default).
+/// * `topic` - The base topic of the state messages.
+/// * `name` - The name of the location (e.g. an alias).
+/// * `units` - The units of the state messages.
+///
+/// # Returns
+///
+/// The retained config messages, a message per sensor.
+pub fn discovery_messages(
+    discovery_prefix: &str,
+    topic: &str,
+    name: &str,
+    units: Units,
+) -> Vec<MqttMessage> {
+    let object_id = object_id(name);
+    let node_id = format!("{DEVICE_PREFIX}_{object_id}");
+    let state_topic = state_topic(topic, &object_id);
+
+    SENSORS
+        .iter()
+        .map(|sensor| {
+            let mut payload = json!({
+                "name": sensor.name,
+                "unique_id": format!("{node_id}_{}", sensor.field),
+                "state_topic": state_topic,
+                "value_template": format!("{{{{ value_json.{} }}}}", sensor.field),
+                "device": {
+                    "identifiers": [node_id],
+                    "name": format!("{name} weather"),
+                    "manufacturer": "weather-rs",
+                },
+            });
+            if let Some(device_class) = sensor.device_class {
+                payload["device_class"] = json!(device_class);
+            }
+            if let Some(unit) = (sensor.unit)(units) {
+                payload["unit_of_measurement"] = json!(unit);
+                payload["state_class"] = json!("measurement");
+            }
+
+            MqttMessage {
+                topic: format!(
+                    "{}/sensor/{node_id}/{}/config",
+                    discovery_prefix.trim_end_matches('/'),
+                    sensor.field
+                ),
+                payload,
+                retain: true,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use weather_api_services::quantities::{Celsius, MetersPerSecond};
+
+    #[rstest]
+    #[case("London", "london")]
+    #[case("New York, US", "new_york_us")]
+    #[case("  Home -- Office ", "home_office")]
+    fn test_object_id(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(object_id(name), expected);
+    }
+
+    #[rstest]
+    fn test_discovery_messages() {
+        let result =
+            discovery_messages("homeassistant/", "weather-rs", "New York", Units::Imperial);
+
+        assert_eq!(result.len(), SENSORS.len());
+        let temperature = &result[0];
+        assert_eq!(
+            temperature.topic,
+            "homeassistant/sensor/weather_rs_new_york/temp/config"
+        );
+        assert!(temperature.retain);
+        assert_eq!(temperature.payload["unique_id"], "weather_rs_new_york_temp");
+        assert_eq!(
+            temperature.payload["state_topic"],
+            "weather-rs/new_york/state"
+        );
+        assert_eq!(
+            temperature.payload["value_template"],
+            "{{ value_json.temp }}"
+        );
+        assert_eq!(temperature.payload["unit_of_measurement"], "°F");
+        assert_eq!(temperature.payload["device"]["name"], "New York weather");
+        let condition = result.last().unwrap();
+        assert!(condition.payload.get("unit_of_measurement").is_none());
+        assert!(condition.payload.get("device_class").is_none());
+    }
+
+    #[rstest]
+    #[case(Units::Metric, 20.0, 10.0)]
+    #[case(Units::Imperial, 68.0, 22.4)]
+    fn test_state_message(
+        #[case] units: Units,
+        #[case] expected_temp: f64,
+        #[case] expected_wind_speed: f64,
+    ) {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(20.0))
+            .wind_speed(MetersPerSecond(10.0))
+            .build();
+
+        let result = state_message("weather-rs", "london", weather_data, units).unwrap();
+
+        assert_eq!(result.topic, "weather-rs/london/state");
+        assert_eq!(result.payload["temp"].as_f64().unwrap(), expected_temp);
+        assert!((result.payload["wind_speed"].as_f64().unwrap() - expected_wind_speed).abs() < 0.1);
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+
+use weather_api_services::models::MinutePrecipitation;
+
+/// The length of a nowcast timeline in minutes.
+pub const TIMELINE_MINUTES: usize = 60;
+
+/// The lowest precipitation intensity in millimeters per hour counted as rain (drizzle below it is ignored).
+const RAIN_THRESHOLD: f32 = 0.1;
+
+/// The precipitation intensity in millimeters per hour shown as a full bar of the timeline.
+const FULL_BAR_INTENSITY: f32 = 10.0;
+
+/// The bars of the timeline by increasing precipitation intensity.
+const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Represents the intensity of rain (AMS classification by the rainfall rate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Intensity {
+    /// Less than 2.5 mm per hour.
+    Light,
+    /// From 2.5 to 7.6 mm per hour.
+    Moderate,
+    /// More than 7.6 mm per hour.
+    Heavy,
+}
+
+/// `Intensity` constructors
+impl Intensity {
+    /// Classifies a precipitation intensity.
+    ///
+    /// # Arguments
+    ///
+    /// * `precipitation` - The precipitation intensity in millimeters per hour.
+    ///
+    /// # Returns
+    ///
+    /// The `Intensity` of the rain or `None` if it doesn't rain.
+    pub fn from_precipitation(precipitation: f32) -> Option<Self> {
+        match precipitation {
+            precipitation if precipitation < RAIN_THRESHOLD => None,
+            precipitation if precipitation < 2.5 => Some(Intensity::Light),
+            precipitation if precipitation <= 7.6 => Some(Intensity::Moderate),
+            _ => Some(Intensity::Heavy),
+        }
+    }
+}
+
+impl fmt::Display for Intensity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Intensity::Light => write!(f, "Light rain"),
+            Intensity::Moderate => write!(f, "Moderate rain"),
+            Intensity::Heavy => write!(f, "Heavy rain"),
+        }
+    }
+}
+
+/// Spreads the forecasted steps (e.g. minutes or 5 minute steps) over the minutes of the next hour.
+///
+/// # Arguments
+///
+/// * `nowcast` - The forecasted steps in millimeters per hour, the current one first.
+///
+/// # Returns
+///
+/// The precipitation intensity of every minute (up to `TIMELINE_MINUTES`); minutes after the last step are missing.
+pub fn minutes(nowcast: &[MinutePrecipitation]) -> Vec<f32> {
+    let Some(first) = nowcast.first() else {
+        return Vec::new();
+    };
+    let last = nowcast
+        .last()
+        .map_or(0, |last| (last.time - first.time) / 60) as usize;
+
+    (0..TIMELINE_MINUTES.min(last + 1))
+        .map(|minute| {
+            nowcast
+                .iter()
+                .take_while(|step| (step.time - first.time) / 60 <= minute as i64)
+                .last()
+                .map_or(0.0, |step| step.precipitation)
+        })
+        .collect()
+}
+
+/// Renders the timeline of the next hour as a bar per minute.
+///
+/// # Arguments
+///
+/// * `minutes` - The precipitation intensity of every minute in millimeters per hour (see `minutes`).
+///
+/// # Returns
+///
+/// The bars of the minutes; the full bar is `FULL_BAR_INTENSITY` or more.
+pub fn timeline(minutes: &[f32]) -> String {
+    minutes
+        .iter()
+        .map(|&precipitation| {
+            if precipitation < RAIN_THRESHOLD {
+                BARS[0]
+            } else {
+                let level = (precipitation / FULL_BAR_INTENSITY * (BARS.len() - 1) as f32).ceil();
+                BARS[(level as usize).clamp(1, BARS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+/// Sums up the next hour in a concise verdict (e.g. `Light rain starting in 14 minutes`).
+///
+/// The intensity of a rain spell is its peak intensity.
+///
+/// # Arguments
+///
+/// * `minutes` - The precipitation intensity of every minute in millimeters per hour (see `minutes`).
+///
+/// # Returns
+///
+/// The verdict of the next hour.
+pub fn verdict(minutes: &[f32]) -> String {
+    let is_raining = |precipitation: &f32| Intensity::from_precipitation(*precipitation).is_some();
+    let spell_intensity = |spell: &[f32]| {
+        spell
+            .iter()
+            .take_while(|precipitation| is_raining(precipitation))
+            .fold(None, |peak, &precipitation| {
+                peak.max(Intensity::from_precipitation(precipitation))
+            })
+    };
+
+    let Some(start) = minutes.iter().position(is_raining) else {
+        return "No rain expected in the next hour".to_owned();
+    };
+    let intensity = spell_intensity(&minutes[start..]).unwrap_or(Intensity::Light);
+    if start > 0 {
+        return format!("{intensity} starting in {}", format_minutes(start));
+    }
+
+    match minutes
+        .iter()
+        .position(|precipitation| !is_raining(precipitation))
+    {
+        Some(end) => format!("{intensity} stopping in {}", format_minutes(end)),
+        None => format!("{intensity} for the next hour"),
+    }
+}
+
+/// Formats a number of minutes (e.g. `1 minute` or `14 minutes`).
+fn format_minutes(minutes: usize) -> String {
+    match minutes {
+        1 => "1 minute".to_owned(),
+        minutes => format!("{minutes} minutes"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn nowcast(steps: &[(i64, f32)]) -> Vec<MinutePrecipitation> {
+        steps
+            .iter()
+            .map(|&(minute, precipitation)| MinutePrecipitation {
+                time: 1_702_209_600 + minute * 60,
+                precipitation,
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(0.05, None)]
+    #[case(0.8, Some(Intensity::Light))]
+    #[case(5.0, Some(Intensity::Moderate))]
+    #[case(12.0, Some(Intensity::Heavy))]
+    fn test_intensity_from_precipitation(
+        #[case] precipitation: f32,
+        #[case] expected: Option<Intensity>,
+    ) {
+        assert_eq!(Intensity::from_precipitation(precipitation), expected);
+    }
+
+    #[rstest]
+    #[case(&[], vec![])]
+    #[case(&[(0, 0.0), (1, 1.0), (2, 2.0)], vec![0.0, 1.0, 2.0])]
+    #[case(&[(0, 0.0), (5, 3.0), (10, 0.0)], [vec![0.0; 5], vec![3.0; 5], vec![0.0]].concat())]
+    #[case(&[(0, 1.0), (90, 1.0)], vec![1.0; TIMELINE_MINUTES])]
+    fn test_minutes(#[case] steps: &[(i64, f32)], #[case] expected: Vec<f32>) {
+        assert_eq!(minutes(&nowcast(steps)), expected);
+    }
+
+    #[rstest]
+    fn test_timeline() {
+        assert_eq!(timeline(&[0.0, 0.05, 0.2, 2.5, 5.0, 9.0, 25.0]), "  ▁▂▄██");
+    }
+
+    #[rstest]
+    #[case(vec![0.0; 60], "No rain expected in the next hour")]
+    #[case([vec![0.0; 14], vec![0.5; 10], vec![0.0; 36]].concat(), "Light rain starting in 14 minutes")]
+    #[case([vec![0.0], vec![0.5, 4.0, 0.5]].concat(), "Moderate rain starting in 1 minute")]
+    #[case([vec![9.0; 20], vec![0.0; 40]].concat(), "Heavy rain stopping in 20 minutes")]
+    #[case(vec![1.0; 60], "Light rain for the next hour")]
+    fn test_verdict(#[case] minutes: Vec<f32>, #[case] expected: &str) {
+        assert_eq!(verdict(&minutes), expected);
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::{APP_NAME, CONFIG_NAME};
+
+/// Represents errors related to directories of the application.
+#[derive(Error, Debug)]
+pub enum PathsError {
+    /// An error indicating that a directory of the application can't be determined (e.g. `HOME` isn't set).
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The kind of the directory.
+    #[error("Failed to determine the {0:?} directory of the application; check the 'HOME' environment variable")]
+    Directory(AppDir),
+}
+
+/// Represents the kinds of directories where the application keeps its files.
+///
+/// | Kind   | Linux                                      | macOS                          | Windows                   |
+/// | ------ | ------------------------------------------ | ------------------------------ | ------------------------- |
+/// | Config | `$XDG_CONFIG_HOME` or `$HOME/.config`      | `$HOME/Library/Preferences`    | `{FOLDERID_RoamingAppData}` |
+/// | Cache  | `$XDG_CACHE_HOME` or `$HOME/.cache`        | `$HOME/Library/Caches`         | `{FOLDERID_LocalAppData}` |
+/// | State  | `$XDG_STATE_HOME` or `$HOME/.local/state`  | `$HOME/Library/Application Support` | `{FOLDERID_LocalAppData}` |
+/// | Data   | `$XDG_DATA_HOME` or `$HOME/.local/share`   | `$HOME/Library/Application Support` | `{FOLDERID_RoamingAppData}` |
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AppDir {
+    /// The configuration (managed by `confy`).
+    Config,
+    /// Files that can be recreated at any time (e.g. fetched climate normals).
+    Cache,
+    /// Files that persist between runs but aren't worth a backup (e.g. usage tracking).
+    State,
+    /// User data that should be kept (e.g. location aliases).
+    Data,
+}
+
+/// Returns the directory of the application of the given kind.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of the directory.
+///
+/// # Returns
+///
+/// A `Result` containing the path to the directory (e.g. `~/.cache/weather-rs`) or a `PathsError`
+/// if it can't be determined.
+pub fn dir(kind: AppDir) -> Result<PathBuf, PathsError> {
+    let base_dir = match kind {
+        AppDir::Config => {
+            return confy::get_configuration_file_path(APP_NAME, CONFIG_NAME)
+                .ok()
+                .as_deref()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+                .ok_or(PathsError::Directory(kind));
+        }
+        AppDir::Cache => dirs_next::cache_dir(),
+        AppDir::State => state_dir(),
+        AppDir::Data => dirs_next::data_dir(),
+    };
+
+    base_dir
+        .map(|base_dir| base_dir.join(APP_NAME))
+        .ok_or(PathsError::Directory(kind))
+}
+
+/// Returns the path to the configuration file for messages in the native format of the platform
+/// (e.g. `/home/user/.config/weather-rs/config.toml` or `C:\Users\user\AppData\Roaming\weather-rs\config\config.toml`).
+///
+/// # Returns
+///
+/// The path or `weather-rs/config.toml` if the configuration directory can't be determined.
+pub fn config_file_hint() -> String {
+    confy::get_configuration_file_path(APP_NAME, CONFIG_NAME).map_or_else(
+        |_| format!("{APP_NAME}/{CONFIG_NAME}.toml"),
+        |path| path.display().to_string(),
+    )
+}
+
+/// Returns the path to a file in the directory of the application of the given kind.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of the directory.
+/// * `name` - The name of the file (e.g. `climate-normals.toml`).
+///
+/// # Returns
+///
+/// A `Result` containing the path to the file or a `PathsError` if the directory can't be determined.
+pub fn file(kind: AppDir, name: &str) -> Result<PathBuf, PathsError> {
+    dir(kind).map(|dir| dir.join(name))
+}
+
+/// Returns the user's state directory (`$XDG_STATE_HOME` or `$HOME/.local/state` on Linux),
+/// which isn't provided by `dirs_next`.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn state_dir() -> Option<PathBuf> {
+    xdg_dir(
+        std::env::var_os("XDG_STATE_HOME").map(PathBuf::from),
+        dirs_next::home_dir(),
+        ".local/state",
+    )
+}
+
+/// Returns the user's state directory; the local data directory is used on macOS and Windows.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn state_dir() -> Option<PathBuf> {
+    dirs_next::data_local_dir()
+}
+
+/// Resolves an XDG base directory from its environment variable, falling back to a path relative to the home
+/// directory if the variable isn't an absolute path (as required by the XDG Base Directory Specification).
+#[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+fn xdg_dir(env_dir: Option<PathBuf>, home_dir: Option<PathBuf>, fallback: &str) -> Option<PathBuf> {
+    env_dir
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home_dir.map(|home_dir| home_dir.join(fallback)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Some("/var/state"), Some("/home/user"), Some("/var/state"))]
+    #[case(
+        Some("relative/state"),
+        Some("/home/user"),
+        Some("/home/user/.local/state")
+    )]
+    #[case(None, Some("/home/user"), Some("/home/user/.local/state"))]
+    #[case(None, None, None)]
+    fn test_xdg_dir(
+        #[case] env_dir: Option<&str>,
+        #[case] home_dir: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let result = xdg_dir(
+            env_dir.map(PathBuf::from),
+            home_dir.map(PathBuf::from),
+            ".local/state",
+        );
+
+        assert_eq!(result, expected.map(PathBuf::from));
+    }
+
+    #[rstest]
+    fn test_file() {
+        let result = file(AppDir::Cache, "climate-normals.toml");
+
+        if let Ok(path) = result {
+            assert!(path.ends_with("weather-rs/climate-normals.toml"));
+        }
+    }
+}
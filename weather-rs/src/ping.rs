@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use narrate::anyhow::Result;
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+use weather_api_services::http::{HttpResponse, Middleware, Next};
+
+/// The address whose current weather is requested to check a provider.
+pub const PING_ADDRESS: &str = "London";
+
+/// Represents errors related to the health check of providers.
+#[derive(Error, Debug)]
+pub enum PingError {
+    /// An error indicating that some providers failed the health check.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string listing the failed providers.
+    #[error("Providers failed the health check - {0}")]
+    Failed(String),
+}
+
+/// Middleware that records the HTTP status of the last response passing through it.
+#[derive(Clone, Debug, Default)]
+pub struct StatusProbe {
+    status: Arc<Mutex<Option<u16>>>,
+}
+
+/// `StatusProbe` methods
+impl StatusProbe {
+    /// Returns the HTTP status of the last response; `None` if no response was received (e.g. for plugins
+    /// or network errors).
+    pub fn status(&self) -> Option<u16> {
+        self.status.lock().ok().and_then(|status| *status)
+    }
+}
+
+#[async_trait]
+impl Middleware for StatusProbe {
+    async fn handle(&self, request: RequestBuilder, next: Next<'_>) -> Result<HttpResponse> {
+        let result = next.run(request).await;
+
+        if let (Ok(response), Ok(mut status)) = (&result, self.status.lock()) {
+            *status = Some(response.status.as_u16());
+        }
+
+        result
+    }
+}
+
+/// Represents the result of the health check of a provider.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct PingResult {
+    pub provider: String,
+    /// The HTTP status of the last response; `None` if no response was received.
+    pub status: Option<u16>,
+    /// The round-trip time of the request (including retries) in milliseconds.
+    pub latency_ms: u64,
+    /// The error of the request; `None` if the provider returned weather data.
+    pub error: Option<String>,
+}
+
+/// `PingResult` methods
+impl PingResult {
+    /// Checks whether the provider returned weather data.
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Checks the results of a health check.
+///
+/// # Arguments
+///
+/// * `results` - The results of the providers.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `PingError` listing the failed providers.
+pub fn check_results(results: &[PingResult]) -> std::result::Result<(), PingError> {
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|result| !result.is_healthy())
+        .map(|result| result.provider.as_str())
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(PingError::Failed(failed.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn ping_result(provider: &str, error: Option<&str>) -> PingResult {
+        PingResult {
+            provider: provider.to_owned(),
+            status: Some(if error.is_some() { 401 } else { 200 }),
+            latency_ms: 120,
+            error: error.map(str::to_owned),
+        }
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![ping_result("open-weather", None)], None)]
+    #[case(
+        vec![
+            ping_result("open-weather", Some("Unauthorized")),
+            ping_result("weather-api", None),
+            ping_result("local", Some("Plugin failed")),
+        ],
+        Some("open-weather, local")
+    )]
+    fn test_check_results(#[case] results: Vec<PingResult>, #[case] expected: Option<&str>) {
+        let result = check_results(&results);
+
+        match expected {
+            Some(expected) => {
+                assert!(matches!(result, Err(PingError::Failed(failed)) if failed == expected))
+            }
+            None => assert!(result.is_ok()),
+        }
+    }
+}
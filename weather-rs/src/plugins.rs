@@ -0,0 +1,497 @@
+use async_trait::async_trait;
+use narrate::anyhow::Result;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use url::Url;
+
+use crate::config::ProviderConfig;
+use crate::theme::{Paint, Role};
+use weather_api_services::{
+    models::{DataSource, MinutePrecipitation, Station, WeatherData},
+    options::ServiceOptions,
+    quantities::{Celsius, HectoPascal, MetersPerSecond},
+    secret::Secret,
+    WeatherApi,
+};
+
+/// The prefix of executable names of provider plugins (similar to cargo subcommands).
+pub const PLUGIN_PREFIX: &str = "weather-rs-provider-";
+
+/// Represents errors related to provider plugins.
+#[derive(Error, Debug)]
+pub enum PluginError {
+    /// An error indicating a failure to start a provider plugin executable.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the name of the plugin.
+    /// * `1` - The `std::io::Error` indicating the specific error.
+    #[error("Failed to run the provider plugin '{0}'")]
+    Spawn(String, std::io::Error),
+
+    /// An error indicating that a provider plugin exited with a failure status.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the name of the plugin.
+    /// * `1` - A string representing the error output of the plugin.
+    #[error("Provider plugin '{0}' failed with the error '{1}'")]
+    Failed(String, String),
+
+    /// An error indicating that a provider plugin returned an invalid response.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the name of the plugin.
+    /// * `1` - The `serde_json::Error` indicating the specific JSON parsing error.
+    #[error("Provider plugin '{0}' returned an invalid JSON response")]
+    Response(String, serde_json::Error),
+}
+
+/// Represents the kinds of data requested from a provider plugin.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginRequest {
+    /// Current or historical weather data.
+    Weather,
+    /// The observation stations near the address.
+    Stations,
+    /// The minutely precipitation forecast of the next hour.
+    Nowcast,
+}
+
+/// Represents the query sent to a provider plugin as JSON on stdin.
+#[derive(Serialize, Debug)]
+pub struct PluginQuery<'a> {
+    pub request: PluginRequest,
+    pub address: &'a str,
+    pub date: &'a Option<String>,
+    pub url: Option<&'a str>,
+    pub api_key: Option<&'a Secret>,
+    pub lang: Option<&'a str>,
+    pub options: &'a ServiceOptions,
+}
+
+/// Represents weather data returned by a provider plugin as JSON on stdout.
+#[derive(Deserialize, Debug)]
+pub struct PluginWeatherData {
+    /// The temperature in degrees Celsius.
+    pub temp: Celsius,
+    #[serde(default)]
+    pub feels_like: Option<Celsius>,
+    pub humidity: u8,
+    #[serde(default)]
+    pub dew_point: Option<Celsius>,
+    pub pressure: HectoPascal,
+    /// The pressure at the elevation of the station in hectopascals.
+    #[serde(default)]
+    pub station_pressure: Option<HectoPascal>,
+    /// The wind speed in meters per second.
+    pub wind_speed: MetersPerSecond,
+    pub visibility: u32,
+    pub description: String,
+    #[serde(default)]
+    pub uv_index: Option<f32>,
+    #[serde(default)]
+    pub is_day: Option<bool>,
+    /// The time of the observation as a Unix timestamp.
+    #[serde(default)]
+    pub observed_at: Option<i64>,
+    /// The identifier of the weather station.
+    #[serde(default)]
+    pub station: Option<String>,
+    /// The attribution text required by the data source.
+    #[serde(default)]
+    pub attribution: Option<String>,
+}
+
+/// Converts data from a provider plugin to `WeatherData`.
+impl From<PluginWeatherData> for WeatherData {
+    fn from(plugin_data: PluginWeatherData) -> Self {
+        let mut builder = WeatherData::builder()
+            .temp(plugin_data.temp)
+            .humidity(plugin_data.humidity)
+            .pressure(plugin_data.pressure)
+            .station_pressure(plugin_data.station_pressure)
+            .wind_speed(plugin_data.wind_speed)
+            .visibility(plugin_data.visibility)
+            .description(plugin_data.description)
+            .uv_index(plugin_data.uv_index)
+            .is_day(plugin_data.is_day.unwrap_or(true))
+            .observed_at(plugin_data.observed_at)
+            .source(DataSource {
+                provider: String::new(),
+                station: plugin_data.station,
+                attribution: plugin_data.attribution,
+            });
+        // the builder computes the "feels like" temperature and the dew point if the plugin doesn't supply them
+        if let Some(feels_like) = plugin_data.feels_like {
+            builder = builder.feels_like(feels_like);
+        }
+        if let Some(dew_point) = plugin_data.dew_point {
+            builder = builder.dew_point(dew_point);
+        }
+
+        builder.build()
+    }
+}
+
+/// Struct that implement the `WeatherApi` trait and interacts with an external provider plugin executable.
+#[derive(Debug)]
+pub struct PluginService {
+    name: String,
+    path: PathBuf,
+    config: ProviderConfig,
+    language: Option<String>,
+}
+
+/// `PluginService` constructors
+impl PluginService {
+    /// Creates a new instance of `PluginService`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the provider plugin.
+    /// * `path` - The path to the provider plugin executable.
+    /// * `config` - The provider configuration passed to the plugin with every query.
+    ///
+    /// # Returns
+    ///
+    /// The initialized `PluginService`.
+    pub fn new(name: String, path: PathBuf, config: ProviderConfig) -> Self {
+        PluginService {
+            name,
+            path,
+            config,
+            language: None,
+        }
+    }
+
+    /// Sets the language of condition descriptions passed to the plugin with every query.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - An optional language code (e.g. `de`, `fr`). Pass `None` for the plugin default.
+    ///
+    /// # Returns
+    ///
+    /// The `PluginService` with the language set.
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Runs the provider plugin with a query written to its stdin as JSON and parses its JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The kind of data requested from the plugin.
+    /// * `address` - A string representing the address of the query.
+    /// * `date` - An optional string containing the date for historical weather data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed response or an error if the plugin fails.
+    async fn run<T: DeserializeOwned>(
+        &self,
+        request: PluginRequest,
+        address: &str,
+        date: &Option<String>,
+    ) -> Result<T> {
+        let query = PluginQuery {
+            request,
+            address,
+            date,
+            url: self.config.url.as_ref().map(Url::as_str),
+            api_key: self.config.api_key.as_ref(),
+            lang: self.language.as_deref(),
+            options: &self.config.options,
+        };
+        let name = self.name.paint(Role::Warning).to_string();
+
+        let mut child = Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| PluginError::Spawn(name.clone(), err))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let write_result = stdin
+                .write_all(serde_json::to_string(&query)?.as_bytes())
+                .await;
+
+            // plugins are allowed to ignore the query and exit before reading it
+            if let Err(err) = write_result {
+                if err.kind() != std::io::ErrorKind::BrokenPipe {
+                    return Err(PluginError::Spawn(name, err).into());
+                }
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|err| PluginError::Spawn(name.clone(), err))?;
+
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            return Err(PluginError::Failed(name, message.paint(Role::Warning).to_string()).into());
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)
+            .map_err(|err| PluginError::Response(name, err))?)
+    }
+}
+
+/// An implementation of the `WeatherApi` trait for provider plugins.
+#[async_trait]
+impl WeatherApi for PluginService {
+    /// Asynchronously retrieves weather data by running the provider plugin.
+    ///
+    /// The query is written to the plugin stdin as JSON and the weather data is read as JSON from its stdout.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which weather data is requested.
+    /// * `date` - An optional string containing the date for historical weather data. Pass `None` for current weather.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the retrieved weather data or an error if the plugin fails.
+    async fn get_weather_data(&self, address: &str, date: &Option<String>) -> Result<WeatherData> {
+        let plugin_data: PluginWeatherData =
+            self.run(PluginRequest::Weather, address, date).await?;
+
+        let mut weather_data: WeatherData = plugin_data.into();
+        weather_data.source.provider = self.name.clone();
+
+        Ok(weather_data)
+    }
+
+    /// Asynchronously retrieves the observation stations near an address by running the provider plugin.
+    ///
+    /// The plugin is queried with the `stations` request and prints the stations as a JSON array.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address near which stations are requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the stations or an error if the plugin fails.
+    async fn get_stations(&self, address: &str) -> Result<Vec<Station>> {
+        self.run(PluginRequest::Stations, address, &None).await
+    }
+
+    /// Asynchronously retrieves the minutely precipitation forecast of the next hour by running the provider plugin.
+    ///
+    /// The plugin is queried with the `nowcast` request and prints the forecasted minutes as a JSON array.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A string representing the address for which the nowcast is requested.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the forecasted minutes or an error if the plugin fails.
+    async fn get_nowcast(&self, address: &str) -> Result<Vec<MinutePrecipitation>> {
+        self.run(PluginRequest::Nowcast, address, &None).await
+    }
+}
+
+/// Finds the executable of a provider plugin with the given name on `PATH`.
+///
+/// # Arguments
+///
+/// * `name` - The name of the provider plugin.
+///
+/// # Returns
+///
+/// The path to the plugin executable or `None` if the plugin is not found.
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    search_paths()
+        .iter()
+        .find_map(|dir| find_plugin_in(dir, name))
+}
+
+/// Discovers names of all provider plugins available on `PATH`.
+///
+/// # Returns
+///
+/// A sorted list of names of the discovered provider plugins without duplicates.
+pub fn discover_plugins() -> Vec<String> {
+    let mut names: Vec<String> = search_paths()
+        .iter()
+        .flat_map(|dir| plugins_in(dir))
+        .collect();
+
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+/// Returns directories listed in the `PATH` environment variable.
+fn search_paths() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).collect())
+        .unwrap_or_default()
+}
+
+/// Finds the executable of a provider plugin with the given name in a directory.
+fn find_plugin_in(dir: &Path, name: &str) -> Option<PathBuf> {
+    let file_name = format!("{PLUGIN_PREFIX}{name}{}", std::env::consts::EXE_SUFFIX);
+    let path = dir.join(file_name);
+
+    is_executable(&path).then_some(path)
+}
+
+/// Lists names of provider plugins in a directory.
+fn plugins_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| is_executable(&entry.path()))
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let name = file_name
+                .strip_prefix(PLUGIN_PREFIX)?
+                .trim_end_matches(std::env::consts::EXE_SUFFIX);
+
+            (!name.is_empty()).then(|| name.to_owned())
+        })
+        .collect()
+}
+
+/// Checks whether a path is an executable file.
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = path.metadata() else {
+        return false;
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn create_plugin(dir_name: &str, name: &str, script: &str) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(dir_name);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join(format!("{PLUGIN_PREFIX}{name}"));
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        (dir, path)
+    }
+
+    #[rstest]
+    fn test_plugins_in() {
+        let (dir, _) = create_plugin("weather-rs-test-plugins-in", "foo", "#!/bin/sh\n");
+
+        assert_eq!(plugins_in(&dir), vec!["foo".to_owned()]);
+        assert!(find_plugin_in(&dir, "foo").is_some());
+        assert!(find_plugin_in(&dir, "bar").is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_weather_data() {
+        let script = r#"#!/bin/sh
+read query
+echo '{"temp": 21.5, "humidity": 40, "pressure": 1012, "wind_speed": 3.5, "visibility": 10000, "description": "Clear", "station": "EGLL"}'
+"#;
+        let (_, path) = create_plugin("weather-rs-test-plugin-data", "foo", script);
+        let service = PluginService::new("foo".to_owned(), path, ProviderConfig::default());
+
+        let result = service.get_weather_data("London", &None).await.unwrap();
+
+        assert_eq!(result.temp, Celsius(21.5));
+        assert_eq!(result.humidity, 40);
+        assert_eq!(result.pressure, HectoPascal(1012));
+        assert_eq!(result.wind_speed, MetersPerSecond(3.5));
+        assert_eq!(result.visibility, 10000);
+        assert_eq!(result.description, "Clear");
+        assert_eq!(result.source.provider, "foo");
+        assert_eq!(result.source.station.as_deref(), Some("EGLL"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_stations() {
+        let script = r#"#!/bin/sh
+read query
+case "$query" in
+  *'"request":"stations"'*) echo '[{"id": "EGLL", "name": "Heathrow", "distance": 22.4, "elevation": 25.0}, {"id": "EGLC", "distance": 12.1}]' ;;
+  *) exit 1 ;;
+esac
+"#;
+        let (_, path) = create_plugin("weather-rs-test-plugin-stations", "foo", script);
+        let service = PluginService::new("foo".to_owned(), path, ProviderConfig::default());
+
+        let result = service.get_stations("London").await.unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, "EGLL");
+        assert_eq!(result[0].name.as_deref(), Some("Heathrow"));
+        assert_eq!(result[0].elevation, Some(25.0));
+        assert_eq!(result[1].name, None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_nowcast() {
+        let script = r#"#!/bin/sh
+read query
+case "$query" in
+  *'"request":"nowcast"'*) echo '[{"time": 1702209600, "precipitation": 0.0}, {"time": 1702209900, "precipitation": 1.2}]' ;;
+  *) exit 1 ;;
+esac
+"#;
+        let (_, path) = create_plugin("weather-rs-test-plugin-nowcast", "foo", script);
+        let service = PluginService::new("foo".to_owned(), path, ProviderConfig::default());
+
+        let result = service.get_nowcast("London").await.unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].time, 1_702_209_900);
+        assert_eq!(result[1].precipitation, 1.2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_weather_data_plugin_failure() {
+        let script = "#!/bin/sh\necho 'unknown city' >&2\nexit 1\n";
+        let (_, path) = create_plugin("weather-rs-test-plugin-failure", "foo", script);
+        let service = PluginService::new("foo".to_owned(), path, ProviderConfig::default());
+
+        let result: PluginError = service
+            .get_weather_data("Nowhere", &None)
+            .await
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+
+        assert!(matches!(result, PluginError::Failed(..)));
+    }
+}
@@ -0,0 +1,158 @@
+use std::future::Future;
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use narrate::anyhow::Result;
+
+use crate::terminal;
+
+/// The interval between redraws of spinners.
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Checks whether progress should be displayed (see `terminal::Capabilities`), e.g. the output isn't piped into
+/// a file or another program.
+///
+/// # Returns
+///
+/// `true` if progress is displayed.
+pub fn is_enabled() -> bool {
+    terminal::capabilities().progress
+}
+
+/// Creates the "Fetching..." spinner of a single request; it's hidden if progress isn't enabled.
+///
+/// # Returns
+///
+/// A `Result` containing the spinner or an error if its template is invalid.
+pub fn spinner() -> Result<ProgressBar> {
+    if !is_enabled() {
+        return Ok(ProgressBar::hidden());
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(ProgressStyle::default_spinner().template("{spinner} Fetching...")?);
+    pb.enable_steady_tick(TICK_INTERVAL);
+
+    Ok(pb)
+}
+
+/// Awaits a future while the "Fetching..." spinner is displayed; the spinner is cleared when the future
+/// completes, whether it succeeds or fails.
+///
+/// # Arguments
+///
+/// * `future` - The future fetching the data.
+///
+/// # Returns
+///
+/// A `Result` containing the output of the future or an error if it fails or the spinner can't be created.
+pub async fn with_spinner<T>(future: impl Future<Output = Result<T>>) -> Result<T> {
+    let pb = spinner()?;
+    let result = future.await;
+    pb.finish_and_clear();
+
+    result
+}
+
+/// Represents the progress of an operation that fetches data for several locations, days or points.
+///
+/// It's displayed as an overall bar with the ETA and a spinner for every item being fetched.
+pub struct BatchProgress {
+    multi: MultiProgress,
+    overall: ProgressBar,
+    item_style: ProgressStyle,
+}
+
+/// `BatchProgress` constructors and methods
+impl BatchProgress {
+    /// Creates the progress of an operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The number of items (e.g. locations) to be fetched.
+    /// * `visible` - A flag to indicate if the progress should be displayed (see `is_enabled`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the progress or an error if a template is invalid.
+    pub fn new(len: usize, visible: bool) -> Result<Self> {
+        let multi = if visible {
+            MultiProgress::new()
+        } else {
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+        };
+        let overall = multi.add(ProgressBar::new(len as u64));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner} Fetching [{bar:30}] {pos}/{len} (ETA {eta})")?
+                .progress_chars("=> "),
+        );
+        overall.enable_steady_tick(TICK_INTERVAL);
+
+        Ok(BatchProgress {
+            multi,
+            overall,
+            item_style: ProgressStyle::default_spinner().template("  {spinner} {msg}")?,
+        })
+    }
+
+    /// Starts fetching an item; its spinner is shown until the returned `ItemProgress` is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label of the item (e.g. the location).
+    ///
+    /// # Returns
+    ///
+    /// The `ItemProgress` of the item.
+    pub fn start(&self, label: impl Into<String>) -> ItemProgress {
+        let bar = self.multi.add(ProgressBar::new_spinner());
+        bar.set_style(self.item_style.clone());
+        bar.set_message(label.into());
+        bar.enable_steady_tick(TICK_INTERVAL);
+
+        ItemProgress {
+            bar,
+            overall: self.overall.clone(),
+        }
+    }
+
+    /// Clears the progress from the terminal.
+    pub fn finish(&self) {
+        self.overall.finish_and_clear();
+        let _ = self.multi.clear();
+    }
+}
+
+/// Represents an item being fetched; the item is counted as done when it's dropped.
+pub struct ItemProgress {
+    bar: ProgressBar,
+    overall: ProgressBar,
+}
+
+impl Drop for ItemProgress {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+        self.overall.inc(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_batch_progress() {
+        let progress = BatchProgress::new(2, false).unwrap();
+
+        drop(progress.start("Kyiv"));
+        let item = progress.start("Warsaw");
+
+        assert_eq!(progress.overall.position(), 1);
+        drop(item);
+        assert_eq!(progress.overall.position(), 2);
+        progress.finish();
+        assert!(progress.overall.is_finished());
+    }
+}
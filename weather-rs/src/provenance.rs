@@ -0,0 +1,198 @@
+use chrono::{DateTime, Local, SecondsFormat, TimeZone};
+use serde::Serialize;
+use std::sync::Mutex;
+
+use weather_api_services::http::RequestMetadata;
+use weather_api_services::units::Units;
+
+/// The metadata of the provider requests sent during the run, in the order of their responses.
+static REQUESTS: Mutex<Vec<RequestMetadata>> = Mutex::new(Vec::new());
+
+/// Represents the provenance of the JSON output, the `meta` object of the JSON envelope.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Meta {
+    /// The names of the service providers the requests were sent to; `None` if no request was sent through
+    /// the pipeline (e.g. to a plugin provider).
+    pub provider: Option<String>,
+    /// The time of the last response in the RFC 3339 format (UTC).
+    pub fetched_at: Option<String>,
+    /// Whether all responses were served by the cache.
+    pub cache_hit: bool,
+    /// The total latency of the requests in milliseconds.
+    pub latency_ms: u64,
+    /// The units of the data.
+    pub request_units: Units,
+}
+
+/// Represents the JSON output wrapped with its provenance.
+#[derive(Serialize, Debug)]
+pub struct Envelope<T> {
+    pub meta: Meta,
+    pub data: T,
+}
+
+/// Records the metadata of a provider request (see `Pipeline::on_request`).
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata of the request.
+pub fn record(metadata: &RequestMetadata) {
+    REQUESTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(metadata.clone());
+}
+
+/// Returns the metadata of the provider requests sent so far.
+pub fn requests() -> Vec<RequestMetadata> {
+    REQUESTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+/// Returns the names of the service providers of requests without duplicates, in the order of their first requests.
+fn providers(requests: &[RequestMetadata]) -> Vec<&str> {
+    let mut providers: Vec<&str> = Vec::new();
+    for metadata in requests {
+        if !providers.contains(&metadata.provider.as_str()) {
+            providers.push(&metadata.provider);
+        }
+    }
+
+    providers
+}
+
+/// Generates the provenance footer of the output (e.g. `source: Weather API · fetched 12:03 · cache: miss · 312 ms`).
+///
+/// The time is the local time of the last response and the latency is the total of all requests; the cache
+/// is `hit` or `miss` if all requests agree and the number of hits otherwise (e.g. `cache: 1/2 hits`).
+///
+/// # Arguments
+///
+/// * `requests` - The metadata of the provider requests.
+///
+/// # Returns
+///
+/// The footer or `None` if no provider request was sent (e.g. a plugin provider).
+pub fn footer(requests: &[RequestMetadata]) -> Option<String> {
+    let last = requests.last()?;
+
+    let fetched_at = Local
+        .timestamp_opt(last.fetched_at, 0)
+        .single()
+        .map_or_else(String::new, |time| time.format("%H:%M").to_string());
+    let hits = requests
+        .iter()
+        .filter(|metadata| metadata.cache_hit)
+        .count();
+    let cache = match hits {
+        0 => "miss".to_owned(),
+        hits if hits == requests.len() => "hit".to_owned(),
+        hits => format!("{hits}/{} hits", requests.len()),
+    };
+    let latency_ms: u64 = requests.iter().map(|metadata| metadata.latency_ms).sum();
+
+    Some(format!(
+        "source: {} · fetched {fetched_at} · cache: {cache} · {latency_ms} ms",
+        providers(requests).join(", ")
+    ))
+}
+
+/// Generates the provenance of the JSON output.
+///
+/// # Arguments
+///
+/// * `requests` - The metadata of the provider requests.
+/// * `units` - The units of the data.
+///
+/// # Returns
+///
+/// The `Meta` of the JSON envelope.
+pub fn meta(requests: &[RequestMetadata], units: Units) -> Meta {
+    let providers = providers(requests);
+
+    Meta {
+        provider: (!providers.is_empty()).then(|| providers.join(", ")),
+        fetched_at: requests
+            .last()
+            .and_then(|last| DateTime::from_timestamp(last.fetched_at, 0))
+            .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        cache_hit: !requests.is_empty() && requests.iter().all(|metadata| metadata.cache_hit),
+        latency_ms: requests.iter().map(|metadata| metadata.latency_ms).sum(),
+        request_units: units,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn metadata(provider: &str, cache_hit: bool, latency_ms: u64) -> RequestMetadata {
+        RequestMetadata {
+            provider: provider.to_owned(),
+            fetched_at: 1_720_607_400,
+            cache_hit,
+            latency_ms,
+        }
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![metadata("Weather API", false, 312)], Some("source: Weather API · fetched {time} · cache: miss · 312 ms"))]
+    #[case(
+        vec![metadata("Weather API", true, 0), metadata("Weather API", true, 1)],
+        Some("source: Weather API · fetched {time} · cache: hit · 1 ms")
+    )]
+    #[case(
+        vec![metadata("Open Weather", false, 200), metadata("Weather API", true, 0)],
+        Some("source: Open Weather, Weather API · fetched {time} · cache: 1/2 hits · 200 ms")
+    )]
+    fn test_footer(#[case] requests: Vec<RequestMetadata>, #[case] expected: Option<&str>) {
+        let time = Local
+            .timestamp_opt(1_720_607_400, 0)
+            .unwrap()
+            .format("%H:%M")
+            .to_string();
+
+        assert_eq!(
+            footer(&requests),
+            expected.map(|expected| expected.replace("{time}", &time))
+        );
+    }
+
+    #[rstest]
+    fn test_meta() {
+        let requests = [
+            metadata("Weather API", true, 0),
+            metadata("Weather API", false, 312),
+        ];
+
+        assert_eq!(
+            meta(&requests, Units::Imperial),
+            Meta {
+                provider: Some("Weather API".to_owned()),
+                fetched_at: Some("2024-07-10T10:30:00Z".to_owned()),
+                cache_hit: false,
+                latency_ms: 312,
+                request_units: Units::Imperial,
+            }
+        );
+        assert_eq!(meta(&[], Units::Metric).provider, None);
+    }
+
+    #[rstest]
+    fn test_envelope() {
+        let envelope = Envelope {
+            meta: meta(&[metadata("Weather API", false, 312)], Units::Metric),
+            data: serde_json::json!({"temp": 12.5}),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&envelope).unwrap(),
+            "{\"meta\":{\"provider\":\"Weather API\",\"fetched_at\":\"2024-07-10T10:30:00Z\",\"cache_hit\":false,\
+             \"latency_ms\":312,\"request_units\":\"metric\"},\"data\":{\"temp\":12.5}}"
+        );
+    }
+}
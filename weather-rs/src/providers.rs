@@ -1,14 +1,107 @@
+use chrono::{Duration, NaiveDate};
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::ValueEnum;
+use narrate::anyhow::Result;
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::LazyLock;
 use thiserror::Error;
 
-/// An array of providers that are not implemented.
+use crate::plugins;
+use crate::theme::{Paint, Role};
+use weather_api_services::WeatherApi;
+use weather_api_services::{
+    http::Pipeline, openweather_service::OpenWeatherApiService, options::ServiceOptions,
+    secret::Secret, weatherapi_service::WeatherApiService,
+};
+
+/// The default number of simultaneous requests of batch operations for provider plugins (every request runs
+/// the plugin executable).
+const PLUGIN_CONCURRENCY: usize = 2;
+
+/// The registry of all weather data providers known to the application.
 ///
-/// This array lists the weather data providers that are not implemented in the current version
-/// of the application.
-pub const NOT_IMPLEMENTED_PROVIDERS: [&Provider; 2] =
-    [&Provider::AccuWeather, &Provider::AerisWeather];
+/// Every provider registers its name, configuration defaults and the factory used to create its service.
+/// The CLI, the `provider-list` command and the configuration iterate this registry, so adding a new provider
+/// only requires a new `Provider` variant and a new entry here.
+pub static PROVIDER_REGISTRY: [ProviderEntry; 4] = [
+    ProviderEntry {
+        provider: Provider::OpenWeather,
+        name: "open-weather",
+        aliases: &["owm", "openweather", "openweathermap"],
+        title: "Open Weather",
+        config_key: "open_weather",
+        api_version: "v2",
+        default_url: "https://api.openweathermap.org/data/2.5/weather",
+        concurrency: 5,
+        localized: true,
+        horizon: Horizon {
+            forecast_days: 5,
+            history_days: 0,
+        },
+        factory: Some(open_weather_factory),
+    },
+    ProviderEntry {
+        provider: Provider::WeatherApi,
+        name: "weather-api",
+        aliases: &["weatherapi"],
+        title: "Weather API",
+        config_key: "weather_api",
+        api_version: "v1",
+        default_url: "https://api.weatherapi.com/v1",
+        concurrency: 4,
+        localized: true,
+        horizon: Horizon {
+            forecast_days: 3,
+            history_days: 7,
+        },
+        factory: Some(weather_api_factory),
+    },
+    ProviderEntry {
+        provider: Provider::AccuWeather,
+        name: "accu-weather",
+        aliases: &["accuweather"],
+        title: "AccuWeather",
+        config_key: "accu_weather",
+        api_version: "v1",
+        default_url: "http://dataservice.accuweather.com/currentconditions/v1",
+        concurrency: 2,
+        localized: true,
+        horizon: Horizon {
+            forecast_days: 5,
+            history_days: 1,
+        },
+        factory: None,
+    },
+    ProviderEntry {
+        provider: Provider::AerisWeather,
+        name: "aeris-weather",
+        aliases: &["aeris", "aerisweather"],
+        title: "AerisWeather",
+        config_key: "aeris_weather",
+        api_version: "v1",
+        default_url: "https://api.aerisapi.com/conditions",
+        concurrency: 2,
+        localized: false,
+        horizon: Horizon {
+            forecast_days: 15,
+            history_days: 0,
+        },
+        factory: None,
+    },
+];
+
+/// The built-in providers in the order of the registry (the values of `ValueEnum`).
+static BUILT_IN_PROVIDERS: LazyLock<Vec<Provider>> = LazyLock::new(|| {
+    PROVIDER_REGISTRY
+        .iter()
+        .map(|entry| entry.provider.clone())
+        .collect()
+});
 
 /// Represents errors related to weather data providers.
 #[derive(Error, Debug)]
@@ -20,17 +113,51 @@ pub enum ProviderError {
     #[error("Weather provider not found; use the command 'weather-rs provider-list' to get a list of all available providers")]
     ProviderNotFound,
 
+    /// An error indicating that a weather data provider name is unknown but close to a registered one.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the unknown name.
+    /// * `1` - A string representing the closest registered name.
+    #[error("Unknown provider '{0}', did you mean '{1}'? Use the command 'weather-rs provider-list' to get a list of all available providers")]
+    UnknownProvider(String, String),
+
     /// An error indicating that a weather data provider is not implemented.
     ///
     /// This error occurs when an attempt is made to use a weather data provider that is not yet
     /// implemented in the current version of the application.
     #[error("Weather provider is not implemented; use the command 'weather-rs provider-list' to get a list of all available providers")]
     ProviderNotImplemented,
+
+    /// An error indicating that more forecasted days are requested than the provider forecasts.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the title of the provider.
+    /// * `1` - The requested number of days.
+    /// * `2` - The maximum number of forecasted days of the provider.
+    #[error(
+        "{0} forecasts at most {2} days, but {1} were requested; use '--days' between 1 and {2}"
+    )]
+    DaysOutOfRange(String, u8, u8),
+
+    /// An error indicating that the weather of a date outside of the history and the forecast of the provider
+    /// is requested.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the title of the provider.
+    /// * `1` - A string representing the requested date.
+    /// * `2` - A string representing the first date of the allowed range.
+    /// * `3` - A string representing the last date of the allowed range.
+    #[error("{0} has no weather for {1}; use a date from {2} to {3}")]
+    DateOutOfRange(String, String, String, String),
 }
 
 /// Represents weather data providers available in the application.
 ///
-/// This enum represents the available weather data providers in the application.
+/// This enum represents the available weather data providers in the application,
+/// including external provider plugins discovered on `PATH`.
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub enum Provider {
     #[default]
@@ -38,6 +165,75 @@ pub enum Provider {
     WeatherApi,
     AccuWeather,
     AerisWeather,
+    /// An external provider plugin (`weather-rs-provider-<name>` executable) with the given name.
+    Plugin(String),
+}
+
+/// A factory function that creates a weather API service from an HTTP client, a service URL, an API key,
+/// an optional language of condition descriptions, extra provider-specific options and a request pipeline.
+pub type ServiceFactory = fn(
+    Client,
+    String,
+    Secret,
+    Option<String>,
+    ServiceOptions,
+    Pipeline,
+) -> Result<Box<dyn WeatherApi + Send + Sync>>;
+
+/// Represents a weather data provider registered in the `PROVIDER_REGISTRY`.
+#[derive(Debug)]
+pub struct ProviderEntry {
+    /// The registered provider.
+    pub provider: Provider,
+    /// The name of the provider used on the command line (kebab-case).
+    pub name: &'static str,
+    /// Other accepted names of the provider on the command line (e.g. `owm`).
+    pub aliases: &'static [&'static str],
+    /// The human-readable title of the provider.
+    pub title: &'static str,
+    /// The key of the provider section in the configuration file.
+    pub config_key: &'static str,
+    /// The version of the provider API supported by the application.
+    pub api_version: &'static str,
+    /// The default URL of the provider API.
+    pub default_url: &'static str,
+    /// The default number of simultaneous requests of batch operations (within the limits of the free plan).
+    pub concurrency: usize,
+    /// Whether the provider returns condition descriptions in a requested language.
+    pub localized: bool,
+    /// The range of dates the provider has weather for (on its free plan).
+    pub horizon: Horizon,
+    /// The factory that creates the provider service; `None` if the provider is not implemented yet.
+    pub factory: Option<ServiceFactory>,
+}
+
+/// Represents the documented range of dates a provider has weather for on its free plan, so requests outside of it
+/// are rejected before they are sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Horizon {
+    /// The maximum number of forecasted days, starting from today.
+    pub forecast_days: u8,
+    /// The number of past days of the history, before today; `0` if the provider has no history.
+    pub history_days: u8,
+}
+
+/// `Horizon` methods
+impl Horizon {
+    /// Returns the first and the last date the provider has weather for.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The current date.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the first and the last date of the range (both inclusive).
+    pub fn range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        (
+            today - Duration::days(i64::from(self.history_days)),
+            today + Duration::days(i64::from(self.forecast_days.max(1)) - 1),
+        )
+    }
 }
 
 impl FromStr for Provider {
@@ -45,9 +241,10 @@ impl FromStr for Provider {
 
     /// Converts a string to a Provider enum variant.
     ///
-    /// This method attempts to parse a string and convert it into a Provider enum variant.
-    /// It returns a Result containing the parsed variant or a ProviderError if the string
-    /// does not match any known providers.
+    /// This method looks up the provider registry for a provider with the given name or alias (case-insensitively,
+    /// see `ValueEnum`) and falls back to provider plugins discovered on `PATH`. It returns a Result containing
+    /// the parsed variant or a ProviderError if the string does not match any known providers; the closest
+    /// registered name is suggested for a misspelled one.
     ///
     /// # Arguments
     ///
@@ -57,20 +254,146 @@ impl FromStr for Provider {
     ///
     /// A Result containing the parsed Provider variant or a ProviderError if the string is not recognized.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_ascii_lowercase().as_str() {
-            "open-weather" => Ok(Provider::OpenWeather),
-            "weather-api" => Ok(Provider::WeatherApi),
-            "accu-weather" => Ok(Provider::AccuWeather),
-            "aeris-weather" => Ok(Provider::AerisWeather),
-            _ => Err(ProviderError::ProviderNotFound),
+        if let Ok(provider) = <Provider as ValueEnum>::from_str(s, true) {
+            return Ok(provider);
+        }
+
+        let name = s.to_ascii_lowercase();
+        if plugins::find_plugin(&name).is_some() {
+            return Ok(Provider::Plugin(name));
+        }
+
+        match closest_name(&name) {
+            Some(closest) => Err(ProviderError::UnknownProvider(
+                s.paint(Role::Warning).to_string(),
+                closest.paint(Role::Success).to_string(),
+            )),
+            None => Err(ProviderError::ProviderNotFound),
+        }
+    }
+}
+
+/// Generates the values of provider arguments from the provider registry, so clap lists the built-in providers
+/// with their titles in the help and completes them; provider plugins are parsed by `ProviderValueParser`.
+impl ValueEnum for Provider {
+    fn value_variants<'a>() -> &'a [Self] {
+        &BUILT_IN_PROVIDERS
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        self.entry().map(|entry| {
+            PossibleValue::new(entry.name)
+                .aliases(entry.aliases.iter().copied())
+                .help(entry.title)
+        })
+    }
+}
+
+/// Parser of provider arguments on the command line: the built-in providers of the registry (case-insensitively,
+/// with aliases) and provider plugins discovered on `PATH`.
+///
+/// Unknown providers are rejected with the list of built-in providers and the closest name, if there is one.
+#[derive(Clone, Copy, Debug)]
+pub struct ProviderValueParser;
+
+impl TypedValueParser for ProviderValueParser {
+    type Value = Provider;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        value.parse().map_err(|_| {
+            let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
+            if let Some(arg) = arg {
+                err.insert(
+                    ContextKind::InvalidArg,
+                    ContextValue::String(arg.to_string()),
+                );
+            }
+            err.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(value.to_owned()),
+            );
+            err.insert(
+                ContextKind::ValidValue,
+                ContextValue::Strings(
+                    Provider::registry()
+                        .map(|entry| entry.name.to_owned())
+                        .collect(),
+                ),
+            );
+            if let Some(closest) = closest_name(&value.to_ascii_lowercase()) {
+                err.insert(
+                    ContextKind::SuggestedValue,
+                    ContextValue::String(closest.to_owned()),
+                );
+            }
+
+            err
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Provider::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+/// The maximum edit distance of a misspelled provider name from the suggested one.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// Finds the registered provider name closest to a misspelled one, comparing it with names and aliases.
+///
+/// # Arguments
+///
+/// * `name` - The misspelled name in lowercase.
+///
+/// # Returns
+///
+/// The closest registered name or `None` if no name is within `MAX_SUGGESTION_DISTANCE` edits.
+fn closest_name(name: &str) -> Option<&'static str> {
+    PROVIDER_REGISTRY
+        .iter()
+        .flat_map(|entry| {
+            std::iter::once(entry.name)
+                .chain(entry.aliases.iter().copied())
+                .map(move |candidate| (edit_distance(name, candidate), entry.name))
+        })
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, closest)| closest)
+}
+
+/// Computes the Levenshtein distance between two strings (the number of inserted, deleted or replaced characters).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let replaced = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = replaced.min(previous[j + 1] + 1).min(current[j] + 1);
         }
+        previous = current;
     }
+
+    previous[b.len()]
 }
 
 impl fmt::Display for Provider {
     /// Formats the Provider enum variant as a string.
     ///
-    /// This method formats a Provider enum variant as a string, which can be useful for
+    /// This method formats a Provider enum variant as its registered name, which can be useful for
     /// displaying provider names or for configuration purposes.
     ///
     /// # Arguments
@@ -81,33 +404,173 @@ impl fmt::Display for Provider {
     ///
     /// A Result containing the formatted string result.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Provider::OpenWeather => write!(f, "open-weather"),
-            Provider::WeatherApi => write!(f, "weather-api"),
-            Provider::AccuWeather => write!(f, "accu-weather"),
-            Provider::AerisWeather => write!(f, "aeris-weather"),
+        match self.entry() {
+            Some(entry) => write!(f, "{}", entry.name),
+            None => write!(f, "{}", self.config_key()),
         }
     }
 }
 
 impl Provider {
-    /// Returns all available variants of the Provider enum.
+    /// Returns all registered providers in the order of the registry.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the entries of the provider registry.
+    pub fn registry() -> impl Iterator<Item = &'static ProviderEntry> {
+        PROVIDER_REGISTRY.iter()
+    }
+
+    /// Returns the registry entry of the provider.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `ProviderEntry` registered for the provider or `None` for provider plugins.
+    pub fn entry(&self) -> Option<&'static ProviderEntry> {
+        PROVIDER_REGISTRY
+            .iter()
+            .find(|entry| &entry.provider == self)
+    }
+
+    /// Returns the key of the provider section in the configuration file.
+    ///
+    /// # Returns
+    ///
+    /// The configuration key of the registry entry or the plugin name for provider plugins.
+    pub fn config_key(&self) -> &str {
+        match self {
+            Provider::Plugin(name) => name,
+            _ => {
+                self.entry()
+                    .expect("every built-in provider must be registered in the provider registry")
+                    .config_key
+            }
+        }
+    }
+
+    /// Returns the default number of simultaneous requests of batch operations (e.g. `trip` and `export`).
+    ///
+    /// # Returns
+    ///
+    /// The concurrency of the registry entry or `PLUGIN_CONCURRENCY` for provider plugins.
+    pub fn default_concurrency(&self) -> usize {
+        self.entry()
+            .map_or(PLUGIN_CONCURRENCY, |entry| entry.concurrency)
+    }
+
+    /// Checks whether the provider returns condition descriptions in a requested language.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the registry entry of the provider is localized, `false` otherwise (e.g. for provider plugins).
+    pub fn is_localized(&self) -> bool {
+        self.entry().is_some_and(|entry| entry.localized)
+    }
+
+    /// Checks that the provider forecasts a number of days (e.g. of the `--days` option).
+    ///
+    /// # Arguments
+    ///
+    /// * `days` - The number of forecasted days, starting from today.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or `ProviderError::DaysOutOfRange` with the allowed range; provider plugins
+    /// aren't checked.
+    pub fn check_days(&self, days: u8) -> Result<(), ProviderError> {
+        match self.entry() {
+            Some(entry) if days > entry.horizon.forecast_days => {
+                Err(ProviderError::DaysOutOfRange(
+                    entry.title.to_owned(),
+                    days,
+                    entry.horizon.forecast_days,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the provider has the weather of a date (e.g. of the `--date` option) in its history or forecast.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The requested date.
+    /// * `today` - The current date.
+    ///
+    /// # Returns
     ///
-    /// This method returns an array containing all available variants of the Provider enum.
+    /// A `Result` indicating success or `ProviderError::DateOutOfRange` with the allowed range; provider plugins
+    /// aren't checked.
+    pub fn check_date(&self, date: NaiveDate, today: NaiveDate) -> Result<(), ProviderError> {
+        let Some(entry) = self.entry() else {
+            return Ok(());
+        };
+        let (first, last) = entry.horizon.range(today);
+
+        if (first..=last).contains(&date) {
+            Ok(())
+        } else {
+            Err(ProviderError::DateOutOfRange(
+                entry.title.to_owned(),
+                date.to_string().paint(Role::Warning).to_string(),
+                first.to_string(),
+                last.to_string(),
+            ))
+        }
+    }
+
+    /// Checks whether the provider has a service implementation.
     ///
     /// # Returns
     ///
-    /// An array containing all available Provider enum variants.
-    pub fn get_all_variants() -> [Provider; 4] {
-        [
-            Provider::OpenWeather,
-            Provider::WeatherApi,
-            Provider::AccuWeather,
-            Provider::AerisWeather,
-        ]
+    /// `true` if the provider is a plugin or has a registered service factory, `false` otherwise.
+    pub fn is_implemented(&self) -> bool {
+        self.entry().is_none_or(|entry| entry.factory.is_some())
     }
 }
 
+/// Creates the Open Weather API service.
+fn open_weather_factory(
+    client: Client,
+    url: String,
+    api_key: Secret,
+    language: Option<String>,
+    options: ServiceOptions,
+    pipeline: Pipeline,
+) -> Result<Box<dyn WeatherApi + Send + Sync>> {
+    Ok(Box::new(
+        OpenWeatherApiService::builder()
+            .client(client)
+            .url(url)
+            .api_key(api_key)
+            .language(language)
+            .options(options)
+            .pipeline(pipeline)
+            .build()?,
+    ))
+}
+
+/// Creates the Weather API service.
+fn weather_api_factory(
+    client: Client,
+    url: String,
+    api_key: Secret,
+    language: Option<String>,
+    options: ServiceOptions,
+    pipeline: Pipeline,
+) -> Result<Box<dyn WeatherApi + Send + Sync>> {
+    Ok(Box::new(
+        WeatherApiService::builder()
+            .client(client)
+            .url(url)
+            .api_key(api_key)
+            .language(language)
+            .options(options)
+            .pipeline(pipeline)
+            .build()?,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,8 +581,11 @@ mod tests {
     #[case("weather-api", Provider::WeatherApi)]
     #[case("accu-weather", Provider::AccuWeather)]
     #[case("aeris-weather", Provider::AerisWeather)]
+    #[case("OWM", Provider::OpenWeather)]
+    #[case("openweather", Provider::OpenWeather)]
+    #[case("weatherapi", Provider::WeatherApi)]
     fn test_from_str_valid_input(#[case] input: &str, #[case] expected: Provider) {
-        let result = Provider::from_str(input).unwrap();
+        let result = input.parse::<Provider>().unwrap();
         assert_eq!(result, expected);
     }
 
@@ -127,24 +593,105 @@ mod tests {
     #[case("invalid-provider")]
     #[case("unknown-provider")]
     fn test_from_str_invalid_input(#[case] input: &str) {
-        let result = Provider::from_str(input).unwrap_err();
+        let result = input.parse::<Provider>().unwrap_err();
         assert!(matches!(result, ProviderError::ProviderNotFound));
     }
 
+    #[rstest]
+    #[case("open-wether", "open-weather")]
+    #[case("opnweather", "open-weather")]
+    #[case("weather_api", "weather-api")]
+    #[case("aeriss", "aeris-weather")]
+    fn test_from_str_suggestion(#[case] input: &str, #[case] expected: &str) {
+        let result = input.parse::<Provider>().unwrap_err();
+        assert!(
+            matches!(result, ProviderError::UnknownProvider(_, ref closest) if closest.contains(expected)),
+            "{result}"
+        );
+    }
+
+    #[rstest]
+    #[case("", "owm", 3)]
+    #[case("open-wether", "open-weather", 1)]
+    #[case("weather-api", "weather-api", 0)]
+    #[case("kitten", "sitting", 3)]
+    fn test_edit_distance(#[case] a: &str, #[case] b: &str, #[case] expected: usize) {
+        assert_eq!(edit_distance(a, b), expected);
+    }
+
     #[rstest]
     #[case(Provider::OpenWeather, "open-weather")]
     #[case(Provider::WeatherApi, "weather-api")]
     #[case(Provider::AccuWeather, "accu-weather")]
     #[case(Provider::AerisWeather, "aeris-weather")]
+    #[case(Provider::Plugin("foo".to_owned()), "foo")]
     fn test_to_string(#[case] input: Provider, #[case] expected: &str) {
         let result = input.to_string();
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case([Provider::OpenWeather, Provider::WeatherApi, Provider::AccuWeather, Provider::AerisWeather])]
-    fn test_get_all_variants(#[case] expected: [Provider; 4]) {
-        let variants = Provider::get_all_variants();
-        assert_eq!(variants, expected);
+    #[case(vec![Provider::OpenWeather, Provider::WeatherApi, Provider::AccuWeather, Provider::AerisWeather])]
+    fn test_registry(#[case] expected: Vec<Provider>) {
+        let providers: Vec<Provider> = Provider::registry()
+            .map(|entry| entry.provider.clone())
+            .collect();
+        assert_eq!(providers, expected);
+    }
+
+    #[rstest]
+    #[case(Provider::OpenWeather, true)]
+    #[case(Provider::WeatherApi, true)]
+    #[case(Provider::AccuWeather, false)]
+    #[case(Provider::AerisWeather, false)]
+    #[case(Provider::Plugin("foo".to_owned()), true)]
+    fn test_is_implemented(#[case] provider: Provider, #[case] expected: bool) {
+        assert_eq!(provider.is_implemented(), expected);
+    }
+
+    #[rstest]
+    #[case(Provider::OpenWeather, "open_weather")]
+    #[case(Provider::AerisWeather, "aeris_weather")]
+    #[case(Provider::Plugin("foo".to_owned()), "foo")]
+    fn test_config_key(#[case] provider: Provider, #[case] expected: &str) {
+        assert_eq!(provider.config_key(), expected);
+    }
+
+    #[rstest]
+    #[case(Provider::WeatherApi, 3, true)]
+    #[case(Provider::WeatherApi, 4, false)]
+    #[case(Provider::OpenWeather, 5, true)]
+    #[case(Provider::Plugin("foo".to_owned()), 14, true)]
+    fn test_check_days(#[case] provider: Provider, #[case] days: u8, #[case] is_valid: bool) {
+        assert_eq!(provider.check_days(days).is_ok(), is_valid);
+    }
+
+    #[rstest]
+    #[case(Provider::WeatherApi, "2024-07-03", true)]
+    #[case(Provider::WeatherApi, "2024-07-02", false)]
+    #[case(Provider::WeatherApi, "2024-07-12", true)]
+    #[case(Provider::WeatherApi, "2024-07-13", false)]
+    #[case(Provider::OpenWeather, "2024-07-09", false)]
+    #[case(Provider::OpenWeather, "2024-07-14", true)]
+    #[case(Provider::Plugin("foo".to_owned()), "2020-01-01", true)]
+    fn test_check_date(#[case] provider: Provider, #[case] date: &str, #[case] is_valid: bool) {
+        let today = NaiveDate::from_ymd_opt(2024, 7, 10).unwrap();
+
+        let result = provider.check_date(date.parse().unwrap(), today);
+
+        assert_eq!(result.is_ok(), is_valid);
+    }
+
+    #[rstest]
+    fn test_date_out_of_range_message() {
+        let today = NaiveDate::from_ymd_opt(2024, 7, 10).unwrap();
+
+        let err = Provider::WeatherApi
+            .check_date("2024-06-01".parse().unwrap(), today)
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .ends_with("; use a date from 2024-07-03 to 2024-07-12"));
     }
 }
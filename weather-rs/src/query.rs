@@ -0,0 +1,248 @@
+use serde_json::Value;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::theme::{Paint, Role};
+
+/// Represents errors related to queries of the JSON output.
+#[derive(Error, Debug)]
+pub enum QueryError {
+    /// An error indicating that a query is malformed.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the query.
+    /// * `1` - A string representing the reason.
+    #[error("Invalid query '{0}' ({1}); use a path like '.temp', '.hours[0].temp' or '.[-1]'")]
+    Syntax(String, String),
+
+    /// An error indicating that a step of a query doesn't apply to the type of the value.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the step of the query.
+    /// * `1` - A string representing the type of the value.
+    #[error("Cannot select '{0}' of {1}")]
+    Type(String, String),
+}
+
+/// Represents a step of a query.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// A field of an object (e.g. `.temp` or `."feels like"`).
+    Field(String),
+    /// An element of an array; negative indexes count from the end (e.g. `[0]` or `[-1]`).
+    Index(i64),
+}
+
+/// Represents a jq-like path selecting a value of the JSON output (e.g. `.hours[0].temp`); `.` selects the whole output.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub steps: Vec<Step>,
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let syntax_error = |reason: &str| {
+            QueryError::Syntax(query.paint(Role::Warning).to_string(), reason.to_owned())
+        };
+
+        let mut chars = query.trim().chars().peekable();
+        if chars.next() != Some('.') {
+            return Err(syntax_error("it should start with '.'"));
+        }
+
+        let mut steps = Vec::new();
+        let mut after_dot = true;
+        while let Some(char) = chars.next() {
+            match char {
+                '.' if !after_dot => after_dot = true,
+                '[' => {
+                    let mut index = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(char) => index.push(char),
+                            None => return Err(syntax_error("a bracket isn't closed")),
+                        }
+                    }
+                    let index = index
+                        .trim()
+                        .parse()
+                        .map_err(|_| syntax_error("an index should be an integer in brackets"))?;
+                    steps.push(Step::Index(index));
+                    after_dot = false;
+                }
+                '"' if after_dot => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some('\\') => field.extend(chars.next()),
+                            Some(char) => field.push(char),
+                            None => return Err(syntax_error("a quoted field isn't closed")),
+                        }
+                    }
+                    steps.push(Step::Field(field));
+                    after_dot = false;
+                }
+                char if after_dot && (char.is_alphanumeric() || char == '_') => {
+                    let mut field = char.to_string();
+                    while let Some(&char) = chars.peek() {
+                        if !(char.is_alphanumeric() || char == '_' || char == '-') {
+                            break;
+                        }
+                        field.push(char);
+                        chars.next();
+                    }
+                    steps.push(Step::Field(field));
+                    after_dot = false;
+                }
+                char => return Err(syntax_error(&format!("unexpected '{char}'"))),
+            }
+        }
+        if after_dot && !steps.is_empty() {
+            return Err(syntax_error("it shouldn't end with '.'"));
+        }
+
+        Ok(Query { steps })
+    }
+}
+
+/// `Query` methods
+impl Query {
+    /// Selects the value of the query.
+    ///
+    /// Like jq, a missing field or an index out of range selects `null`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON output.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the selected value or a `QueryError` if a step doesn't apply to the type of a value
+    /// (e.g. a field of an array).
+    pub fn select<'a>(&self, value: &'a Value) -> Result<&'a Value, QueryError> {
+        self.steps
+            .iter()
+            .try_fold(value, |value, step| match (step, value) {
+                (_, Value::Null) => Ok(&Value::Null),
+                (Step::Field(field), Value::Object(object)) => {
+                    Ok(object.get(field).unwrap_or(&Value::Null))
+                }
+                (Step::Index(index), Value::Array(array)) => {
+                    let index = if *index < 0 {
+                        array.len().checked_sub(index.unsigned_abs() as usize)
+                    } else {
+                        Some(*index as usize)
+                    };
+                    Ok(index
+                        .and_then(|index| array.get(index))
+                        .unwrap_or(&Value::Null))
+                }
+                (step, value) => Err(QueryError::Type(
+                    step.to_string().paint(Role::Warning).to_string(),
+                    type_name(value).to_owned(),
+                )),
+            })
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Field(field) => write!(f, ".{field}"),
+            Step::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
+/// Returns the name of the type of a JSON value with an article (e.g. `an array`).
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Formats a selected value for scripts: strings are printed raw (without quotes), other values as compact JSON.
+///
+/// # Arguments
+///
+/// * `value` - The selected value.
+///
+/// # Returns
+///
+/// The formatted value.
+pub fn format(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case(".", vec![])]
+    #[case(".temp", vec![Step::Field("temp".to_owned())])]
+    #[case(
+        ".hours[0].chance_of_rain",
+        vec![Step::Field("hours".to_owned()), Step::Index(0), Step::Field("chance_of_rain".to_owned())]
+    )]
+    #[case(".[-1].\"max temp\"", vec![Step::Index(-1), Step::Field("max temp".to_owned())])]
+    fn test_query_from_str(#[case] query: &str, #[case] expected: Vec<Step>) {
+        assert_eq!(query.parse::<Query>().unwrap().steps, expected);
+    }
+
+    #[rstest]
+    #[case("temp")]
+    #[case(".temp.")]
+    #[case(".hours[first]")]
+    #[case(".\"temp")]
+    #[case(".temp..humidity")]
+    #[case(".hours[0")]
+    fn test_query_from_str_invalid(#[case] query: &str) {
+        assert!(matches!(
+            query.parse::<Query>(),
+            Err(QueryError::Syntax(..))
+        ));
+    }
+
+    #[rstest]
+    #[case(".", Ok(json!({"temp": 12.5, "days": [{"date": "2024-03-08"}, {"date": "2024-03-09"}]})))]
+    #[case(".temp", Ok(json!(12.5)))]
+    #[case(".days[-1].date", Ok(json!("2024-03-09")))]
+    #[case(".days[5].date", Ok(Value::Null))]
+    #[case(".humidity", Ok(Value::Null))]
+    #[case(".temp.max", Err(()))]
+    #[case(".days.date", Err(()))]
+    fn test_query_select(#[case] query: &str, #[case] expected: Result<Value, ()>) {
+        let output =
+            json!({"temp": 12.5, "days": [{"date": "2024-03-08"}, {"date": "2024-03-09"}]});
+
+        let result = query.parse::<Query>().unwrap().select(&output);
+
+        assert_eq!(result.cloned().map_err(|_| ()), expected);
+    }
+
+    #[rstest]
+    #[case(json!("clear sky"), "clear sky")]
+    #[case(json!(12.5), "12.5")]
+    #[case(json!({"temp": 12.5}), "{\"temp\":12.5}")]
+    #[case(Value::Null, "null")]
+    fn test_format(#[case] value: Value, #[case] expected: &str) {
+        assert_eq!(format(&value), expected);
+    }
+}
@@ -0,0 +1,506 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use convert_case::{Case, Casing};
+use std::fmt::Write;
+use thiserror::Error;
+
+use crate::cli_parser::ReportFormat;
+use crate::i18n::{translate, Label};
+use crate::stats;
+use crate::views;
+use weather_api_services::models::{ForecastDay, WeatherData};
+use weather_api_services::quantities::Celsius;
+use weather_api_services::units::{PressureUnit, Units, VisibilityUnit};
+
+/// The template of HTML reports.
+const HTML_TEMPLATE: &str = include_str!("../templates/report.html");
+
+/// The template of Markdown reports.
+const MARKDOWN_TEMPLATE: &str = include_str!("../templates/report.md");
+
+/// The size of the forecast chart of HTML reports in SVG user units.
+const CHART_WIDTH: f32 = 600.0;
+const CHART_HEIGHT: f32 = 200.0;
+
+/// The padding around the plot area of the forecast chart in SVG user units.
+const CHART_PADDING: f32 = 30.0;
+
+/// Represents errors related to weather reports.
+#[derive(Error, Debug)]
+pub enum ReportError {
+    /// An error indicating a failure to write the report file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the report file.
+    #[error("Failed to write the report '{1}'")]
+    Io(std::io::Error, String),
+}
+
+/// Represents a shareable weather report of an address with the current conditions and the forecast.
+pub struct Report {
+    pub address: String,
+    /// The current weather in the units of the report.
+    pub weather: WeatherData,
+    /// The forecast in the units of the report.
+    pub forecast: Vec<ForecastDay>,
+    /// The local time the report was generated at.
+    pub generated_at: NaiveDateTime,
+}
+
+/// `Report` rendering methods
+impl Report {
+    /// Renders the report from the template of the format.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format of the report.
+    /// * `units` - The units of the weather data.
+    /// * `pressure_unit` - The units of pressure in the report.
+    /// * `visibility_unit` - The units of visibility in the report.
+    /// * `language` - An optional language of the labels. Pass `None` for English.
+    ///
+    /// # Returns
+    ///
+    /// The rendered report.
+    pub fn render(
+        &self,
+        format: ReportFormat,
+        units: Units,
+        pressure_unit: PressureUnit,
+        visibility_unit: VisibilityUnit,
+        language: Option<&str>,
+    ) -> String {
+        let escape = match format {
+            ReportFormat::Html => escape_html,
+            ReportFormat::Md => escape_markdown,
+        };
+        let label = |label| translate(label, language);
+        let temp_symbol = units.temperature_symbol();
+        let weather = &self.weather;
+
+        let mut current = vec![
+            (
+                label(Label::Description),
+                weather.description.to_case(Case::Title),
+            ),
+            (
+                label(Label::Temperature),
+                format!("{:.1} {temp_symbol}", units.temperature(weather.temp)),
+            ),
+            (
+                label(Label::FeelsLike),
+                format!("{:.1} {temp_symbol}", units.temperature(weather.feels_like)),
+            ),
+            (label(Label::Humidity), format!("{} %", weather.humidity)),
+            (
+                label(Label::DewPoint),
+                format!("{:.1} {temp_symbol}", units.temperature(weather.dew_point)),
+            ),
+            (
+                label(Label::Pressure),
+                pressure_unit.format(weather.pressure),
+            ),
+            (
+                label(Label::WindSpeed),
+                format!(
+                    "{:.1} {}",
+                    units.speed(weather.wind_speed),
+                    units.speed_symbol()
+                ),
+            ),
+            (
+                label(Label::Visibility),
+                visibility_unit.format(weather.visibility),
+            ),
+        ];
+        if let Some(observed) = weather
+            .observed_at
+            .and_then(|observed_at| Local.timestamp_opt(observed_at, 0).single())
+        {
+            current.push((
+                label(Label::Observed),
+                observed.format("%Y-%m-%d %H:%M").to_string(),
+            ));
+        }
+        let current: Vec<Vec<String>> = current
+            .into_iter()
+            .map(|(name, value)| vec![name.to_owned(), value])
+            .collect();
+
+        let forecast_header = [
+            label(Label::Date),
+            label(Label::Description),
+            label(Label::Temperature),
+            label(Label::Precipitation),
+        ]
+        .map(str::to_owned)
+        .to_vec();
+        let forecast_rows: Vec<Vec<String>> = self
+            .forecast
+            .iter()
+            .map(|day| {
+                vec![
+                    day.date.clone(),
+                    day.description.to_case(Case::Title),
+                    format!(
+                        "{:.1}..{:.1} {temp_symbol}",
+                        units.temperature(day.min_temp),
+                        units.temperature(day.max_temp)
+                    ),
+                    format!(
+                        "{}% {:.1} {}",
+                        day.chance_of_rain.max(day.chance_of_snow),
+                        day.precipitation,
+                        units.precipitation_symbol()
+                    ),
+                ]
+            })
+            .collect();
+
+        let footer = views::source_footer(&weather.source)
+            .map_or_else(String::new, |source| format!("{source} · "))
+            + "Generated by weather-rs";
+        let variables = [
+            ("title", escape(&format!("Weather in {}", self.address))),
+            (
+                "generated",
+                escape(&format!(
+                    "Generated at {}",
+                    self.generated_at.format("%Y-%m-%d %H:%M")
+                )),
+            ),
+            ("language", escape(language.unwrap_or("en"))),
+            ("footer", escape(&footer)),
+        ];
+
+        let (template, current, forecast, chart) = match format {
+            ReportFormat::Html => (
+                HTML_TEMPLATE,
+                html_rows(None, &current),
+                html_rows(Some(&forecast_header), &forecast_rows),
+                chart_svg(&self.forecast, units),
+            ),
+            ReportFormat::Md => (
+                MARKDOWN_TEMPLATE,
+                markdown_table(
+                    &[label(Label::Name), label(Label::Value)].map(str::to_owned),
+                    &current,
+                ),
+                markdown_table(&forecast_header, &forecast_rows),
+                chart_sparklines(&self.forecast, units),
+            ),
+        };
+
+        fill(
+            template,
+            variables.into_iter().chain([
+                ("current", current),
+                ("forecast", forecast),
+                ("chart", chart),
+            ]),
+        )
+    }
+}
+
+/// Fills the `{{ name }}` placeholders of a template with values.
+///
+/// # Arguments
+///
+/// * `template` - The template.
+/// * `variables` - The names and the (already escaped) values of the placeholders.
+///
+/// # Returns
+///
+/// The filled template; unknown placeholders are kept as they are.
+fn fill(template: &str, variables: impl IntoIterator<Item = (&'static str, String)>) -> String {
+    variables
+        .into_iter()
+        .fold(template.to_owned(), |content, (name, value)| {
+            content.replace(&format!("{{{{ {name} }}}}"), &value)
+        })
+}
+
+/// Escapes the characters with a special meaning in HTML.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Escapes the characters that would break Markdown table cells or start inline markup.
+pub fn escape_markdown(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, char| {
+        if matches!(char, '|' | '*' | '_' | '`' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+        escaped
+    })
+}
+
+/// Renders the rows of an HTML table with escaped cells; the header row is rendered with `th` cells.
+fn html_rows(header: Option<&[String]>, rows: &[Vec<String>]) -> String {
+    let row = |cells: &[String], tag: &str| {
+        let cells: String = cells
+            .iter()
+            .map(|cell| format!("<{tag}>{}</{tag}>", escape_html(cell)))
+            .collect();
+
+        format!("<tr>{cells}</tr>")
+    };
+
+    header
+        .map(|header| row(header, "th"))
+        .into_iter()
+        .chain(rows.iter().map(|cells| row(cells, "td")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders a Markdown table with escaped cells.
+fn markdown_table(header: &[String], rows: &[Vec<String>]) -> String {
+    let row = |cells: &[String]| {
+        let cells: Vec<String> = cells.iter().map(|cell| escape_markdown(cell)).collect();
+
+        format!("| {} |", cells.join(" | "))
+    };
+
+    [row(header), format!("|{}", "---|".repeat(header.len()))]
+        .into_iter()
+        .chain(rows.iter().map(|cells| row(cells)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the forecast chart of HTML reports as inline SVG: bars of the chance of precipitation and lines of
+/// the daily maximum and minimum temperatures.
+///
+/// # Arguments
+///
+/// * `forecast` - The forecasted days.
+/// * `units` - The units of the temperatures.
+///
+/// # Returns
+///
+/// The `svg` element or an empty string if there are no forecasted days.
+fn chart_svg(forecast: &[ForecastDay], units: Units) -> String {
+    if forecast.is_empty() {
+        return String::new();
+    }
+
+    let min = forecast
+        .iter()
+        .map(|day| units.temperature(day.min_temp))
+        .fold(f32::INFINITY, f32::min);
+    let max = forecast
+        .iter()
+        .map(|day| units.temperature(day.max_temp))
+        .fold(f32::NEG_INFINITY, f32::max);
+    let range = (max - min).max(1.0);
+    let plot_height = CHART_HEIGHT - 2.0 * CHART_PADDING;
+    let step = (CHART_WIDTH - 2.0 * CHART_PADDING) / forecast.len() as f32;
+    let x = |index: usize| CHART_PADDING + step * (index as f32 + 0.5);
+    let y = |temp: f32| CHART_PADDING + (max - temp) / range * plot_height;
+
+    let mut svg = format!(
+        r#"<svg viewBox="0 0 {CHART_WIDTH} {CHART_HEIGHT}" xmlns="http://www.w3.org/2000/svg" role="img" aria-label="Temperature and chance of precipitation">"#
+    );
+    for (index, day) in forecast.iter().enumerate() {
+        let chance = f32::from(day.chance_of_rain.max(day.chance_of_snow).min(100));
+        let height = chance / 100.0 * plot_height;
+        let _ = write!(
+            svg,
+            r#"<rect class="rain" x="{:.1}" y="{:.1}" width="{:.1}" height="{height:.1}"/><text class="axis" x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            x(index) - step * 0.3,
+            CHART_PADDING + plot_height - height,
+            step * 0.6,
+            x(index),
+            CHART_HEIGHT - CHART_PADDING / 3.0,
+            escape_html(&day.date)
+        );
+    }
+    for (class, temp_of) in [
+        ("max", (|day| day.max_temp) as fn(&ForecastDay) -> Celsius),
+        ("min", |day| day.min_temp),
+    ] {
+        let temp_of = |day| units.temperature(temp_of(day));
+        let points: Vec<String> = forecast
+            .iter()
+            .enumerate()
+            .map(|(index, day)| format!("{:.1},{:.1}", x(index), y(temp_of(day))))
+            .collect();
+        let _ = write!(
+            svg,
+            r#"<polyline class="{class}" fill="none" stroke-width="2" points="{}"/>"#,
+            points.join(" ")
+        );
+        for (index, day) in forecast.iter().enumerate() {
+            let temp = temp_of(day);
+            let _ = write!(
+                svg,
+                r#"<circle class="{class}" cx="{:.1}" cy="{:.1}" r="3"/><text class="axis" x="{:.1}" y="{:.1}" text-anchor="middle">{temp:.0}{}</text>"#,
+                x(index),
+                y(temp),
+                x(index),
+                y(temp) - 8.0,
+                units.temperature_symbol()
+            );
+        }
+    }
+    svg.push_str("</svg>");
+
+    svg
+}
+
+/// Renders the forecast chart of Markdown reports as sparklines of the daily maximum and minimum temperatures.
+///
+/// # Arguments
+///
+/// * `forecast` - The forecasted days.
+/// * `units` - The units of the temperatures.
+///
+/// # Returns
+///
+/// A code block with the sparklines or an empty string if there are no forecasted days.
+fn chart_sparklines(forecast: &[ForecastDay], units: Units) -> String {
+    if forecast.is_empty() {
+        return String::new();
+    }
+
+    let temp_symbol = units.temperature_symbol();
+    let line = |name: &str, temps: Vec<f32>| {
+        let (min, max) = temps
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), temp| {
+                (min.min(*temp), max.max(*temp))
+            });
+
+        format!(
+            "{name} {} {min:.1}..{max:.1} {temp_symbol}",
+            stats::sparkline(&temps)
+        )
+    };
+
+    format!(
+        "```\n{}\n{}\n```",
+        line(
+            "max",
+            forecast
+                .iter()
+                .map(|day| units.temperature(day.max_temp))
+                .collect()
+        ),
+        line(
+            "min",
+            forecast
+                .iter()
+                .map(|day| units.temperature(day.min_temp))
+                .collect()
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use weather_api_services::models::DataSource;
+    use weather_api_services::quantities::{HectoPascal, MetersPerSecond};
+
+    fn report() -> Report {
+        let day = |date: &str, min_temp, max_temp| ForecastDay {
+            date: date.to_owned(),
+            min_temp: Celsius(min_temp),
+            max_temp: Celsius(max_temp),
+            description: "light rain".to_owned(),
+            chance_of_rain: 80,
+            chance_of_snow: 0,
+            precipitation: 2.5,
+            hours: Vec::new(),
+        };
+
+        Report {
+            address: "Rock & Roll <Town>".to_owned(),
+            weather: WeatherData::builder()
+                .temp(Celsius(14.5))
+                .feels_like(Celsius(13.0))
+                .humidity(70)
+                .dew_point(Celsius(9.0))
+                .pressure(HectoPascal(1012))
+                .wind_speed(MetersPerSecond(4.0))
+                .visibility(10000)
+                .description("partly cloudy")
+                .source(DataSource {
+                    provider: "Weather API".to_owned(),
+                    ..Default::default()
+                })
+                .build(),
+            forecast: vec![day("2024-07-10", 10.0, 18.0), day("2024-07-11", 12.0, 20.0)],
+            generated_at: NaiveDateTime::parse_from_str("2024-07-10 12:30", "%Y-%m-%d %H:%M")
+                .unwrap(),
+        }
+    }
+
+    #[rstest]
+    fn test_render_html() {
+        let result = report().render(
+            ReportFormat::Html,
+            Units::Metric,
+            PressureUnit::Hpa,
+            VisibilityUnit::Meters,
+            None,
+        );
+
+        assert!(result.contains("<title>Weather in Rock &amp; Roll &lt;Town&gt;</title>"));
+        assert!(result.contains("<tr><td>Temperature</td><td>14.5 °C</td></tr>"));
+        assert!(result.contains("<tr><td>2024-07-11</td><td>Light Rain</td><td>12.0..20.0 °C</td><td>80% 2.5 mm</td></tr>"));
+        assert!(result.contains("<svg"));
+        assert!(result.contains("Weather API · Generated by weather-rs"));
+        assert!(!result.contains("{{"));
+    }
+
+    #[rstest]
+    fn test_render_markdown() {
+        let result = report().render(
+            ReportFormat::Md,
+            Units::Imperial,
+            PressureUnit::InHg,
+            VisibilityUnit::Miles,
+            Some("de"),
+        );
+
+        assert!(result.starts_with("# Weather in Rock & Roll <Town>\n"));
+        assert!(result.contains("| Temperatur | 58.1 °F |"));
+        assert!(result.contains(" | 29.88 inHg |"));
+        assert!(result.contains(" | 6.2 mi |"));
+        assert!(result.contains("| 2024-07-10 | Light Rain | 50.0..64.4 °F | 80% 2.5 in |"));
+        assert!(result.contains("max ▁█ 64.4..68.0 °F"));
+        assert!(!result.contains("{{"));
+    }
+
+    #[rstest]
+    #[case("a|b", r"a\|b")]
+    #[case("snake_case", r"snake\_case")]
+    #[case("plain", "plain")]
+    fn test_escape_markdown(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(escape_markdown(text), expected);
+    }
+
+    #[rstest]
+    #[case("html", ReportFormat::Html)]
+    #[case("markdown", ReportFormat::Md)]
+    fn test_render_snapshot(#[case] name: &str, #[case] format: ReportFormat) {
+        let result = report().render(
+            format,
+            Units::Metric,
+            PressureUnit::Hpa,
+            VisibilityUnit::Kilometers,
+            None,
+        );
+
+        insta::assert_snapshot!(name, result);
+    }
+}
@@ -0,0 +1,523 @@
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::theme::{Paint, Role};
+use weather_api_services::models::{ForecastDay, ForecastHour};
+use weather_api_services::DateTimeError;
+
+/// The mean radius of the Earth in kilometers.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// The longest step between forecasted hours of the providers (OpenWeather forecasts in 3 hour steps) in seconds.
+const MAX_HOUR_DISTANCE_SECS: i64 = 3 * 3600;
+
+/// Represents errors related to reading a route track.
+#[derive(Error, Debug)]
+pub enum RouteError {
+    /// An error indicating a failure to read the track file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific read error.
+    /// * `1` - A string representing the path to the track file.
+    #[error("Failed to read the track file '{1}'")]
+    Read(std::io::Error, String),
+
+    /// An error indicating that the track file is neither a GPX nor a GeoJSON document.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the reason of the parse failure.
+    #[error("Failed to parse the track; expected a GPX file with track or route points or a GeoJSON LineString ({0})")]
+    Parse(String),
+
+    /// An error indicating that the track doesn't contain any points.
+    #[error("The track doesn't contain any points")]
+    Empty,
+}
+
+/// Represents a point of a route.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RoutePoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// `RoutePoint` methods
+impl RoutePoint {
+    /// Computes the great-circle distance to another point (haversine formula).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other point.
+    ///
+    /// # Returns
+    ///
+    /// The distance in kilometers.
+    pub fn distance_to(&self, other: &RoutePoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let delta_lat = lat2 - lat1;
+        let delta_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    /// Returns the point as a `latitude,longitude` query that providers accept as an address.
+    pub fn to_query(self) -> String {
+        format!("{:.4},{:.4}", self.latitude, self.longitude)
+    }
+
+    /// Interpolates linearly between this point and another one.
+    fn interpolate(&self, other: &RoutePoint, fraction: f64) -> RoutePoint {
+        RoutePoint {
+            latitude: self.latitude + (other.latitude - self.latitude) * fraction,
+            longitude: self.longitude + (other.longitude - self.longitude) * fraction,
+        }
+    }
+}
+
+/// Represents a point sampled along a route with its distance from the start.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct SampledPoint {
+    #[serde(flatten)]
+    pub point: RoutePoint,
+    /// The distance from the start of the route in kilometers.
+    pub distance: f64,
+}
+
+/// Parses the points of a route track from a GPX or a GeoJSON document.
+///
+/// GPX documents are read for track points (`trkpt`) or, if there are none, route points (`rtept`).
+/// GeoJSON documents are read for the coordinates of a `LineString` (or `MultiLineString`) geometry,
+/// which can be wrapped into a `Feature` or a `FeatureCollection`.
+///
+/// # Arguments
+///
+/// * `content` - The content of the track file.
+///
+/// # Returns
+///
+/// A `Result` containing the points of the route or a `RouteError` if the track can't be parsed or is empty.
+pub fn parse_track(content: &str) -> Result<Vec<RoutePoint>, RouteError> {
+    let points = if content.trim_start().starts_with('<') {
+        let track_points = parse_gpx_points(content, "trkpt")?;
+
+        if track_points.is_empty() {
+            parse_gpx_points(content, "rtept")?
+        } else {
+            track_points
+        }
+    } else {
+        let geojson: Value =
+            serde_json::from_str(content).map_err(|err| RouteError::Parse(err.to_string()))?;
+        let mut points = Vec::new();
+        collect_geojson_points(&geojson, &mut points);
+        points
+    };
+
+    if points.is_empty() {
+        return Err(RouteError::Empty);
+    }
+
+    Ok(points)
+}
+
+/// Samples points along a route at regular distances, interpolating between the track points.
+///
+/// The start and the end of the route are always sampled.
+///
+/// # Arguments
+///
+/// * `points` - The points of the route.
+/// * `every` - The distance between the sampled points in kilometers.
+///
+/// # Returns
+///
+/// The sampled points with their distances from the start.
+pub fn sample(points: &[RoutePoint], every: f64) -> Vec<SampledPoint> {
+    let Some(first) = points.first() else {
+        return Vec::new();
+    };
+
+    let mut samples = vec![SampledPoint {
+        point: *first,
+        distance: 0.0,
+    }];
+    let mut next_distance = every;
+    let mut travelled = 0.0;
+
+    for segment in points.windows(2) {
+        let length = segment[0].distance_to(&segment[1]);
+
+        while every > 0.0 && length > 0.0 && next_distance <= travelled + length {
+            let fraction = (next_distance - travelled) / length;
+            samples.push(SampledPoint {
+                point: segment[0].interpolate(&segment[1], fraction),
+                distance: next_distance,
+            });
+            next_distance += every;
+        }
+
+        travelled += length;
+    }
+
+    let last_sample_distance = samples.last().map_or(0.0, |sample| sample.distance);
+    if travelled - last_sample_distance > f64::EPSILON {
+        samples.push(SampledPoint {
+            point: points[points.len() - 1],
+            distance: travelled,
+        });
+    }
+
+    samples
+}
+
+/// Parses the departure time of a route.
+///
+/// # Arguments
+///
+/// * `start` - An optional departure time, either `HH:MM` (today) or `YYYY-MM-DD HH:MM`. Pass `None` to depart now.
+/// * `now` - The current local date and time.
+///
+/// # Returns
+///
+/// A `Result` containing the departure time or a `DateTimeError` if the time is invalid.
+pub fn departure_time(
+    start: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<NaiveDateTime, DateTimeError> {
+    let Some(start) = start.map(str::trim) else {
+        return Ok(now);
+    };
+
+    NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M")
+        .or_else(|_| {
+            NaiveTime::parse_from_str(start, "%H:%M").map(|time| now.date().and_time(time))
+        })
+        .map_err(|_| DateTimeError::Parse(start.paint(Role::Warning).to_string()))
+}
+
+/// Estimates the arrival time at a distance along the route.
+///
+/// # Arguments
+///
+/// * `departure` - The departure time.
+/// * `distance` - The distance from the start in kilometers.
+/// * `speed` - The average speed in kilometers per hour.
+///
+/// # Returns
+///
+/// The estimated arrival time.
+pub fn arrival_time(departure: NaiveDateTime, distance: f64, speed: f64) -> NaiveDateTime {
+    let seconds = if speed > 0.0 {
+        distance / speed * 3600.0
+    } else {
+        0.0
+    };
+
+    departure + Duration::seconds(seconds.round() as i64)
+}
+
+/// Finds the forecasted hour nearest to a time.
+///
+/// The hours are compared by their timestamps, so the time of interest and the local times of the forecast
+/// don't have to be in the same time zone; hours without a timestamp are skipped.
+///
+/// # Arguments
+///
+/// * `forecast` - The forecasted days with hourly data.
+/// * `time` - The time of interest.
+///
+/// # Returns
+///
+/// The nearest forecasted hour or `None` if the forecast doesn't have hourly data or the nearest hour is
+/// more than one forecast step (3 hours) away from the time.
+pub fn nearest_hour(forecast: Vec<ForecastDay>, time: DateTime<Utc>) -> Option<ForecastHour> {
+    forecast
+        .into_iter()
+        .flat_map(|day| day.hours)
+        .filter_map(|hour| Some(((hour.timestamp? - time.timestamp()).abs(), hour)))
+        .min_by_key(|(difference, _)| *difference)
+        .filter(|(difference, _)| *difference <= MAX_HOUR_DISTANCE_SECS)
+        .map(|(_, hour)| hour)
+}
+
+/// Parses the `lat` and `lon` attributes of all GPX elements with the given name.
+fn parse_gpx_points(content: &str, element: &str) -> Result<Vec<RoutePoint>, RouteError> {
+    let opening = format!("<{element}");
+
+    content
+        .match_indices(&opening)
+        .filter_map(|(start, _)| {
+            let tag = &content[start..];
+            let end = tag.find('>')?;
+            let tag = &tag[..end];
+            // skips elements with a longer name that starts with the same prefix
+            tag[opening.len()..]
+                .starts_with(char::is_whitespace)
+                .then_some(tag)
+        })
+        .map(|tag| {
+            let attribute = |name| {
+                gpx_attribute(tag, name)
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .ok_or_else(|| RouteError::Parse(format!("invalid '{name}' of <{element}>")))
+            };
+
+            Ok(RoutePoint {
+                latitude: attribute("lat")?,
+                longitude: attribute("lon")?,
+            })
+        })
+        .collect()
+}
+
+/// Returns the value of an attribute of an XML tag (e.g. `<trkpt lat = "50.45" lon='30.52'/`).
+///
+/// The attributes are read one by one as `name = "value"`, so whitespace around `=` and either kind
+/// of quotes are accepted.
+fn gpx_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    // skips the element name
+    let mut rest = tag
+        .trim_start_matches('<')
+        .trim_start_matches(|c: char| !c.is_whitespace());
+
+    loop {
+        rest = rest.trim_start();
+        let (key, after_key) = rest.split_at(rest.find(|c: char| c == '=' || c.is_whitespace())?);
+        let value = after_key.trim_start().strip_prefix('=')?.trim_start();
+        let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let value = &value[1..];
+        let end = value.find(quote)?;
+
+        if key == name {
+            return Some(&value[..end]);
+        }
+        rest = &value[end + 1..];
+    }
+}
+
+/// Collects the coordinates of the `LineString` and `MultiLineString` geometries of a GeoJSON value.
+fn collect_geojson_points(value: &Value, points: &mut Vec<RoutePoint>) {
+    let position = |position: &Value| {
+        let position = position.as_array()?;
+
+        Some(RoutePoint {
+            longitude: position.first()?.as_f64()?,
+            latitude: position.get(1)?.as_f64()?,
+        })
+    };
+
+    match value.get("type").and_then(Value::as_str) {
+        Some("FeatureCollection") => value
+            .get("features")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .for_each(|feature| collect_geojson_points(feature, points)),
+        Some("Feature") => {
+            if let Some(geometry) = value.get("geometry") {
+                collect_geojson_points(geometry, points);
+            }
+        }
+        Some("LineString") => points.extend(
+            value
+                .get("coordinates")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(position),
+        ),
+        Some("MultiLineString") => points.extend(
+            value
+                .get("coordinates")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_array)
+                .flatten()
+                .filter_map(position),
+        ),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use weather_api_services::quantities::{Celsius, MetersPerSecond};
+
+    const KYIV: RoutePoint = RoutePoint {
+        latitude: 50.45,
+        longitude: 30.52,
+    };
+    const EQUATOR: [RoutePoint; 2] = [
+        RoutePoint {
+            latitude: 0.0,
+            longitude: 0.0,
+        },
+        RoutePoint {
+            latitude: 0.0,
+            longitude: 1.0,
+        },
+    ];
+
+    #[rstest]
+    #[case(
+        r#"<?xml version="1.0"?><gpx><trk><trkseg>
+            <trkpt lat="50.45" lon="30.52"><ele>179</ele></trkpt>
+            <trkpt lon="30.53" lat="50.46"/>
+        </trkseg></trk></gpx>"#,
+        vec![(50.45, 30.52), (50.46, 30.53)]
+    )]
+    #[case(
+        r#"<gpx><rte><rtept lat='1.5' lon='2.5'></rtept></rte></gpx>"#,
+        vec![(1.5, 2.5)]
+    )]
+    #[case(
+        r#"<gpx><trk><trkseg><trkpt name="lat=0" lat = "1" lon= '2'/></trkseg></trk></gpx>"#,
+        vec![(1.0, 2.0)]
+    )]
+    #[case(
+        r#"{"type": "Feature", "geometry": {"type": "LineString", "coordinates": [[30.52, 50.45], [30.53, 50.46, 180.0]]}}"#,
+        vec![(50.45, 30.52), (50.46, 30.53)]
+    )]
+    #[case(
+        r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "geometry": {"type": "MultiLineString", "coordinates": [[[1.0, 2.0]], [[3.0, 4.0]]]}}]}"#,
+        vec![(2.0, 1.0), (4.0, 3.0)]
+    )]
+    fn test_parse_track(#[case] content: &str, #[case] expected: Vec<(f64, f64)>) {
+        let expected: Vec<RoutePoint> = expected
+            .into_iter()
+            .map(|(latitude, longitude)| RoutePoint {
+                latitude,
+                longitude,
+            })
+            .collect();
+
+        assert_eq!(parse_track(content).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("<gpx></gpx>")]
+    #[case(r#"{"type": "Point", "coordinates": [1.0, 2.0]}"#)]
+    #[case(r#"<gpx><trkpt lat="north" lon="1"/></gpx>"#)]
+    #[case("not a track")]
+    fn test_parse_track_error(#[case] content: &str) {
+        assert!(parse_track(content).is_err());
+    }
+
+    #[rstest]
+    fn test_distance_to() {
+        let warsaw = RoutePoint {
+            latitude: 52.23,
+            longitude: 21.01,
+        };
+
+        let distance = KYIV.distance_to(&warsaw);
+
+        assert!((distance - 690.0).abs() < 5.0, "{distance}");
+    }
+
+    #[rstest]
+    #[case(50.0, vec![0.0, 50.0, 100.0, 111.19])]
+    #[case(200.0, vec![0.0, 111.19])]
+    fn test_sample(#[case] every: f64, #[case] expected: Vec<f64>) {
+        let distances: Vec<f64> = sample(&EQUATOR, every)
+            .iter()
+            .map(|sample| (sample.distance * 100.0).round() / 100.0)
+            .collect();
+
+        assert_eq!(distances, expected);
+    }
+
+    #[rstest]
+    #[case(None, "2024-07-01 10:30")]
+    #[case(Some("08:00"), "2024-07-01 08:00")]
+    #[case(Some("2024-07-02 06:15"), "2024-07-02 06:15")]
+    fn test_departure_time(#[case] start: Option<&str>, #[case] expected: &str) {
+        let now = NaiveDateTime::parse_from_str("2024-07-01 10:30", "%Y-%m-%d %H:%M").unwrap();
+
+        let result = departure_time(start, now).unwrap();
+
+        assert_eq!(result.format("%Y-%m-%d %H:%M").to_string(), expected);
+    }
+
+    #[rstest]
+    fn test_departure_time_invalid() {
+        let now = NaiveDateTime::parse_from_str("2024-07-01 10:30", "%Y-%m-%d %H:%M").unwrap();
+
+        assert!(departure_time(Some("8 am"), now).is_err());
+    }
+
+    #[rstest]
+    #[case(0.0, 15.0, "08:00")]
+    #[case(45.0, 15.0, "11:00")]
+    #[case(10.0, 40.0, "08:15")]
+    fn test_arrival_time(#[case] distance: f64, #[case] speed: f64, #[case] expected: &str) {
+        let departure =
+            NaiveDateTime::parse_from_str("2024-07-01 08:00", "%Y-%m-%d %H:%M").unwrap();
+
+        let result = arrival_time(departure, distance, speed);
+
+        assert_eq!(result.format("%H:%M").to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("2024-07-01 08:20", Some("2024-07-01 08:00"))]
+    #[case("2024-07-01 10:50", Some("2024-07-01 11:00"))]
+    #[case("2024-07-02 03:00", Some("2024-07-02 00:00"))]
+    #[case("2024-07-02 03:01", None)]
+    #[case("2024-07-03 00:00", None)]
+    fn test_nearest_hour(#[case] time: &str, #[case] expected: Option<&str>) {
+        let utc = |time: &str| {
+            NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M")
+                .unwrap()
+                .and_utc()
+        };
+        let hour = |time: &str| ForecastHour {
+            time: time.to_owned(),
+            timestamp: Some(utc(time).timestamp()),
+            temp: Celsius(20.0),
+            description: String::new(),
+            wind_speed: MetersPerSecond(2.0),
+            chance_of_rain: 0,
+            chance_of_snow: 0,
+            precipitation: 0.0,
+        };
+        let day = |date: &str, hours: Vec<ForecastHour>| ForecastDay {
+            date: date.to_owned(),
+            min_temp: Celsius(20.0),
+            max_temp: Celsius(20.0),
+            description: String::new(),
+            chance_of_rain: 0,
+            chance_of_snow: 0,
+            precipitation: 0.0,
+            hours,
+        };
+        let forecast = vec![
+            day(
+                "2024-07-01",
+                vec![hour("2024-07-01 08:00"), hour("2024-07-01 11:00")],
+            ),
+            day("2024-07-02", vec![hour("2024-07-02 00:00")]),
+        ];
+
+        let result = nearest_hour(forecast, utc(time));
+
+        assert_eq!(result.map(|hour| hour.time).as_deref(), expected);
+    }
+
+    #[rstest]
+    fn test_sample_interpolates_points() {
+        let samples = sample(&EQUATOR, 55.6);
+
+        assert!((samples[1].point.longitude - 0.5).abs() < 0.01);
+    }
+}
@@ -0,0 +1,305 @@
+use chrono::{Duration, NaiveDate, TimeZone};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::store::StoredObservation;
+use crate::theme::{Paint, Role};
+use weather_api_services::output::IntoOutput;
+use weather_api_services::quantities::Celsius;
+use weather_api_services::units::{ConvertUnits, Units};
+
+/// The characters of a sparkline from the lowest to the highest value.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Words of weather descriptions that mark a rainy observation.
+const RAIN_WORDS: [&str; 5] = ["rain", "drizzle", "shower", "thunder", "sleet"];
+
+/// Represents errors related to statistics over stored observations.
+#[derive(Error, Debug)]
+pub enum StatsError {
+    /// An error indicating an invalid period.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the invalid period.
+    #[error("Invalid period - {0}. Please use a number of days or weeks (e.g., '30d' or '4w')")]
+    Period(String),
+
+    /// An error indicating that there are no stored observations of the address in the period.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the address.
+    #[error("No stored observations of '{0}' in the period; observations are stored by the 'get' and 'export' commands")]
+    NoObservations(String),
+}
+
+/// Represents statistics of the observations of an address over a period.
+///
+/// The temperatures are `Celsius`, or plain degrees of the output units in the JSON output (see `IntoOutput`).
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Statistics<T = Celsius> {
+    /// The number of observations.
+    pub observations: usize,
+    /// The number of days with observations.
+    pub days: usize,
+    pub min_temp: T,
+    pub max_temp: T,
+    pub avg_temp: T,
+    /// The number of days with at least one rainy observation.
+    pub rainy_days: usize,
+    /// The trend of the daily mean temperature in degrees per day (least-squares slope); degrees Celsius unless
+    /// converted with `into_units`.
+    pub trend: f32,
+    /// The mean temperatures of the days with observations in chronological order.
+    pub daily_mean_temps: Vec<(NaiveDate, T)>,
+}
+
+/// `Statistics` constructors
+impl Statistics {
+    /// Computes statistics of observations.
+    ///
+    /// # Arguments
+    ///
+    /// * `observations` - The observations in metric units.
+    /// * `timezone` - The time zone in which observations are grouped by days.
+    ///
+    /// # Returns
+    ///
+    /// The `Statistics` or `None` if there are no observations.
+    pub fn compute<Tz: TimeZone>(
+        observations: &[StoredObservation],
+        timezone: &Tz,
+    ) -> Option<Self> {
+        if observations.is_empty() {
+            return None;
+        }
+
+        let mut days: BTreeMap<NaiveDate, (Vec<f32>, bool)> = BTreeMap::new();
+        for observation in observations {
+            let Some(time) = timezone.timestamp_opt(observation.observed_at, 0).single() else {
+                continue;
+            };
+            let (temps, rainy) = days.entry(time.date_naive()).or_default();
+            temps.push(observation.temp.value());
+            *rainy |= is_rainy(&observation.description);
+        }
+
+        let temps: Vec<f32> = observations
+            .iter()
+            .map(|observation| observation.temp.value())
+            .collect();
+        let daily_mean_temps: Vec<(NaiveDate, f32)> = days
+            .iter()
+            .map(|(date, (temps, _))| (*date, mean(temps)))
+            .collect();
+
+        Some(Statistics {
+            observations: observations.len(),
+            days: days.len(),
+            min_temp: Celsius(temps.iter().copied().fold(f32::INFINITY, f32::min)),
+            max_temp: Celsius(temps.iter().copied().fold(f32::NEG_INFINITY, f32::max)),
+            avg_temp: Celsius(mean(&temps)),
+            rainy_days: days.values().filter(|(_, rainy)| *rainy).count(),
+            trend: trend(&daily_mean_temps),
+            daily_mean_temps: daily_mean_temps
+                .into_iter()
+                .map(|(date, temp)| (date, Celsius(temp)))
+                .collect(),
+        })
+    }
+}
+
+impl ConvertUnits for Statistics {
+    fn into_units(mut self, units: Units) -> Self {
+        // the typed temperatures stay in degrees Celsius; only the untyped trend is converted
+        self.trend = units.temperature_difference(self.trend);
+        self
+    }
+}
+
+impl IntoOutput for Statistics {
+    type Output = Statistics<f32>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        Statistics {
+            observations: self.observations,
+            days: self.days,
+            min_temp: units.temperature(self.min_temp),
+            max_temp: units.temperature(self.max_temp),
+            avg_temp: units.temperature(self.avg_temp),
+            rainy_days: self.rainy_days,
+            trend: self.trend,
+            daily_mean_temps: self
+                .daily_mean_temps
+                .into_iter()
+                .map(|(date, temp)| (date, units.temperature(temp)))
+                .collect(),
+        }
+    }
+}
+
+/// Parses a period of statistics (e.g. `30d` or `4w`).
+///
+/// # Arguments
+///
+/// * `period` - The number of days (`d`) or weeks (`w`).
+///
+/// # Returns
+///
+/// A `Result` containing the duration of the period or a `StatsError` if it's invalid.
+pub fn parse_period(period: &str) -> Result<Duration, StatsError> {
+    let invalid = || StatsError::Period(period.paint(Role::Warning).to_string());
+    let period = period.trim();
+    let unit = period.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = period[..period.len() - unit.len_utf8()]
+        .parse()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(invalid)?;
+
+    match unit {
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+/// Renders values as a sparkline (e.g. `▁▃▅█`) scaled between the lowest and the highest value.
+///
+/// # Arguments
+///
+/// * `values` - The values in display order.
+///
+/// # Returns
+///
+/// The sparkline with a character per value.
+pub fn sparkline(values: &[f32]) -> String {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            let level = if range > 0.0 {
+                ((value - min) / range * (SPARKS.len() - 1) as f32).round() as usize
+            } else {
+                SPARKS.len() / 2
+            };
+
+            SPARKS[level.min(SPARKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Checks whether a weather description reports rain (e.g. `Light rain shower`).
+fn is_rainy(description: &str) -> bool {
+    let description = description.to_lowercase();
+
+    RAIN_WORDS.iter().any(|word| description.contains(word))
+}
+
+/// Computes the mean of values.
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len().max(1) as f32
+}
+
+/// Computes the least-squares slope of daily values in units per day; `0` for fewer than two days.
+fn trend(daily_values: &[(NaiveDate, f32)]) -> f32 {
+    let Some((first_date, _)) = daily_values.first() else {
+        return 0.0;
+    };
+    let points: Vec<(f32, f32)> = daily_values
+        .iter()
+        .map(|(date, value)| ((*date - *first_date).num_days() as f32, *value))
+        .collect();
+    let mean_x = mean(&points.iter().map(|(x, _)| *x).collect::<Vec<_>>());
+    let mean_y = mean(&points.iter().map(|(_, y)| *y).collect::<Vec<_>>());
+    let covariance: f32 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f32 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rstest::rstest;
+    use weather_api_services::quantities::{HectoPascal, MetersPerSecond};
+
+    fn observation(observed_at: i64, temp: f32, description: &str) -> StoredObservation {
+        StoredObservation {
+            address: "london".to_owned(),
+            observed_at,
+            temp: Celsius(temp),
+            humidity: 50,
+            pressure: HectoPascal(1013),
+            wind_speed: MetersPerSecond(3.0),
+            description: description.to_owned(),
+        }
+    }
+
+    #[rstest]
+    fn test_statistics_compute() {
+        // 2024-07-01 00:00 UTC
+        let day = 1_719_792_000;
+        let observations = [
+            observation(day + 3_600, 10.0, "Sunny"),
+            observation(day + 43_200, 14.0, "Light rain"),
+            observation(day + 86_400 + 3_600, 13.0, "Cloudy"),
+            observation(day + 2 * 86_400, 16.0, "Patchy light drizzle"),
+        ];
+
+        let result = Statistics::compute(&observations, &Utc).unwrap();
+
+        assert_eq!(result.observations, 4);
+        assert_eq!(result.days, 3);
+        assert_eq!(result.min_temp, Celsius(10.0));
+        assert_eq!(result.max_temp, Celsius(16.0));
+        assert_eq!(result.avg_temp, Celsius(13.25));
+        assert_eq!(result.rainy_days, 2);
+        assert_eq!(result.trend, 2.0);
+        assert_eq!(
+            result
+                .daily_mean_temps
+                .iter()
+                .map(|(_, temp)| temp.value())
+                .collect::<Vec<_>>(),
+            vec![12.0, 13.0, 16.0]
+        );
+        assert!(Statistics::compute(&[], &Utc).is_none());
+    }
+
+    #[rstest]
+    #[case("30d", Some(30))]
+    #[case("4w", Some(28))]
+    #[case("0d", None)]
+    #[case("30", None)]
+    #[case("d", None)]
+    #[case("month", None)]
+    #[case("30д", None)]
+    fn test_parse_period(#[case] period: &str, #[case] expected_days: Option<i64>) {
+        let result = parse_period(period).ok().map(|period| period.num_days());
+
+        assert_eq!(result, expected_days);
+    }
+
+    #[rstest]
+    #[case(&[1.0, 2.0, 3.0, 8.0], "▁▂▃█")]
+    #[case(&[5.0, 5.0], "▅▅")]
+    #[case(&[], "")]
+    fn test_sparkline(#[case] values: &[f32], #[case] expected: &str) {
+        assert_eq!(sparkline(values), expected);
+    }
+}
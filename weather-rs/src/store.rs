@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use thiserror::Error;
+
+use weather_api_services::models::WeatherData;
+use weather_api_services::quantities::{Celsius, HectoPascal, MetersPerSecond};
+
+/// Represents errors related to the local store of observations.
+#[derive(Error, Debug)]
+pub enum StoreError {
+    /// An error indicating a failure to read or write the store file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the store file.
+    #[error("Failed to access the observations store '{1}'")]
+    Io(std::io::Error, String),
+}
+
+/// Represents an observation kept in the local store; values are always in metric units.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredObservation {
+    /// The lowercase address of the observation.
+    pub address: String,
+    /// The time of the observation as a Unix timestamp.
+    pub observed_at: i64,
+    pub temp: Celsius,
+    pub humidity: u8,
+    pub pressure: HectoPascal,
+    pub wind_speed: MetersPerSecond,
+    pub description: String,
+}
+
+/// `StoredObservation` constructors
+impl StoredObservation {
+    /// Creates an observation of an address from weather data in metric units.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the weather data.
+    /// * `weather_data` - The weather data in metric units.
+    /// * `fallback_time` - The Unix timestamp used if the provider doesn't report the observation time.
+    ///
+    /// # Returns
+    ///
+    /// The observation to store.
+    pub fn new(address: &str, weather_data: &WeatherData, fallback_time: i64) -> Self {
+        StoredObservation {
+            address: address.trim().to_lowercase(),
+            observed_at: weather_data.observed_at.unwrap_or(fallback_time),
+            temp: weather_data.temp,
+            humidity: weather_data.humidity,
+            pressure: weather_data.pressure,
+            wind_speed: weather_data.wind_speed,
+            description: weather_data.description.clone(),
+        }
+    }
+}
+
+/// Appends observations to the store, one JSON object per line.
+///
+/// # Arguments
+///
+/// * `path` - The path to the store file; it's created with its directory if it doesn't exist.
+/// * `observations` - The observations to append.
+///
+/// # Returns
+///
+/// A `Result` indicating success or a `StoreError` if the store can't be written.
+pub fn append(path: &Path, observations: &[StoredObservation]) -> Result<(), StoreError> {
+    let to_error = |err| StoreError::Io(err, path.display().to_string());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(to_error)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(to_error)?;
+
+    for observation in observations {
+        let line = serde_json::to_string(observation).map_err(|err| to_error(err.into()))?;
+        writeln!(file, "{line}").map_err(to_error)?;
+    }
+
+    Ok(())
+}
+
+/// Loads the observations of an address since the given time from the store.
+///
+/// Lines that aren't valid observations are skipped and repeated observations (the same time of the same address)
+/// are loaded once.
+///
+/// # Arguments
+///
+/// * `path` - The path to the store file.
+/// * `address` - The address of the observations (case-insensitive).
+/// * `since` - The Unix timestamp of the earliest observation to load.
+///
+/// # Returns
+///
+/// A `Result` containing the observations sorted by time (empty if the store doesn't exist)
+/// or a `StoreError` if the store can't be read.
+pub fn load(path: &Path, address: &str, since: i64) -> Result<Vec<StoredObservation>, StoreError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(StoreError::Io(err, path.display().to_string())),
+    };
+    let address = address.trim().to_lowercase();
+
+    let mut observations: Vec<StoredObservation> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<StoredObservation>(line).ok())
+        .filter(|observation| observation.address == address && observation.observed_at >= since)
+        .collect();
+    observations.sort_by_key(|observation| observation.observed_at);
+    observations.dedup_by_key(|observation| observation.observed_at);
+
+    Ok(observations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn observation(address: &str, observed_at: i64, temp: f32) -> StoredObservation {
+        StoredObservation {
+            address: address.to_owned(),
+            observed_at,
+            temp: Celsius(temp),
+            humidity: 50,
+            pressure: HectoPascal(1013),
+            wind_speed: MetersPerSecond(3.0),
+            description: "Sunny".to_owned(),
+        }
+    }
+
+    #[rstest]
+    fn test_append_and_load() {
+        let path = std::env::temp_dir()
+            .join("weather-rs-test-store")
+            .join("observations.ndjson");
+        let _ = fs::remove_file(&path);
+
+        append(
+            &path,
+            &[
+                observation("london", 300, 14.0),
+                observation("paris", 200, 20.0),
+                observation("london", 100, 12.0),
+                observation("london", 300, 14.0),
+            ],
+        )
+        .unwrap();
+        fs::write(
+            &path,
+            fs::read_to_string(&path).unwrap() + "not an observation\n",
+        )
+        .unwrap();
+        let result = load(&path, " London ", 200).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, vec![observation("london", 300, 14.0)]);
+        assert!(load(&path, "London", 0).unwrap().is_empty());
+    }
+}
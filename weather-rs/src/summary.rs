@@ -0,0 +1,260 @@
+use chrono::{DateTime, Utc};
+use weather_api_services::models::{ForecastDay, ForecastHour};
+use weather_api_services::quantities::{Celsius, MetersPerSecond};
+use weather_api_services::units::Units;
+
+/// The chance of precipitation in percent from which precipitation is expected.
+const PRECIPITATION_CHANCE_THRESHOLD: u8 = 50;
+
+/// Generates a one-sentence summary of a forecasted day from its hourly data
+/// (e.g. `Cold and breezy morning, rain expected after 15:00, high of 9 °C`).
+///
+/// The summary is rule based: hours that have already passed are dropped, the temperature and the wind
+/// speed of the current hour describe the current part of the day, the first upcoming hour with a likely
+/// precipitation describes when rain or snow is expected, and the maximal temperature of the day is given
+/// as the high.
+///
+/// # Arguments
+///
+/// * `day` - The forecasted day to summarize.
+/// * `units` - The units of the high temperature in the summary.
+/// * `now` - The current time, used to drop the hours that have already passed.
+///
+/// # Returns
+///
+/// The summary sentence in English.
+pub fn summarize(day: &ForecastDay, units: Units, now: DateTime<Utc>) -> String {
+    let hours = upcoming_hours(&day.hours, now);
+    let current_hour = hours.first();
+    let temp = current_hour.map_or(day.min_temp, |hour| hour.temp);
+    let wind_speed = current_hour.map_or(MetersPerSecond::default(), |hour| hour.wind_speed);
+    let period = current_hour.map_or("day", |hour| day_period(&hour.time));
+
+    let mut conditions = temperature_adjective(temp).to_string();
+    if let Some(wind) = wind_adjective(wind_speed) {
+        conditions = format!("{conditions} and {wind}");
+    }
+
+    format!(
+        "{conditions} {period}, {}, high of {:.0} {}",
+        precipitation_outlook(day, hours),
+        units.temperature(day.max_temp),
+        units.temperature_symbol()
+    )
+}
+
+/// Returns the hours starting with the current one (the last hour that began before `now`).
+///
+/// The hours are compared by their timestamps, so the comparison doesn't depend on the time zone
+/// of the location; hours without a timestamp are kept.
+fn upcoming_hours(hours: &[ForecastHour], now: DateTime<Utc>) -> &[ForecastHour] {
+    let current = hours
+        .iter()
+        .rposition(|hour| {
+            hour.timestamp
+                .is_some_and(|timestamp| timestamp <= now.timestamp())
+        })
+        .unwrap_or(0);
+
+    &hours[current..]
+}
+
+/// Describes the temperature with an adjective.
+fn temperature_adjective(temp: Celsius) -> &'static str {
+    let temp = temp.value();
+
+    if temp < 0.0 {
+        "Freezing"
+    } else if temp < 8.0 {
+        "Cold"
+    } else if temp < 15.0 {
+        "Cool"
+    } else if temp < 22.0 {
+        "Mild"
+    } else if temp < 28.0 {
+        "Warm"
+    } else {
+        "Hot"
+    }
+}
+
+/// Describes the wind speed with an adjective or returns `None` for a light wind.
+fn wind_adjective(wind_speed: MetersPerSecond) -> Option<&'static str> {
+    let wind_speed = wind_speed.value();
+
+    if wind_speed >= 8.0 {
+        Some("windy")
+    } else if wind_speed >= 5.0 {
+        Some("breezy")
+    } else {
+        None
+    }
+}
+
+/// Returns the part of the day of a forecast time (`YYYY-MM-DD HH:MM`).
+fn day_period(time: &str) -> &'static str {
+    let hour = time
+        .get(11..13)
+        .and_then(|hour| hour.parse::<u8>().ok())
+        .unwrap_or(12);
+
+    match hour {
+        6..=11 => "morning",
+        12..=17 => "afternoon",
+        18..=21 => "evening",
+        _ => "night",
+    }
+}
+
+/// Describes when rain or snow is expected during the rest of the day.
+///
+/// The daily chances of precipitation are only used when the day has no hourly data.
+fn precipitation_outlook(day: &ForecastDay, hours: &[ForecastHour]) -> String {
+    let is_likely = |chance_of_rain: u8, chance_of_snow: u8| {
+        chance_of_rain.max(chance_of_snow) >= PRECIPITATION_CHANCE_THRESHOLD
+    };
+    let kind = |chance_of_rain: u8, chance_of_snow: u8| {
+        if chance_of_snow > chance_of_rain {
+            "snow"
+        } else {
+            "rain"
+        }
+    };
+
+    let first_likely_hour = hours
+        .iter()
+        .find(|hour| is_likely(hour.chance_of_rain, hour.chance_of_snow));
+
+    match first_likely_hour {
+        Some(ForecastHour {
+            time,
+            chance_of_rain,
+            chance_of_snow,
+            ..
+        }) => format!(
+            "{} expected after {}",
+            kind(*chance_of_rain, *chance_of_snow),
+            time.get(11..).unwrap_or(time)
+        ),
+        None if hours.is_empty() && is_likely(day.chance_of_rain, day.chance_of_snow) => {
+            format!("{} expected", kind(day.chance_of_rain, day.chance_of_snow))
+        }
+        None => "no precipitation expected".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use rstest::rstest;
+
+    /// Parses a time in the `YYYY-MM-DD hh:mm` format as UTC.
+    fn utc(time: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+    }
+
+    fn hour(
+        time: &str,
+        temp: f32,
+        wind_speed: f32,
+        chance_of_rain: u8,
+        chance_of_snow: u8,
+    ) -> ForecastHour {
+        ForecastHour {
+            time: time.to_string(),
+            timestamp: Some(utc(time).timestamp()),
+            temp: Celsius(temp),
+            description: String::new(),
+            wind_speed: MetersPerSecond(wind_speed),
+            chance_of_rain,
+            chance_of_snow,
+            precipitation: 0.0,
+        }
+    }
+
+    fn day(hours: Vec<ForecastHour>, chance_of_rain: u8, max_temp: f32) -> ForecastDay {
+        ForecastDay {
+            date: "2024-01-01".to_string(),
+            min_temp: Celsius(0.0),
+            max_temp: Celsius(max_temp),
+            description: String::new(),
+            chance_of_rain,
+            chance_of_snow: 0,
+            precipitation: 0.0,
+            hours,
+        }
+    }
+
+    #[rstest]
+    #[case(
+        day(vec![hour("2024-01-01 09:00", 4.0, 6.0, 10, 0), hour("2024-01-01 15:00", 9.0, 6.0, 70, 0)], 70, 9.0),
+        "Cold and breezy morning, rain expected after 15:00, high of 9 °C"
+    )]
+    #[case(
+        day(vec![hour("2024-01-01 13:00", 29.0, 1.0, 0, 0)], 0, 31.4),
+        "Hot afternoon, no precipitation expected, high of 31 °C"
+    )]
+    #[case(
+        day(vec![hour("2024-01-01 20:00", -3.0, 9.0, 0, 80)], 80, -1.0),
+        "Freezing and windy evening, snow expected after 20:00, high of -1 °C"
+    )]
+    #[case(day(vec![], 60, 12.0), "Cold day, rain expected, high of 12 °C")]
+    fn test_summarize(#[case] day: ForecastDay, #[case] expected: &str) {
+        assert_eq!(
+            summarize(&day, Units::Metric, utc("2024-01-01 00:00")),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "2024-01-01 10:30",
+        "Cool morning, no precipitation expected, high of 14 °C"
+    )]
+    #[case(
+        "2024-01-01 02:00",
+        "Cold night, rain expected after 03:00, high of 14 °C"
+    )]
+    #[case(
+        "2024-01-01 23:59",
+        "Cold night, no precipitation expected, high of 14 °C"
+    )]
+    fn test_summarize_drops_past_hours(#[case] now: &str, #[case] expected: &str) {
+        let day = day(
+            vec![
+                hour("2024-01-01 00:00", 2.0, 1.0, 0, 0),
+                hour("2024-01-01 03:00", 3.0, 1.0, 80, 0),
+                hour("2024-01-01 10:00", 12.0, 2.0, 0, 0),
+                hour("2024-01-01 14:00", 14.0, 2.0, 10, 0),
+                hour("2024-01-01 23:00", 6.0, 1.0, 0, 0),
+            ],
+            80,
+            14.0,
+        );
+
+        assert_eq!(summarize(&day, Units::Metric, utc(now)), expected);
+    }
+
+    #[rstest]
+    fn test_summarize_imperial() {
+        let day = day(vec![hour("2024-01-01 13:00", 29.0, 1.0, 0, 0)], 0, 31.4);
+
+        assert_eq!(
+            summarize(&day, Units::Imperial, utc("2024-01-01 00:00")),
+            "Hot afternoon, no precipitation expected, high of 89 °F"
+        );
+    }
+
+    #[rstest]
+    #[case("2024-01-01 00:00", "night")]
+    #[case("2024-01-01 06:00", "morning")]
+    #[case("2024-01-01 17:00", "afternoon")]
+    #[case("2024-01-01 21:00", "evening")]
+    #[case("2024-01-01 22:00", "night")]
+    fn test_day_period(#[case] time: &str, #[case] expected: &str) {
+        assert_eq!(day_period(time), expected);
+    }
+}
@@ -0,0 +1,205 @@
+use handlebars::{
+    no_escape, Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderErrorReason,
+};
+use serde::Serialize;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::theme::{Paint, Role};
+use weather_api_services::output::{ForecastDayOutput, WeatherDataOutput};
+use weather_api_services::units::Units;
+
+/// Represents errors related to custom output templates.
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    /// An error indicating a failure to read the template file.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - The `std::io::Error` indicating the specific error.
+    /// * `1` - A string representing the path to the template file.
+    #[error("Failed to read the template '{1}'")]
+    Io(std::io::Error, String),
+
+    /// An error indicating that the template is malformed or can't be rendered.
+    ///
+    /// # Parameters
+    ///
+    /// * `0` - A string representing the reason.
+    #[error("Failed to render the template - {0}")]
+    Render(String),
+}
+
+/// Represents the symbols of the units of the output, so templates don't hardcode them.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct UnitSymbols {
+    pub temperature: &'static str,
+    pub speed: &'static str,
+    pub precipitation: &'static str,
+}
+
+/// `UnitSymbols` constructors
+impl UnitSymbols {
+    /// Creates the symbols of a unit system.
+    pub fn new(units: Units) -> Self {
+        UnitSymbols {
+            temperature: units.temperature_symbol(),
+            speed: units.speed_symbol(),
+            precipitation: units.precipitation_symbol(),
+        }
+    }
+}
+
+/// Represents the data available in templates; only the data of the command is set.
+#[derive(Serialize, Debug)]
+pub struct TemplateContext<'a> {
+    pub address: &'a str,
+    pub units: UnitSymbols,
+    /// The current (or historical) weather of the `get` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather: Option<WeatherDataOutput>,
+    /// The forecasted days of the `forecast` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forecast: Option<Vec<ForecastDayOutput>>,
+    /// The summary of today's forecast of the `forecast` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<&'a str>,
+}
+
+/// Renders the output from a Handlebars template file.
+///
+/// # Arguments
+///
+/// * `path` - The path to the template file.
+/// * `context` - The data of the output.
+///
+/// # Returns
+///
+/// A `Result` containing the rendered output or a `TemplateError` if the template can't be read or rendered.
+pub fn render_file(path: &Path, context: &TemplateContext) -> Result<String, TemplateError> {
+    let template = std::fs::read_to_string(path).map_err(|err| {
+        TemplateError::Io(
+            err,
+            path.display().to_string().paint(Role::Warning).to_string(),
+        )
+    })?;
+
+    render(&template, context)
+}
+
+/// Renders the output from a Handlebars template.
+///
+/// Values aren't HTML-escaped, and the `round` helper formats numbers with a number of decimal places
+/// (e.g. `{{round weather.temp 1}}`; none by default).
+///
+/// # Arguments
+///
+/// * `template` - The Handlebars template.
+/// * `context` - The data of the output.
+///
+/// # Returns
+///
+/// A `Result` containing the rendered output or a `TemplateError` if the template is malformed.
+pub fn render(template: &str, context: &TemplateContext) -> Result<String, TemplateError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(no_escape);
+    handlebars.register_helper("round", Box::new(round_helper));
+
+    handlebars
+        .render_template(template, context)
+        .map_err(|err| TemplateError::Render(err.to_string().paint(Role::Warning).to_string()))
+}
+
+/// Formats a number with a number of decimal places (`{{round <NUMBER> [<DIGITS>]}}`).
+fn round_helper(
+    helper: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let value = helper
+        .param(0)
+        .ok_or(RenderErrorReason::ParamNotFoundForIndex("round", 0))?
+        .value()
+        .as_f64()
+        .ok_or(RenderErrorReason::InvalidParamType("number"))?;
+    let digits = helper
+        .param(1)
+        .and_then(|digits| digits.value().as_u64())
+        .unwrap_or(0) as usize;
+
+    out.write(&format!("{value:.digits$}"))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use weather_api_services::models::{ForecastDay, WeatherData};
+    use weather_api_services::output::IntoOutput;
+    use weather_api_services::quantities::{Celsius, HectoPascal, MetersPerSecond};
+
+    #[rstest]
+    #[case(
+        "{{address}}: {{round weather.temp}}{{units.temperature}}, {{weather.description}}",
+        "Rock & Roll: 22°C, clear sky"
+    )]
+    #[case(
+        "{{round weather.wind_speed 1}} {{units.speed}}{{#if weather.uv_index}} UV{{/if}}",
+        "3.5 m/s"
+    )]
+    #[case("{{weather.missing}}|{{forecast}}", "|")]
+    fn test_render_weather(#[case] template: &str, #[case] expected: &str) {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(21.6))
+            .humidity(40)
+            .pressure(HectoPascal(1012))
+            .wind_speed(MetersPerSecond(3.46))
+            .visibility(10000)
+            .description("clear sky")
+            .build();
+        let context = TemplateContext {
+            address: "Rock & Roll",
+            units: UnitSymbols::new(Units::Metric),
+            weather: Some(weather_data.into_output(Units::Metric)),
+            forecast: None,
+            summary: None,
+        };
+
+        assert_eq!(render(template, &context).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "{{#each forecast}}{{date}} {{round max_temp}}{{../units.temperature}}\n{{/each}}",
+        Ok("2024-07-10 77°F\n")
+    )]
+    #[case("{{#each forecast}}", Err(()))]
+    #[case("{{round summary}}", Err(()))]
+    fn test_render_forecast(#[case] template: &str, #[case] expected: Result<&str, ()>) {
+        let forecast = vec![ForecastDay {
+            date: "2024-07-10".to_owned(),
+            min_temp: Celsius(12.2),
+            max_temp: Celsius(24.8),
+            description: "sunny".to_owned(),
+            chance_of_rain: 0,
+            chance_of_snow: 0,
+            precipitation: 0.0,
+            hours: Vec::new(),
+        }];
+        let context = TemplateContext {
+            address: "London",
+            units: UnitSymbols::new(Units::Imperial),
+            weather: None,
+            forecast: Some(forecast.into_output(Units::Imperial)),
+            summary: Some("Warm afternoon"),
+        };
+
+        let result = render(template, &context);
+
+        assert_eq!(result.as_deref().map_err(|_| ()), expected);
+    }
+}
@@ -0,0 +1,141 @@
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// The capabilities of the terminal, detected once at startup.
+static CAPABILITIES: OnceLock<Capabilities> = OnceLock::new();
+
+/// Represents when the progress of requests (spinners and bars) is displayed.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ProgressMode {
+    /// The progress is never displayed.
+    Never,
+    /// The progress is displayed if both the standard output and error are terminals and the output isn't quiet.
+    #[default]
+    Auto,
+    /// The progress is displayed even if the output is piped or quiet (as long as the standard error is a terminal).
+    Always,
+}
+
+/// Represents what the output of the application may use, decided by the streams connected to terminals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capabilities {
+    /// Whether the output is colored: the standard output is a terminal that processes ANSI escape sequences.
+    pub colors: bool,
+    /// Whether the progress of requests is displayed (see `ProgressMode`).
+    pub progress: bool,
+    /// Whether the user can be asked: both the standard input and error are terminals.
+    pub interactive: bool,
+}
+
+/// `Capabilities` constructors
+impl Capabilities {
+    /// Decides the capabilities of the output from the streams connected to terminals.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdin` - Whether the standard input is a terminal.
+    /// * `stdout` - Whether the standard output is a terminal.
+    /// * `stderr` - Whether the standard error is a terminal.
+    /// * `escape_sequences` - Whether the console processes ANSI escape sequences (see `console::init`).
+    /// * `progress` - When the progress is displayed (`--progress`).
+    /// * `quiet` - Whether spinners and informational messages are suppressed (`--quiet`).
+    ///
+    /// # Returns
+    ///
+    /// The capabilities of the output.
+    pub fn new(
+        stdin: bool,
+        stdout: bool,
+        stderr: bool,
+        escape_sequences: bool,
+        progress: ProgressMode,
+        quiet: bool,
+    ) -> Self {
+        Capabilities {
+            colors: stdout && escape_sequences,
+            progress: match progress {
+                ProgressMode::Never => false,
+                ProgressMode::Auto => stdout && stderr && !quiet,
+                ProgressMode::Always => true,
+            },
+            interactive: stdin && stderr,
+        }
+    }
+
+    /// Detects the capabilities of the output of the process.
+    ///
+    /// # Arguments
+    ///
+    /// * `escape_sequences` - Whether the console processes ANSI escape sequences (see `console::init`).
+    /// * `progress` - When the progress is displayed (`--progress`).
+    /// * `quiet` - Whether spinners and informational messages are suppressed (`--quiet`).
+    ///
+    /// # Returns
+    ///
+    /// The capabilities of the output.
+    pub fn detect(escape_sequences: bool, progress: ProgressMode, quiet: bool) -> Self {
+        Capabilities::new(
+            std::io::stdin().is_terminal(),
+            std::io::stdout().is_terminal(),
+            std::io::stderr().is_terminal(),
+            escape_sequences,
+            progress,
+            quiet,
+        )
+    }
+}
+
+/// Applies the capabilities of the terminal to the output; only the first call takes effect.
+///
+/// Colors are turned off if they aren't supported (e.g. the output is piped into a file or a cron job's mail);
+/// otherwise the `NO_COLOR` environment variable is respected.
+///
+/// # Arguments
+///
+/// * `capabilities` - The capabilities of the terminal.
+pub fn init(capabilities: Capabilities) {
+    if CAPABILITIES.set(capabilities).is_ok() && !capabilities.colors {
+        narrate::colored::control::set_override(false);
+    }
+}
+
+/// Returns the capabilities of the terminal (detected with the defaults if they aren't initialized).
+pub fn capabilities() -> Capabilities {
+    *CAPABILITIES.get_or_init(|| Capabilities::detect(true, ProgressMode::Auto, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(true, true, true, true, ProgressMode::Auto, false, (true, true, true))]
+    #[case(false, false, false, true, ProgressMode::Auto, false, (false, false, false))]
+    #[case(true, false, true, true, ProgressMode::Auto, false, (false, false, true))]
+    #[case(true, true, false, true, ProgressMode::Auto, false, (true, false, false))]
+    #[case(true, true, true, false, ProgressMode::Auto, true, (false, false, true))]
+    #[case(true, true, true, true, ProgressMode::Never, false, (true, false, true))]
+    #[case(false, false, true, true, ProgressMode::Always, true, (false, true, false))]
+    fn test_capabilities(
+        #[case] stdin: bool,
+        #[case] stdout: bool,
+        #[case] stderr: bool,
+        #[case] escape_sequences: bool,
+        #[case] progress: ProgressMode,
+        #[case] quiet: bool,
+        #[case] expected: (bool, bool, bool),
+    ) {
+        let (colors, progress_displayed, interactive) = expected;
+
+        assert_eq!(
+            Capabilities::new(stdin, stdout, stderr, escape_sequences, progress, quiet),
+            Capabilities {
+                colors,
+                progress: progress_displayed,
+                interactive,
+            }
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use clap::ValueEnum;
+use narrate::colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// The theme of the terminal output, selected once at startup.
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Represents the named color themes of the terminal output.
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// The standard 16 terminal colors.
+    #[default]
+    Default,
+    /// The Solarized palette (needs a terminal with true color support).
+    Solarized,
+    /// No colors; only bold and dimmed text.
+    Monochrome,
+    /// Bright colors and bold warnings for low-vision users and bright backgrounds.
+    HighContrast,
+}
+
+/// Represents the roles of text in the terminal output; every role is styled by the theme.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    /// A weather condition description by day.
+    Condition,
+    /// A weather condition description at night.
+    NightCondition,
+    Temperature,
+    /// Humidity, rain and sea values.
+    Water,
+    /// Wind and swell values.
+    Wind,
+    Pressure,
+    Visibility,
+    Snow,
+    /// Values above the normal or the highest values.
+    Warm,
+    /// Values below the normal or the lowest values.
+    Cold,
+    /// Secondary values distinguished from their neighbours.
+    Accent,
+    /// Values standing out of plain text (e.g. dates of moon phases).
+    Highlight,
+    /// Names of successfully changed or written things (e.g. a selected provider).
+    Success,
+    /// Invalid input in error messages and missing data.
+    Warning,
+    /// Unsupported features.
+    Error,
+    /// The label of error hints.
+    Hint,
+    /// Footers and notices of less importance.
+    Muted,
+    /// Headings and totals.
+    Emphasis,
+}
+
+/// Represents the style of a role in a theme.
+struct Style {
+    color: Option<Color>,
+    bold: bool,
+    dimmed: bool,
+}
+
+/// `Theme` methods for styling text
+impl Theme {
+    /// Styles text of a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to style.
+    /// * `role` - The role of the text.
+    ///
+    /// # Returns
+    ///
+    /// The styled text.
+    pub fn paint(self, text: &str, role: Role) -> ColoredString {
+        let style = self.style(role);
+        let mut painted = match style.color {
+            Some(color) => text.color(color),
+            None => text.normal(),
+        };
+        if style.bold {
+            painted = painted.bold();
+        }
+        if style.dimmed {
+            painted = painted.dimmed();
+        }
+
+        painted
+    }
+
+    /// Styles text with a color picked by a scale or a standard scheme (e.g. the WHO UV index colors).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to style.
+    /// * `color` - The color of the text.
+    ///
+    /// # Returns
+    ///
+    /// The text in the color; without the color in the monochrome theme and bold in the high-contrast theme.
+    pub fn paint_color(self, text: &str, color: Color) -> ColoredString {
+        match self {
+            Theme::Default | Theme::Solarized => text.color(color),
+            Theme::Monochrome => text.normal(),
+            Theme::HighContrast => text.color(color).bold(),
+        }
+    }
+
+    /// Returns the style of a role.
+    fn style(self, role: Role) -> Style {
+        let color = |color| Style {
+            color: Some(color),
+            bold: false,
+            dimmed: false,
+        };
+        let bold = |color| Style {
+            color,
+            bold: true,
+            dimmed: false,
+        };
+        let dimmed = Style {
+            color: None,
+            bold: false,
+            dimmed: true,
+        };
+
+        match (self, role) {
+            (Theme::Monochrome, role) => match role {
+                Role::Warning | Role::Error | Role::Hint | Role::Emphasis => bold(None),
+                Role::Muted => dimmed,
+                _ => Style {
+                    color: None,
+                    bold: false,
+                    dimmed: false,
+                },
+            },
+            (Theme::Default, role) => match role {
+                Role::Condition | Role::Pressure | Role::Success => color(Color::Green),
+                Role::NightCondition => color(Color::BrightBlue),
+                Role::Temperature | Role::Highlight | Role::Warning => color(Color::Yellow),
+                Role::Water | Role::Cold => color(Color::Blue),
+                Role::Wind | Role::Accent => color(Color::Cyan),
+                Role::Visibility => color(Color::Magenta),
+                Role::Snow => color(Color::White),
+                Role::Warm | Role::Error => color(Color::Red),
+                Role::Hint => bold(Some(Color::Cyan)),
+                Role::Muted => dimmed,
+                Role::Emphasis => bold(None),
+            },
+            (Theme::Solarized, role) => {
+                let solarized = |r, g, b| Color::TrueColor { r, g, b };
+                match role {
+                    Role::Condition | Role::Pressure | Role::Success => {
+                        color(solarized(133, 153, 0))
+                    }
+                    Role::NightCondition => color(solarized(108, 113, 196)),
+                    Role::Temperature | Role::Highlight | Role::Warning => {
+                        color(solarized(181, 137, 0))
+                    }
+                    Role::Water | Role::Cold => color(solarized(38, 139, 210)),
+                    Role::Wind | Role::Accent => color(solarized(42, 161, 152)),
+                    Role::Visibility => color(solarized(211, 54, 130)),
+                    Role::Snow => color(solarized(147, 161, 161)),
+                    Role::Warm => color(solarized(203, 75, 22)),
+                    Role::Error => color(solarized(220, 50, 47)),
+                    Role::Hint => bold(Some(solarized(42, 161, 152))),
+                    Role::Muted => color(solarized(88, 110, 117)),
+                    Role::Emphasis => bold(None),
+                }
+            }
+            (Theme::HighContrast, role) => match role {
+                Role::Condition | Role::Pressure => color(Color::BrightGreen),
+                Role::NightCondition | Role::Water => color(Color::BrightBlue),
+                Role::Temperature | Role::Highlight => color(Color::BrightYellow),
+                Role::Wind | Role::Cold | Role::Accent => color(Color::BrightCyan),
+                Role::Visibility => color(Color::BrightMagenta),
+                Role::Snow => color(Color::BrightWhite),
+                Role::Warm => color(Color::BrightRed),
+                Role::Success => bold(Some(Color::BrightGreen)),
+                Role::Warning => bold(Some(Color::BrightYellow)),
+                Role::Error => bold(Some(Color::BrightRed)),
+                Role::Hint => bold(Some(Color::BrightCyan)),
+                // dimmed text is hard to read, so muted text is only less bright
+                Role::Muted => color(Color::White),
+                Role::Emphasis => bold(None),
+            },
+        }
+    }
+}
+
+/// Selects the theme of the terminal output; only the first selection takes effect.
+///
+/// # Arguments
+///
+/// * `theme` - The theme.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// Returns the selected theme of the terminal output (the default theme if none is selected).
+pub fn current() -> Theme {
+    THEME.get().copied().unwrap_or_default()
+}
+
+/// Styles text with the selected theme (e.g. `"London".paint(Role::Success)`).
+pub trait Paint {
+    /// Styles the text as the given role (see `Theme::paint`).
+    fn paint(&self, role: Role) -> ColoredString;
+
+    /// Styles the text with a color picked by a scale (see `Theme::paint_color`).
+    fn paint_color(&self, color: Color) -> ColoredString;
+}
+
+impl<T: AsRef<str> + ?Sized> Paint for T {
+    fn paint(&self, role: Role) -> ColoredString {
+        current().paint(self.as_ref(), role)
+    }
+
+    fn paint_color(&self, color: Color) -> ColoredString {
+        current().paint_color(self.as_ref(), color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Theme::Default, Role::Temperature, Some(Color::Yellow), false)]
+    #[case(Theme::Default, Role::Hint, Some(Color::Cyan), true)]
+    #[case(Theme::Solarized, Role::Water, Some(Color::TrueColor { r: 38, g: 139, b: 210 }), false)]
+    #[case(Theme::Monochrome, Role::Temperature, None, false)]
+    #[case(Theme::Monochrome, Role::Warning, None, true)]
+    #[case(Theme::HighContrast, Role::Warning, Some(Color::BrightYellow), true)]
+    fn test_theme_style(
+        #[case] theme: Theme,
+        #[case] role: Role,
+        #[case] expected_color: Option<Color>,
+        #[case] expected_bold: bool,
+    ) {
+        let result = theme.style(role);
+
+        assert_eq!(result.color, expected_color);
+        assert_eq!(result.bold, expected_bold);
+    }
+
+    #[rstest]
+    fn test_theme_paint() {
+        assert_eq!(
+            Theme::Monochrome.paint("12.0 °C", Role::Temperature),
+            "12.0 °C".normal()
+        );
+        assert_eq!(
+            Theme::Default.paint("12.0 °C", Role::Temperature),
+            "12.0 °C".yellow()
+        );
+        assert_eq!(
+            Theme::Monochrome.paint_color("High", Color::Red),
+            "High".normal()
+        );
+        assert_eq!(
+            Theme::HighContrast.paint_color("High", Color::Red),
+            "High".red().bold()
+        );
+    }
+}
@@ -1,62 +1,1963 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
 use convert_case::{Case, Casing};
 use narrate::anyhow::Result;
-use narrate::colored::Colorize;
-use prettytable::{row, Table};
+use narrate::colored::{ColoredString, Colorize};
+use prettytable::{row, Cell, Row, Table};
+use terminal_size::{terminal_size, Width};
 
-use weather_api_services::models::WeatherData;
+use crate::advisory::{self, UvCategory};
+use crate::astronomy::{self, MoonDay};
+use crate::bench::BenchResult;
+use crate::config::{ColorScale, ColorScales};
+use crate::favorites::Favorite;
+use crate::history::RecentQuery;
+use crate::i18n::{translate, Label};
+use crate::maps::{self, RadarMap};
+use crate::nowcast;
+use crate::ping::PingResult;
+use crate::provenance;
+use crate::providers::Provider;
+use crate::query::{self, Query};
+use crate::route::SampledPoint;
+use crate::stats::{self, Statistics};
+use crate::theme::{Paint, Role};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use weather_api_services::climatology::ClimateNormal;
+use weather_api_services::derived::HumidityComfort;
+use weather_api_services::models::{
+    DataSource, ForecastDay, ForecastHour, MarineData, SnowReport, Station, WeatherData,
+};
+use weather_api_services::output::{
+    ForecastDayOutput, ForecastHourOutput, IntoOutput, WeatherDataOutput,
+};
+use weather_api_services::quantities::Celsius;
+use weather_api_services::units::{PressureUnit, Units, VisibilityUnit};
+
+/// Represents the forecasted day at a location, a row of the multi-location view.
+///
+/// The day is a `ForecastDayOutput` in the JSON output (see `IntoOutput`).
+#[derive(Serialize, Debug)]
+pub struct LocationForecast<D = ForecastDay> {
+    /// The location (address) of the forecast.
+    pub location: String,
+    #[serde(flatten)]
+    pub day: D,
+}
+
+impl IntoOutput for LocationForecast {
+    type Output = LocationForecast<ForecastDayOutput>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        LocationForecast {
+            location: self.location,
+            day: self.day.into_output(units),
+        }
+    }
+}
+
+/// Represents the current weather at a saved location.
+///
+/// The weather is a `WeatherDataOutput` in the JSON output (see `IntoOutput`).
+#[derive(Serialize, Debug)]
+pub struct LocationWeather<W = WeatherData> {
+    /// The name of the favorite query of the location.
+    pub location: String,
+    pub address: String,
+    pub weather: W,
+}
+
+impl IntoOutput for LocationWeather {
+    type Output = LocationWeather<WeatherDataOutput>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        LocationWeather {
+            location: self.location,
+            address: self.address,
+            weather: self.weather.into_output(units),
+        }
+    }
+}
+
+/// Represents the forecasted weather at a point of a route at the estimated arrival time.
+///
+/// The forecast is a `ForecastHourOutput` in the JSON output (see `IntoOutput`).
+#[derive(Serialize, Debug)]
+pub struct RouteWeather<H = ForecastHour> {
+    #[serde(flatten)]
+    pub point: SampledPoint,
+    /// The estimated arrival time in the `YYYY-MM-DD HH:MM` format.
+    pub arrival: String,
+    /// The forecasted hour nearest to the arrival time.
+    pub forecast: H,
+}
+
+impl IntoOutput for RouteWeather {
+    type Output = RouteWeather<ForecastHourOutput>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        RouteWeather {
+            point: self.point,
+            arrival: self.arrival,
+            forecast: self.forecast.into_output(units),
+        }
+    }
+}
+
+/// Represents the current weather at a saved location, a row of the dashboard.
+///
+/// The weather is a `WeatherDataOutput` in the JSON output (see `IntoOutput`).
+#[derive(Serialize, Debug)]
+pub struct DashboardRow<W = WeatherData> {
+    /// The name of the favorite query of the location.
+    pub location: String,
+    pub address: String,
+    /// The current weather; `None` if it couldn't be fetched.
+    pub weather: Option<W>,
+    /// The number of active weather alerts; `None` if the provider doesn't report alerts.
+    pub alerts: Option<usize>,
+    /// The error of the request of the current weather.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IntoOutput for DashboardRow {
+    type Output = DashboardRow<WeatherDataOutput>;
+
+    fn into_output(self, units: Units) -> Self::Output {
+        DashboardRow {
+            location: self.location,
+            address: self.address,
+            weather: self.weather.into_output(units),
+            alerts: self.alerts,
+            error: self.error,
+        }
+    }
+}
+
+/// Represents layouts of the weather data table, selected by the width of the terminal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableLayout {
+    /// The full two-column table with the "Name" and "Value" columns.
+    Full,
+    /// A vertical list of `name: value` lines for narrow terminals.
+    List,
+    /// A single line of values for very narrow terminals and status bars.
+    Compact,
+}
+
+/// `TableLayout` methods for selecting a layout
+impl TableLayout {
+    /// Selects the widest layout that fits the terminal width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the terminal in columns; `None` if the output is not a terminal.
+    /// * `rows` - The rows of the table as pairs of labels and values.
+    ///
+    /// # Returns
+    ///
+    /// The selected `TableLayout`; the full table if the width is unknown.
+    pub fn select(width: Option<usize>, rows: &[(&str, ColoredString)]) -> Self {
+        let Some(width) = width else {
+            return TableLayout::Full;
+        };
+
+        let label_width = rows.iter().map(|(label, _)| label.chars().count()).max();
+        let value_width = rows.iter().map(|(_, value)| value.chars().count()).max();
+        let content_width = label_width.unwrap_or_default() + value_width.unwrap_or_default();
+
+        // the full table adds borders and padding ("| " + " | " + " |"), the list adds ": "
+        if width >= content_width + 7 {
+            TableLayout::Full
+        } else if width >= content_width + 2 {
+            TableLayout::List
+        } else {
+            TableLayout::Compact
+        }
+    }
+}
 
 /// Renders weather data in a tabular format for display in the terminal.
 ///
-/// This function takes weather data as input and displays it in a tabular format.
+/// This function takes weather data as input and renders it in a tabular format.
 /// It creates a table with columns "Name" and "Value" to present the weather data attributes.
+/// The labels are translated to the given language and the temperature and humidity values are colored
+/// by the configured color scales.
+/// The observation time reported by the provider is shown with its age (e.g. `5 minutes ago`) and the table
+/// is followed by a footer line with the source of the data and the attribution required by the provider.
+/// In narrow terminals the table is rendered as a vertical list or a single line (see `TableLayout`).
 ///
 /// # Arguments
 ///
 /// * `weather_data` - The `WeatherData` structure containing weather-related information to be displayed.
-pub fn table_terminal_view(weather_data: WeatherData) {
+/// * `units` - The units of the weather data.
+/// * `pressure_unit` - The units of pressure in the output.
+/// * `visibility_unit` - The units of visibility in the output.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+/// * `color_scales` - The color scales of the values.
+/// * `width` - The width of the terminal in columns (see `terminal_width`); the full table if `None`.
+///
+/// # Returns
+///
+/// The table (or the list or the line of values) followed by the source footer.
+pub fn table_terminal_view(
+    weather_data: WeatherData,
+    units: Units,
+    pressure_unit: PressureUnit,
+    visibility_unit: VisibilityUnit,
+    language: Option<&str>,
+    color_scales: &ColorScales,
+    width: Option<usize>,
+) -> String {
+    let mut output = String::new();
+
+    let rows = weather_rows(
+        &weather_data,
+        units,
+        pressure_unit,
+        visibility_unit,
+        language,
+        color_scales,
+    );
+
+    match TableLayout::select(width, &rows) {
+        TableLayout::Full => {
+            let mut table = Table::new();
+            table.add_row(row![
+                translate(Label::Name, language),
+                translate(Label::Value, language)
+            ]);
+            for (name, value) in rows {
+                table.add_row(row![name, value]);
+            }
+
+            output.push_str(&table.to_string());
+        }
+        TableLayout::List => {
+            for (name, value) in rows {
+                output.push_str(&format!("{}: {}\n", name, value));
+            }
+        }
+        TableLayout::Compact => {
+            let values: Vec<String> = rows.iter().map(|(_, value)| value.to_string()).collect();
+
+            output.push_str(&format!("{}\n", values.join(" ")));
+            return output;
+        }
+    }
+
+    if let Some(footer) = source_footer(&weather_data.source) {
+        output.push_str(&format!("{}\n", footer.paint(Role::Muted)));
+    }
+
+    output
+}
+
+/// Renders weather data as plain text without colors (e.g. to paste it into a chat).
+///
+/// # Arguments
+///
+/// * `weather_data` - The weather data to be rendered.
+/// * `units` - The units of the weather data.
+/// * `pressure_unit` - The units of pressure in the output.
+/// * `visibility_unit` - The units of visibility in the output.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The `name: value` lines of the weather data table followed by the source footer.
+pub fn plain_text_view(
+    weather_data: &WeatherData,
+    units: Units,
+    pressure_unit: PressureUnit,
+    visibility_unit: VisibilityUnit,
+    language: Option<&str>,
+) -> String {
+    weather_rows(
+        weather_data,
+        units,
+        pressure_unit,
+        visibility_unit,
+        language,
+        &ColorScales::default(),
+    )
+    .into_iter()
+    .map(|(name, value)| format!("{name}: {}", &*value))
+    .chain(source_footer(&weather_data.source))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Creates the rows of the weather data table as pairs of translated labels and colored values.
+fn weather_rows(
+    weather_data: &WeatherData,
+    units: Units,
+    pressure_unit: PressureUnit,
+    visibility_unit: VisibilityUnit,
+    language: Option<&str>,
+    color_scales: &ColorScales,
+) -> Vec<(&'static str, ColoredString)> {
+    let label = |label| translate(label, language);
+    let temp_symbol = units.temperature_symbol();
+    let temp_color = |temp: Celsius| color_scales.temperature.color_of(temp.value());
+
+    let mut rows = vec![
+        (
+            label(Label::Description),
+            description_view(&weather_data.description, weather_data.is_day),
+        ),
+        (
+            label(Label::Temperature),
+            format!("{:.2} {temp_symbol}", units.temperature(weather_data.temp))
+                .paint_color(temp_color(weather_data.temp)),
+        ),
+        (
+            label(Label::FeelsLike),
+            format!(
+                "{:.2} {temp_symbol}",
+                units.temperature(weather_data.feels_like)
+            )
+            .paint_color(temp_color(weather_data.feels_like)),
+        ),
+        (
+            label(Label::Humidity),
+            format!("{} %", weather_data.humidity).paint_color(
+                color_scales
+                    .humidity
+                    .color_of(f32::from(weather_data.humidity)),
+            ),
+        ),
+        (
+            label(Label::DewPoint),
+            format!(
+                "{:.2} {temp_symbol} ({})",
+                units.temperature(weather_data.dew_point),
+                HumidityComfort::from_dew_point(weather_data.dew_point)
+            )
+            .paint(Role::Water),
+        ),
+        (
+            label(Label::Pressure),
+            pressure_unit
+                .format(weather_data.pressure)
+                .paint(Role::Pressure),
+        ),
+        (
+            label(Label::WindSpeed),
+            format!(
+                "{:.2} {}",
+                units.speed(weather_data.wind_speed),
+                units.speed_symbol()
+            )
+            .paint(Role::Wind),
+        ),
+        (
+            label(Label::Visibility),
+            visibility_unit
+                .format(weather_data.visibility)
+                .paint(Role::Visibility),
+        ),
+    ];
+    if let Some(station_pressure) = weather_data.station_pressure {
+        rows.push((
+            label(Label::StationPressure),
+            pressure_unit.format(station_pressure).paint(Role::Pressure),
+        ));
+    }
+    if let Some(observed_at) = weather_data.observed_at {
+        rows.push((
+            label(Label::Observed),
+            observation_view(observed_at, Local::now()).normal(),
+        ));
+    }
+
+    rows
+}
+
+/// Formats the footer line with the source of weather data.
+///
+/// # Arguments
+///
+/// * `source` - The source of the weather data.
+///
+/// # Returns
+///
+/// The provider, the station and the attribution separated by `·` (e.g. `Weather API · Powered by WeatherAPI.com`)
+/// or `None` if the source is unknown.
+pub fn source_footer(source: &DataSource) -> Option<String> {
+    let station = source
+        .station
+        .as_ref()
+        .map(|station| format!("station {station}"));
+    let parts: Vec<&str> = [
+        Some(&source.provider),
+        station.as_ref(),
+        source.attribution.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(String::as_str)
+    .filter(|part| !part.is_empty())
+    .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+/// Formats the observation time in the local time zone with its age relative to the current time.
+///
+/// # Arguments
+///
+/// * `observed_at` - The time of the observation as a Unix timestamp.
+/// * `now` - The current time.
+///
+/// # Returns
+///
+/// The observation time and its age (e.g. `2024-07-10 12:30 (5 minutes ago)`); future times
+/// (e.g. forecasted hours) are shown as `in 3 hours`.
+fn observation_view<Tz: TimeZone>(observed_at: i64, now: DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let Some(observed) = now.timezone().timestamp_opt(observed_at, 0).single() else {
+        return observed_at.to_string();
+    };
+    let seconds = now.timestamp() - observed_at;
+    let (amount, unit) = match seconds.abs() {
+        ..=59 => return format!("{} (just now)", observed.format("%Y-%m-%d %H:%M")),
+        60..=3_599 => (seconds.abs() / 60, "minute"),
+        3_600..=172_799 => (seconds.abs() / 3_600, "hour"),
+        _ => (seconds.abs() / 86_400, "day"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    let age = if seconds > 0 {
+        format!("{amount} {unit}{plural} ago")
+    } else {
+        format!("in {amount} {unit}{plural}")
+    };
+
+    format!("{} ({age})", observed.format("%Y-%m-%d %H:%M"))
+}
+
+/// Formats the weather description with the condition icon and the day or night color theme.
+///
+/// # Arguments
+///
+/// * `description` - The weather description from the provider.
+/// * `is_day` - A flag indicating whether it's day (between sunrise and sunset) at the location.
+///
+/// # Returns
+///
+/// The description prefixed with the icon, colored green by day and bright blue at night.
+fn description_view(description: &str, is_day: bool) -> ColoredString {
+    let description = description.to_case(Case::Title);
+    let description = match condition_icon(&description, is_day) {
+        Some(icon) => format!("{icon} {description}"),
+        None => description,
+    };
+
+    if is_day {
+        description.paint(Role::Condition)
+    } else {
+        description.paint(Role::NightCondition)
+    }
+}
+
+/// Picks the icon of a weather condition by keywords of its (English) description.
+///
+/// # Arguments
+///
+/// * `description` - The weather description.
+/// * `is_day` - A flag indicating whether the day or the night icon variant should be used.
+///
+/// # Returns
+///
+/// The icon of the condition or `None` if the condition is not recognized.
+pub fn condition_icon(description: &str, is_day: bool) -> Option<&'static str> {
+    let description = description.to_lowercase();
+    let contains_any = |keywords: &[&str]| keywords.iter().any(|k| description.contains(k));
+
+    let icon = if contains_any(&["thunder", "storm"]) {
+        "⛈"
+    } else if contains_any(&["snow", "sleet", "blizzard", "ice"]) {
+        "❄"
+    } else if contains_any(&["rain", "drizzle", "shower"]) {
+        "🌧"
+    } else if contains_any(&["fog", "mist", "haze", "smoke"]) {
+        "🌫"
+    } else if contains_any(&["partly", "few clouds", "scattered"]) {
+        if is_day {
+            "⛅"
+        } else {
+            "☁"
+        }
+    } else if contains_any(&["cloud", "overcast"]) {
+        "☁"
+    } else if contains_any(&["clear", "sunny"]) {
+        if is_day {
+            "☀"
+        } else {
+            "☾"
+        }
+    } else {
+        return None;
+    };
+
+    Some(icon)
+}
+
+/// Returns the width of the terminal in columns.
+///
+/// The `COLUMNS` environment variable takes precedence, so the layout can be forced for status bars.
+///
+/// # Returns
+///
+/// The width of the terminal or `None` if the output is not a terminal.
+pub fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .or_else(|| terminal_size().map(|(Width(width), _)| width as usize))
+}
+
+/// Renders the UV index advisory with the WHO exposure category and the recommended protection time.
+///
+/// # Arguments
+///
+/// * `uv_index` - The UV index; `None` if the provider doesn't support it.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+/// * `color_scale` - The color scale of the UV index value.
+///
+/// # Returns
+///
+/// The lines of the advisory.
+pub fn uv_terminal_view(
+    uv_index: Option<f32>,
+    language: Option<&str>,
+    color_scale: &ColorScale,
+) -> String {
+    let mut output = String::new();
+
+    let label = translate(Label::UvIndex, language);
+
+    let Some(uv_index) = uv_index else {
+        output.push_str(&format!(
+            "{}: {}\n",
+            label,
+            "not available from the provider".paint(Role::Warning)
+        ));
+        return output;
+    };
+
+    let category = UvCategory::from_index(uv_index);
+
+    output.push_str(&format!(
+        "{}: {} ({})\n",
+        label,
+        format!("{:.1}", uv_index)
+            .paint_color(color_scale.color_of(uv_index))
+            .bold(),
+        category.colored_name()
+    ));
+    output.push_str(&format!("{}\n", category.recommendation()));
+    if let Some(minutes) = advisory::protection_time(uv_index) {
+        output.push_str(&format!(
+            "Unprotected fair skin may burn after ~{} outdoors\n",
+            format!("{} min", minutes).paint(Role::Emphasis)
+        ));
+    }
+
+    output
+}
+
+/// Renders the comparison of the temperature with the climate normal of the calendar day
+/// (e.g. `Normal (2014-2023): 12.0 °C, +4.1 °C above normal`).
+///
+/// # Arguments
+///
+/// * `temp` - The observed temperature.
+/// * `climate_normal` - The climate normal of the calendar day.
+/// * `units` - The units of the temperatures in the output.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The line of the comparison.
+pub fn normal_terminal_view(
+    temp: Celsius,
+    climate_normal: &ClimateNormal,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let mut output = String::new();
+
+    let temp_symbol = units.temperature_symbol();
+    let anomaly = units.temperature_difference(climate_normal.anomaly(temp));
+    let anomaly_view = if anomaly >= 0.0 {
+        format!("{:+.1} {temp_symbol} above normal", anomaly).paint(Role::Warm)
+    } else {
+        format!("{:+.1} {temp_symbol} below normal", anomaly).paint(Role::Cold)
+    };
+
+    output.push_str(&format!(
+        "{} ({}-{}): {}, {}\n",
+        translate(Label::Normal, language),
+        climate_normal.first_year,
+        climate_normal.last_year,
+        format!(
+            "{:.1} {temp_symbol}",
+            units.temperature(climate_normal.mean_temp)
+        )
+        .paint(Role::Temperature),
+        anomaly_view.bold()
+    ));
+
+    output
+}
+
+/// Renders the weather forecast in a tabular format for display in the terminal.
+///
+/// The table has a row per forecasted day (or hour, if `hourly` is set) with the description, the temperature
+/// and the precipitation column, which visualizes the chance of precipitation with a rain bar.
+///
+/// # Arguments
+///
+/// * `forecast` - The forecasted days to be displayed.
+/// * `hourly` - A flag to indicate if the hourly forecast should be displayed instead of the daily one.
+/// * `units` - The units of the forecast.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the forecast.
+pub fn forecast_table_terminal_view(
+    forecast: Vec<ForecastDay>,
+    hourly: bool,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let label = |label| translate(label, language);
+    let temp_symbol = units.temperature_symbol();
+    let first_column = if hourly { Label::Time } else { Label::Date };
+
+    let mut table = Table::new();
+    table.add_row(row![
+        label(first_column),
+        label(Label::Description),
+        label(Label::Temperature),
+        label(Label::Precipitation)
+    ]);
+
+    for day in forecast {
+        if hourly {
+            for hour in day.hours {
+                table.add_row(row![
+                    hour.time,
+                    hour.description.to_case(Case::Title).paint(Role::Condition),
+                    format!("{:.1} {temp_symbol}", units.temperature(hour.temp))
+                        .paint(Role::Temperature),
+                    precipitation_bar(
+                        hour.chance_of_rain,
+                        hour.chance_of_snow,
+                        hour.precipitation,
+                        units
+                    )
+                ]);
+            }
+        } else {
+            table.add_row(row![
+                day.date,
+                day.description.to_case(Case::Title).paint(Role::Condition),
+                format!(
+                    "{:.1}..{:.1} {temp_symbol}",
+                    units.temperature(day.min_temp),
+                    units.temperature(day.max_temp)
+                )
+                .paint(Role::Temperature),
+                precipitation_bar(
+                    day.chance_of_rain,
+                    day.chance_of_snow,
+                    day.precipitation,
+                    units
+                )
+            ]);
+        }
+    }
+
+    table.to_string()
+}
+
+/// Renders the one-sentence forecast summary.
+///
+/// # Arguments
+///
+/// * `summary` - The summary sentence.
+///
+/// # Returns
+///
+/// The line of the summary.
+pub fn summary_terminal_view(summary: &str) -> String {
+    format!("{}\n", summary.paint(Role::Emphasis))
+}
+
+/// Formats the chance and amount of precipitation with a rain bar (e.g. `██████░░░░ 60% 1.5 mm`).
+///
+/// The bar shows the highest chance of rain or snow; snow is colored white and rain is colored blue.
+///
+/// # Arguments
+///
+/// * `chance_of_rain` - The chance of rain in percent.
+/// * `chance_of_snow` - The chance of snow in percent.
+/// * `precipitation` - The expected amount of precipitation.
+/// * `units` - The units of the amount of precipitation.
+///
+/// # Returns
+///
+/// The colored precipitation column value.
+fn precipitation_bar(
+    chance_of_rain: u8,
+    chance_of_snow: u8,
+    precipitation: f32,
+    units: Units,
+) -> ColoredString {
+    let chance = chance_of_rain.max(chance_of_snow).min(100);
+    let filled = (chance as usize + 5) / 10;
+    let bar = format!(
+        "{}{} {:>3}% {:.1} {}",
+        "█".repeat(filled),
+        "░".repeat(10 - filled),
+        chance,
+        precipitation,
+        units.precipitation_symbol()
+    );
+
+    if chance_of_snow > chance_of_rain {
+        bar.paint(Role::Snow)
+    } else {
+        bar.paint(Role::Water)
+    }
+}
+
+/// Renders marine weather data in a tabular format for display in the terminal.
+///
+/// The first table lists tide times and heights, the second one shows waves, swell and water temperature
+/// in 3 hour steps.
+///
+/// # Arguments
+///
+/// * `marine_data` - The marine data to be displayed.
+/// * `units` - The units of the marine data.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The tables of the tides and the conditions.
+pub fn marine_table_terminal_view(
+    marine_data: MarineData,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let mut output = String::new();
+
+    let label = |label| translate(label, language);
+    let height_symbol = units.height_symbol();
+
+    let mut tides_table = Table::new();
+    tides_table.add_row(row![
+        label(Label::Time),
+        label(Label::Tide),
+        label(Label::Height)
+    ]);
+
+    let mut conditions_table = Table::new();
+    conditions_table.add_row(row![
+        label(Label::Time),
+        label(Label::WaveHeight),
+        label(Label::Swell),
+        label(Label::WaterTemperature)
+    ]);
+
+    for day in marine_data.days {
+        for tide in day.tides {
+            let tide_type = tide.tide_type.to_case(Case::Title);
+            let tide_type = if tide.tide_type.eq_ignore_ascii_case("high") {
+                tide_type.paint(Role::Water)
+            } else {
+                tide_type.paint(Role::Accent)
+            };
+
+            tides_table.add_row(row![
+                tide.time,
+                tide_type,
+                format!("{:.2} {height_symbol}", tide.height).paint(Role::Pressure)
+            ]);
+        }
+
+        for hour in day.hours.into_iter().step_by(3) {
+            conditions_table.add_row(row![
+                hour.time,
+                format!("{:.1} {height_symbol}", hour.wave_height).paint(Role::Water),
+                format!(
+                    "{:.1} {height_symbol} / {:.0} s {}",
+                    hour.swell_height, hour.swell_period, hour.swell_direction
+                )
+                .paint(Role::Wind),
+                format!(
+                    "{:.1} {}",
+                    units.temperature(hour.water_temp),
+                    units.temperature_symbol()
+                )
+                .paint(Role::Temperature)
+            ]);
+        }
+    }
+
+    output.push_str(&tides_table.to_string());
+    output.push_str(&conditions_table.to_string());
+
+    output
+}
+
+/// Renders the snow report in a tabular format for display in the terminal.
+///
+/// # Arguments
+///
+/// * `snow_report` - The snow report to be displayed.
+/// * `units` - The units of the snow report.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the snow report.
+pub fn snow_table_terminal_view(
+    snow_report: SnowReport,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let label = |label| translate(label, language);
+    let snow_symbol = units.snow_symbol();
+
+    let mut table = Table::new();
+    table.add_row(row![
+        label(Label::Date),
+        label(Label::Snowfall),
+        label(Label::Temperature)
+    ]);
+
+    for day in snow_report.days {
+        table.add_row(row![
+            day.date,
+            format!("{:.1} {snow_symbol}", day.snowfall).paint(Role::Snow),
+            format!(
+                "{:.1}..{:.1} {}",
+                units.temperature(day.min_temp),
+                units.temperature(day.max_temp),
+                units.temperature_symbol()
+            )
+            .paint(Role::Temperature)
+        ]);
+    }
+
+    table.add_row(row![
+        label(Label::Total).paint(Role::Emphasis),
+        format!("{:.1} {snow_symbol}", snow_report.total_snowfall)
+            .paint(Role::Snow)
+            .bold(),
+        ""
+    ]);
+
+    table.to_string()
+}
+
+/// Renders observation stations in a tabular format for display in the terminal.
+///
+/// # Arguments
+///
+/// * `stations` - The stations to be displayed, nearest first.
+/// * `units` - The units of the elevations; distances are always in kilometers.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the stations.
+pub fn stations_table_terminal_view(
+    stations: Vec<Station>,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let label = |label| translate(label, language);
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "ID",
+        label(Label::Name),
+        label(Label::Distance),
+        label(Label::Height)
+    ]);
+
+    for station in stations {
+        table.add_row(row![
+            station.id.paint(Role::Emphasis),
+            station.name.unwrap_or_else(|| "-".to_owned()),
+            format!("{:.1} km", station.distance),
+            station.elevation.map_or_else(
+                || "-".to_owned(),
+                |elevation| format!("{elevation:.0} {}", units.height_symbol())
+            )
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the forecasted days of several locations (e.g. stops of a trip) in a tabular format for display
+/// in the terminal.
+///
+/// # Arguments
+///
+/// * `locations` - The forecasted days of the locations in the display order.
+/// * `units` - The units of the forecasts.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the locations.
+pub fn locations_table_terminal_view(
+    locations: Vec<LocationForecast>,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let label = |label| translate(label, language);
+
     let mut table = Table::new();
-    table.add_row(row!["Name", "Value"]);
     table.add_row(row![
-        "Description",
-        weather_data.description.to_case(Case::Title).green()
+        label(Label::Location),
+        label(Label::Date),
+        label(Label::Description),
+        label(Label::Temperature),
+        label(Label::Precipitation)
     ]);
+
+    for LocationForecast { location, day } in locations {
+        table.add_row(row![
+            location.paint(Role::Emphasis),
+            day.date,
+            day.description.to_case(Case::Title).paint(Role::Condition),
+            format!(
+                "{:.1}..{:.1} {}",
+                units.temperature(day.min_temp),
+                units.temperature(day.max_temp),
+                units.temperature_symbol()
+            )
+            .paint(Role::Temperature),
+            precipitation_bar(
+                day.chance_of_rain,
+                day.chance_of_snow,
+                day.precipitation,
+                units
+            )
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the current weather of the saved locations as a single overview table (location, condition with its icon,
+/// temperature, wind speed and the number of alerts) for display in the terminal.
+///
+/// # Arguments
+///
+/// * `rows` - The current weather of the locations in the display order.
+/// * `units` - The units of the weather data.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the locations.
+pub fn dashboard_table_terminal_view(
+    rows: &[DashboardRow],
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let label = |label| translate(label, language);
+
+    let mut table = Table::new();
     table.add_row(row![
-        "Temperature",
-        format!("{:.2} °C", weather_data.temp).yellow()
+        label(Label::Location),
+        label(Label::Description),
+        label(Label::Temperature),
+        label(Label::WindSpeed),
+        label(Label::Alerts)
     ]);
+
+    for row in rows {
+        let Some(weather_data) = &row.weather else {
+            table.add_row(row![
+                row.location.paint(Role::Emphasis),
+                row.error.as_deref().unwrap_or_default().paint(Role::Error),
+                "-",
+                "-",
+                "-"
+            ]);
+            continue;
+        };
+
+        let icon = condition_icon(&weather_data.description, weather_data.is_day).unwrap_or(" ");
+        let alerts = match row.alerts {
+            None => "-".paint(Role::Muted),
+            Some(0) => "0".paint(Role::Muted),
+            Some(alerts) => alerts.to_string().paint(Role::Warning),
+        };
+
+        table.add_row(row![
+            row.location.paint(Role::Emphasis),
+            format!("{icon} {}", weather_data.description.to_case(Case::Title))
+                .paint(Role::Condition),
+            format!(
+                "{:.1} {}",
+                units.temperature(weather_data.temp),
+                units.temperature_symbol()
+            )
+            .paint(Role::Temperature),
+            format!(
+                "{:.1} {}",
+                units.speed(weather_data.wind_speed),
+                units.speed_symbol()
+            )
+            .paint(Role::Wind),
+            alerts
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the weather along a route in a tabular format for display in the terminal.
+///
+/// # Arguments
+///
+/// * `route_weather` - The forecasted weather at the sampled points of the route.
+/// * `units` - The units of the forecasts; distances are always in kilometers.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the route.
+pub fn route_table_terminal_view(
+    route_weather: Vec<RouteWeather>,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let label = |label| translate(label, language);
+
+    let mut table = Table::new();
     table.add_row(row![
-        "Humidity",
-        format!("{} %", weather_data.humidity).blue()
+        label(Label::Distance),
+        label(Label::Time),
+        label(Label::Location),
+        label(Label::Description),
+        label(Label::Temperature),
+        label(Label::WindSpeed),
+        label(Label::Precipitation)
     ]);
+
+    for RouteWeather {
+        point,
+        arrival,
+        forecast,
+    } in route_weather
+    {
+        table.add_row(row![
+            format!("{:.1} km", point.distance).paint(Role::Emphasis),
+            arrival,
+            point.point.to_query(),
+            forecast
+                .description
+                .to_case(Case::Title)
+                .paint(Role::Condition),
+            format!(
+                "{:.1} {}",
+                units.temperature(forecast.temp),
+                units.temperature_symbol()
+            )
+            .paint(Role::Temperature),
+            format!(
+                "{:.1} {}",
+                units.speed(forecast.wind_speed),
+                units.speed_symbol()
+            )
+            .paint(Role::Wind),
+            precipitation_bar(
+                forecast.chance_of_rain,
+                forecast.chance_of_snow,
+                forecast.precipitation,
+                units
+            )
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders statistics of the stored observations of an address in a tabular format for display in the terminal.
+///
+/// The daily mean temperatures are shown as a sparkline below the table.
+///
+/// # Arguments
+///
+/// * `address` - The address of the observations.
+/// * `statistics` - The statistics to be displayed.
+/// * `units` - The units of the statistics.
+/// * `language` - An optional language of the labels. Pass `None` for English.
+///
+/// # Returns
+///
+/// The table of the statistics followed by the sparkline.
+pub fn stats_table_terminal_view(
+    address: &str,
+    statistics: Statistics,
+    units: Units,
+    language: Option<&str>,
+) -> String {
+    let mut output = String::new();
+
+    let label = |label| translate(label, language);
+    let temp_symbol = units.temperature_symbol();
+
+    let mut table = Table::new();
+    table.add_row(row![label(Label::Location), address.paint(Role::Emphasis)]);
     table.add_row(row![
-        "Pressure",
-        format!("{} hPa", weather_data.pressure).green()
+        label(Label::Observations),
+        format!("{} ({} d)", statistics.observations, statistics.days)
     ]);
     table.add_row(row![
-        "Wind speed",
-        format!("{:.2} m/sec", weather_data.wind_speed).cyan()
+        label(Label::Minimum),
+        format!(
+            "{:.1} {temp_symbol}",
+            units.temperature(statistics.min_temp)
+        )
+        .paint(Role::Cold)
     ]);
     table.add_row(row![
-        "Visibility",
-        format!("{} m", weather_data.visibility).magenta()
+        label(Label::Maximum),
+        format!(
+            "{:.1} {temp_symbol}",
+            units.temperature(statistics.max_temp)
+        )
+        .paint(Role::Warm)
     ]);
+    table.add_row(row![
+        label(Label::Average),
+        format!(
+            "{:.1} {temp_symbol}",
+            units.temperature(statistics.avg_temp)
+        )
+        .paint(Role::Temperature)
+    ]);
+    table.add_row(row![
+        label(Label::RainyDays),
+        format!("{} / {}", statistics.rainy_days, statistics.days).paint(Role::Water)
+    ]);
+    table.add_row(row![
+        label(Label::Trend),
+        format!("{:+.2} {temp_symbol}/d", statistics.trend).paint(Role::Pressure)
+    ]);
+
+    output.push_str(&table.to_string());
+
+    let daily_mean_temps: Vec<f32> = statistics
+        .daily_mean_temps
+        .iter()
+        .map(|(_, temp)| temp.value())
+        .collect();
+    if daily_mean_temps.len() > 1 {
+        output.push_str(&format!(
+            "{}\n",
+            stats::sparkline(&daily_mean_temps).paint(Role::Temperature)
+        ));
+    }
+
+    output
+}
+
+/// Renders the results of the health check of providers as a table for display in the terminal.
+///
+/// # Arguments
+///
+/// * `results` - The results of the providers.
+///
+/// # Returns
+///
+/// The table of the results.
+pub fn ping_table_terminal_view(results: &[PingResult]) -> String {
+    let mut table = Table::new();
+    table.add_row(row!["Provider", "Status", "Latency"]);
+
+    for result in results {
+        let status = result
+            .status
+            .map_or_else(|| "-".to_owned(), |status| status.to_string());
+        let status = match &result.error {
+            None => status.paint(Role::Success),
+            Some(err) => format!("{status} ({err})").paint(Role::Error),
+        };
+
+        table.add_row(row![
+            result.provider.paint(Role::Emphasis),
+            status,
+            format!("{} ms", result.latency_ms).paint(Role::Accent)
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the results of the benchmark of providers as a table for display in the terminal.
+///
+/// # Arguments
+///
+/// * `results` - The results of the providers, the fastest provider first.
+///
+/// # Returns
+///
+/// The table of the results.
+pub fn bench_table_terminal_view(results: &[BenchResult]) -> String {
+    let mut table = Table::new();
+    table.add_row(row![
+        "Provider", "p50", "p95", "Payload", "Parse", "Failures"
+    ]);
+
+    for result in results {
+        let milliseconds = |value: Option<u64>| {
+            value.map_or_else(|| "-".to_owned(), |value| format!("{value} ms"))
+        };
+        let failures = format!("{}/{}", result.failures, result.runs);
+        let failures = match &result.error {
+            None => failures.paint(Role::Success),
+            Some(err) => format!("{failures} ({err})").paint(Role::Error),
+        };
+
+        table.add_row(row![
+            result.provider.paint(Role::Emphasis),
+            milliseconds(result.p50_ms).paint(Role::Accent),
+            milliseconds(result.p95_ms).paint(Role::Accent),
+            result.payload_bytes.map_or_else(
+                || "-".to_owned(),
+                |bytes| format!("{:.1} KB", bytes as f64 / 1024.0)
+            ),
+            result
+                .parse_ms
+                .map_or_else(|| "-".to_owned(), |parse_ms| format!("{parse_ms:.2} ms")),
+            failures
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the recent queries as a table of their local times, providers and addresses for display in the terminal.
+///
+/// # Arguments
+///
+/// * `queries` - The recent queries, most recent first.
+///
+/// # Returns
+///
+/// The table of the queries or a notice if there are no recent queries.
+pub fn recent_queries_terminal_view(queries: &[RecentQuery]) -> String {
+    if queries.is_empty() {
+        return format!("{}\n", "There are no recent queries".paint(Role::Muted));
+    }
+
+    let mut table = Table::new();
+    table.add_row(row!["Time", "Provider", "Address"]);
+
+    for query in queries {
+        let queried_at = Local
+            .timestamp_opt(query.queried_at, 0)
+            .single()
+            .map_or_else(
+                || "-".to_owned(),
+                |time| time.format("%Y-%m-%d %H:%M").to_string(),
+            );
+
+        table.add_row(row![
+            queried_at.paint(Role::Muted),
+            query.provider.to_string().paint(Role::Emphasis),
+            query.address
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the favorite queries as a table of their names, addresses, presets and intervals of scheduled runs for
+/// display in the terminal.
+///
+/// # Arguments
+///
+/// * `favorites` - The favorite queries keyed by their names.
+///
+/// # Returns
+///
+/// The table of the favorites or a notice if there are no favorites.
+pub fn favorites_terminal_view(favorites: &BTreeMap<&String, &Favorite>) -> String {
+    if favorites.is_empty() {
+        return format!("{}\n", "There are no favorites".paint(Role::Muted));
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Name", "Address", "Provider", "Units", "Output", "Every", "Tags"
+    ]);
+
+    for (name, favorite) in favorites {
+        let provider = favorite
+            .provider
+            .as_ref()
+            .map_or_else(|| "selected".to_owned(), Provider::to_string);
+        let units = favorite.units.map_or("default", |units| match units {
+            Units::Metric => "metric",
+            Units::Imperial => "imperial",
+        });
+        let output = if favorite.json { "json" } else { "table" };
+
+        table.add_row(row![
+            name.paint(Role::Emphasis),
+            favorite.address,
+            provider,
+            units,
+            output,
+            favorite.every.as_deref().unwrap_or("-").paint(Role::Accent),
+            favorite.tags.join(", ")
+        ]);
+    }
+
+    table.to_string()
+}
+
+/// Renders the status of every provider (not supported, configured or not configured) and of the discovered
+/// provider plugins, marking the selected one, followed by the supported providers with their example URLs.
+///
+/// # Arguments
+///
+/// * `selected_provider` - The selected weather data provider.
+/// * `configured_providers` - The configured weather data providers.
+/// * `plugins` - The names of the discovered provider plugins.
+///
+/// # Returns
+///
+/// The lines of the statuses and the supported providers.
+pub fn provider_list_terminal_view(
+    selected_provider: &Provider,
+    configured_providers: &[&Provider],
+    plugins: &[String],
+) -> String {
+    let mut output = String::new();
+    let status_line = |provider_str: ColoredString, selected: bool| {
+        if selected {
+            format!("*{} (selected)\n", provider_str)
+        } else {
+            format!(" {}\n", provider_str)
+        }
+    };
+
+    output.push_str("Current status of providers: \n");
 
-    table.printstd();
+    for entry in Provider::registry() {
+        let provider = &entry.provider;
+        let provider_str = if !provider.is_implemented() {
+            format!("{} (not supported)", provider).paint(Role::Error)
+        } else if configured_providers.contains(&provider) {
+            format!("{} (configured)", provider).paint(Role::Success)
+        } else {
+            format!("{} (not configured)", provider).paint(Role::Warning)
+        };
+
+        output.push_str(&status_line(provider_str, provider == selected_provider));
+    }
+
+    for name in plugins {
+        let provider = Provider::Plugin(name.clone());
+        let provider_str = if configured_providers.contains(&&provider) {
+            format!("{} (plugin; configured)", provider).paint(Role::Success)
+        } else {
+            format!("{} (plugin)", provider).paint(Role::Accent)
+        };
+
+        output.push_str(&status_line(provider_str, &provider == selected_provider));
+    }
+
+    output.push_str("\nCurrently supported providers is\n");
+    for entry in Provider::registry().filter(|entry| entry.provider.is_implemented()) {
+        output.push_str(&format!(
+            "\t{} ({}; example url: '{}')\n",
+            entry.title,
+            entry.api_version.paint(Role::Accent),
+            entry.default_url.paint(Role::Success)
+        ));
+    }
+
+    output
 }
 
-/// Renders weather data in JSON format for display in the terminal.
+/// Renders the moon phase calendar of a month as a grid of weeks for display in the terminal.
+///
+/// The grid is followed by the dates of the principal phases (new moon, first quarter, full moon and last quarter).
 ///
-/// This function takes weather data as input, serializes it into JSON format, and prints it to the terminal.
+/// # Arguments
+///
+/// * `first_day` - The first day of the month.
+/// * `calendar` - The moon phases of every day of the month.
+///
+/// # Returns
+///
+/// The title of the month, the grid and the principal phases.
+pub fn moon_calendar_terminal_view(first_day: NaiveDate, calendar: Vec<MoonDay>) -> String {
+    let mut output = String::new();
+
+    let mut table = Table::new();
+    table.set_titles(Row::new(
+        ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .into_iter()
+            .map(|weekday| Cell::new(weekday).style_spec("bc"))
+            .collect(),
+    ));
+
+    let offset = first_day.weekday().num_days_from_monday() as usize;
+    let mut cells: Vec<Cell> = (0..offset).map(|_| Cell::new("")).collect();
+    cells.extend(calendar.iter().enumerate().map(|(index, moon_day)| {
+        Cell::new(&format!("{:>2} {}", index + 1, moon_day.phase.icon()))
+    }));
+    cells.resize(cells.len().div_ceil(7) * 7, Cell::new(""));
+
+    while !cells.is_empty() {
+        let week: Vec<Cell> = cells.drain(..7).collect();
+        table.add_row(Row::new(week));
+    }
+
+    output.push_str(&format!(
+        "{}\n",
+        first_day.format("%B %Y").to_string().paint(Role::Emphasis)
+    ));
+    output.push_str(&table.to_string());
+
+    for moon_day in astronomy::principal_phase_days(&calendar) {
+        output.push_str(&format!(
+            "{} {}: {}\n",
+            moon_day.phase.icon(),
+            moon_day.phase.name(),
+            moon_day.date.paint(Role::Highlight)
+        ));
+    }
+
+    output
+}
+
+/// Renders the nowcast of the next hour: a timeline with a bar per minute, its time axis and the verdict.
 ///
 /// # Arguments
 ///
-/// * `weather_data` - The `WeatherData` structure containing weather-related information to be displayed in JSON format.
+/// * `timeline` - The bars of the minutes (see `nowcast::timeline`).
+/// * `verdict` - The verdict of the next hour (see `nowcast::verdict`).
 ///
 /// # Returns
 ///
-/// A `Result` indicating success or an error when serializing the weather data into JSON format.
-pub fn json_terminal_view(weather_data: WeatherData) -> Result<()> {
-    println!("{}", serde_json::to_string(&weather_data)?);
+/// The lines of the timeline, its time axis and the verdict.
+pub fn nowcast_terminal_view(timeline: &str, verdict: &str) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "{}{}{}\n",
+        "│".paint(Role::Muted),
+        format!("{timeline:<width$}", width = nowcast::TIMELINE_MINUTES).paint(Role::Water),
+        "│".paint(Role::Muted)
+    ));
+    output.push_str(&format!(
+        "{}\n",
+        format!(
+            " {:<15}{:<15}{:<15}{:<12}+60m",
+            "now", "+15m", "+30m", "+45m"
+        )
+        .paint(Role::Muted)
+    ));
+
+    let role = if verdict.starts_with("No rain") {
+        Role::Success
+    } else {
+        Role::Highlight
+    };
+    output.push_str(&format!("{}\n", verdict.paint(role)));
+
+    output
+}
+
+/// Renders a coarse precipitation map around a location in the terminal with block characters.
+///
+/// The map is followed by the time of the radar frame, a legend of the shades and the attribution of the radar data.
+///
+/// # Arguments
+///
+/// * `radar_map` - The radar map around the location.
+/// * `width` - The width of the terminal in columns (see `terminal_width`); the map is up to 64 columns wide
+///   and 64 columns if `None`.
+///
+/// # Returns
+///
+/// The lines of the map, the time, the legend and the attribution.
+pub fn radar_terminal_view(radar_map: &RadarMap, width: Option<usize>) -> String {
+    let mut output = String::new();
+
+    let columns = width
+        .map(|width| width.saturating_sub(2) as u32)
+        .unwrap_or(64)
+        .clamp(8, 64);
+    // terminal cells are about twice as high as wide, so the square area needs half as many rows
+    let rows = columns / 2;
+    let (marker_column, marker_row) = ((columns / 2) as usize, (rows / 2) as usize);
+
+    let border = "─".repeat(columns as usize);
+    output.push_str(&format!("{}\n", format!("┌{border}┐").paint(Role::Muted)));
+    for (row, cells) in radar_map.coverage(columns, rows).iter().enumerate() {
+        let line: String = cells
+            .iter()
+            .enumerate()
+            .map(|(column, &coverage)| {
+                if (column, row) == (marker_column, marker_row) {
+                    "+".paint(Role::Error).to_string()
+                } else {
+                    maps::shade(coverage)
+                        .to_string()
+                        .paint(Role::Water)
+                        .to_string()
+                }
+            })
+            .collect();
+        output.push_str(&format!(
+            "{}{line}{}\n",
+            "│".paint(Role::Muted),
+            "│".paint(Role::Muted)
+        ));
+    }
+    output.push_str(&format!("{}\n", format!("└{border}┘").paint(Role::Muted)));
+
+    let time = DateTime::from_timestamp(radar_map.time, 0)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    output.push_str(&format!("Radar: {}\n", time.paint(Role::Highlight)));
+    output.push_str(&format!(
+        "{} location  {} ≤25%  {} ≤50%  {} ≤75%  {} >75% of the area with precipitation\n",
+        "+".paint(Role::Error),
+        maps::SHADES[1].to_string().paint(Role::Water),
+        maps::SHADES[2].to_string().paint(Role::Water),
+        maps::SHADES[3].to_string().paint(Role::Water),
+        maps::SHADES[4].to_string().paint(Role::Water)
+    ));
+    output.push_str(&format!(
+        "{}\n",
+        maps::RAINVIEWER_ATTRIBUTION.paint(Role::Muted)
+    ));
+
+    output
+}
+
+/// Renders data in JSON format for display in the terminal.
+///
+/// This function takes data as input and serializes it into a line of JSON.
+///
+/// # Arguments
+///
+/// * `data` - The data (e.g. the results of the 'ping' command) to be displayed in JSON format.
+///
+/// # Returns
+///
+/// A `Result` containing the output or an error when serializing the data into JSON format.
+pub fn json_terminal_view(data: impl Serialize) -> Result<String> {
+    json_output(data, None)
+}
+
+/// Renders weather data (e.g. weather data, forecast, marine data or snow report) in JSON format for display
+/// in the terminal, wrapped in an object with its provenance if requested with `--json-envelope`
+/// (see `provenance::meta`).
+///
+/// # Arguments
+///
+/// * `data` - The data (e.g. the `WeatherDataOutput` structure) to be displayed in JSON format.
+/// * `units` - The units of the data, reported in the envelope.
+/// * `envelope` - A flag to indicate if the data should be wrapped in the envelope.
+/// * `query` - The query selecting the rendered value (see `query::format`); the whole output if not set.
+///
+/// # Returns
+///
+/// A `Result` containing the output or an error when serializing the data into JSON format or selecting the value.
+pub fn json_data_view(
+    data: impl Serialize,
+    units: Units,
+    envelope: bool,
+    query: Option<&Query>,
+) -> Result<String> {
+    if envelope {
+        json_output(
+            provenance::Envelope {
+                meta: provenance::meta(&provenance::requests(), units),
+                data,
+            },
+            query,
+        )
+    } else {
+        json_output(data, query)
+    }
+}
+
+/// Serializes data into a line of JSON or the value selected by a query.
+fn json_output(data: impl Serialize, query: Option<&Query>) -> Result<String> {
+    let output = match query {
+        Some(query) => query::format(query.select(&serde_json::to_value(&data)?)?),
+        None => serde_json::to_string(&data)?,
+    };
+
+    Ok(format!("{output}\n"))
+}
+
+/// Renders the original JSON response of a provider pretty-printed for display in the terminal.
+///
+/// # Arguments
+///
+/// * `response` - The JSON response of the provider.
+/// * `query` - The query selecting the rendered value (see `query::format`); the whole response if not set.
+///
+/// # Returns
+///
+/// A `Result` containing the output or an error when selecting the value.
+pub fn raw_json_terminal_view(
+    response: &serde_json::Value,
+    query: Option<&Query>,
+) -> Result<String> {
+    let output = match query {
+        Some(query) => query::format(query.select(response)?),
+        None => serde_json::to_string_pretty(response)?,
+    };
+
+    Ok(format!("{output}\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{self, Theme};
+    use rstest::{fixture, rstest};
+    use weather_api_services::quantities::{HectoPascal, MetersPerSecond};
+
+    #[rstest]
+    #[case(None, TableLayout::Full)]
+    #[case(Some(80), TableLayout::Full)]
+    #[case(Some(28), TableLayout::Full)]
+    #[case(Some(27), TableLayout::List)]
+    #[case(Some(23), TableLayout::List)]
+    #[case(Some(22), TableLayout::Compact)]
+    fn test_table_layout_select(#[case] width: Option<usize>, #[case] expected: TableLayout) {
+        let rows = [
+            ("Temperature", "12.00 °C".yellow()),
+            ("Wind speed", "5.00 m/sec".cyan()),
+        ];
+
+        assert_eq!(TableLayout::select(width, &rows), expected);
+    }
+
+    #[rstest]
+    #[case("Clear Sky", true, Some("☀"))]
+    #[case("Clear", false, Some("☾"))]
+    #[case("Partly Cloudy", true, Some("⛅"))]
+    #[case("Partly Cloudy", false, Some("☁"))]
+    #[case("Patchy Light Rain With Thunder", false, Some("⛈"))]
+    #[case("Light Snow", true, Some("❄"))]
+    #[case("Bedeckt", true, None)]
+    fn test_condition_icon(
+        #[case] description: &str,
+        #[case] is_day: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(condition_icon(description, is_day), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0, 0.0, "░░░░░░░░░░   0% 0.0 mm")]
+    #[case(60, 0, 1.5, "██████░░░░  60% 1.5 mm")]
+    #[case(20, 84, 2.25, "████████░░  84% 2.2 mm")]
+    #[case(100, 0, 12.0, "██████████ 100% 12.0 mm")]
+    fn test_precipitation_bar(
+        #[case] chance_of_rain: u8,
+        #[case] chance_of_snow: u8,
+        #[case] precipitation: f32,
+        #[case] expected: &str,
+    ) {
+        let result =
+            precipitation_bar(chance_of_rain, chance_of_snow, precipitation, Units::Metric);
+
+        assert_eq!(&*result, expected);
+    }
+
+    #[rstest]
+    fn test_precipitation_bar_imperial() {
+        let result = precipitation_bar(60, 0, 0.5, Units::Imperial);
+
+        assert_eq!(&*result, "██████░░░░  60% 0.5 in");
+    }
+
+    #[rstest]
+    #[case(1_720_607_400, "2024-07-10 10:30 (just now)")]
+    #[case(1_720_607_100, "2024-07-10 10:25 (5 minutes ago)")]
+    #[case(1_720_603_800, "2024-07-10 09:30 (1 hour ago)")]
+    #[case(1_720_348_200, "2024-07-07 10:30 (3 days ago)")]
+    #[case(1_720_618_200, "2024-07-10 13:30 (in 3 hours)")]
+    fn test_observation_view(#[case] observed_at: i64, #[case] expected: &str) {
+        let now = chrono::Utc.timestamp_opt(1_720_607_400, 0).unwrap();
+
+        assert_eq!(observation_view(observed_at, now), expected);
+    }
+
+    #[rstest]
+    #[case(DataSource::default(), None)]
+    #[case(
+        DataSource {
+            provider: "Weather API".to_owned(),
+            station: None,
+            attribution: Some("Powered by WeatherAPI.com".to_owned()),
+        },
+        Some("Weather API · Powered by WeatherAPI.com")
+    )]
+    #[case(
+        DataSource {
+            provider: "my-plugin".to_owned(),
+            station: Some("EGLL".to_owned()),
+            attribution: None,
+        },
+        Some("my-plugin · station EGLL")
+    )]
+    fn test_source_footer(#[case] source: DataSource, #[case] expected: Option<&str>) {
+        assert_eq!(source_footer(&source).as_deref(), expected);
+    }
+
+    #[rstest]
+    fn test_plain_text_view() {
+        let weather_data = WeatherData::builder()
+            .temp(Celsius(20.0))
+            .feels_like(Celsius(19.5))
+            .humidity(50)
+            .dew_point(Celsius(9.3))
+            .pressure(HectoPascal(1013))
+            .station_pressure(Some(HectoPascal(954)))
+            .wind_speed(MetersPerSecond(3.0))
+            .visibility(10000)
+            .description("sunny")
+            .source(DataSource {
+                provider: "Weather API".to_owned(),
+                ..Default::default()
+            })
+            .build();
+
+        let result = plain_text_view(
+            &weather_data,
+            Units::Metric,
+            PressureUnit::InHg,
+            VisibilityUnit::Kilometers,
+            None,
+        );
+
+        assert_eq!(
+            result,
+            "Description: ☀ Sunny\n\
+             Temperature: 20.00 °C\n\
+             Feels like: 19.50 °C\n\
+             Humidity: 50 %\n\
+             Dew point: 9.30 °C (Dry)\n\
+             Pressure: 29.91 inHg\n\
+             Wind speed: 3.00 m/s\n\
+             Visibility: 10.0 km\n\
+             Station pressure: 28.17 inHg\n\
+             Weather API"
+        );
+    }
+
+    /// The width of the terminal in columns the views are rendered at in snapshot tests.
+    const SNAPSHOT_WIDTH: usize = 80;
+
+    /// Renders the views in the monochrome theme without colors, as the output piped with `--theme monochrome`,
+    /// so the results don't depend on the terminal running the tests.
+    #[fixture]
+    fn monochrome() {
+        narrate::colored::control::set_override(false);
+        theme::set_theme(Theme::Monochrome);
+    }
+
+    #[rstest]
+    fn test_provider_list_terminal_view(_monochrome: ()) {
+        let plugin = Provider::Plugin("metar".to_owned());
+
+        let result = provider_list_terminal_view(
+            &plugin,
+            &[&Provider::WeatherApi, &plugin],
+            &["metar".to_owned(), "pirate".to_owned()],
+        );
+
+        assert!(result.starts_with("Current status of providers: \n"));
+        assert!(result.contains("\n weather-api (configured)\n"));
+        assert!(result.contains("\n open-weather (not configured)\n"));
+        assert!(result.contains("\n*metar (plugin; configured) (selected)\n"));
+        assert!(result.contains("\n pirate (plugin)\n"));
+    }
+
+    #[fixture]
+    fn weather_data() -> WeatherData {
+        WeatherData::builder()
+            .temp(Celsius(7.6))
+            .feels_like(Celsius(5.2))
+            .humidity(81)
+            .dew_point(Celsius(4.5))
+            .pressure(HectoPascal(1004))
+            .wind_speed(MetersPerSecond(5.5))
+            .visibility(10000)
+            .description("light rain")
+            .is_day(true)
+            .source(DataSource {
+                provider: "Weather API".to_owned(),
+                station: None,
+                attribution: Some("Powered by WeatherAPI.com".to_owned()),
+            })
+            .build()
+    }
+
+    #[fixture]
+    fn forecast() -> Vec<ForecastDay> {
+        let hour = |time: &str, temp, chance_of_rain, precipitation| ForecastHour {
+            time: time.to_owned(),
+            timestamp: None,
+            temp: Celsius(temp),
+            description: "patchy rain nearby".to_owned(),
+            wind_speed: MetersPerSecond(4.2),
+            chance_of_rain,
+            chance_of_snow: 0,
+            precipitation,
+        };
+
+        vec![
+            ForecastDay {
+                date: "2024-03-08".to_owned(),
+                min_temp: Celsius(3.4),
+                max_temp: Celsius(9.1),
+                description: "patchy rain nearby".to_owned(),
+                chance_of_rain: 80,
+                chance_of_snow: 10,
+                precipitation: 2.4,
+                hours: vec![
+                    hour("2024-03-08 09:00", 4.1, 20, 0.0),
+                    hour("2024-03-08 12:00", 7.8, 80, 1.2),
+                ],
+            },
+            ForecastDay {
+                date: "2024-03-09".to_owned(),
+                min_temp: Celsius(1.2),
+                max_temp: Celsius(6.5),
+                description: "sunny".to_owned(),
+                chance_of_rain: 0,
+                chance_of_snow: 0,
+                precipitation: 0.0,
+                hours: Vec::new(),
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case("table_full", SNAPSHOT_WIDTH)]
+    #[case("table_list", 28)]
+    #[case("table_compact", 20)]
+    fn test_table_terminal_view_snapshot(
+        _monochrome: (),
+        weather_data: WeatherData,
+        #[case] name: &str,
+        #[case] width: usize,
+    ) {
+        let result = table_terminal_view(
+            weather_data,
+            Units::Metric,
+            PressureUnit::Hpa,
+            VisibilityUnit::Kilometers,
+            None,
+            &ColorScales::default(),
+            Some(width),
+        );
+
+        insta::assert_snapshot!(name, result);
+    }
+
+    #[rstest]
+    fn test_plain_text_view_snapshot(_monochrome: (), weather_data: WeatherData) {
+        let result = plain_text_view(
+            &weather_data,
+            Units::Imperial,
+            PressureUnit::Hpa,
+            VisibilityUnit::Miles,
+            Some("de"),
+        );
+
+        insta::assert_snapshot!("plain_text", result);
+    }
+
+    #[rstest]
+    #[case("forecast_table_daily", false)]
+    #[case("forecast_table_hourly", true)]
+    fn test_forecast_table_terminal_view_snapshot(
+        _monochrome: (),
+        forecast: Vec<ForecastDay>,
+        #[case] name: &str,
+        #[case] hourly: bool,
+    ) {
+        let result = forecast_table_terminal_view(forecast, hourly, Units::Metric, None);
+
+        insta::assert_snapshot!(name, result);
+    }
+
+    #[rstest]
+    fn test_dashboard_table_terminal_view_snapshot(_monochrome: (), weather_data: WeatherData) {
+        let row = |location: &str, weather, alerts, error: Option<&str>| DashboardRow {
+            location: location.to_owned(),
+            address: location.to_owned(),
+            weather,
+            alerts,
+            error: error.map(str::to_owned),
+        };
+        let rows = [
+            row("home", Some(weather_data.clone()), Some(0), None),
+            row("commute", Some(weather_data), Some(2), None),
+            row("cabin", None, None, Some("Location not found")),
+        ];
+
+        let result = dashboard_table_terminal_view(&rows, Units::Metric, None);
+
+        insta::assert_snapshot!("dashboard_table", result);
+    }
+
+    #[rstest]
+    #[case(false, None, "{\"temp\":21.5}\n")]
+    #[case(false, Some(".temp"), "21.5\n")]
+    #[case(true, Some(".data.temp"), "21.5\n")]
+    #[case(true, None, "{\"meta\":{\"provider\":null,\"fetched_at\":null,\"cache_hit\":false,\"latency_ms\":0,\"request_units\":\"imperial\"},\"data\":{\"temp\":21.5}}\n")]
+    fn test_json_data_view(
+        #[case] envelope: bool,
+        #[case] query: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let data = serde_json::json!({ "temp": 21.5 });
+        let query: Option<Query> = query.map(|query| query.parse().unwrap());
 
-    Ok(())
+        assert_eq!(
+            json_data_view(&data, Units::Imperial, envelope, query.as_ref()).unwrap(),
+            expected
+        );
+        assert_eq!(json_terminal_view(&data).unwrap(), "{\"temp\":21.5}\n");
+    }
 }